@@ -0,0 +1,163 @@
+//! Timings of splitting and recovery across thresholds, prime sizes and
+//! field backends, run with `cargo bench --bench arithmetic [-- FILTER]`.
+//!
+//! The benchmarks run under criterion, so only those whose name matches
+//! `FILTER` run, and `-- --save-baseline NAME` records a run that a later
+//! `-- --baseline NAME` is compared against, reporting the changes that
+//! are beyond noise. The `ff` and `crypto-bigint` features add the
+//! backends behind them.
+
+use criterion::{
+    criterion_group, criterion_main, measurement::WallTime, BenchmarkGroup, Criterion,
+};
+use num_bigint::BigInt;
+use tiny_ss::{
+    field::{Field, PrimeField, SmallPrimeField},
+    gf256::{Gf256, Gf256Sharing},
+    montgomery::MontgomeryField,
+    presets, SecretShare, Share,
+};
+
+// Splitting and recovering under `ss`, from all shares, and again from `t`
+// of them through a recovery context
+fn split_recover<F: Field>(
+    group: &mut BenchmarkGroup<'_, WallTime>,
+    label: &str,
+    ss: &SecretShare<F>,
+    secret: F::Elem,
+    coefficient: F::Elem,
+) {
+    let (t, n) = (ss.t(), ss.n());
+    let split = || ss.split_with(secret.clone(), || coefficient.clone());
+    let shares: Vec<Share<F::Elem>> = split().unwrap();
+    group.bench_function(format!("{} split {}-of-{}", label, t, n), |b| b.iter(split));
+    group.bench_function(format!("{} recover {}-of-{}", label, t, n), |b| {
+        b.iter(|| ss.recover(&shares))
+    });
+    let indices: Vec<usize> = shares[..t].iter().map(|s| s.index()).collect();
+    let context = ss.recovery_context(&indices).unwrap();
+    group.bench_function(format!("{} context recover {}-of-{}", label, t, n), |b| {
+        b.iter(|| context.recover(&shares[..t]))
+    });
+}
+
+// p - k, a large element of the prime field of p
+fn near_p(field: &PrimeField, k: u32) -> BigInt {
+    field.p() - BigInt::from(k)
+}
+
+// single operations in the default field
+fn operations(c: &mut Criterion) {
+    let field = SecretShare::secp256k1_order(2, 3).unwrap().field().clone();
+    let a = near_p(&field, 12345);
+    c.bench_function("secp256k1 mul", |b| b.iter(|| field.mul(&a, &a)));
+    c.bench_function("secp256k1 invert", |b| b.iter(|| field.inv(&a)));
+}
+
+// thresholds, over the secp256k1 order
+fn thresholds(c: &mut Criterion) {
+    let mut group = c.benchmark_group("thresholds");
+    for (t, n) in [
+        (3, 5),
+        (16, 32),
+        (32, 128),
+        (64, 256),
+        (128, 512),
+        (256, 1024),
+        (1024, 4096),
+    ] {
+        // the largest dealings take long enough that fewer samples do
+        group.sample_size(if t >= 256 { 10 } else { 100 });
+        let ss = SecretShare::secp256k1_order(t, n).unwrap();
+        let (secret, coefficient) = (near_p(ss.field(), 12345), near_p(ss.field(), 7));
+        split_recover(&mut group, "secp256k1", &ss, secret, coefficient);
+    }
+    group.finish();
+}
+
+// prime sizes, 5-of-10
+fn primes(c: &mut Criterion) {
+    let mut group = c.benchmark_group("primes");
+    let primes = [
+        ("mersenne127", SecretShare::mersenne127(5, 10).unwrap()),
+        ("ed25519", SecretShare::ed25519(5, 10).unwrap()),
+        ("mersenne521", SecretShare::mersenne521(5, 10).unwrap()),
+    ];
+    for (label, ss) in &primes {
+        let (secret, coefficient) = (near_p(ss.field(), 12345), near_p(ss.field(), 7));
+        split_recover(&mut group, label, ss, secret, coefficient);
+    }
+    group.finish();
+}
+
+// backends, 5-of-10 and 64-of-128
+fn backends(c: &mut Criterion) {
+    let mut group = c.benchmark_group("backends");
+    for (t, n) in [(5, 10), (64, 128)] {
+        let order = presets::modulus(presets::SECP256K1_ORDER);
+        let ss = SecretShare::montgomery(t, n, order).unwrap();
+        let coefficient = ss.field().to_montgomery(&(ss.field().p() - 7u32));
+        let secret = ss.field().to_montgomery(&(ss.field().p() - 12345u32));
+        split_recover::<MontgomeryField>(
+            &mut group,
+            "montgomery secp256k1",
+            &ss,
+            secret,
+            coefficient,
+        );
+
+        let ss = SecretShare::mersenne61(t, n).unwrap();
+        let p = SmallPrimeField::MAX_MODULUS;
+        split_recover::<SmallPrimeField>(&mut group, "mersenne61", &ss, p - 12345, p - 7);
+
+        let ss = SecretShare::with_field(t, n, Gf256).unwrap();
+        split_recover::<Gf256>(&mut group, "gf256 field", &ss, 0xa5, 0x5a);
+
+        #[cfg(feature = "ff")]
+        {
+            use bls12_381::Scalar;
+            let ss = SecretShare::<tiny_ss::ff_field::FfField<Scalar>>::ff(t, n).unwrap();
+            let (secret, coefficient) = (-Scalar::from(12345u64), -Scalar::from(7u64));
+            split_recover(&mut group, "bls12-381 scalar", &ss, secret, coefficient);
+        }
+
+        #[cfg(feature = "crypto-bigint")]
+        {
+            use crypto_bigint::U256;
+            let order = U256::from_be_hex(presets::SECP256K1_ORDER);
+            let ss = SecretShare::ct(t, n, order).unwrap();
+            let elem = |k: u64| {
+                ss.field()
+                    .elem(&order.wrapping_sub(&U256::from_u64(k)))
+                    .unwrap()
+            };
+            let (secret, coefficient) = (elem(12345), elem(7));
+            split_recover(
+                &mut group,
+                "constant-time secp256k1",
+                &ss,
+                secret,
+                coefficient,
+            );
+        }
+    }
+    group.finish();
+}
+
+// the byte-oriented scheme over a 1 MiB secret
+fn bytes(c: &mut Criterion) {
+    let gf = Gf256Sharing::new(3, 5).unwrap();
+    let secret: Vec<u8> = (0..1 << 20)
+        .map(|i: u32| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+        .collect();
+    let shares = gf.split_with(&secret, |buf| buf.fill(0xa5));
+    c.bench_function("gf256 bytes split 3-of-5 1MiB", |b| {
+        b.iter(|| gf.split_with(&secret, |buf| buf.fill(0xa5)))
+    });
+    c.bench_function("gf256 bytes recover 3-of-5 1MiB", |b| {
+        b.iter(|| gf.recover(&shares[..3]))
+    });
+}
+
+criterion_group!(benches, operations, thresholds, primes, backends, bytes);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+//! Arbitrary bytes into every share parser: none may panic, and whatever
+//! one accepts must come back unchanged through its encoder.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use tiny_ss::{chunked::ChunkedShare, described::DescribedShare, SecretShare, Share};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(share) = Share::from_bytes(data) {
+        assert_eq!(Share::from_bytes(&share.to_bytes()), Ok(share));
+    }
+    if let Ok(share) = ChunkedShare::from_bytes(data) {
+        assert!(ChunkedShare::from_bytes(&share.to_bytes()).is_ok_and(|again| again == share));
+    }
+    if let Ok(share) = DescribedShare::from_bytes(data) {
+        assert!(DescribedShare::from_bytes(&share.to_bytes()).is_ok_and(|again| again == share));
+    }
+
+    // fixed-width values encode back to the very same bytes
+    let ss = SecretShare::secp256k1_order(2, 3).unwrap();
+    if let Ok(share) = ss.decode_share(data) {
+        assert_eq!(ss.encode_share(&share), data);
+    }
+
+    if let Ok(text) = core::str::from_utf8(data) {
+        if let Ok(share) = text.parse::<Share>() {
+            assert_eq!(share.to_hex().parse::<Share>(), Ok(share));
+        }
+        if let Ok(share) = Share::from_base64(text) {
+            assert_eq!(Share::from_base64(&share.to_base64()), Ok(share));
+        }
+        if let Ok(share) = Share::from_mnemonic(text) {
+            assert_eq!(Share::from_mnemonic(&share.to_mnemonic()), Ok(share));
+        }
+    }
+});
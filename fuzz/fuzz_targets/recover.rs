@@ -0,0 +1,90 @@
+//! Arbitrary share sets into recovery. The input picks a scheme, a secret,
+//! the coefficients of a dealing, and a sequence of submissions that each
+//! repeat a dealt share or forge one. Recovery must not panic; without
+//! forgeries it must return the secret exactly when `t` distinct shares
+//! are in, and `NotEnoughShares` otherwise.
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use num_bigint::BigInt;
+use std::collections::BTreeSet;
+use tiny_ss::{described::DescribedShare, gf256::Gf256Sharing, SecretShare, Share, TinySsError};
+
+/// the Mersenne prime 2^61 - 1
+const P: u64 = (1 << 61) - 1;
+
+// Reads the input front to back, as zeros once it runs out
+struct Input<'a>(core::slice::Iter<'a, u8>);
+
+impl Input<'_> {
+    fn byte(&mut self) -> u8 {
+        self.0.next().copied().unwrap_or(0)
+    }
+
+    fn u64(&mut self) -> u64 {
+        u64::from_be_bytes([(); 8].map(|_| self.byte()))
+    }
+}
+
+fuzz_target!(|data: &[u8]| {
+    let mut input = Input(data.iter());
+    let n = 2 + usize::from(input.byte()) % 15;
+    let t = 1 + usize::from(input.byte()) % (n - 1);
+    let ss = SecretShare::new(t, n, BigInt::from(P)).unwrap();
+    let secret = BigInt::from(input.u64() % P);
+    let shares = ss
+        .split_with(secret.clone(), || BigInt::from(input.u64() % P))
+        .unwrap();
+
+    let (mut submitted, mut forged) = (Vec::new(), false);
+    for _ in 0..input.byte() % 32 {
+        let choice = input.byte();
+        if choice < 224 {
+            submitted.push(shares[usize::from(choice) % n].clone());
+        } else {
+            // any index, any value, canonical or not
+            let index = usize::from(input.byte());
+            submitted.push(Share::new(index, BigInt::from(input.u64())));
+            forged = true;
+        }
+    }
+    let result = ss.recover(&submitted);
+    if !forged {
+        let distinct = submitted.iter().map(Share::index).collect::<BTreeSet<_>>().len();
+        if distinct >= t {
+            assert_eq!(result, Ok(secret));
+        } else {
+            assert_eq!(
+                result,
+                Err(TinySsError::NotEnoughShares {
+                    required: t,
+                    got: distinct
+                })
+            );
+        }
+    }
+
+    // the byte-oriented scheme, from shares of arbitrary lengths
+    let gf = Gf256Sharing::new(t, n).unwrap();
+    let byte_shares: Vec<Share<Vec<u8>>> = (0..input.byte() % 8)
+        .map(|_| {
+            let index = usize::from(input.byte());
+            let len = usize::from(input.byte() % 5);
+            Share::new(index, (0..len).map(|_| input.byte()).collect())
+        })
+        .collect();
+    let _ = gf.recover(&byte_shares);
+
+    // self-describing shares rebuild their scheme from what they carry;
+    // the rest of the input is cut into candidate encodings
+    let described: Vec<DescribedShare> = input
+        .0
+        .as_slice()
+        .split(|&b| b == 0xff)
+        .filter_map(|piece| DescribedShare::from_bytes(piece).ok())
+        // keep primality checks on the moduli short
+        .filter(|share| share.p().bits() <= 1024)
+        .collect();
+    let _ = SecretShare::recover_auto(&described);
+});
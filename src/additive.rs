@@ -0,0 +1,261 @@
+//! Additive `n`-of-`n` sharing and conversions to and from Shamir shares.
+//!
+//! The shares of an additive sharing simply add up to the secret, which is
+//! what many MPC protocols work with natively. A set of at least `t` Shamir
+//! holders turns its shares into additive ones locally by weighting them
+//! with their Lagrange coefficients at zero. The way back is interactive:
+//! every additive holder Shamir-shares its own share, and every Shamir holder
+//! adds up the subshares it received.
+
+use crate::{
+    field::{Field, PrimeField},
+    poly, SecretShare, Share, Subshare, TinySsError,
+};
+use alloc::vec::Vec;
+
+/// An `n`-of-`n` additive scheme over the field `F`.
+#[derive(Clone, Debug)]
+pub struct AdditiveSharing<F: Field = PrimeField> {
+    /// total number of shares, all needed for recovery
+    n: usize,
+    field: F,
+}
+
+impl<F: Field> AdditiveSharing<F> {
+    /// Creates a scheme of `n >= 2` shares.
+    pub fn new(n: usize, field: F) -> Result<Self, TinySsError> {
+        if n < 2 {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        Ok(AdditiveSharing { n, field })
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn field(&self) -> &F {
+        &self.field
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: F::Elem) -> Vec<Share<F::Elem>> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, || self.field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Splits `secret` into `n` shares indexed `1..=n`, drawing all but the
+    /// last from `sample`, which must return uniformly random field
+    /// elements.
+    pub fn split_with<S>(&self, secret: F::Elem, mut sample: S) -> Vec<Share<F::Elem>>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let mut rest = secret;
+        let mut shares: Vec<Share<F::Elem>> = (1..self.n)
+            .map(|index| {
+                let value = sample();
+                rest = self.field.sub(&rest, &value);
+                Share::new(index, value)
+            })
+            .collect();
+        shares.push(Share::new(self.n, rest));
+        shares
+    }
+
+    /// Recovers the secret from all `n` shares.
+    pub fn recover(&self, shares: &[Share<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        let unique = poly::distinct_shares(shares, self.n)?;
+        if unique.iter().any(|s| s.index() == 0 || s.index() > self.n) {
+            return Err(TinySsError::InvalidIndex);
+        }
+        Ok(unique
+            .iter()
+            .fold(self.field.zero(), |sum, s| self.field.add(&sum, s.value())))
+    }
+}
+
+impl<F: Field> SecretShare<F> {
+    /// Turns the Shamir share of one of the holders at `parties` into its
+    /// additive share: the results of all of `parties`, at least `t` of
+    /// them, add up to the secret.
+    pub fn to_additive(
+        &self,
+        share: &Share<F::Elem>,
+        parties: &[usize],
+    ) -> Result<Share<F::Elem>, TinySsError> {
+        let position = parties
+            .iter()
+            .position(|&p| p == share.index())
+            .ok_or(TinySsError::InvalidIndex)?;
+        for (i, &party) in parties.iter().enumerate() {
+            if parties[..i].contains(&party) {
+                return Err(TinySsError::ConflictingShares { index: party });
+            }
+            if party == 0 || party > self.n() {
+                return Err(TinySsError::InvalidIndex);
+            }
+        }
+        if parties.len() < self.t() {
+            return Err(TinySsError::NotEnoughShares {
+                required: self.t(),
+                got: parties.len(),
+            });
+        }
+        let field = self.field();
+        let xs: Vec<F::Elem> = parties.iter().map(|&p| field.elem_from_index(p)).collect();
+        let lambdas = poly::lagrange_coefficients(field, &xs, &field.zero())?;
+        Ok(Share::new(
+            share.index(),
+            field.mul(&lambdas[position], share.value()),
+        ))
+    }
+
+    /// Shamir-shares an additive share into one subshare for each holder of
+    /// this scheme.
+    #[cfg(feature = "rand")]
+    pub fn from_additive(
+        &self,
+        share: &Share<F::Elem>,
+    ) -> Result<Vec<Subshare<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.from_additive_with(share, || {
+            self.field().random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Shamir-shares an additive share, drawing the coefficients from
+    /// `sample`, which must return uniformly random field elements.
+    pub fn from_additive_with<S>(
+        &self,
+        share: &Share<F::Elem>,
+        sample: S,
+    ) -> Result<Vec<Subshare<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        if share.index() == 0 {
+            return Err(TinySsError::InvalidIndex);
+        }
+        Ok(self
+            .split_with(share.value().clone(), sample)?
+            .into_iter()
+            .map(|piece| Subshare::new(share.index(), piece))
+            .collect())
+    }
+
+    /// Adds up the subshares one holder received from every holder of
+    /// `additive` into its Shamir share of the same secret.
+    pub fn combine_additive(
+        &self,
+        additive: &AdditiveSharing<F>,
+        subshares: &[Subshare<F::Elem>],
+    ) -> Result<Share<F::Elem>, TinySsError> {
+        let receiver = subshares.first().map_or(0, |s| s.receiver());
+        if receiver == 0 || receiver > self.n() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        if subshares.iter().any(|s| s.receiver() != receiver) {
+            return Err(TinySsError::ConflictingShares { index: receiver });
+        }
+        // keyed by sender, so that the usual deduplication applies
+        let by_sender: Vec<Share<F::Elem>> = subshares
+            .iter()
+            .map(|s| Share::new(s.sender(), s.value().clone()))
+            .collect();
+        let value = additive.recover(&by_sender)?;
+        Ok(Share::new(receiver, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    fn field() -> PrimeField {
+        PrimeField::new(BigInt::from(1613)).unwrap()
+    }
+
+    #[test]
+    fn additive_test() {
+        let additive = AdditiveSharing::new(3, field()).unwrap();
+        let mut next = 0;
+        let shares = additive.split_with(BigInt::from(1234), || {
+            next += 700;
+            BigInt::from(next)
+        });
+        assert_eq!(shares[0], Share::new(1, BigInt::from(700)));
+        assert_eq!(additive.recover(&shares).unwrap(), BigInt::from(1234));
+        assert_eq!(
+            additive.recover(&shares[1..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+        assert_eq!(
+            AdditiveSharing::new(1, field()).unwrap_err(),
+            TinySsError::InvalidThreshold
+        );
+    }
+
+    #[test]
+    fn conversion_test() {
+        let ss = SecretShare::with_field(3, 5, field()).unwrap();
+        let secret = BigInt::from(1234);
+        let shares = ss
+            .split_with_coefficients(secret.clone(), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+
+        // Shamir holders 2, 4 and 5 become a 3-of-3 additive sharing
+        let parties = [2, 4, 5];
+        let additive = AdditiveSharing::new(3, field()).unwrap();
+        let converted: Vec<Share> = parties
+            .iter()
+            .enumerate()
+            .map(|(i, &p)| {
+                let share = ss.to_additive(&shares[p - 1], &parties).unwrap();
+                Share::new(i + 1, share.value().clone())
+            })
+            .collect();
+        assert_eq!(additive.recover(&converted).unwrap(), secret);
+
+        // and back to a fresh Shamir sharing
+        let mut next = 9;
+        let subshares: Vec<Vec<Subshare<BigInt>>> = converted
+            .iter()
+            .map(|s| {
+                ss.from_additive_with(s, || {
+                    next += 55;
+                    BigInt::from(next)
+                })
+                .unwrap()
+            })
+            .collect();
+        let fresh: Vec<Share> = (0..ss.n())
+            .map(|j| {
+                let received: Vec<Subshare<BigInt>> =
+                    subshares.iter().map(|s| s[j].clone()).collect();
+                ss.combine_additive(&additive, &received).unwrap()
+            })
+            .collect();
+        assert_ne!(fresh, shares);
+        assert_eq!(ss.recover(&fresh[..3]).unwrap(), secret);
+
+        assert_eq!(
+            ss.to_additive(&shares[0], &parties).unwrap_err(),
+            TinySsError::InvalidIndex
+        );
+        assert_eq!(
+            ss.to_additive(&shares[1], &[2, 4]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+    }
+}
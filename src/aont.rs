@@ -0,0 +1,128 @@
+//! AONT-RS after Resch and Plank ("AONT-RS: Blending Security and
+//! Performance in Dispersed Storage Systems", FAST '11).
+//!
+//! The data and a known canary are encrypted under a fresh random key, and
+//! the key, masked with the SHA-256 of the ciphertext, is appended. Without
+//! the whole package the key stays hidden, so this all-or-nothing transform
+//! is dispersed with the plain IDA: storage is close to `n / t` times the
+//! data, yet fewer than `t` fragments reveal nothing short of breaking the
+//! cipher. The canary detects corrupted fragments at recovery.
+
+use crate::{
+    css::{apply_keystream, KEY_BYTES},
+    ida::{Fragment, Ida},
+    TinySsError,
+};
+use alloc::vec::Vec;
+use sha2::{Digest, Sha256};
+
+/// bytes of the zero canary appended to the data
+pub const CANARY_BYTES: usize = 16;
+
+/// A `t`-of-`n` AONT-RS dispersal.
+#[derive(Clone, Debug)]
+pub struct AontRs {
+    fragments: Ida,
+}
+
+impl AontRs {
+    /// Creates a `t`-of-`n` dispersal with `0 < t <= n <= 255`.
+    pub fn new(t: usize, n: usize) -> Result<Self, TinySsError> {
+        Ok(AontRs {
+            fragments: Ida::new(t, n)?,
+        })
+    }
+
+    /// fragments needed to restore the data
+    pub fn t(&self) -> usize {
+        self.fragments.t()
+    }
+
+    /// total number of fragments
+    pub fn n(&self) -> usize {
+        self.fragments.n()
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, data: &[u8]) -> Vec<Fragment> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(data, |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits `data` drawing the key from `fill_random`, which must fill the
+    /// buffer with uniformly random bytes.
+    pub fn split_with<F>(&self, data: &[u8], mut fill_random: F) -> Vec<Fragment>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        let mut key = [0u8; KEY_BYTES];
+        fill_random(&mut key);
+        let mut package = data.to_vec();
+        package.resize(data.len() + CANARY_BYTES, 0);
+        apply_keystream(&key, &mut package);
+        let digest = Sha256::digest(&package);
+        package.extend(key.iter().zip(digest).map(|(k, h)| k ^ h));
+        self.fragments.disperse(&package)
+    }
+
+    /// Recovers the data from at least `t` fragments.
+    pub fn recover(&self, fragments: &[Fragment]) -> Result<Vec<u8>, TinySsError> {
+        let mut package = self.fragments.restore(fragments)?;
+        if package.len() < CANARY_BYTES + KEY_BYTES {
+            return Err(TinySsError::LengthMismatch);
+        }
+        let masked = package.split_off(package.len() - KEY_BYTES);
+        let digest = Sha256::digest(&package);
+        let key: Vec<u8> = masked.iter().zip(digest).map(|(m, h)| m ^ h).collect();
+        apply_keystream(&key, &mut package);
+        let canary = package.split_off(package.len() - CANARY_BYTES);
+        if canary.iter().any(|&b| b != 0) {
+            return Err(TinySsError::IntegrityCheckFailed);
+        }
+        Ok(package)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+
+    #[test]
+    fn aont_rs_test() {
+        let aont = AontRs::new(3, 5).unwrap();
+        let data = [9u8; 272];
+        let fragments = aont.split_with(&data, counter(71));
+        // 272 bytes plus canary and masked key, in thirds
+        assert!(fragments.iter().all(|f| f.bytes().len() == 107));
+        // even the systematic fragments carry no plaintext
+        assert_ne!(fragments[0].bytes(), &data[..107]);
+        assert_eq!(aont.recover(&fragments[2..]).unwrap(), data);
+        assert_eq!(aont.recover(&fragments[..3]).unwrap(), data);
+        assert_eq!(
+            aont.recover(&fragments[3..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+        assert!(aont
+            .recover(&aont.split_with(b"", counter(71)))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn corruption_test() {
+        let aont = AontRs::new(2, 3).unwrap();
+        let fragments = aont.split_with(b"all or nothing", counter(71));
+        let mut bytes = fragments[1].bytes().to_vec();
+        bytes[0] ^= 1;
+        let corrupted = Fragment::new(2, fragments[1].len(), bytes);
+        assert_eq!(
+            aont.recover(&[fragments[0].clone(), corrupted]),
+            Err(TinySsError::IntegrityCheckFailed)
+        );
+    }
+}
@@ -0,0 +1,207 @@
+//! Repeated recovery by a fixed set of holders.
+//!
+//! Every `recover` recomputes the Lagrange denominators of the indices it
+//! is given, which depend on the indices alone. When the same quorum
+//! recovers many secrets, such as in threshold decryption of many
+//! ciphertexts, a `RecoveryContext` computes the barycentric weights
+//! `w_j = 1 / prod_{k != j} (x_j - x_k)` and the Lagrange coefficients at
+//! zero once, after which recovering a secret takes `t` multiplications and
+//! evaluating at any other point a single batched inversion.
+
+use crate::{field::Field, field::PrimeField, poly, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+
+/// Precomputed weights for the holders at a fixed set of indices.
+#[derive(Clone, Debug)]
+pub struct RecoveryContext<'a, F: Field = PrimeField> {
+    scheme: &'a SecretShare<F>,
+    indices: Vec<usize>,
+    xs: Vec<F::Elem>,
+    /// barycentric weights, one per index
+    weights: Vec<F::Elem>,
+    /// Lagrange coefficients at zero, one per index
+    at_zero: Vec<F::Elem>,
+}
+
+impl<F: Field> RecoveryContext<'_, F> {
+    /// indices of the holders, in the order they were given
+    pub fn indices(&self) -> &[usize] {
+        &self.indices
+    }
+
+    /// Recovers the secret from one share per index of the context.
+    pub fn recover(&self, shares: &[Share<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        let field = self.scheme.field();
+        let ys = self.values(shares)?;
+        let mut secret = field.zero();
+        for (mut term, y) in self.at_zero.iter().cloned().zip(&ys) {
+            field.mul_assign(&mut term, y);
+            field.add_assign(&mut secret, &term);
+        }
+        Ok(secret)
+    }
+
+    /// Evaluates the dealt polynomial at `x` from one share per index of the
+    /// context, by the barycentric formula
+    /// `l(x) * sum_j w_j y_j / (x - x_j)` with `l(x) = prod_j (x - x_j)`.
+    pub fn recover_at(
+        &self,
+        x: F::Elem,
+        shares: &[Share<F::Elem>],
+    ) -> Result<F::Elem, TinySsError> {
+        let field = self.scheme.field();
+        let x = field.canonical(&x);
+        let ys = self.values(shares)?;
+        if let Some(j) = self.xs.iter().position(|xj| *xj == x) {
+            return Ok(ys[j].clone());
+        }
+        let offsets: Vec<F::Elem> = self.xs.iter().map(|xj| field.sub(&x, xj)).collect();
+        let inverses = poly::batch_inverse(field, &offsets).ok_or(TinySsError::NotInvertible)?;
+        let mut sum = field.zero();
+        let mut l = field.one();
+        for (((inverse, weight), y), offset) in inverses
+            .into_iter()
+            .zip(&self.weights)
+            .zip(&ys)
+            .zip(&offsets)
+        {
+            let mut term = inverse;
+            field.mul_assign(&mut term, weight);
+            field.mul_assign(&mut term, y);
+            field.add_assign(&mut sum, &term);
+            field.mul_assign(&mut l, offset);
+        }
+        field.mul_assign(&mut sum, &l);
+        Ok(sum)
+    }
+
+    // The share values in the order of the indices, failing with
+    // `IndexMismatch` unless the shares cover exactly those indices
+    fn values(&self, shares: &[Share<F::Elem>]) -> Result<Vec<F::Elem>, TinySsError> {
+        let field = self.scheme.field();
+        let unique = poly::distinct_field_shares(field, shares, 0)?;
+        if unique.len() != self.indices.len() {
+            return Err(TinySsError::IndexMismatch);
+        }
+        self.indices
+            .iter()
+            .map(|&index| {
+                unique
+                    .iter()
+                    .find(|share| share.index() == index)
+                    .map(|share| field.canonical(share.value()))
+                    .ok_or(TinySsError::IndexMismatch)
+            })
+            .collect()
+    }
+}
+
+impl<F: Field> SecretShare<F> {
+    /// Precomputes the weights for the holders at `indices`, at least `t`
+    /// distinct ones, checked as `lagrange_coefficients_at` checks them, for
+    /// recovering from their shares many times.
+    pub fn recovery_context(
+        &self,
+        indices: &[usize],
+    ) -> Result<RecoveryContext<'_, F>, TinySsError> {
+        if indices.len() < self.t() {
+            return Err(TinySsError::NotEnoughShares {
+                required: self.t(),
+                got: indices.len(),
+            });
+        }
+        for (k, &i) in indices.iter().enumerate() {
+            self.check_new_index(i)?;
+            if indices[..k].contains(&i) {
+                return Err(TinySsError::ConflictingShares { index: i });
+            }
+        }
+        let field = self.field();
+        let xs: Vec<F::Elem> = indices.iter().map(|&i| field.elem_from_index(i)).collect();
+        let denominators: Vec<F::Elem> = xs
+            .iter()
+            .enumerate()
+            .map(|(j, xj)| {
+                let mut denominator = field.one();
+                for (_, xk) in xs.iter().enumerate().filter(|(k, _)| *k != j) {
+                    field.mul_assign(&mut denominator, &field.sub(xj, xk));
+                }
+                denominator
+            })
+            .collect();
+        let weights =
+            poly::batch_inverse(field, &denominators).ok_or(TinySsError::NotInvertible)?;
+        // l_j(0) = w_j * prod_{k != j} (0 - x_k)
+        let at_zero = weights
+            .iter()
+            .enumerate()
+            .map(|(j, weight)| {
+                let mut coefficient = weight.clone();
+                for (_, xk) in xs.iter().enumerate().filter(|(k, _)| *k != j) {
+                    field.mul_assign(&mut coefficient, &field.sub(&field.zero(), xk));
+                }
+                coefficient
+            })
+            .collect();
+        Ok(RecoveryContext {
+            scheme: self,
+            indices: indices.to_vec(),
+            xs,
+            weights,
+            at_zero,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn recovery_context_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        let polynomial = [BigInt::from(1234), BigInt::from(166), BigInt::from(94)];
+        let shares = ss
+            .split_with_coefficients(polynomial[0].clone(), &polynomial[1..])
+            .unwrap();
+        let context = ss.recovery_context(&[4, 1, 2]).unwrap();
+        assert_eq!(context.indices(), [4, 1, 2]);
+        let quorum = [shares[0].clone(), shares[1].clone(), shares[3].clone()];
+        assert_eq!(context.recover(&quorum).unwrap(), BigInt::from(1234));
+        for x in [0, 2, 7, 1612] {
+            assert_eq!(
+                context.recover_at(BigInt::from(x), &quorum).unwrap(),
+                ss.recover_at(BigInt::from(x), &quorum).unwrap()
+            );
+        }
+        let other = ss
+            .split_with_coefficients(BigInt::from(99), &[BigInt::from(1), BigInt::from(2)])
+            .unwrap();
+        let quorum = [other[3].clone(), other[1].clone(), other[0].clone()];
+        assert_eq!(context.recover(&quorum).unwrap(), BigInt::from(99));
+
+        let wider = ss.recovery_context(&[1, 2, 3, 4]).unwrap();
+        assert_eq!(wider.recover(&shares[..4]).unwrap(), BigInt::from(1234));
+
+        assert_eq!(
+            context.recover(&shares[..3]),
+            Err(TinySsError::IndexMismatch)
+        );
+        assert_eq!(
+            context.recover(&shares[..2]),
+            Err(TinySsError::IndexMismatch)
+        );
+        assert_eq!(
+            ss.recovery_context(&[1, 2]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+        assert_eq!(
+            ss.recovery_context(&[1, 2, 2]).unwrap_err(),
+            TinySsError::ConflictingShares { index: 2 }
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Multiplication of shared secrets with Beaver triples.
+//!
+//! A trusted dealer hands out sharings of random `a` and `b` and of their
+//! product `c = a * b`. To multiply shared `x` and `y`, every holder masks
+//! its shares as `x_i - a_i` and `y_i - b_i`, the masked values `d = x - a`
+//! and `e = y - b` are opened, and holder `i` computes
+//! `c_i + d * b_i + e * a_i + d * e`, its share of `x * y`. The opened `d`
+//! and `e` are uniform and tell nothing about `x` and `y`, as long as each
+//! triple is used once only.
+
+use crate::{
+    field::{Field, PrimeField},
+    SecretShare, Share, TinySsError,
+};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// One holder's shares of a triple `(a, b, a * b)`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct BeaverTriple<V = num_bigint::BigInt> {
+    index: usize,
+    a: V,
+    b: V,
+    c: V,
+}
+
+impl<V: fmt::Debug> fmt::Debug for BeaverTriple<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BeaverTriple")
+            .field("index", &self.index)
+            .field("a", crate::debug::secret(&self.a))
+            .field("b", crate::debug::secret(&self.b))
+            .field("c", crate::debug::secret(&self.c))
+            .finish()
+    }
+}
+
+impl<V> BeaverTriple<V> {
+    pub fn new(index: usize, a: V, b: V, c: V) -> Self {
+        BeaverTriple { index, a, b, c }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// share of a
+    pub fn a(&self) -> &V {
+        &self.a
+    }
+
+    /// share of b
+    pub fn b(&self) -> &V {
+        &self.b
+    }
+
+    /// share of a * b
+    pub fn c(&self) -> &V {
+        &self.c
+    }
+}
+
+/// A holder's masked shares `x_i - a_i` and `y_i - b_i`, to be opened.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MaskedShares<V = num_bigint::BigInt> {
+    index: usize,
+    d: V,
+    e: V,
+}
+
+impl<V> MaskedShares<V> {
+    pub fn new(index: usize, d: V, e: V) -> Self {
+        MaskedShares { index, d, e }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// share of x - a
+    pub fn d(&self) -> &V {
+        &self.d
+    }
+
+    /// share of y - b
+    pub fn e(&self) -> &V {
+        &self.e
+    }
+}
+
+/// Beaver multiplication on top of a `t`-of-`n` Shamir scheme.
+#[derive(Clone, Debug)]
+pub struct Beaver<F: Field = PrimeField> {
+    scheme: SecretShare<F>,
+}
+
+impl<F: Field> Beaver<F> {
+    pub fn new(scheme: SecretShare<F>) -> Self {
+        Beaver { scheme }
+    }
+
+    /// The Shamir scheme the shares belong to.
+    pub fn shamir(&self) -> &SecretShare<F> {
+        &self.scheme
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn deal(&self) -> Result<Vec<BeaverTriple<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let field = self.scheme.field();
+        self.deal_with(|| field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Deals one triple to every holder, drawing `a`, `b` and the sharing
+    /// polynomials from `sample`, which must return uniformly random field
+    /// elements.
+    pub fn deal_with<S>(&self, mut sample: S) -> Result<Vec<BeaverTriple<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let field = self.scheme.field();
+        let (a, b) = (sample(), sample());
+        let c = field.mul(&a, &b);
+        let a = self.scheme.split_with(a, &mut sample)?;
+        let b = self.scheme.split_with(b, &mut sample)?;
+        let c = self.scheme.split_with(c, &mut sample)?;
+        Ok(a.into_iter()
+            .zip(b)
+            .zip(c)
+            .map(|((a, b), c)| {
+                BeaverTriple::new(
+                    a.index(),
+                    a.value().clone(),
+                    b.value().clone(),
+                    c.value().clone(),
+                )
+            })
+            .collect())
+    }
+
+    /// A holder's step one: masks its shares of `x` and `y` with its
+    /// triple.
+    pub fn mask(
+        &self,
+        x: &Share<F::Elem>,
+        y: &Share<F::Elem>,
+        triple: &BeaverTriple<F::Elem>,
+    ) -> Result<MaskedShares<F::Elem>, TinySsError> {
+        if x.index() != triple.index || y.index() != triple.index {
+            return Err(TinySsError::IndexMismatch);
+        }
+        let field = self.scheme.field();
+        Ok(MaskedShares::new(
+            triple.index,
+            field.sub(x.value(), &triple.a),
+            field.sub(y.value(), &triple.b),
+        ))
+    }
+
+    /// Opens `d = x - a` and `e = y - b` from the masked shares of at least
+    /// `t` holders.
+    pub fn open(
+        &self,
+        masked: &[MaskedShares<F::Elem>],
+    ) -> Result<(F::Elem, F::Elem), TinySsError> {
+        let d: Vec<Share<F::Elem>> = masked
+            .iter()
+            .map(|m| Share::new(m.index, m.d.clone()))
+            .collect();
+        let e: Vec<Share<F::Elem>> = masked
+            .iter()
+            .map(|m| Share::new(m.index, m.e.clone()))
+            .collect();
+        Ok((self.scheme.recover(&d)?, self.scheme.recover(&e)?))
+    }
+
+    /// A holder's step two: its share of `x * y` from its triple and the
+    /// opened `d` and `e`.
+    pub fn multiply(
+        &self,
+        triple: &BeaverTriple<F::Elem>,
+        (d, e): (&F::Elem, &F::Elem),
+    ) -> Share<F::Elem> {
+        let field = self.scheme.field();
+        let value = [
+            field.mul(d, &triple.b),
+            field.mul(e, &triple.a),
+            field.mul(d, e),
+        ]
+        .iter()
+        .fold(triple.c.clone(), |sum, term| field.add(&sum, term));
+        Share::new(triple.index, value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn beaver_test() {
+        let ss = SecretShare::new(2, 4, BigInt::from(1613)).unwrap();
+        let x = ss
+            .split_with_coefficients(BigInt::from(40), &[BigInt::from(77)])
+            .unwrap();
+        let y = ss
+            .split_with_coefficients(BigInt::from(50), &[BigInt::from(123)])
+            .unwrap();
+        let beaver = Beaver::new(ss);
+        let mut next = 5;
+        let triples = beaver
+            .deal_with(|| {
+                next = (next * next + 3) % 1613;
+                BigInt::from(next)
+            })
+            .unwrap();
+        assert_eq!(triples.len(), 4);
+        #[cfg(not(feature = "danger_debug"))]
+        assert!(alloc::format!("{:?}", triples[0]).contains("[REDACTED]"));
+        let c: Vec<Share> = triples
+            .iter()
+            .map(|t| Share::new(t.index(), t.c().clone()))
+            .collect();
+        let (a, b) = (
+            beaver
+                .shamir()
+                .recover(
+                    &triples
+                        .iter()
+                        .map(|t| Share::new(t.index(), t.a().clone()))
+                        .collect::<Vec<_>>(),
+                )
+                .unwrap(),
+            beaver
+                .shamir()
+                .recover(
+                    &triples
+                        .iter()
+                        .map(|t| Share::new(t.index(), t.b().clone()))
+                        .collect::<Vec<_>>(),
+                )
+                .unwrap(),
+        );
+        assert_eq!(beaver.shamir().recover(&c).unwrap(), (a * b) % 1613);
+
+        let masked: Vec<MaskedShares> = x
+            .iter()
+            .zip(&y)
+            .zip(&triples)
+            .map(|((x, y), t)| beaver.mask(x, y, t).unwrap())
+            .collect();
+        // any t holders suffice to open, and 40 * 50 wraps modulo 1613
+        let (d, e) = beaver.open(&masked[1..3]).unwrap();
+        let product: Vec<Share> = triples
+            .iter()
+            .map(|t| beaver.multiply(t, (&d, &e)))
+            .collect();
+        assert_eq!(
+            beaver.shamir().recover(&product[2..]).unwrap(),
+            BigInt::from(387)
+        );
+        assert_eq!(
+            beaver.mask(&x[0], &y[1], &triples[0]),
+            Err(TinySsError::IndexMismatch)
+        );
+    }
+}
@@ -0,0 +1,264 @@
+//! Modular inversion by the binary extended GCD.
+//!
+//! The loop follows Pornin, "Optimized Binary GCD for Modular Inversion":
+//! each round runs `BATCH` steps of the binary GCD on a word holding the
+//! low and the top bits of the two values, which decide the same steps as
+//! the full values would, and then applies them to the full values at
+//! once, as a linear combination by small factors. No round divides
+//! multi-limb numbers, unlike Euclid's algorithm, and the halvings of the
+//! coefficients are undone by a Montgomery step each round. The values are
+//! held in limbs of fixed length, so a round allocates nothing.
+
+use alloc::{vec, vec::Vec};
+use num_bigint::{BigInt, BigUint, Sign};
+
+/// binary GCD steps per round
+const BATCH: u32 = 62;
+const LOW_MASK: u64 = (1 << BATCH) - 1;
+
+/// `1 / y mod p` for an odd `p > 1` and `y` in `[0, p)`, `None` if they
+/// share a divisor.
+pub(crate) fn inverse(y: &BigInt, p: &BigInt) -> Option<BigInt> {
+    debug_assert!(p.bit(0) && y.sign() != Sign::Minus && y < p);
+    // two limbs at least, so that a word of top bits never overlaps the low
+    let len = p.iter_u64_digits().len().max(2);
+    let modulus = limbs(p, len);
+    let p_neg_inv = neg_inverse(modulus[0]);
+    // a = u * y and b = v * y (mod p)
+    let (mut a, mut b) = (limbs(y, len), modulus.clone());
+    let (mut u, mut v) = (vec![0u64; len], vec![0u64; len]);
+    u[0] = 1;
+    let mut next = [(); 4].map(|_| vec![0u64; len + 1]);
+    // every round takes BATCH bits off len(a) + len(b) at least
+    let rounds = (2 * p.bits() as u32 - 1).div_ceil(BATCH);
+    for _ in 0..rounds {
+        let [f0, g0, f1, g1] = steps(&a, &b);
+        let [next_a, next_b, next_u, next_v] = &mut next;
+        // a and b are replaced by their absolute values, so the factors for
+        // u and v change sign with them
+        let (f0, g0) = match combine(&a, f0, &b, g0, next_a) {
+            true => (-f0, -g0),
+            false => (f0, g0),
+        };
+        let (f1, g1) = match combine(&a, f1, &b, g1, next_b) {
+            true => (-f1, -g1),
+            false => (f1, g1),
+        };
+        montgomery_combine(&u, f0, &v, g0, &modulus, p_neg_inv, next_u);
+        montgomery_combine(&u, f1, &v, g1, &modulus, p_neg_inv, next_v);
+        a.copy_from_slice(&next_a[..len]);
+        b.copy_from_slice(&next_b[..len]);
+        u.copy_from_slice(&next_u[..len]);
+        v.copy_from_slice(&next_v[..len]);
+    }
+    if b[0] != 1 || b[1..].iter().any(|&limb| limb != 0) {
+        return None;
+    }
+    Some(to_bigint(&v))
+}
+
+// The factors [f0, g0, f1, g1] of BATCH steps, which take (a, b) to
+// ((a f0 + b g0) / 2^BATCH, (a f1 + b g1) / 2^BATCH) up to signs
+fn steps(a: &[u64], b: &[u64]) -> [i64; 4] {
+    let n = bits(a).max(bits(b)).max(2 * BATCH + 2);
+    let approximate =
+        |x: &[u64]| u128::from(x[0] & LOW_MASK) | u128::from(window(x, n - BATCH - 2)) << BATCH;
+    let (mut a, mut b) = (approximate(a), approximate(b));
+    let (mut f0, mut g0, mut f1, mut g1) = (1i64, 0i64, 0i64, 1i64);
+    for _ in 0..BATCH {
+        if a & 1 == 1 {
+            if a < b {
+                (a, b) = (b, a);
+                (f0, f1) = (f1, f0);
+                (g0, g1) = (g1, g0);
+            }
+            a -= b;
+            f0 -= f1;
+            g0 -= g1;
+        }
+        a >>= 1;
+        f1 <<= 1;
+        g1 <<= 1;
+    }
+    [f0, g0, f1, g1]
+}
+
+// |x f + y g| / 2^BATCH into the first limbs of `out`, and whether the
+// combination was negative; it must be divisible by 2^BATCH
+fn combine(x: &[u64], f: i64, y: &[u64], g: i64, out: &mut [u64]) -> bool {
+    linear(x, f, y, g, out);
+    let negative = shift(out);
+    if negative {
+        negate(out);
+    }
+    negative
+}
+
+// (x f + y g) / 2^BATCH mod p into the first limbs of `out`, for x and y
+// in [0, p), adding the multiple of p that clears the low bits
+fn montgomery_combine(
+    x: &[u64],
+    f: i64,
+    y: &[u64],
+    g: i64,
+    p: &[u64],
+    p_neg_inv: u64,
+    out: &mut [u64],
+) {
+    linear(x, f, y, g, out);
+    let m = out[0].wrapping_mul(p_neg_inv) & LOW_MASK;
+    let mut carry = 0u128;
+    for (limb, &pi) in out.iter_mut().zip(p) {
+        let sum = u128::from(*limb) + u128::from(m) * u128::from(pi) + carry;
+        *limb = sum as u64;
+        carry = sum >> 64;
+    }
+    let top = out.len() - 1;
+    out[top] = out[top].wrapping_add(carry as u64);
+    // now in (-p, 2p)
+    if shift(out) {
+        add_assign(out, p);
+    } else if !less(out, p) {
+        sub_assign(out, p);
+    }
+}
+
+// x f + y g in two's complement over the limbs of `out`, one longer than
+// x and y; |f| + |g| <= 2^BATCH keeps every column within an i128
+fn linear(x: &[u64], f: i64, y: &[u64], g: i64, out: &mut [u64]) {
+    let mut carry = 0i128;
+    for ((limb, &xi), &yi) in out.iter_mut().zip(x).zip(y) {
+        let column = i128::from(xi) * i128::from(f) + i128::from(yi) * i128::from(g) + carry;
+        *limb = column as u64;
+        carry = column >> 64;
+    }
+    let top = out.len() - 1;
+    out[top] = carry as u64;
+}
+
+// Arithmetic shift right by BATCH of a two's complement value, returning
+// whether it is negative
+fn shift(x: &mut [u64]) -> bool {
+    let negative = x[x.len() - 1] >> 63 == 1;
+    for i in 0..x.len() {
+        let high = match x.get(i + 1) {
+            Some(&next) => next,
+            None if negative => u64::MAX,
+            None => 0,
+        };
+        x[i] = x[i] >> BATCH | high << (64 - BATCH);
+    }
+    negative
+}
+
+fn negate(x: &mut [u64]) {
+    let mut carry = true;
+    for limb in x.iter_mut() {
+        (*limb, carry) = (!*limb).overflowing_add(u64::from(carry));
+    }
+}
+
+fn add_assign(x: &mut [u64], y: &[u64]) {
+    let mut carry = false;
+    for (i, limb) in x.iter_mut().enumerate() {
+        let yi = y.get(i).copied().unwrap_or(0);
+        let (sum, c0) = limb.overflowing_add(yi);
+        let (sum, c1) = sum.overflowing_add(u64::from(carry));
+        (*limb, carry) = (sum, c0 | c1);
+    }
+}
+
+fn sub_assign(x: &mut [u64], y: &[u64]) {
+    let mut borrow = false;
+    for (i, limb) in x.iter_mut().enumerate() {
+        let yi = y.get(i).copied().unwrap_or(0);
+        let (difference, b0) = limb.overflowing_sub(yi);
+        let (difference, b1) = difference.overflowing_sub(u64::from(borrow));
+        (*limb, borrow) = (difference, b0 | b1);
+    }
+}
+
+// whether the non-negative x, with limbs past those of y, is below y
+fn less(x: &[u64], y: &[u64]) -> bool {
+    if x[y.len()..].iter().any(|&limb| limb != 0) {
+        return false;
+    }
+    for (xi, yi) in x[..y.len()].iter().zip(y).rev() {
+        if xi != yi {
+            return xi < yi;
+        }
+    }
+    false
+}
+
+fn bits(x: &[u64]) -> u32 {
+    x.iter()
+        .rposition(|&limb| limb != 0)
+        .map_or(0, |i| 64 * i as u32 + 64 - x[i].leading_zeros())
+}
+
+// the 64 bits of x from bit `start` on
+fn window(x: &[u64], start: u32) -> u64 {
+    let (limb, offset) = ((start / 64) as usize, start % 64);
+    let low = x.get(limb).copied().unwrap_or(0) >> offset;
+    match (offset, x.get(limb + 1)) {
+        (0, _) | (_, None) => low,
+        (_, Some(&high)) => low | high << (64 - offset),
+    }
+}
+
+// -p^-1 mod 2^64 by Newton's iteration, which doubles the correct bits
+fn neg_inverse(p: u64) -> u64 {
+    // correct to 3 bits for any odd p
+    let mut inverse = p;
+    for _ in 0..5 {
+        inverse = inverse.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inverse)));
+    }
+    inverse.wrapping_neg()
+}
+
+fn limbs(x: &BigInt, len: usize) -> Vec<u64> {
+    let mut limbs: Vec<u64> = x.iter_u64_digits().collect();
+    limbs.resize(len, 0);
+    limbs
+}
+
+fn to_bigint(limbs: &[u64]) -> BigInt {
+    let digits = limbs
+        .iter()
+        .flat_map(|&limb| [limb as u32, (limb >> 32) as u32])
+        .collect();
+    BigInt::from_biguint(Sign::Plus, BigUint::new(digits))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets;
+
+    #[test]
+    fn inverse_test() {
+        for p in [3u32, 9, 1613, 65537] {
+            let p = BigInt::from(p);
+            for y in 0..p.iter_u32_digits().next().unwrap() {
+                let y = BigInt::from(y);
+                let expected = y.modinv(&p);
+                assert_eq!(inverse(&y, &p), expected);
+            }
+        }
+        let two = BigInt::from(2);
+        for p in [
+            presets::modulus(presets::SECP256K1_ORDER),
+            (BigInt::from(1) << 521) - 1u32,
+        ] {
+            let mut y = BigInt::from(12345);
+            for _ in 0..100 {
+                y = (&y * &y + 7u32) % &p;
+                assert_eq!(inverse(&y, &p), Some(y.modpow(&(&p - &two), &p)));
+            }
+        }
+        let p = presets::modulus(presets::SECP256K1_ORDER);
+        assert_eq!(inverse(&BigInt::from(0), &p), None);
+        assert_eq!(inverse(&(&p - 1u32), &p), Some(&p - 1u32));
+    }
+}
@@ -0,0 +1,183 @@
+//! Blakley's geometric threshold scheme.
+//!
+//! The secret is the first coordinate of a random point in `t`-dimensional
+//! space over the field, and every share is a random hyperplane through that
+//! point. Any `t` hyperplanes in general position intersect in the point
+//! alone, while `t - 1` of them leave a whole line through every candidate
+//! secret. Shares are `t` times the size of Shamir shares, so this is mainly
+//! of educational and interop interest.
+
+use crate::{
+    field::{Field, PrimeField},
+    poly, Share, TinySsError,
+};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+/// The hyperplane `coefficients . x = value`.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Hyperplane<V> {
+    coefficients: Vec<V>,
+    value: V,
+}
+
+impl<V: fmt::Debug> fmt::Debug for Hyperplane<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Hyperplane")
+            .field("coefficients", crate::debug::secret(&self.coefficients))
+            .field("value", crate::debug::secret(&self.value))
+            .finish()
+    }
+}
+
+impl<V> Hyperplane<V> {
+    pub fn new(coefficients: Vec<V>, value: V) -> Self {
+        Hyperplane {
+            coefficients,
+            value,
+        }
+    }
+
+    pub fn coefficients(&self) -> &[V] {
+        &self.coefficients
+    }
+
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+}
+
+/// A `t`-of-`n` Blakley scheme over the field `F`.
+#[derive(Clone, Debug)]
+pub struct BlakleySharing<F: Field = PrimeField> {
+    /// threshold, the dimension of the space
+    t: usize,
+    /// total number of shares
+    n: usize,
+    field: F,
+}
+
+impl<F: Field> BlakleySharing<F> {
+    /// Creates a `t`-of-`n` scheme with `0 < t < n`.
+    pub fn new(t: usize, n: usize, field: F) -> Result<Self, TinySsError> {
+        if t == 0 || t >= n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        Ok(BlakleySharing { t, n, field })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn field(&self) -> &F {
+        &self.field
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: F::Elem) -> Vec<Share<Hyperplane<F::Elem>>> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, || self.field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Splits `secret` drawing the point and the hyperplanes from `sample`,
+    /// which must return uniformly random field elements.
+    pub fn split_with<S>(&self, secret: F::Elem, mut sample: S) -> Vec<Share<Hyperplane<F::Elem>>>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let mut point = vec![secret];
+        point.extend((1..self.t).map(|_| sample()));
+        (1..=self.n)
+            .map(|index| {
+                let coefficients: Vec<F::Elem> = (0..self.t).map(|_| sample()).collect();
+                let value = coefficients
+                    .iter()
+                    .zip(&point)
+                    .fold(self.field.zero(), |sum, (a, x)| {
+                        self.field.add(&sum, &self.field.mul(a, x))
+                    });
+                Share::new(index, Hyperplane::new(coefficients, value))
+            })
+            .collect()
+    }
+
+    /// Recovers the secret by intersecting the first `t` distinct
+    /// hyperplanes.
+    ///
+    /// Hyperplanes that happen not to be in general position fail with
+    /// `NotInvertible`, which is unlikely for a large field.
+    pub fn recover(&self, shares: &[Share<Hyperplane<F::Elem>>]) -> Result<F::Elem, TinySsError> {
+        if shares
+            .iter()
+            .any(|s| s.value().coefficients.len() != self.t)
+        {
+            return Err(TinySsError::LengthMismatch);
+        }
+        let unique = poly::distinct_shares(shares, self.t)?;
+        let (matrix, values) = unique
+            .iter()
+            .take(self.t)
+            .map(|s| (s.value().coefficients.clone(), s.value().value.clone()))
+            .unzip();
+        let point = poly::solve(&self.field, matrix, values)?;
+        Ok(point[0].clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn blakley_test() {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        let blakley = BlakleySharing::new(3, 5, field).unwrap();
+        let mut next = 1;
+        let shares = blakley.split_with(BigInt::from(1234), || {
+            next = (next * next + 11) % 1613;
+            BigInt::from(next)
+        });
+        assert_eq!(shares[0].value().coefficients().len(), 3);
+        assert_eq!(blakley.recover(&shares[2..]).unwrap(), BigInt::from(1234));
+        assert_eq!(blakley.recover(&shares[..3]).unwrap(), BigInt::from(1234));
+        assert_eq!(
+            blakley.recover(&shares[3..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+    }
+
+    #[test]
+    fn degenerate_test() {
+        let field = PrimeField::new(BigInt::from(7)).unwrap();
+        let blakley = BlakleySharing::new(2, 3, field).unwrap();
+        // parallel lines never meet in a single point
+        let shares = [
+            Share::new(
+                1,
+                Hyperplane::new(vec![BigInt::from(1), BigInt::from(2)], BigInt::from(3)),
+            ),
+            Share::new(
+                2,
+                Hyperplane::new(vec![BigInt::from(2), BigInt::from(4)], BigInt::from(1)),
+            ),
+        ];
+        assert_eq!(blakley.recover(&shares), Err(TinySsError::NotInvertible));
+        let short = [Share::new(
+            1,
+            Hyperplane::new(vec![BigInt::from(1)], BigInt::from(3)),
+        )];
+        assert_eq!(blakley.recover(&short), Err(TinySsError::LengthMismatch));
+    }
+}
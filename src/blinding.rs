@@ -0,0 +1,78 @@
+// Recovery on blinded share values.
+//
+// With fresh random `r != 0` and `b`, interpolating the values `r y_i + b`
+// at 0 gives `r s + b`, as the Lagrange coefficients at 0 sum to 1, and the
+// secret is `(r s + b) / r`. The arithmetic of the interpolation then runs
+// on values independent of the shares, so that timing or cache behaviour
+// observed during it correlates with `r` and `b` rather than the secret.
+
+use crate::{field::Field, poly, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+
+impl<F: Field> SecretShare<F> {
+    #[cfg(feature = "rand")]
+    pub fn recover_blinded(&self, shares: &[Share<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.recover_blinded_with(shares, || {
+            self.field().random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// `recover` with the share values blinded by random elements drawn
+    /// from `sample`, which must return uniformly random field elements.
+    pub fn recover_blinded_with<S>(
+        &self,
+        shares: &[Share<F::Elem>],
+        mut sample: S,
+    ) -> Result<F::Elem, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let field = self.field();
+        let unique = poly::distinct_field_shares(field, shares, self.t())?;
+        let (xs, mut ys) = self.interpolation_points(&unique);
+        let (r, r_inverse) = loop {
+            let r = sample();
+            if let Some(inverse) = field.inv(&r) {
+                break (r, inverse);
+            }
+        };
+        let b = sample();
+        let blinded: Vec<F::Elem> = ys
+            .iter()
+            .map(|y| field.add(&field.mul(&r, y), &b))
+            .collect();
+        self.wipe(&mut ys);
+        let masked = self.lagrange_interpolation(field.zero(), xs, blinded)?;
+        Ok(field.mul(&field.sub(&masked, &b), &r_inverse))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn recover_blinded_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        let shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+        // a zero blinding factor is drawn again
+        let mut draws = [0, 0, 977, 41].into_iter().map(BigInt::from);
+        assert_eq!(
+            ss.recover_blinded_with(&shares[1..4], || draws.next().unwrap())
+                .unwrap(),
+            BigInt::from(1234)
+        );
+        assert_eq!(
+            ss.recover_blinded_with(&shares[..2], || BigInt::from(1)),
+            Err(TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Threshold BLS signatures over BLS12-381.
+//!
+//! The secret scalar `x` is Shamir shared over the scalar field of the
+//! curve, and the public key `X = x G1` lives in G1 while signatures live in
+//! G2. Holder `i` signs the hashed message `H(m)` as `x_i H(m)`, which
+//! anyone checks with `e(X_i, H(m)) = e(G1, sigma_i)` against its public
+//! share `X_i = x_i G1`, and any `t` partial signatures combine, with the
+//! Lagrange coefficients at 0 applied in the exponent, into the signature
+//! `x H(m)` that verifies under `X`.
+//!
+//! Messages come in already hashed to G2, such as with a hash-to-curve
+//! suite of RFC 9380; hashing to a multiple of the generator would make
+//! every signature forgeable.
+
+use crate::{ff_field::FfField, field::Field, poly, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+use bls12_381::{pairing, G1Affine, G1Projective, G2Affine, G2Projective, Scalar};
+
+/// A holder's signature share `x_i H(m)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PartialSignature {
+    index: usize,
+    value: G2Affine,
+}
+
+impl PartialSignature {
+    pub fn new(index: usize, value: G2Affine) -> Self {
+        PartialSignature { index, value }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn value(&self) -> &G2Affine {
+        &self.value
+    }
+}
+
+/// BLS signing with a `t`-of-`n` sharing of the secret scalar.
+#[derive(Clone, Debug)]
+pub struct ThresholdBls {
+    sharing: SecretShare<FfField<Scalar>>,
+}
+
+impl ThresholdBls {
+    pub fn new(t: usize, n: usize) -> Result<Self, TinySsError> {
+        Ok(ThresholdBls {
+            sharing: SecretShare::ff(t, n)?,
+        })
+    }
+
+    /// The sharing of the secret scalar.
+    pub fn shamir(&self) -> &SecretShare<FfField<Scalar>> {
+        &self.sharing
+    }
+
+    /// The public key `x G1` of a secret, or the public share of a share.
+    pub fn public_key(secret: &Scalar) -> G1Affine {
+        (G1Projective::generator() * secret).into()
+    }
+
+    /// A holder's signature share on the hashed message.
+    pub fn sign_partial(
+        &self,
+        share: &Share<Scalar>,
+        message: &G2Affine,
+    ) -> Result<PartialSignature, TinySsError> {
+        if share.index() == 0 || share.index() > self.sharing.n() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let value = (G2Projective::from(message) * share.value()).into();
+        Ok(PartialSignature::new(share.index(), value))
+    }
+
+    /// Checks a signature share against the holder's public share.
+    pub fn verify_partial(
+        &self,
+        partial: &PartialSignature,
+        public_share: &G1Affine,
+        message: &G2Affine,
+    ) -> bool {
+        verify(public_share, message, &partial.value)
+    }
+
+    /// Combines at least `t` signature shares into the signature under the
+    /// shared key.
+    pub fn combine(&self, partials: &[PartialSignature]) -> Result<G2Affine, TinySsError> {
+        if partials
+            .iter()
+            .any(|p| p.index == 0 || p.index > self.sharing.n())
+        {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let shares: Vec<Share<G2Affine>> = partials
+            .iter()
+            .map(|p| Share::new(p.index, p.value))
+            .collect();
+        let unique = poly::distinct_shares(&shares, self.sharing.t())?;
+        let scalars = self.sharing.field();
+        let xs: Vec<Scalar> = unique
+            .iter()
+            .map(|s| scalars.elem_from_index(s.index()))
+            .collect();
+        let weights = poly::lagrange_coefficients(scalars, &xs, &Scalar::zero())?;
+        let signature = unique
+            .iter()
+            .zip(&weights)
+            .fold(G2Projective::identity(), |sum, (s, w)| {
+                sum + G2Projective::from(s.value()) * w
+            });
+        Ok(signature.into())
+    }
+}
+
+/// Whether `signature` signs the hashed message under `public_key`.
+pub fn verify(public_key: &G1Affine, message: &G2Affine, signature: &G2Affine) -> bool {
+    !bool::from(public_key.is_identity())
+        && pairing(public_key, message) == pairing(&G1Affine::generator(), signature)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold_bls_test() {
+        let bls = ThresholdBls::new(2, 3).unwrap();
+        let x = Scalar::from(777u64);
+        let shares = bls
+            .shamir()
+            .split_with_coefficients(x, &[Scalar::from(13u64)])
+            .unwrap();
+        // stands in for a hash-to-curve output in the test only
+        let message: G2Affine = (G2Projective::generator() * Scalar::from(4242u64)).into();
+        let other: G2Affine = (G2Projective::generator() * Scalar::from(4243u64)).into();
+
+        let partials: Vec<PartialSignature> = shares
+            .iter()
+            .map(|s| bls.sign_partial(s, &message).unwrap())
+            .collect();
+        for (partial, share) in partials.iter().zip(&shares) {
+            let public_share = ThresholdBls::public_key(share.value());
+            assert!(bls.verify_partial(partial, &public_share, &message));
+            assert!(!bls.verify_partial(partial, &public_share, &other));
+        }
+        let signature = bls.combine(&partials[1..]).unwrap();
+        assert_eq!(bls.combine(&[partials[2], partials[0]]).unwrap(), signature);
+        let public_key = ThresholdBls::public_key(&x);
+        assert!(verify(&public_key, &message, &signature));
+        assert!(!verify(&public_key, &other, &signature));
+
+        assert_eq!(
+            bls.combine(&partials[..1]),
+            Err(TinySsError::NotEnoughShares {
+                required: 2,
+                got: 1
+            })
+        );
+    }
+}
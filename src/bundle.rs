@@ -0,0 +1,167 @@
+//! Sharing of many secrets at once.
+//!
+//! Provisioning many keys to the same holders with `split` hands each holder
+//! a separate share per key. `split_many` shares them all at the same
+//! indices and hands each holder one `ShareBundle` with its value for every
+//! secret, and `recover_many` computes the Lagrange weights of the holders
+//! once for all secrets instead of once per secret.
+
+use crate::{field::Field, poly, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A holder's shares of several secrets, all at one index.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ShareBundle<V = num_bigint::BigInt> {
+    /// x-coordinate
+    index: usize,
+    /// y-coordinates, one per secret in order
+    values: Vec<V>,
+}
+
+impl<V: fmt::Debug> fmt::Debug for ShareBundle<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShareBundle")
+            .field("index", &self.index)
+            .field("values", crate::debug::secret(&self.values))
+            .finish()
+    }
+}
+
+impl<V> ShareBundle<V> {
+    pub fn new(index: usize, values: Vec<V>) -> Self {
+        ShareBundle { index, values }
+    }
+
+    /// x-coordinate
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// y-coordinates, one per secret in order
+    pub fn values(&self) -> &[V] {
+        &self.values
+    }
+}
+
+impl<F: Field> SecretShare<F> {
+    #[cfg(feature = "rand")]
+    pub fn split_many(
+        &self,
+        secrets: &[F::Elem],
+    ) -> Result<Vec<ShareBundle<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_many_with(secrets, || {
+            self.field().random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Splits every secret in `secrets` with its own polynomial, drawing the
+    /// coefficients from `sample` as `split_with` does, and returns the `n`
+    /// bundles in order of index.
+    pub fn split_many_with<S>(
+        &self,
+        secrets: &[F::Elem],
+        mut sample: S,
+    ) -> Result<Vec<ShareBundle<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let field = self.field();
+        let mut polynomials: Vec<Vec<F::Elem>> = secrets
+            .iter()
+            .map(|secret| self.random_polynomial(secret.clone(), &mut sample))
+            .collect();
+        let bundles = (1..=self.n())
+            .map(|index| {
+                let x = field.elem_from_index(index);
+                let values = polynomials
+                    .iter()
+                    .map(|polynomial| poly::evaluate(field, polynomial, &x))
+                    .collect();
+                ShareBundle::new(index, values)
+            })
+            .collect();
+        polynomials
+            .iter_mut()
+            .for_each(|polynomial| self.wipe(polynomial));
+        Ok(bundles)
+    }
+
+    /// Recovers the secrets split by `split_many` from at least `t` bundles,
+    /// which must hold a value for each secret. As with `recover`, bundles
+    /// repeating an index are ignored and the first `t` distinct ones used.
+    pub fn recover_many(
+        &self,
+        bundles: &[ShareBundle<F::Elem>],
+    ) -> Result<Vec<F::Elem>, TinySsError> {
+        let count = bundles.first().map_or(0, |b| b.values.len());
+        if bundles.iter().any(|b| b.values.len() != count) {
+            return Err(TinySsError::LengthMismatch);
+        }
+        let columns: Vec<Share<&[F::Elem]>> = bundles
+            .iter()
+            .map(|b| Share::new(b.index, b.values.as_slice()))
+            .collect();
+        let unique = &poly::distinct_shares(&columns, self.t())?[..self.t()];
+        let field = self.field();
+        let indices: Vec<usize> = unique.iter().map(|s| s.index()).collect();
+        let weights = self.lagrange_coefficients_at(field.zero(), &indices)?;
+        Ok((0..count)
+            .map(|position| {
+                unique
+                    .iter()
+                    .zip(&weights)
+                    .fold(field.zero(), |sum, (share, weight)| {
+                        field.add(&sum, &field.mul(weight, &share.value()[position]))
+                    })
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn split_many_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        let secrets = [1234, 0, 1612].map(BigInt::from);
+        let mut next = 0;
+        let bundles = ss
+            .split_many_with(&secrets, || {
+                next += 11;
+                BigInt::from(next)
+            })
+            .unwrap();
+        assert_eq!(bundles.len(), 5);
+        assert_eq!(bundles[4].index(), 5);
+        // 1234 + 11 * 5 + 22 * 25 = 1839 = 226 (mod 1613)
+        assert_eq!(bundles[4].values()[0], BigInt::from(226));
+        assert_eq!(ss.recover_many(&bundles[2..]).unwrap(), secrets);
+        let shuffled = [
+            bundles[3].clone(),
+            bundles[0].clone(),
+            bundles[3].clone(),
+            bundles[1].clone(),
+        ];
+        assert_eq!(ss.recover_many(&shuffled).unwrap(), secrets);
+
+        let mut short = bundles[1].clone();
+        short.values.pop();
+        assert_eq!(
+            ss.recover_many(&[bundles[0].clone(), short, bundles[2].clone()]),
+            Err(TinySsError::LengthMismatch)
+        );
+        assert_eq!(
+            ss.recover_many(&bundles[..2]),
+            Err(TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            })
+        );
+    }
+}
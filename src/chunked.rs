@@ -0,0 +1,389 @@
+//! Splitting of byte strings longer than the modulus admits.
+//!
+//! `split` reduces its secret modulo `p`, so a secret of `p` or more comes
+//! back from `recover` as its residue. `split_chunked` instead cuts the
+//! secret into big-endian chunks of `chunk_bytes` bytes, each below `p`,
+//! and shares every chunk with its own polynomial; a holder's
+//! `ChunkedShare` carries the values of all chunks at its index, and
+//! `recover_chunked` reassembles the secret chunk by chunk. `split_bytes`
+//! is the case of a single chunk, for keys that fit into one element.
+//! Either way the share records the length of the secret, so that it comes
+//! back with its leading zero bytes. `split_str` and `recover_string` share
+//! text, such as passphrases, in chunks.
+//!
+//! The binary form is `magic || version || index || len || count ||
+//! chunks`, where `index` and the secret length `len` are big-endian `u64`s,
+//! `count` a big-endian `u32`, and every chunk is `position || size ||
+//! value` with its position and value size as big-endian `u32`s and the
+//! value as a big-endian magnitude.
+
+use crate::{
+    codec::{take, take_magnitude, take_u32, take_u64},
+    poly, DecodeError, SecretShare, Share, TinySsError,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt;
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+
+/// magic bytes opening every binary chunked share
+const MAGIC: [u8; 3] = *b"TSC";
+/// current binary format version
+const VERSION: u8 = 1;
+
+/// A holder's shares of every chunk of a byte string.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ChunkedShare {
+    /// x-coordinate
+    index: usize,
+    /// byte length of the secret
+    len: usize,
+    /// y-coordinates, one per chunk in order
+    chunks: Vec<BigInt>,
+}
+
+impl fmt::Debug for ChunkedShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ChunkedShare")
+            .field("index", &self.index)
+            .field("len", &self.len)
+            .field("chunks", crate::debug::secret(&self.chunks))
+            .finish()
+    }
+}
+
+impl ChunkedShare {
+    pub fn new(index: usize, len: usize, chunks: Vec<BigInt>) -> Self {
+        ChunkedShare { index, len, chunks }
+    }
+
+    /// x-coordinate
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// byte length of the secret
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// y-coordinates, one per chunk in order
+    pub fn chunks(&self) -> &[BigInt] {
+        &self.chunks
+    }
+
+    /// Encodes the share in the binary form of the module documentation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.extend_from_slice(&(self.index as u64).to_be_bytes());
+        bytes.extend_from_slice(&(self.len as u64).to_be_bytes());
+        bytes.extend_from_slice(&(self.chunks.len() as u32).to_be_bytes());
+        for (position, chunk) in self.chunks.iter().enumerate() {
+            let value = if chunk.is_zero() {
+                Vec::new()
+            } else {
+                chunk.magnitude().to_bytes_be()
+            };
+            bytes.extend_from_slice(&(position as u32).to_be_bytes());
+            bytes.extend_from_slice(&(value.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(&value);
+        }
+        bytes
+    }
+
+    /// Parses a share written by `to_bytes`, rejecting anything but exactly
+    /// one well-formed share with its chunks in order.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let rest = bytes.strip_prefix(&MAGIC).ok_or(DecodeError::BadMagic)?;
+        let (version, rest) = take(rest, 1)?;
+        if version[0] != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version[0]));
+        }
+        let (index, rest) = take_u64(rest)?;
+        let (len, rest) = take_u64(rest)?;
+        let (count, mut rest) = take_u32(rest)?;
+        let mut chunks = Vec::new();
+        for expected in 0..count {
+            let (position, tail) = take_u32(rest)?;
+            if position != expected {
+                return Err(DecodeError::MisorderedChunk);
+            }
+            let (value, tail) = take_magnitude(tail)?;
+            chunks.push(value);
+            rest = tail;
+        }
+        if !rest.is_empty() {
+            return Err(DecodeError::TrailingBytes);
+        }
+        Ok(ChunkedShare::new(index, len, chunks))
+    }
+}
+
+impl SecretShare {
+    /// Bytes per chunk, the most for which every chunk lies below `p`.
+    pub fn chunk_bytes(&self) -> usize {
+        (self.p().bits() as usize - 1) / 8
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split_chunked(&self, secret: &[u8]) -> Result<Vec<ChunkedShare>, TinySsError> {
+        use crate::field::Field;
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_chunked_with(secret, || {
+            self.field().random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Splits `secret` chunk by chunk, drawing the coefficients of every
+    /// chunk's polynomial from `sample` as `split_with` does. Fails with
+    /// `SecretOutOfRange` if `p` is below 257, where chunks would be empty.
+    pub fn split_chunked_with<S>(
+        &self,
+        secret: &[u8],
+        mut sample: S,
+    ) -> Result<Vec<ChunkedShare>, TinySsError>
+    where
+        S: FnMut() -> BigInt,
+    {
+        let chunk_bytes = self.chunk_bytes();
+        if chunk_bytes == 0 {
+            return Err(TinySsError::SecretOutOfRange);
+        }
+        let mut shares: Vec<ChunkedShare> = (1..=self.n())
+            .map(|index| ChunkedShare::new(index, secret.len(), Vec::new()))
+            .collect();
+        for chunk in secret.chunks(chunk_bytes) {
+            let value = BigInt::from_bytes_be(Sign::Plus, chunk);
+            for (holder, share) in shares.iter_mut().zip(self.split_with(value, &mut sample)?) {
+                holder.chunks.push(share.value().clone());
+            }
+        }
+        Ok(shares)
+    }
+
+    /// Recovers a secret split by `split_chunked` from at least `t` shares,
+    /// which must agree on the length of the secret and hold a value for
+    /// each of its chunks.
+    pub fn recover_chunked(&self, shares: &[ChunkedShare]) -> Result<Vec<u8>, TinySsError> {
+        let chunk_bytes = self.chunk_bytes();
+        let len = shares.first().map_or(0, |s| s.len);
+        if chunk_bytes == 0 {
+            return Err(TinySsError::SecretOutOfRange);
+        }
+        let indices: Vec<Share<()>> = shares.iter().map(|s| Share::new(s.index, ())).collect();
+        poly::distinct_shares(&indices, self.t())?;
+        let count = len.div_ceil(chunk_bytes);
+        if shares
+            .iter()
+            .any(|s| s.len != len || s.chunks.len() != count)
+        {
+            return Err(TinySsError::LengthMismatch);
+        }
+        let mut secret = Vec::with_capacity(len);
+        for position in 0..count {
+            let column: Vec<Share> = shares
+                .iter()
+                .map(|s| Share::new(s.index, s.chunks[position].clone()))
+                .collect();
+            let value = self.recover(&column)?;
+            let width = chunk_bytes.min(len - position * chunk_bytes);
+            secret.extend_from_slice(&to_width(&value, width)?);
+        }
+        Ok(secret)
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split_bytes(&self, secret: &[u8]) -> Result<Vec<ChunkedShare>, TinySsError> {
+        use crate::field::Field;
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_bytes_with(secret, || {
+            self.field().random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Splits `secret`, read as one big-endian field element, so that
+    /// `recover_bytes` returns exactly its bytes, leading zeros included.
+    /// Fails with `SecretOutOfRange` if it is longer than `chunk_bytes`;
+    /// `split_chunked` takes secrets of any length.
+    pub fn split_bytes_with<S>(
+        &self,
+        secret: &[u8],
+        sample: S,
+    ) -> Result<Vec<ChunkedShare>, TinySsError>
+    where
+        S: FnMut() -> BigInt,
+    {
+        if secret.len() > self.chunk_bytes() {
+            return Err(TinySsError::SecretOutOfRange);
+        }
+        self.split_chunked_with(secret, sample)
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split_str(&self, secret: &str) -> Result<Vec<ChunkedShare>, TinySsError> {
+        self.split_chunked(secret.as_bytes())
+    }
+
+    /// Splits the UTF-8 bytes of `secret` in chunks, as `split_chunked_with`
+    /// does.
+    pub fn split_str_with<S>(
+        &self,
+        secret: &str,
+        sample: S,
+    ) -> Result<Vec<ChunkedShare>, TinySsError>
+    where
+        S: FnMut() -> BigInt,
+    {
+        self.split_chunked_with(secret.as_bytes(), sample)
+    }
+
+    /// Recovers a string split by `split_str`, failing with `InvalidUtf8` if
+    /// the recovered bytes are not UTF-8, which corrupted shares that still
+    /// recover chunks of the right width most likely produce.
+    pub fn recover_string(&self, shares: &[ChunkedShare]) -> Result<String, TinySsError> {
+        String::from_utf8(self.recover_chunked(shares)?).map_err(|_| TinySsError::InvalidUtf8)
+    }
+
+    /// Recovers a secret split by `split_bytes` from at least `t` shares.
+    pub fn recover_bytes(&self, shares: &[ChunkedShare]) -> Result<Vec<u8>, TinySsError> {
+        if shares.iter().any(|s| s.chunks.len() > 1) {
+            return Err(TinySsError::LengthMismatch);
+        }
+        self.recover_chunked(shares)
+    }
+}
+
+// The big-endian bytes of `value`, left padded to `width`
+fn to_width(value: &BigInt, width: usize) -> Result<Vec<u8>, TinySsError> {
+    let magnitude = if value.is_zero() {
+        Vec::new()
+    } else {
+        value.magnitude().to_bytes_be()
+    };
+    // a value wider than its slot comes from corrupted shares
+    if magnitude.len() > width {
+        return Err(TinySsError::IntegrityCheckFailed);
+    }
+    let mut bytes = vec![0u8; width - magnitude.len()];
+    bytes.extend_from_slice(&magnitude);
+    Ok(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunked_test() {
+        let ss = SecretShare::new(2, 3, BigInt::from(65537)).unwrap();
+        assert_eq!(ss.chunk_bytes(), 2);
+        let secret = b"\x00\x01longer than p\x00\x00";
+        let mut next = 0;
+        let shares = ss
+            .split_chunked_with(secret, || {
+                next += 11;
+                BigInt::from(next)
+            })
+            .unwrap();
+        assert_eq!(shares[0].chunks().len(), 9);
+        assert_eq!(ss.recover_chunked(&shares[1..]).unwrap(), secret);
+        assert_eq!(
+            ss.recover_chunked(&[shares[2].clone(), shares[0].clone()])
+                .unwrap(),
+            secret
+        );
+
+        let decoded: Vec<ChunkedShare> = shares
+            .iter()
+            .map(|s| ChunkedShare::from_bytes(&s.to_bytes()).unwrap())
+            .collect();
+        assert_eq!(decoded, shares);
+        let mut swapped = shares[0].to_bytes();
+        // the position of the first chunk, right after the header
+        swapped[27] = 1;
+        assert_eq!(
+            ChunkedShare::from_bytes(&swapped),
+            Err(DecodeError::MisorderedChunk)
+        );
+
+        let mut short = shares[1].clone();
+        short.chunks.pop();
+        assert_eq!(
+            ss.recover_chunked(&[shares[0].clone(), short]),
+            Err(TinySsError::LengthMismatch)
+        );
+        let empty = ss.split_chunked_with(b"", || BigInt::from(1)).unwrap();
+        assert_eq!(ss.recover_chunked(&empty).unwrap(), b"");
+        assert_eq!(
+            ss.recover_chunked(&empty[..1]),
+            Err(TinySsError::NotEnoughShares {
+                required: 2,
+                got: 1
+            })
+        );
+        let tiny = SecretShare::new(2, 3, BigInt::from(251)).unwrap();
+        assert_eq!(
+            tiny.split_chunked_with(secret, || BigInt::from(1)),
+            Err(TinySsError::SecretOutOfRange)
+        );
+    }
+
+    #[test]
+    fn split_str_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(65537)).unwrap();
+        let passphrase = "correct horse battery stäple";
+        let mut next = 0;
+        let mut sample = || {
+            next += 3;
+            BigInt::from(next)
+        };
+        let shares = ss.split_str_with(passphrase, &mut sample).unwrap();
+        assert_eq!(ss.recover_string(&shares[1..4]).unwrap(), passphrase);
+
+        // the bytes of a dealing of something other than text
+        let bytes = ss.split_chunked_with(b"\xc3\x28", &mut sample).unwrap();
+        assert_eq!(
+            ss.recover_string(&bytes[..3]),
+            Err(TinySsError::InvalidUtf8)
+        );
+    }
+
+    #[test]
+    fn split_bytes_test() {
+        let ss = SecretShare::secp256k1_order(3, 5).unwrap();
+        let mut key = [0u8; 31];
+        key[2] = 0x7f;
+        let mut next = 0;
+        let mut sample = || {
+            next += 5;
+            BigInt::from(next)
+        };
+        let shares = ss.split_bytes_with(&key, &mut sample).unwrap();
+        assert_eq!(shares[0].chunks().len(), 1);
+        assert_eq!(shares[0].len(), 31);
+        assert_eq!(ss.recover_bytes(&shares[2..]).unwrap(), key);
+        let zeros = ss.split_bytes_with(&[0; 4], &mut sample).unwrap();
+        assert_eq!(ss.recover_bytes(&zeros[..3]).unwrap(), [0; 4]);
+
+        assert_eq!(
+            ss.split_bytes_with(&[1; 32], &mut sample),
+            Err(TinySsError::SecretOutOfRange)
+        );
+        let long = ss.split_chunked_with(&[1; 32], &mut sample).unwrap();
+        assert_eq!(ss.recover_bytes(&long), Err(TinySsError::LengthMismatch));
+        let mut wrong = shares.clone();
+        wrong[0] = ChunkedShare::new(1, 31, vec![BigInt::from(5)]);
+        assert_eq!(
+            ss.recover_bytes(&wrong[..3]),
+            Err(TinySsError::IntegrityCheckFailed)
+        );
+    }
+}
@@ -0,0 +1,157 @@
+// Text encodings for binary shares, lowercase hex and padded standard
+// base64, and the readers of the fields of the binary forms.
+
+use crate::DecodeError;
+use alloc::{string::String, vec::Vec};
+use num_bigint::{BigInt, Sign};
+
+const HEX: &[u8; 16] = b"0123456789abcdef";
+const BASE64: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut hex = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        hex.push(HEX[(byte >> 4) as usize] as char);
+        hex.push(HEX[(byte & 0x0f) as usize] as char);
+    }
+    hex
+}
+
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, DecodeError> {
+    let hex = hex.as_bytes();
+    if !hex.len().is_multiple_of(2) {
+        return Err(DecodeError::InvalidEncoding);
+    }
+    hex.chunks(2)
+        .map(|pair| Ok((hex_digit(pair[0])? << 4) | hex_digit(pair[1])?))
+        .collect()
+}
+
+fn hex_digit(c: u8) -> Result<u8, DecodeError> {
+    match c {
+        b'0'..=b'9' => Ok(c - b'0'),
+        b'a'..=b'f' => Ok(c - b'a' + 10),
+        b'A'..=b'F' => Ok(c - b'A' + 10),
+        _ => Err(DecodeError::InvalidEncoding),
+    }
+}
+
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            chunk.get(1).copied().unwrap_or(0),
+            chunk.get(2).copied().unwrap_or(0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(BASE64[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+    encoded
+}
+
+pub fn from_base64(encoded: &str) -> Result<Vec<u8>, DecodeError> {
+    let encoded = encoded.as_bytes();
+    if !encoded.len().is_multiple_of(4) {
+        return Err(DecodeError::InvalidEncoding);
+    }
+    let mut bytes = Vec::with_capacity(encoded.len() / 4 * 3);
+    let chunks = encoded.len() / 4;
+    for (i, chunk) in encoded.chunks(4).enumerate() {
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && i + 1 != chunks) {
+            return Err(DecodeError::InvalidEncoding);
+        }
+        let mut n = 0u32;
+        for &c in &chunk[..4 - padding] {
+            n = n << 6 | base64_digit(c)?;
+        }
+        n <<= 6 * padding;
+        let decoded = [(n >> 16) as u8, (n >> 8) as u8, n as u8];
+        // reject non-canonical encodings with stray bits in the padding
+        if decoded[3 - padding..].iter().any(|&b| b != 0) {
+            return Err(DecodeError::InvalidEncoding);
+        }
+        bytes.extend_from_slice(&decoded[..3 - padding]);
+    }
+    Ok(bytes)
+}
+
+fn base64_digit(c: u8) -> Result<u32, DecodeError> {
+    match c {
+        b'A'..=b'Z' => Ok((c - b'A') as u32),
+        b'a'..=b'z' => Ok((c - b'a' + 26) as u32),
+        b'0'..=b'9' => Ok((c - b'0' + 52) as u32),
+        b'+' => Ok(62),
+        b'/' => Ok(63),
+        _ => Err(DecodeError::InvalidEncoding),
+    }
+}
+
+// Splits `len` bytes off the front of `bytes`
+pub fn take(bytes: &[u8], len: usize) -> Result<(&[u8], &[u8]), DecodeError> {
+    if bytes.len() < len {
+        return Err(DecodeError::Truncated);
+    }
+    Ok(bytes.split_at(len))
+}
+
+pub fn take_u32(bytes: &[u8]) -> Result<(usize, &[u8]), DecodeError> {
+    let (value, rest) = take(bytes, 4)?;
+    let value = u32::from_be_bytes(value.try_into().unwrap());
+    let value = usize::try_from(value).map_err(|_| DecodeError::IndexOverflow)?;
+    Ok((value, rest))
+}
+
+pub fn take_u64(bytes: &[u8]) -> Result<(usize, &[u8]), DecodeError> {
+    let (value, rest) = take(bytes, 8)?;
+    let value = u64::from_be_bytes(value.try_into().unwrap());
+    let value = usize::try_from(value).map_err(|_| DecodeError::IndexOverflow)?;
+    Ok((value, rest))
+}
+
+// A big-endian `u32` length followed by a big-endian magnitude
+pub fn take_magnitude(bytes: &[u8]) -> Result<(BigInt, &[u8]), DecodeError> {
+    let (len, rest) = take_u32(bytes)?;
+    let (value, rest) = take(rest, len)?;
+    Ok((BigInt::from_bytes_be(Sign::Plus, value), rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_test() {
+        assert_eq!(to_hex(&[0x00, 0x4d, 0xff]), "004dff");
+        assert_eq!(from_hex("004DfF").unwrap(), [0x00, 0x4d, 0xff]);
+        assert_eq!(from_hex("abc"), Err(DecodeError::InvalidEncoding));
+        assert_eq!(from_hex("zz"), Err(DecodeError::InvalidEncoding));
+    }
+
+    #[test]
+    fn base64_test() {
+        for (raw, encoded) in [
+            (&b""[..], ""),
+            (b"f", "Zg=="),
+            (b"fo", "Zm8="),
+            (b"foo", "Zm9v"),
+            (b"foob", "Zm9vYg=="),
+            (b"fooba", "Zm9vYmE="),
+            (b"foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(to_base64(raw), encoded);
+            assert_eq!(from_base64(encoded).unwrap(), raw);
+        }
+        assert_eq!(from_base64("Zm9"), Err(DecodeError::InvalidEncoding));
+        assert_eq!(from_base64("Zg==Zm8="), Err(DecodeError::InvalidEncoding));
+        assert_eq!(from_base64("Zh=="), Err(DecodeError::InvalidEncoding));
+        assert_eq!(from_base64("Z!=="), Err(DecodeError::InvalidEncoding));
+    }
+}
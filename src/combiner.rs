@@ -0,0 +1,233 @@
+//! Recovery from shares that arrive one at a time.
+//!
+//! A `Combiner` checks each submitted share as it comes in, so that a
+//! holder submitting a bad share is told at once, and recovers the secret
+//! once `t` shares are in. A rejected submission leaves the combiner as it
+//! was; the `Rejection` names its position among the submissions, its
+//! index and the reason.
+
+use crate::{field::Field, field::PrimeField, SecretShare, Share, TinySsError};
+use alloc::{boxed::Box, vec::Vec};
+use core::fmt;
+
+/// A submission `Combiner::add_share` turned down.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rejection {
+    /// position of the submission, counting rejected ones, from 0
+    pub submission: usize,
+    /// index the share claimed
+    pub index: usize,
+    pub reason: TinySsError,
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "submission {} with index {} rejected: {}",
+            self.submission, self.index, self.reason
+        )
+    }
+}
+
+impl From<Rejection> for TinySsError {
+    fn from(rejection: Rejection) -> Self {
+        rejection.reason
+    }
+}
+
+type Check<'a, V> = Box<dyn Fn(&Share<V>) -> bool + 'a>;
+
+/// Collects and checks shares of one dealing until it can recover.
+pub struct Combiner<'a, F: Field = PrimeField> {
+    scheme: &'a SecretShare<F>,
+    shares: Vec<Share<F::Elem>>,
+    /// submissions so far, including rejected ones
+    submissions: usize,
+    check: Option<Check<'a, F::Elem>>,
+}
+
+impl<F: Field + fmt::Debug> fmt::Debug for Combiner<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Combiner")
+            .field("scheme", self.scheme)
+            .field("shares", &self.shares)
+            .field("submissions", &self.submissions)
+            .field("check", &self.check.is_some())
+            .finish()
+    }
+}
+
+impl<'a, F: Field> Combiner<'a, F> {
+    pub fn new(scheme: &'a SecretShare<F>) -> Self {
+        Combiner {
+            scheme,
+            shares: Vec::new(),
+            submissions: 0,
+            check: None,
+        }
+    }
+
+    /// Also rejects shares for which `check` returns false, such as those
+    /// failing a commitment check, with `InvalidShare`.
+    pub fn with_check<C>(mut self, check: C) -> Self
+    where
+        C: Fn(&Share<F::Elem>) -> bool + 'a,
+    {
+        self.check = Some(Box::new(check));
+        self
+    }
+
+    /// Checks and keeps `share`. It is rejected with `InvalidIndex` for an
+    /// index outside `1..=n`, `InvalidValue` for a value that is not a
+    /// canonical field element, `ConflictingShares` for a value differing
+    /// from an accepted share of the same index, and `InvalidShare` if the
+    /// check fails. A repeat of an accepted share is accepted and ignored.
+    pub fn add_share(&mut self, share: Share<F::Elem>) -> Result<&mut Self, Rejection> {
+        let submission = self.submissions;
+        self.submissions += 1;
+        let index = share.index();
+        let reject = |reason| Rejection {
+            submission,
+            index,
+            reason,
+        };
+        if index == 0 || index > self.scheme.n() {
+            return Err(reject(TinySsError::InvalidIndex));
+        }
+        if !self.scheme.field().is_canonical(share.value()) {
+            return Err(reject(TinySsError::InvalidValue { index }));
+        }
+        if let Some(accepted) = self.shares.iter().find(|s| s.index() == index) {
+            if accepted.value() != share.value() {
+                return Err(reject(TinySsError::ConflictingShares { index }));
+            }
+            return Ok(self);
+        }
+        if self.check.as_ref().is_some_and(|check| !check(&share)) {
+            return Err(reject(TinySsError::InvalidShare { index }));
+        }
+        self.shares.push(share);
+        Ok(self)
+    }
+
+    /// accepted shares, in order of arrival
+    pub fn shares(&self) -> &[Share<F::Elem>] {
+        &self.shares
+    }
+
+    /// whether enough shares are in to recover
+    pub fn is_ready(&self) -> bool {
+        self.shares.len() >= self.scheme.t()
+    }
+
+    /// Recovers the secret from the accepted shares.
+    pub fn finish(&self) -> Result<F::Elem, TinySsError> {
+        self.scheme.recover(&self.shares)
+    }
+}
+
+#[cfg(feature = "vss")]
+impl<'a> Combiner<'a> {
+    /// Also rejects shares that do not match the dealer's Feldman
+    /// commitments in `group`.
+    pub fn with_commitments(
+        self,
+        group: &'a crate::group::SchnorrGroup,
+        commitments: &'a [num_bigint::BigInt],
+    ) -> Self {
+        self.with_check(move |share| group.verify_feldman(commitments, share))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn combiner_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        let shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+        let mut combiner = Combiner::new(&ss);
+        combiner
+            .add_share(shares[4].clone())
+            .unwrap()
+            .add_share(shares[4].clone())
+            .unwrap();
+        assert!(!combiner.is_ready());
+        assert_eq!(
+            combiner
+                .add_share(Share::new(6, BigInt::from(1)))
+                .unwrap_err(),
+            Rejection {
+                submission: 2,
+                index: 6,
+                reason: TinySsError::InvalidIndex
+            }
+        );
+        assert_eq!(
+            combiner
+                .add_share(Share::new(2, BigInt::from(1613)))
+                .unwrap_err()
+                .reason,
+            TinySsError::InvalidValue { index: 2 }
+        );
+        assert_eq!(
+            combiner
+                .add_share(Share::new(5, BigInt::from(1)))
+                .unwrap_err()
+                .reason,
+            TinySsError::ConflictingShares { index: 5 }
+        );
+        assert_eq!(
+            combiner.finish(),
+            Err(TinySsError::NotEnoughShares {
+                required: 3,
+                got: 1
+            })
+        );
+        combiner
+            .add_share(shares[0].clone())
+            .unwrap()
+            .add_share(shares[2].clone())
+            .unwrap();
+        assert!(combiner.is_ready());
+        assert_eq!(combiner.finish().unwrap(), BigInt::from(1234));
+
+        let mut checked = Combiner::new(&ss).with_check(|share| share.index() != 3);
+        let rejection = checked.add_share(shares[2].clone()).unwrap_err();
+        assert_eq!(rejection.submission, 0);
+        assert_eq!(
+            TinySsError::from(rejection),
+            TinySsError::InvalidShare { index: 3 }
+        );
+        assert!(checked.shares().is_empty());
+    }
+
+    #[cfg(feature = "vss")]
+    #[test]
+    fn with_commitments_test() {
+        let group = crate::group::SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        let ss = SecretShare::new(2, 3, group.q().clone()).unwrap();
+        let polynomial = [BigInt::from(1000), BigInt::from(17)];
+        let commitments: Vec<BigInt> = polynomial.iter().map(|a| group.exp(group.g(), a)).collect();
+        let shares = ss
+            .split_with_coefficients(polynomial[0].clone(), &polynomial[1..])
+            .unwrap();
+        let mut combiner = Combiner::new(&ss).with_commitments(&group, &commitments);
+        let forged = Share::new(1, group.scalars().add(shares[0].value(), &BigInt::from(1)));
+        assert_eq!(
+            combiner.add_share(forged).unwrap_err().reason,
+            TinySsError::InvalidShare { index: 1 }
+        );
+        combiner
+            .add_share(shares[0].clone())
+            .unwrap()
+            .add_share(shares[1].clone())
+            .unwrap();
+        assert_eq!(combiner.finish().unwrap(), BigInt::from(1000));
+    }
+}
@@ -0,0 +1,216 @@
+//! Compartmented access structures.
+//!
+//! The secret is split into random additive parts, one per compartment, and
+//! every part is shared with an independent Shamir scheme among the members
+//! of its compartment. Recovery thus needs a threshold of members from every
+//! compartment, e.g. "at least 2 from legal and at least 2 from
+//! engineering".
+
+use crate::{
+    field::{Field, PrimeField},
+    SecretShare, Share, TinySsError,
+};
+use alloc::{string::String, vec::Vec};
+
+/// A share held by a member of one compartment.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompartmentShare<V = num_bigint::BigInt> {
+    compartment: String,
+    share: Share<V>,
+}
+
+impl<V> CompartmentShare<V> {
+    pub fn new(compartment: &str, share: Share<V>) -> Self {
+        CompartmentShare {
+            compartment: String::from(compartment),
+            share,
+        }
+    }
+
+    pub fn compartment(&self) -> &str {
+        &self.compartment
+    }
+
+    /// the share within the compartment's own scheme
+    pub fn share(&self) -> &Share<V> {
+        &self.share
+    }
+}
+
+/// A scheme needing a threshold of members from every compartment.
+#[derive(Clone, Debug)]
+pub struct CompartmentedSharing<F: Field = PrimeField> {
+    /// names and schemes, in the order they were added
+    compartments: Vec<(String, SecretShare<F>)>,
+    field: F,
+}
+
+impl<F: Field + Clone> CompartmentedSharing<F> {
+    /// Creates a scheme without compartments yet.
+    pub fn new(field: F) -> Self {
+        CompartmentedSharing {
+            compartments: Vec::new(),
+            field,
+        }
+    }
+
+    /// Adds a compartment of `n` members, `t` of whom must take part.
+    pub fn add_compartment(
+        &mut self,
+        name: &str,
+        t: usize,
+        n: usize,
+    ) -> Result<&mut Self, TinySsError> {
+        if self.compartment(name).is_some() {
+            return Err(TinySsError::InvalidParticipant);
+        }
+        let scheme = SecretShare::with_field(t, n, self.field.clone())?;
+        self.compartments.push((String::from(name), scheme));
+        Ok(self)
+    }
+
+    /// Scheme of the compartment called `name`, if any.
+    pub fn compartment(&self, name: &str) -> Option<&SecretShare<F>> {
+        self.compartments
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, scheme)| scheme)
+    }
+
+    pub fn field(&self) -> &F {
+        &self.field
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split_compartmented(
+        &self,
+        secret: F::Elem,
+    ) -> Result<Vec<CompartmentShare<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_compartmented_with(secret, || self.field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Splits `secret` into shares for the members of every compartment,
+    /// compartment by compartment, drawing the randomness from `sample`,
+    /// which must return uniformly random field elements.
+    pub fn split_compartmented_with<S>(
+        &self,
+        secret: F::Elem,
+        mut sample: S,
+    ) -> Result<Vec<CompartmentShare<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        if self.compartments.is_empty() {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        // random parts for all but the last compartment, which gets the rest
+        let mut rest = secret;
+        let mut parts: Vec<F::Elem> = (1..self.compartments.len())
+            .map(|_| {
+                let part = sample();
+                rest = self.field.sub(&rest, &part);
+                part
+            })
+            .collect();
+        parts.push(rest);
+
+        let mut shares = Vec::new();
+        for ((name, scheme), part) in self.compartments.iter().zip(parts) {
+            for share in scheme.split_with(part, &mut sample)? {
+                shares.push(CompartmentShare::new(name, share));
+            }
+        }
+        Ok(shares)
+    }
+
+    /// Routes the shares to their compartments and recovers the secret,
+    /// failing unless every compartment reaches its threshold.
+    pub fn recover(&self, shares: &[CompartmentShare<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        if shares
+            .iter()
+            .any(|s| self.compartment(&s.compartment).is_none())
+        {
+            return Err(TinySsError::InvalidParticipant);
+        }
+        let mut secret = self.field.zero();
+        for (name, scheme) in &self.compartments {
+            let routed: Vec<Share<F::Elem>> = shares
+                .iter()
+                .filter(|s| &s.compartment == name)
+                .map(|s| s.share.clone())
+                .collect();
+            secret = self.field.add(&secret, &scheme.recover(&routed)?);
+        }
+        Ok(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    fn scheme() -> CompartmentedSharing {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        let mut compartmented = CompartmentedSharing::new(field);
+        compartmented
+            .add_compartment("legal", 2, 3)
+            .unwrap()
+            .add_compartment("engineering", 2, 4)
+            .unwrap();
+        compartmented
+    }
+
+    #[test]
+    fn compartmented_test() {
+        let compartmented = scheme();
+        let mut next = 5;
+        let shares = compartmented
+            .split_compartmented_with(BigInt::from(1234), || {
+                next += 211;
+                BigInt::from(next)
+            })
+            .unwrap();
+        assert_eq!(shares.len(), 7);
+        assert_eq!(shares[3].compartment(), "engineering");
+
+        // two of legal and two of engineering, in any order
+        let chosen = [
+            shares[6].clone(),
+            shares[0].clone(),
+            shares[4].clone(),
+            shares[2].clone(),
+        ];
+        assert_eq!(compartmented.recover(&chosen).unwrap(), BigInt::from(1234));
+
+        // all of engineering is not enough without legal
+        assert_eq!(
+            compartmented.recover(&shares[2..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 2,
+                got: 1
+            }
+        );
+    }
+
+    #[test]
+    fn compartment_error_test() {
+        let mut compartmented = scheme();
+        assert_eq!(
+            compartmented.add_compartment("legal", 2, 3).unwrap_err(),
+            TinySsError::InvalidParticipant
+        );
+        let stray = CompartmentShare::new("sales", Share::new(1, BigInt::from(1)));
+        assert_eq!(
+            compartmented.recover(&[stray]).unwrap_err(),
+            TinySsError::InvalidParticipant
+        );
+        let empty = CompartmentedSharing::new(PrimeField::new(BigInt::from(1613)).unwrap());
+        assert_eq!(
+            empty.split_compartmented_with(BigInt::from(1), || BigInt::from(1)),
+            Err(TinySsError::InvalidThreshold)
+        );
+    }
+}
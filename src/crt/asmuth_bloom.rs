@@ -0,0 +1,166 @@
+use super::{check_moduli, combine, coprime_moduli, random_below};
+use crate::{Share, TinySsError};
+use alloc::{vec, vec::Vec};
+use num_bigint::{BigInt, Sign};
+use num_traits::One;
+
+/// extra bits of the generated moduli over the secret, which bound the
+/// leakage of `t - 1` shares
+const MARGIN_BITS: u64 = 64;
+
+/// The Asmuth–Bloom `t`-of-`n` scheme over secrets modulo `m0`.
+///
+/// The dealer blinds the secret `s` as `y = s + a * m0` with a random `a`
+/// such that `y` stays below the product of the `t` smallest moduli, and
+/// share `i` is `y` modulo `m_i`. The moduli must satisfy `m0` times the
+/// product of the `t - 1` largest being below the product of the `t`
+/// smallest; the larger the gap, the closer `t - 1` shares come to being
+/// independent of the secret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AsmuthBloom {
+    /// threshold
+    t: usize,
+    /// modulus of the secrets
+    m0: BigInt,
+    /// increasing, pairwise coprime moduli, one per holder
+    moduli: Vec<BigInt>,
+}
+
+impl AsmuthBloom {
+    /// Creates a scheme from `0 < t < n` and `n` moduli, all of them
+    /// coprime to and larger than `m0`.
+    pub fn new(t: usize, m0: BigInt, moduli: Vec<BigInt>) -> Result<Self, TinySsError> {
+        if t == 0 || t >= moduli.len() {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        let mut chain = vec![m0.clone()];
+        chain.extend_from_slice(&moduli);
+        check_moduli(&chain)?;
+        let scheme = AsmuthBloom { t, m0, moduli };
+        let smallest: BigInt = scheme.moduli[..t].iter().product();
+        let largest: BigInt = scheme.moduli[scheme.n() - t + 1..].iter().product();
+        if &scheme.m0 * largest >= smallest {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(scheme)
+    }
+
+    /// Generates a scheme for secrets of `secret_bits` bits, so `m0` is
+    /// `2^secret_bits`, with moduli 64 bits longer.
+    pub fn generate(t: usize, n: usize, secret_bits: u64) -> Result<Self, TinySsError> {
+        if secret_bits == 0 {
+            return Err(TinySsError::InvalidModulus);
+        }
+        let m0 = BigInt::one() << secret_bits;
+        let start = BigInt::one() << (secret_bits + MARGIN_BITS - 1);
+        let moduli = coprime_moduli(start, n, core::slice::from_ref(&m0));
+        AsmuthBloom::new(t, m0, moduli)
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.moduli.len()
+    }
+
+    /// modulus of the secrets
+    pub fn m0(&self) -> &BigInt {
+        &self.m0
+    }
+
+    pub fn moduli(&self) -> &[BigInt] {
+        &self.moduli
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: &BigInt) -> Result<Vec<Share>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, &mut |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits a secret in `[0, m0)`, drawing the blinding from
+    /// `fill_random`, which must fill the buffer with uniformly random
+    /// bytes.
+    pub fn split_with(
+        &self,
+        secret: &BigInt,
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<Vec<Share>, TinySsError> {
+        if secret.sign() == Sign::Minus || *secret >= self.m0 {
+            return Err(TinySsError::SecretOutOfRange);
+        }
+        // y = s + a * m0 < product of the t smallest moduli
+        let smallest: BigInt = self.moduli[..self.t].iter().product();
+        let bound = (smallest - 1 - secret) / &self.m0 + 1;
+        let y = secret + random_below(&bound, fill_random) * &self.m0;
+        Ok(self
+            .moduli
+            .iter()
+            .enumerate()
+            .map(|(i, m)| Share::new(i + 1, &y % m))
+            .collect())
+    }
+
+    /// Recovers the secret from at least `t` shares.
+    pub fn recover(&self, shares: &[Share]) -> Result<BigInt, TinySsError> {
+        let (y, _) = combine(&self.moduli, shares, self.t)?;
+        Ok(y % &self.m0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+
+    #[test]
+    fn asmuth_bloom_test() {
+        // 5 * 23 * 29 = 3335 < 17 * 19 * 23 = 7429
+        let moduli = [17, 19, 23, 29].map(BigInt::from).to_vec();
+        let asmuth_bloom = AsmuthBloom::new(3, BigInt::from(5), moduli).unwrap();
+        let mut fill = counter(41);
+        let shares = asmuth_bloom
+            .split_with(&BigInt::from(3), &mut fill)
+            .unwrap();
+        assert_eq!(asmuth_bloom.recover(&shares[1..]).unwrap(), BigInt::from(3));
+        assert_eq!(
+            asmuth_bloom.recover(&shares[2..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+        assert_eq!(
+            asmuth_bloom.split_with(&BigInt::from(5), &mut fill),
+            Err(TinySsError::SecretOutOfRange)
+        );
+
+        // the blinding differs between dealings
+        let again = asmuth_bloom
+            .split_with(&BigInt::from(3), &mut fill)
+            .unwrap();
+        assert_ne!(shares, again);
+    }
+
+    #[test]
+    fn generate_test() {
+        let asmuth_bloom = AsmuthBloom::generate(3, 5, 128).unwrap();
+        assert!(asmuth_bloom.moduli().iter().all(|m| m.bits() == 192));
+        let secret = asmuth_bloom.m0() - 1;
+        let mut fill = counter(7);
+        let shares = asmuth_bloom.split_with(&secret, &mut fill).unwrap();
+        assert_eq!(asmuth_bloom.recover(&shares[..3]).unwrap(), secret);
+
+        // m0 too large for the moduli
+        let moduli = [17, 19, 23, 29].map(BigInt::from).to_vec();
+        assert_eq!(
+            AsmuthBloom::new(3, BigInt::from(13), moduli).unwrap_err(),
+            TinySsError::InvalidModulus
+        );
+    }
+}
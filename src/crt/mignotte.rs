@@ -0,0 +1,134 @@
+use super::{check_moduli, combine, coprime_moduli};
+use crate::{Share, TinySsError};
+use alloc::vec::Vec;
+use num_bigint::BigInt;
+use num_traits::One;
+
+/// Mignotte's `t`-of-`n` scheme: share `i` is the secret modulo `m_i`.
+///
+/// The moduli form a Mignotte sequence: the product `alpha` of the `t`
+/// smallest exceeds the product `beta` of the `t - 1` largest, and secrets
+/// must lie strictly between the two. Fewer than `t` shares narrow the
+/// secret down considerably, so the scheme does not have perfect privacy;
+/// see `AsmuthBloom` for a variant that does.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Mignotte {
+    /// threshold
+    t: usize,
+    /// increasing, pairwise coprime moduli, one per holder
+    moduli: Vec<BigInt>,
+}
+
+impl Mignotte {
+    /// Creates a scheme from `0 < t < n` and a Mignotte sequence of `n`
+    /// moduli.
+    pub fn new(t: usize, moduli: Vec<BigInt>) -> Result<Self, TinySsError> {
+        if t == 0 || t >= moduli.len() {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        check_moduli(&moduli)?;
+        let scheme = Mignotte { t, moduli };
+        if scheme.alpha() <= scheme.beta() + 1 {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(scheme)
+    }
+
+    /// Generates a scheme whose moduli are the smallest pairwise coprime
+    /// integers of `bits` bits.
+    pub fn generate(t: usize, n: usize, bits: u64) -> Result<Self, TinySsError> {
+        if bits < 2 {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Mignotte::new(t, coprime_moduli(BigInt::one() << (bits - 1), n, &[]))
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.moduli.len()
+    }
+
+    pub fn moduli(&self) -> &[BigInt] {
+        &self.moduli
+    }
+
+    /// product of the `t` smallest moduli, secrets must be below it
+    pub fn alpha(&self) -> BigInt {
+        self.moduli[..self.t].iter().product()
+    }
+
+    /// product of the `t - 1` largest moduli, secrets must be above it
+    pub fn beta(&self) -> BigInt {
+        self.moduli[self.n() - self.t + 1..].iter().product()
+    }
+
+    /// Splits a secret with `beta < secret < alpha`.
+    pub fn split(&self, secret: &BigInt) -> Result<Vec<Share>, TinySsError> {
+        if *secret <= self.beta() || *secret >= self.alpha() {
+            return Err(TinySsError::SecretOutOfRange);
+        }
+        Ok(self
+            .moduli
+            .iter()
+            .enumerate()
+            .map(|(i, m)| Share::new(i + 1, secret % m))
+            .collect())
+    }
+
+    /// Recovers the secret from at least `t` shares.
+    pub fn recover(&self, shares: &[Share]) -> Result<BigInt, TinySsError> {
+        let (secret, _) = combine(&self.moduli, shares, self.t)?;
+        Ok(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn mignotte_test() {
+        let moduli = [11, 13, 17, 19, 23].map(BigInt::from).to_vec();
+        let mignotte = Mignotte::new(3, moduli).unwrap();
+        assert_eq!(mignotte.alpha(), BigInt::from(11 * 13 * 17));
+        assert_eq!(mignotte.beta(), BigInt::from(19 * 23));
+
+        let secret = BigInt::from(1000);
+        let shares = mignotte.split(&secret).unwrap();
+        assert_eq!(shares[0], Share::new(1, BigInt::from(1000 % 11)));
+        assert_eq!(mignotte.recover(&shares[2..]).unwrap(), secret);
+        assert_eq!(mignotte.recover(&shares[..3]).unwrap(), secret);
+        assert_eq!(
+            mignotte.recover(&shares[3..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+        assert_eq!(
+            mignotte.split(&BigInt::from(400)),
+            Err(TinySsError::SecretOutOfRange)
+        );
+    }
+
+    #[test]
+    fn generate_test() {
+        let mignotte = Mignotte::generate(3, 5, 64).unwrap();
+        assert!(mignotte.moduli().iter().all(|m| m.bits() == 64));
+        let secret = mignotte.beta() + 12345;
+        let shares = mignotte.split(&secret).unwrap();
+        assert_eq!(mignotte.recover(&shares[1..4]).unwrap(), secret);
+
+        // not a Mignotte sequence
+        let moduli = [3, 5, 7, 11, 13].map(BigInt::from).to_vec();
+        assert_eq!(
+            Mignotte::new(3, moduli).unwrap_err(),
+            TinySsError::InvalidModulus
+        );
+    }
+}
@@ -0,0 +1,119 @@
+//! Threshold schemes based on the Chinese remainder theorem.
+//!
+//! Every holder is assigned one of a sequence of pairwise coprime moduli,
+//! and its share is the secret, or a blinded form of it, reduced modulo its
+//! own modulus. Any `t` shares determine the value modulo the product of
+//! their moduli, which the schemes arrange to be large enough.
+
+mod asmuth_bloom;
+mod mignotte;
+
+pub use asmuth_bloom::AsmuthBloom;
+pub use mignotte::Mignotte;
+
+use crate::{poly, Share, TinySsError};
+use alloc::{vec, vec::Vec};
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
+
+// Increasing, pairwise coprime moduli from `start` on, also coprime to all
+// of `others`
+pub(crate) fn coprime_moduli(start: BigInt, n: usize, others: &[BigInt]) -> Vec<BigInt> {
+    let mut moduli: Vec<BigInt> = Vec::with_capacity(n);
+    let mut candidate = start;
+    while moduli.len() < n {
+        if moduli
+            .iter()
+            .chain(others)
+            .all(|m| candidate.modinv(m).is_some())
+        {
+            moduli.push(candidate.clone());
+        }
+        candidate += 1;
+    }
+    moduli
+}
+
+// Moduli must be increasing, larger than one and pairwise coprime
+pub(crate) fn check_moduli(moduli: &[BigInt]) -> Result<(), TinySsError> {
+    if moduli.first().is_none_or(|m| *m <= BigInt::one()) || moduli.windows(2).any(|w| w[0] >= w[1])
+    {
+        return Err(TinySsError::InvalidModulus);
+    }
+    for (i, m) in moduli.iter().enumerate() {
+        if moduli[..i].iter().any(|other| m.modinv(other).is_none()) {
+            return Err(TinySsError::InvalidModulus);
+        }
+    }
+    Ok(())
+}
+
+// Rejection sampling of a uniform value in `[0, bound)`
+pub(crate) fn random_below(bound: &BigInt, fill_random: &mut dyn FnMut(&mut [u8])) -> BigInt {
+    let bits = bound.bits() as usize;
+    let mut bytes = vec![0u8; bits.div_ceil(8)];
+    loop {
+        fill_random(&mut bytes);
+        bytes[0] &= 0xff >> (bytes.len() * 8 - bits);
+        let value = BigInt::from_bytes_be(Sign::Plus, &bytes);
+        if value < *bound {
+            return value;
+        }
+    }
+}
+
+// Combines the first `t` distinct shares into the value modulo the product
+// of their moduli, returned along with that product
+pub(crate) fn combine(
+    moduli: &[BigInt],
+    shares: &[Share],
+    t: usize,
+) -> Result<(BigInt, BigInt), TinySsError> {
+    let unique = poly::distinct_shares(shares, t)?;
+    let unique = &unique[..t];
+    if unique
+        .iter()
+        .any(|s| s.index() == 0 || s.index() > moduli.len())
+    {
+        return Err(TinySsError::InvalidIndex);
+    }
+    let product: BigInt = unique.iter().map(|s| &moduli[s.index() - 1]).product();
+    let mut value = BigInt::zero();
+    for share in unique {
+        let m = &moduli[share.index() - 1];
+        let rest = &product / m;
+        let inverse = rest.modinv(m).ok_or(TinySsError::NotInvertible)?;
+        value += share.value() * rest * inverse;
+    }
+    Ok((value % &product, product))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn moduli_test() {
+        let moduli = coprime_moduli(BigInt::from(10), 4, &[]);
+        assert_eq!(
+            coprime_moduli(BigInt::from(10), 2, &[BigInt::from(2)]),
+            [11, 13].map(BigInt::from)
+        );
+        assert_eq!(moduli, [10, 11, 13, 17].map(BigInt::from));
+        assert_eq!(check_moduli(&moduli), Ok(()));
+        for bad in [&[][..], &[1, 3], &[3, 2], &[4, 6]] {
+            let bad: Vec<BigInt> = bad.iter().map(|&m| BigInt::from(m)).collect();
+            assert_eq!(check_moduli(&bad), Err(TinySsError::InvalidModulus));
+        }
+
+        let shares = [
+            Share::new(3, BigInt::from(4)),
+            Share::new(1, BigInt::from(5)),
+        ];
+        // 95 = 4 mod 13 = 5 mod 10
+        assert_eq!(
+            combine(&moduli, &shares, 2),
+            Ok((BigInt::from(95), BigInt::from(130)))
+        );
+    }
+}
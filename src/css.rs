@@ -0,0 +1,218 @@
+//! Computational secret sharing after Krawczyk ("Secret Sharing Made
+//! Short", CRYPTO '93).
+//!
+//! The payload is encrypted under a fresh random key, the key alone is
+//! Shamir-shared over GF(256), and the ciphertext is dispersed so that any
+//! `t` fragments restore it. Each share thus carries `|secret| / t` bytes of
+//! ciphertext plus a 32-byte key share, instead of `|secret|` bytes. The
+//! cipher is HMAC-SHA256 in counter mode, so privacy is computational.
+
+use crate::{
+    gf256::{ByteShare, Gf256Sharing},
+    ida::{Fragment, Ida},
+    Share, TinySsError,
+};
+use alloc::vec::Vec;
+use core::fmt;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// bytes of the symmetric key
+pub const KEY_BYTES: usize = 32;
+
+/// A share of a payload: a share of the key and a ciphertext fragment.
+#[derive(Clone, PartialEq, Eq)]
+pub struct CssShare {
+    index: usize,
+    /// GF(256) share of the key
+    key: Vec<u8>,
+    /// dispersed piece of the ciphertext
+    fragment: Vec<u8>,
+    /// length of the payload
+    len: usize,
+}
+
+impl fmt::Debug for CssShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CssShare")
+            .field("index", &self.index)
+            .field("key", crate::debug::secret(&self.key))
+            .field("fragment", &self.fragment)
+            .field("len", &self.len)
+            .finish()
+    }
+}
+
+impl CssShare {
+    pub fn new(index: usize, key: Vec<u8>, fragment: Vec<u8>, len: usize) -> Self {
+        CssShare {
+            index,
+            key,
+            fragment,
+            len,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn key(&self) -> &[u8] {
+        &self.key
+    }
+
+    pub fn fragment(&self) -> &[u8] {
+        &self.fragment
+    }
+
+    /// length of the payload
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+}
+
+/// A `t`-of-`n` computational scheme for byte strings.
+#[derive(Clone, Debug)]
+pub struct Css {
+    keys: Gf256Sharing,
+    fragments: Ida,
+}
+
+impl Css {
+    /// Creates a `t`-of-`n` scheme with `0 < t < n <= 255`.
+    pub fn new(t: usize, n: usize) -> Result<Self, TinySsError> {
+        Ok(Css {
+            keys: Gf256Sharing::new(t, n)?,
+            fragments: Ida::new(t, n)?,
+        })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.keys.t()
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.keys.n()
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: &[u8]) -> Vec<CssShare> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits `secret` drawing the key and the coefficients of its sharing
+    /// from `fill_random`, which must fill the buffer with uniformly random
+    /// bytes.
+    pub fn split_with<F>(&self, secret: &[u8], mut fill_random: F) -> Vec<CssShare>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        let mut key = [0u8; KEY_BYTES];
+        fill_random(&mut key);
+        let mut ciphertext = secret.to_vec();
+        apply_keystream(&key, &mut ciphertext);
+        let fragments = self.fragments.disperse(&ciphertext);
+        self.keys
+            .split_with(&key, fill_random)
+            .into_iter()
+            .zip(fragments)
+            .map(|(share, fragment)| {
+                CssShare::new(
+                    share.index(),
+                    share.value().clone(),
+                    fragment.bytes().to_vec(),
+                    secret.len(),
+                )
+            })
+            .collect()
+    }
+
+    /// Recovers the payload from at least `t` shares.
+    pub fn recover(&self, shares: &[CssShare]) -> Result<Vec<u8>, TinySsError> {
+        if shares.iter().any(|s| s.key.len() != KEY_BYTES) {
+            return Err(TinySsError::LengthMismatch);
+        }
+        let fragments: Vec<Fragment> = shares
+            .iter()
+            .map(|s| Fragment::new(s.index, s.len, s.fragment.clone()))
+            .collect();
+        let mut secret = self.fragments.restore(&fragments)?;
+        let key_shares: Vec<ByteShare> = shares
+            .iter()
+            .map(|s| Share::new(s.index, s.key.clone()))
+            .collect();
+        let key = self.keys.recover(&key_shares)?;
+        apply_keystream(&key, &mut secret);
+        Ok(secret)
+    }
+}
+
+// XORs HMAC-SHA256(key, counter) blocks into `data`
+pub(crate) fn apply_keystream(key: &[u8], data: &mut [u8]) {
+    for (counter, chunk) in data.chunks_mut(32).enumerate() {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+        mac.update(&(counter as u64).to_be_bytes());
+        let block = mac.finalize().into_bytes();
+        for (byte, k) in chunk.iter_mut().zip(block) {
+            *byte ^= k;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+
+    #[test]
+    fn css_test() {
+        let css = Css::new(3, 5).unwrap();
+        let secret = [7u8; 300];
+        let shares = css.split_with(&secret, counter(53));
+        #[cfg(not(feature = "danger_debug"))]
+        assert!(alloc::format!("{:?}", shares[0]).contains("[REDACTED]"));
+        assert!(shares.iter().all(|s| s.fragment().len() == 100));
+        // the fragments are ciphertext, not the payload
+        assert_ne!(shares[0].fragment(), &secret[..100]);
+        assert_eq!(css.recover(&shares[2..]).unwrap(), secret);
+        assert_eq!(
+            css.recover(&[shares[4].clone(), shares[0].clone(), shares[1].clone()])
+                .unwrap(),
+            secret
+        );
+        assert_eq!(
+            css.recover(&shares[3..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+    }
+
+    #[test]
+    fn css_error_test() {
+        let css = Css::new(2, 3).unwrap();
+        let shares = css.split_with(b"short", counter(53));
+        let mut truncated = shares[1].clone();
+        truncated.fragment.pop();
+        assert_eq!(
+            css.recover(&[shares[0].clone(), truncated]),
+            Err(TinySsError::LengthMismatch)
+        );
+        let mut tampered = shares[0].clone();
+        tampered.fragment[0] ^= 1;
+        assert_eq!(
+            css.recover(&[shares[0].clone(), tampered, shares[1].clone()]),
+            Err(TinySsError::ConflictingShares { index: 1 })
+        );
+        assert_eq!(css.split_with(b"", counter(53)).len(), 3);
+    }
+}
@@ -0,0 +1,202 @@
+//! Constant-time backend on `crypto_bigint`.
+//!
+//! `CtField<LIMBS>` computes modulo an odd prime of up to `LIMBS` machine
+//! words. Elements are `DynResidue`s in Montgomery form: fixed size, kept on
+//! the stack, and combined without secret-dependent branches or memory
+//! accesses, and inversion is a fixed-window exponentiation, so splitting
+//! and interpolation do not leak the secret through timing. The generic
+//! evaluation and interpolation only branch on the public share indices.
+
+use crate::{field::Field, prime, SecretShare, TinySsError};
+use alloc::{vec, vec::Vec};
+use crypto_bigint::{
+    modular::runtime_mod::{DynResidue, DynResidueParams},
+    subtle::{ConstantTimeLess, CtOption},
+    Integer, Limb, Uint, Zero,
+};
+use num_bigint::{BigInt, Sign};
+
+/// The prime field of an odd modulus of at most `LIMBS` words.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CtField<const LIMBS: usize> {
+    /// Montgomery parameters of the prime
+    params: DynResidueParams<LIMBS>,
+}
+
+impl<const LIMBS: usize> CtField<LIMBS> {
+    /// `p` must be an odd prime; even moduli have no Montgomery form.
+    pub fn new(p: Uint<LIMBS>) -> Result<Self, TinySsError> {
+        if !bool::from(p.is_odd()) || p == Uint::ONE {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(CtField {
+            params: DynResidueParams::new(&p),
+        })
+    }
+
+    /// prime in ff
+    pub fn p(&self) -> &Uint<LIMBS> {
+        self.params.modulus()
+    }
+
+    /// Lifts an integer below `p` into the field.
+    pub fn elem(&self, value: &Uint<LIMBS>) -> Option<DynResidue<LIMBS>> {
+        if bool::from(value.ct_lt(self.p())) {
+            Some(DynResidue::new(value, self.params))
+        } else {
+            None
+        }
+    }
+
+    // bytes needed for the largest element
+    fn byte_len(&self) -> usize {
+        self.p().bits().div_ceil(8)
+    }
+}
+
+impl<const LIMBS: usize> SecretShare<CtField<LIMBS>> {
+    /// Creates a `t`-of-`n` scheme over the odd prime `p`.
+    pub fn ct(t: usize, n: usize, p: Uint<LIMBS>) -> Result<Self, TinySsError> {
+        let ss = SecretShare::with_field(t, n, CtField::new(p)?)?;
+        let words: Vec<u8> = p.as_words().iter().flat_map(|w| w.to_le_bytes()).collect();
+        prime::check_modulus(&BigInt::from_bytes_le(Sign::Plus, &words))?;
+        Ok(ss)
+    }
+}
+
+impl<const LIMBS: usize> Field for CtField<LIMBS> {
+    type Elem = DynResidue<LIMBS>;
+
+    fn zero(&self) -> DynResidue<LIMBS> {
+        DynResidue::zero(self.params)
+    }
+
+    fn one(&self) -> DynResidue<LIMBS> {
+        DynResidue::one(self.params)
+    }
+
+    fn add(&self, a: &DynResidue<LIMBS>, b: &DynResidue<LIMBS>) -> DynResidue<LIMBS> {
+        a.add(b)
+    }
+
+    fn sub(&self, a: &DynResidue<LIMBS>, b: &DynResidue<LIMBS>) -> DynResidue<LIMBS> {
+        a.sub(b)
+    }
+
+    fn mul(&self, a: &DynResidue<LIMBS>, b: &DynResidue<LIMBS>) -> DynResidue<LIMBS> {
+        a.mul(b)
+    }
+
+    // Fermat's little theorem, a^(p - 2), with the fixed 4-bit windows and
+    // table lookups of `pow`, so that unlike a GCD the running time does not
+    // depend on `a`; only whether `a` is zero shows in the result
+    fn inv(&self, a: &DynResidue<LIMBS>) -> Option<DynResidue<LIMBS>> {
+        let inverse = a.pow(&self.p().wrapping_sub(&Uint::from_u64(2)));
+        let exists = !a.retrieve().is_zero();
+        CtOption::new(inverse, exists).into()
+    }
+
+    fn elem_from_index(&self, index: usize) -> DynResidue<LIMBS> {
+        DynResidue::new(&Uint::from_u64(index as u64), self.params)
+    }
+
+    fn has_indices(&self, n: usize) -> bool {
+        Uint::from_u64(n as u64) < *self.p()
+    }
+
+    // Rejection sampling over the bit length of p
+    fn random(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> DynResidue<LIMBS> {
+        let mut bytes = vec![0u8; Limb::BYTES * LIMBS];
+        let start = bytes.len() - self.byte_len();
+        let excess_bits = self.byte_len() * 8 - self.p().bits();
+        loop {
+            fill_random(&mut bytes[start..]);
+            bytes[start] &= 0xff >> excess_bits;
+            if let Some(value) = self.elem(&Uint::from_be_slice(&bytes)) {
+                return value;
+            }
+        }
+    }
+
+    // big-endian, padded to the byte length of p
+    fn encode(&self, a: &DynResidue<LIMBS>) -> Vec<u8> {
+        let value = a.retrieve();
+        let bytes: Vec<u8> = value
+            .as_words()
+            .iter()
+            .rev()
+            .flat_map(|word| word.to_be_bytes())
+            .collect();
+        bytes[bytes.len() - self.byte_len()..].to_vec()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<DynResidue<LIMBS>> {
+        if bytes.len() != self.byte_len() {
+            return None;
+        }
+        let mut padded = vec![0u8; Limb::BYTES * LIMBS];
+        let start = padded.len() - bytes.len();
+        padded[start..].copy_from_slice(bytes);
+        self.elem(&Uint::from_be_slice(&padded))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crypto_bigint::U256;
+
+    // order of the secp256k1 group
+    const N: U256 =
+        U256::from_be_hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141");
+
+    #[test]
+    fn ct_field_test() {
+        let field = CtField::new(U256::from_u64(1613)).unwrap();
+        let elem = |v: u64| field.elem(&U256::from_u64(v)).unwrap();
+        assert_eq!(field.sub(&elem(3), &elem(5)), elem(1611));
+        assert_eq!(field.mul(&elem(1612), &elem(3)), elem(1610));
+        assert_eq!(
+            field.mul(&elem(166), &field.inv(&elem(166)).unwrap()),
+            field.one()
+        );
+        assert_eq!(field.inv(&field.zero()), None);
+        assert_eq!(field.elem(&U256::from_u64(1613)), None);
+
+        assert_eq!(field.encode(&elem(77)), [0, 77]);
+        assert_eq!(field.decode(&[6, 0x4c]), Some(elem(1612)));
+        assert_eq!(field.decode(&[6, 0x4d]), None);
+        assert_eq!(field.decode(&[77]), None);
+
+        assert_eq!(
+            CtField::new(U256::from_u64(1614)),
+            Err(TinySsError::InvalidModulus)
+        );
+        assert_eq!(CtField::new(U256::ONE), Err(TinySsError::InvalidModulus));
+    }
+
+    #[test]
+    fn secp256k1_order_test() {
+        let ss = SecretShare::ct(3, 5, N).unwrap();
+        let field = *ss.field();
+        let secret = field.elem(&N.wrapping_sub(&U256::from_u64(2))).unwrap();
+
+        let mut counter = 0u8;
+        let shares = ss
+            .split_with(secret, || {
+                field.random(&mut |buf| {
+                    buf.fill(counter);
+                    counter += 1;
+                })
+            })
+            .unwrap();
+        assert_eq!(ss.recover(&shares[2..]).unwrap(), secret);
+        assert_eq!(ss.verify_consistent(&shares), Ok(()));
+
+        let bytes = field.encode(&secret);
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(field.decode(&bytes), Some(secret));
+        assert_eq!(field.inv(&secret), Some(secret.invert().0));
+        assert_eq!(field.decode(&[0xff; 32]), None);
+    }
+}
@@ -0,0 +1,89 @@
+//! A dealer whose type follows the steps of a dealing.
+//!
+//! A `Dealer<F>` starts from finished parameters and takes the secret,
+//! turning into a `Dealer<F, Loaded<F::Elem>>`, which `deal` consumes to
+//! emit the shares. Dealing twice from one dealer, or getting at the secret
+//! or the coefficients once the shares are out, does not compile, as
+//! `deal` takes the dealer by value and nothing hands out its state.
+
+use crate::{field::Field, field::PrimeField, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// State of a dealer that has no secret yet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Configured;
+
+/// State of a dealer holding the secret it is about to deal.
+pub struct Loaded<V>(V);
+
+impl<V> fmt::Debug for Loaded<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Loaded([REDACTED])")
+    }
+}
+
+/// Deals a single secret under `scheme`, at most once.
+#[derive(Debug)]
+pub struct Dealer<'a, F: Field = PrimeField, S = Configured> {
+    scheme: &'a SecretShare<F>,
+    state: S,
+}
+
+impl<'a, F: Field> Dealer<'a, F> {
+    pub fn new(scheme: &'a SecretShare<F>) -> Self {
+        Dealer {
+            scheme,
+            state: Configured,
+        }
+    }
+
+    /// Takes the secret to deal.
+    pub fn secret(self, secret: F::Elem) -> Dealer<'a, F, Loaded<F::Elem>> {
+        Dealer {
+            scheme: self.scheme,
+            state: Loaded(secret),
+        }
+    }
+}
+
+impl<F: Field, S> Dealer<'_, F, S> {
+    pub fn scheme(&self) -> &SecretShare<F> {
+        self.scheme
+    }
+}
+
+impl<F: Field> Dealer<'_, F, Loaded<F::Elem>> {
+    #[cfg(feature = "rand")]
+    pub fn deal(self) -> Result<Vec<Share<F::Elem>>, TinySsError> {
+        self.scheme.split(self.state.0)
+    }
+
+    /// Emits the shares, drawing the coefficients from `sample` as
+    /// `SecretShare::split_with` does; they are wiped with the `zeroize`
+    /// feature and never handed out.
+    pub fn deal_with<S>(self, sample: S) -> Result<Vec<Share<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        self.scheme.split_with(self.state.0, sample)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::format;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn dealer_test() {
+        let ss = SecretShare::new(2, 3, BigInt::from(1613)).unwrap();
+        let dealer = Dealer::new(&ss).secret(BigInt::from(1000));
+        assert_eq!(dealer.scheme().t(), 2);
+        assert!(format!("{:?}", dealer).contains("Loaded([REDACTED])"));
+        let shares = dealer.deal_with(|| BigInt::from(5)).unwrap();
+        assert_eq!(shares[2], Share::new(3, BigInt::from(1015)));
+        assert_eq!(ss.recover(&shares[..2]).unwrap(), BigInt::from(1000));
+    }
+}
@@ -0,0 +1,47 @@
+// Debug output that keeps secret values out of logs.
+//
+// Share values, polynomial coefficients and key shares print as
+// `[REDACTED]`, unless the `danger_debug` feature is enabled for debugging
+// the crate itself.
+
+use core::fmt;
+
+#[cfg(not(feature = "danger_debug"))]
+struct Redacted;
+
+#[cfg(not(feature = "danger_debug"))]
+impl fmt::Debug for Redacted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("[REDACTED]")
+    }
+}
+
+// `value` itself with `danger_debug`, a placeholder otherwise
+pub(crate) fn secret<T: fmt::Debug>(value: &T) -> &dyn fmt::Debug {
+    #[cfg(feature = "danger_debug")]
+    return value;
+    #[cfg(not(feature = "danger_debug"))]
+    {
+        let _ = value;
+        &Redacted
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Share;
+    use alloc::format;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn debug_test() {
+        let share = Share::new(3, BigInt::from(1234));
+        #[cfg(not(feature = "danger_debug"))]
+        assert_eq!(
+            format!("{:?}", share),
+            "Share { index: 3, value: [REDACTED] }"
+        );
+        #[cfg(feature = "danger_debug")]
+        assert_eq!(format!("{:?}", share), "Share { index: 3, value: 1234 }");
+    }
+}
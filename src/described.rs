@@ -0,0 +1,268 @@
+//! Self-describing shares, carrying the parameters of their dealing.
+//!
+//! A plain `Share` recovers only under the scheme it was dealt with, and
+//! nothing stops shares of two dealings from being combined into garbage.
+//! A `DescribedShare` also records the scheme that dealt it, the threshold,
+//! the number of shares, the modulus, which identifies the field, and a
+//! nonce drawn afresh for every dealing, so that `SecretShare::recover_auto` needs nothing but the
+//! shares and refuses to mix dealings.
+//!
+//! The binary form is `magic || version || scheme || t || n || nonce ||
+//! modulus || index || value`, where `scheme` is one byte, `t`, `n` and
+//! `index` are big-endian `u64`s, the nonce is `NONCE_BYTES` bytes, and the modulus and value are each a
+//! big-endian `u32` length followed by a big-endian magnitude.
+
+use crate::{
+    codec::{take, take_magnitude, take_u64},
+    DecodeError, SecretShare, Share, TinySsError,
+};
+use alloc::vec::Vec;
+use num_bigint::BigInt;
+use num_traits::Zero;
+
+/// magic bytes opening every binary self-describing share
+const MAGIC: [u8; 3] = *b"TSD";
+/// current binary format version
+const VERSION: u8 = 1;
+
+/// scheme id of Shamir sharing over a prime field, the only scheme whose
+/// shares are described so far
+pub const SHAMIR_PRIME: u8 = 1;
+
+/// bytes of a dealing nonce
+pub const NONCE_BYTES: usize = 16;
+
+/// A share together with the parameters and nonce of its dealing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DescribedShare {
+    /// scheme id of the dealing
+    scheme: u8,
+    t: usize,
+    n: usize,
+    /// modulus of the field
+    p: BigInt,
+    nonce: [u8; NONCE_BYTES],
+    share: Share,
+}
+
+impl DescribedShare {
+    pub fn new(t: usize, n: usize, p: BigInt, nonce: [u8; NONCE_BYTES], share: Share) -> Self {
+        DescribedShare {
+            scheme: SHAMIR_PRIME,
+            t,
+            n,
+            p,
+            nonce,
+            share,
+        }
+    }
+
+    /// scheme id of the dealing
+    pub fn scheme(&self) -> u8 {
+        self.scheme
+    }
+
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// modulus of the field
+    pub fn p(&self) -> &BigInt {
+        &self.p
+    }
+
+    /// nonce of the dealing
+    pub fn nonce(&self) -> &[u8; NONCE_BYTES] {
+        &self.nonce
+    }
+
+    pub fn share(&self) -> &Share {
+        &self.share
+    }
+
+    // Whether both shares come from the same dealing
+    fn same_dealing(&self, other: &DescribedShare) -> bool {
+        self.scheme == other.scheme
+            && self.t == other.t
+            && self.n == other.n
+            && self.p == other.p
+            && self.nonce == other.nonce
+    }
+
+    /// Encodes the share in the binary form of the module documentation.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&MAGIC);
+        bytes.push(VERSION);
+        bytes.push(self.scheme);
+        bytes.extend_from_slice(&(self.t as u64).to_be_bytes());
+        bytes.extend_from_slice(&(self.n as u64).to_be_bytes());
+        bytes.extend_from_slice(&self.nonce);
+        put_magnitude(&mut bytes, &self.p);
+        bytes.extend_from_slice(&(self.share.index() as u64).to_be_bytes());
+        put_magnitude(&mut bytes, self.share.value());
+        bytes
+    }
+
+    /// Parses a share written by `to_bytes`, rejecting anything but exactly
+    /// one well-formed share of a known scheme.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let rest = bytes.strip_prefix(&MAGIC).ok_or(DecodeError::BadMagic)?;
+        let (version, rest) = take(rest, 1)?;
+        if version[0] != VERSION {
+            return Err(DecodeError::UnsupportedVersion(version[0]));
+        }
+        let (scheme, rest) = take(rest, 1)?;
+        if scheme[0] != SHAMIR_PRIME {
+            return Err(DecodeError::UnsupportedScheme(scheme[0]));
+        }
+        let (t, rest) = take_u64(rest)?;
+        let (n, rest) = take_u64(rest)?;
+        let (nonce, rest) = take(rest, NONCE_BYTES)?;
+        let (p, rest) = take_magnitude(rest)?;
+        let (index, rest) = take_u64(rest)?;
+        let (value, rest) = take_magnitude(rest)?;
+        if !rest.is_empty() {
+            return Err(DecodeError::TrailingBytes);
+        }
+        let nonce = nonce.try_into().unwrap();
+        Ok(DescribedShare::new(
+            t,
+            n,
+            p,
+            nonce,
+            Share::new(index, value),
+        ))
+    }
+}
+
+// Appends the length and big-endian magnitude of `value`
+fn put_magnitude(bytes: &mut Vec<u8>, value: &BigInt) {
+    let magnitude = if value.is_zero() {
+        Vec::new()
+    } else {
+        value.magnitude().to_bytes_be()
+    };
+    bytes.extend_from_slice(&(magnitude.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(&magnitude);
+}
+
+impl SecretShare {
+    #[cfg(feature = "rand")]
+    pub fn split_described(&self, secret: BigInt) -> Result<Vec<DescribedShare>, TinySsError> {
+        use crate::field::Field;
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let mut nonce = [0u8; NONCE_BYTES];
+        rng.fill_bytes(&mut nonce);
+        self.split_described_with(secret, nonce, || {
+            self.field().random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Splits `secret` as `split_with` does and labels every share with the
+    /// parameters of the scheme and `nonce`, which must differ between
+    /// dealings for `recover_auto` to tell them apart.
+    pub fn split_described_with<S>(
+        &self,
+        secret: BigInt,
+        nonce: [u8; NONCE_BYTES],
+        sample: S,
+    ) -> Result<Vec<DescribedShare>, TinySsError>
+    where
+        S: FnMut() -> BigInt,
+    {
+        let shares = self.split_with(secret, sample)?;
+        Ok(shares
+            .into_iter()
+            .map(|share| DescribedShare::new(self.t(), self.n(), self.p().clone(), nonce, share))
+            .collect())
+    }
+
+    /// Recovers the secret from such shares alone, rebuilding the scheme
+    /// from their parameters. Shares of different dealings or parameter
+    /// sets fail with `MixedDealings`, and parameters that describe no
+    /// scheme with the errors of `SecretShare::new`.
+    pub fn recover_auto(shares: &[DescribedShare]) -> Result<BigInt, TinySsError> {
+        let first = shares.first().ok_or(TinySsError::NotEnoughShares {
+            required: 1,
+            got: 0,
+        })?;
+        if !shares.iter().all(|share| share.same_dealing(first)) {
+            return Err(TinySsError::MixedDealings);
+        }
+        let scheme = SecretShare::new(first.t, first.n, first.p.clone())?;
+        let shares: Vec<Share> = shares.iter().map(|share| share.share.clone()).collect();
+        scheme.recover(&shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn described_test() {
+        let ss = SecretShare::new(2, 3, BigInt::from(1613)).unwrap();
+        let shares = ss
+            .split_described_with(BigInt::from(1000), [7; NONCE_BYTES], || BigInt::from(5))
+            .unwrap();
+        assert_eq!(shares[2].share(), &Share::new(3, BigInt::from(1015)));
+        assert_eq!(shares[2].nonce(), &[7; NONCE_BYTES]);
+        assert_eq!(
+            SecretShare::recover_auto(&shares[1..]).unwrap(),
+            BigInt::from(1000)
+        );
+
+        let decoded: Vec<DescribedShare> = shares
+            .iter()
+            .map(|s| DescribedShare::from_bytes(&s.to_bytes()).unwrap())
+            .collect();
+        assert_eq!(decoded, shares);
+        let bytes = shares[0].to_bytes();
+        assert_eq!(
+            DescribedShare::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(DecodeError::Truncated)
+        );
+        assert_eq!(shares[0].scheme(), SHAMIR_PRIME);
+        let mut foreign = bytes.clone();
+        foreign[MAGIC.len() + 1] = SHAMIR_PRIME + 1;
+        assert_eq!(
+            DescribedShare::from_bytes(&foreign),
+            Err(DecodeError::UnsupportedScheme(SHAMIR_PRIME + 1))
+        );
+        let mut relabelled = shares[1].clone();
+        relabelled.scheme = SHAMIR_PRIME + 1;
+        assert_eq!(
+            SecretShare::recover_auto(&[shares[0].clone(), relabelled]),
+            Err(TinySsError::MixedDealings)
+        );
+
+        let other = ss
+            .split_described_with(BigInt::from(1000), [8; NONCE_BYTES], || BigInt::from(5))
+            .unwrap();
+        assert_eq!(
+            SecretShare::recover_auto(&[shares[0].clone(), other[1].clone()]),
+            Err(TinySsError::MixedDealings)
+        );
+        let wider = SecretShare::new(2, 4, BigInt::from(1613)).unwrap();
+        let wider = wider
+            .split_described_with(BigInt::from(1000), [7; NONCE_BYTES], || BigInt::from(5))
+            .unwrap();
+        assert_eq!(
+            SecretShare::recover_auto(&[shares[0].clone(), wider[1].clone()]),
+            Err(TinySsError::MixedDealings)
+        );
+        assert_eq!(
+            SecretShare::recover_auto(&[]),
+            Err(TinySsError::NotEnoughShares {
+                required: 1,
+                got: 0
+            })
+        );
+    }
+}
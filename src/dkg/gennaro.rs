@@ -0,0 +1,626 @@
+//! Distributed key generation of Gennaro, Jarecki, Krawczyk and Rabin
+//! (EUROCRYPT '99).
+//!
+//! Dealers first commit with perfectly hiding Pedersen commitments, so no
+//! dealer can bias the joint key by looking at the others, and shareholders
+//! complain about bad shares. A dealer drawing `t` complaints, or failing to
+//! answer one with a valid share, is disqualified. The qualified dealers
+//! then publish Feldman commitments to extract the public key; a dealer
+//! caught publishing wrong ones has its secret reconstructed in public.
+//!
+//! 1. broadcast `commitments`, send `share_for` each receiver
+//! 2. broadcast the `Complaint`s returned by `receive`
+//! 3. broadcast the `justify` answers to complaints against this dealer
+//! 4. `qualify`, then broadcast `extraction_commitments`
+//! 5. broadcast the accusations returned by `check_extraction`
+//! 6. broadcast the shares returned by `reveal`
+//! 7. `finish` outputs the `KeyShare`
+//!
+//! Every message is broadcast except the shares of round 1, and all
+//! parties must see the same broadcasts.
+
+use super::{by_sender, Commitments, KeyShare};
+use crate::{field::Field, group::SchnorrGroup, poly, SecretShare, TinySsError};
+use alloc::vec::Vec;
+use core::fmt;
+use num_bigint::BigInt;
+
+/// A dealer's share for one receiver together with its blinding.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SharePair {
+    sender: usize,
+    receiver: usize,
+    /// a(receiver)
+    value: BigInt,
+    /// b(receiver)
+    blinding: BigInt,
+}
+
+impl fmt::Debug for SharePair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharePair")
+            .field("sender", &self.sender)
+            .field("receiver", &self.receiver)
+            .field("value", crate::debug::secret(&self.value))
+            .field("blinding", crate::debug::secret(&self.blinding))
+            .finish()
+    }
+}
+
+impl SharePair {
+    pub fn new(sender: usize, receiver: usize, value: BigInt, blinding: BigInt) -> Self {
+        SharePair {
+            sender,
+            receiver,
+            value,
+            blinding,
+        }
+    }
+
+    pub fn sender(&self) -> usize {
+        self.sender
+    }
+
+    pub fn receiver(&self) -> usize {
+        self.receiver
+    }
+
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    pub fn blinding(&self) -> &BigInt {
+        &self.blinding
+    }
+}
+
+/// A shareholder's claim that a dealer sent it a bad share, or none.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Complaint {
+    accuser: usize,
+    accused: usize,
+}
+
+impl Complaint {
+    pub fn new(accuser: usize, accused: usize) -> Self {
+        Complaint { accuser, accused }
+    }
+
+    pub fn accuser(&self) -> usize {
+        self.accuser
+    }
+
+    pub fn accused(&self) -> usize {
+        self.accused
+    }
+}
+
+/// One participant of a `t`-of-`n` key generation.
+#[derive(Clone)]
+pub struct Party {
+    group: SchnorrGroup,
+    /// sharing over the exponents, modulo q
+    sharing: SecretShare,
+    index: usize,
+    /// coefficients of the dealt polynomial, constant term first
+    polynomial: Vec<BigInt>,
+    /// coefficients of the blinding polynomial
+    blinding: Vec<BigInt>,
+    /// Pedersen commitments of every dealer, by index
+    commitments: Vec<Option<Commitments>>,
+    /// shares received from every dealer, by index
+    received: Vec<Option<SharePair>>,
+    /// indices of the qualified dealers
+    qualified: Vec<usize>,
+}
+
+impl fmt::Debug for Party {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Party")
+            .field("group", &self.group)
+            .field("sharing", &self.sharing)
+            .field("index", &self.index)
+            .field("polynomial", crate::debug::secret(&self.polynomial))
+            .field("blinding", crate::debug::secret(&self.blinding))
+            .field("commitments", &self.commitments)
+            .field("received", &self.received)
+            .field("qualified", &self.qualified)
+            .finish()
+    }
+}
+
+impl Party {
+    #[cfg(feature = "rand")]
+    pub fn new(group: SchnorrGroup, t: usize, n: usize, index: usize) -> Result<Self, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let scalars = group.scalars().clone();
+        Party::new_with(group, t, n, index, || {
+            scalars.random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Creates the party at `index` in `1..=n`, drawing both polynomials
+    /// from `sample`, which must return uniformly random elements modulo q.
+    pub fn new_with<S>(
+        group: SchnorrGroup,
+        t: usize,
+        n: usize,
+        index: usize,
+        mut sample: S,
+    ) -> Result<Self, TinySsError>
+    where
+        S: FnMut() -> BigInt,
+    {
+        let sharing = SecretShare::new(t, n, group.q().clone())?;
+        if index == 0 || index > n {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let polynomial = (0..t).map(|_| sample()).collect();
+        let blinding = (0..t).map(|_| sample()).collect();
+        Ok(Party {
+            group,
+            sharing,
+            index,
+            polynomial,
+            blinding,
+            commitments: (0..n).map(|_| None).collect(),
+            received: (0..n).map(|_| None).collect(),
+            qualified: Vec::new(),
+        })
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn group(&self) -> &SchnorrGroup {
+        &self.group
+    }
+
+    /// Indices of the dealers that survived the complaint rounds, empty
+    /// before `qualify`.
+    pub fn qualified(&self) -> &[usize] {
+        &self.qualified
+    }
+
+    /// Round 1 broadcast of the Pedersen commitments `g^{a_j} h^{b_j}`.
+    pub fn commitments(&self) -> Commitments {
+        let values = self
+            .polynomial
+            .iter()
+            .zip(&self.blinding)
+            .map(|(a, b)| self.group.commit(a, b))
+            .collect();
+        Commitments::new(self.index, values)
+    }
+
+    /// Round 1 private message to the party at `receiver`.
+    pub fn share_for(&self, receiver: usize) -> Result<SharePair, TinySsError> {
+        if receiver == 0 || receiver > self.sharing.n() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let scalars = self.group.scalars();
+        let x = scalars.elem_from_index(receiver);
+        Ok(SharePair::new(
+            self.index,
+            receiver,
+            poly::evaluate(scalars, &self.polynomial, &x),
+            poly::evaluate(scalars, &self.blinding, &x),
+        ))
+    }
+
+    /// Round 2: stores the round 1 messages and complains about every
+    /// dealer that sent this party a bad share or none at all.
+    pub fn receive(
+        &mut self,
+        commitments: &[Commitments],
+        shares: &[SharePair],
+    ) -> Result<Vec<Complaint>, TinySsError> {
+        let n = self.sharing.n();
+        self.commitments = by_sender(n, commitments, |c| c.sender)?
+            .into_iter()
+            .map(|c| c.cloned())
+            .collect();
+        let own = shares.iter().filter(|s| s.receiver == self.index);
+        let own: Vec<SharePair> = own.cloned().collect();
+        self.received = by_sender(n, &own, |s| s.sender)?
+            .into_iter()
+            .map(|s| s.cloned())
+            .collect();
+
+        Ok((1..=n)
+            .filter(|&dealer| match &self.commitments[dealer - 1] {
+                // a dealer without commitments is out regardless
+                None => false,
+                Some(commitments) => !self.received[dealer - 1]
+                    .as_ref()
+                    .is_some_and(|share| self.is_valid(commitments, share)),
+            })
+            .map(|dealer| Complaint::new(self.index, dealer))
+            .collect())
+    }
+
+    /// Round 3: publishes the shares this dealer sent to its accusers.
+    pub fn justify(&self, complaints: &[Complaint]) -> Vec<SharePair> {
+        complaints
+            .iter()
+            .filter(|c| c.accused == self.index)
+            .filter_map(|c| self.share_for(c.accuser).ok())
+            .collect()
+    }
+
+    /// Round 4: decides the qualified dealers from the broadcast
+    /// complaints and justifications, adopting justified shares in place of
+    /// bad ones.
+    pub fn qualify(
+        &mut self,
+        complaints: &[Complaint],
+        justifications: &[SharePair],
+    ) -> Result<(), TinySsError> {
+        let (t, n) = (self.sharing.t(), self.sharing.n());
+        let mut qualified = Vec::new();
+        for dealer in 1..=n {
+            let Some(commitments) = &self.commitments[dealer - 1] else {
+                continue;
+            };
+            let mut accusers: Vec<usize> = complaints
+                .iter()
+                .filter(|c| c.accused == dealer && (1..=n).contains(&c.accuser))
+                .map(|c| c.accuser)
+                .collect();
+            accusers.sort_unstable();
+            accusers.dedup();
+            if accusers.len() >= t {
+                continue;
+            }
+            let answers: Option<Vec<&SharePair>> = accusers
+                .iter()
+                .map(|&accuser| {
+                    justifications.iter().find(|s| {
+                        s.sender == dealer
+                            && s.receiver == accuser
+                            && self.is_valid_at(commitments, s, accuser)
+                    })
+                })
+                .collect();
+            let Some(answers) = answers else {
+                continue;
+            };
+            if let Some(answer) = answers.iter().find(|s| s.receiver == self.index) {
+                self.received[dealer - 1] = Some((*answer).clone());
+            }
+            qualified.push(dealer);
+        }
+        if qualified.is_empty() {
+            return Err(TinySsError::NotEnoughShares {
+                required: 1,
+                got: 0,
+            });
+        }
+        self.qualified = qualified;
+        Ok(())
+    }
+
+    /// Round 4 broadcast of the Feldman commitments `g^{a_j}`.
+    pub fn extraction_commitments(&self) -> Commitments {
+        let values = self
+            .polynomial
+            .iter()
+            .map(|a| self.group.exp(self.group.g(), a))
+            .collect();
+        Commitments::new(self.index, values)
+    }
+
+    /// Round 5: accuses every qualified dealer whose Feldman commitments
+    /// contradict the share it sent, by publishing that share.
+    pub fn check_extraction(
+        &self,
+        extraction: &[Commitments],
+    ) -> Result<Vec<SharePair>, TinySsError> {
+        let extraction = by_sender(self.sharing.n(), extraction, |c| c.sender)?;
+        Ok(self
+            .qualified
+            .iter()
+            .filter_map(|&dealer| {
+                let share = self.received[dealer - 1].as_ref()?;
+                let valid = extraction[dealer - 1]
+                    .is_some_and(|a| self.is_feldman_valid(a, share, self.index));
+                (!valid).then(|| share.clone())
+            })
+            .collect())
+    }
+
+    /// Round 6: publishes this party's shares of every exposed dealer, so
+    /// that their secrets can be reconstructed.
+    pub fn reveal(
+        &self,
+        extraction: &[Commitments],
+        accusations: &[SharePair],
+    ) -> Result<Vec<SharePair>, TinySsError> {
+        let exposed = self.exposed(extraction, accusations)?;
+        Ok(exposed
+            .iter()
+            .filter_map(|&dealer| self.received[dealer - 1].clone())
+            .collect())
+    }
+
+    /// Round 7: combines the shares of the qualified dealers and extracts
+    /// the joint public key.
+    pub fn finish(
+        &self,
+        extraction: &[Commitments],
+        accusations: &[SharePair],
+        revealed: &[SharePair],
+    ) -> Result<KeyShare, TinySsError> {
+        let (t, n) = (self.sharing.t(), self.sharing.n());
+        let group = &self.group;
+        let scalars = group.scalars();
+        let exposed = self.exposed(extraction, accusations)?;
+        let extraction = by_sender(n, extraction, |c| c.sender)?;
+
+        let mut secret = scalars.zero();
+        let mut public_key = BigInt::from(1);
+        let mut public_shares = alloc::vec![BigInt::from(1); n];
+        for &dealer in &self.qualified {
+            let share = self.received[dealer - 1]
+                .as_ref()
+                .ok_or(TinySsError::InvalidShare { index: dealer })?;
+            secret = scalars.add(&secret, &share.value);
+
+            // g^{a(i)} for i = 0..=n
+            let powers: Vec<BigInt> = if exposed.contains(&dealer) {
+                let commitments = self.commitments[dealer - 1]
+                    .as_ref()
+                    .expect("qualified dealers have commitments");
+                let mut points: Vec<&SharePair> = Vec::new();
+                for share in revealed {
+                    if share.sender == dealer
+                        && !points.iter().any(|p| p.receiver == share.receiver)
+                        && self.is_valid_at(commitments, share, share.receiver)
+                    {
+                        points.push(share);
+                    }
+                }
+                if points.len() < t {
+                    return Err(TinySsError::NotEnoughShares {
+                        required: t,
+                        got: points.len(),
+                    });
+                }
+                let xs: Vec<BigInt> = points[..t]
+                    .iter()
+                    .map(|p| scalars.elem_from_index(p.receiver))
+                    .collect();
+                let ys: Vec<BigInt> = points[..t].iter().map(|p| p.value.clone()).collect();
+                (0..=n)
+                    .map(|i| {
+                        let x = scalars.elem_from_index(i);
+                        poly::interpolate(scalars, &xs, &ys, &x).map(|y| group.exp(group.g(), &y))
+                    })
+                    .collect::<Result<_, _>>()?
+            } else {
+                let a = extraction[dealer - 1].expect("unexposed dealers have commitments");
+                (0..=n)
+                    .map(|i| group.eval_commitments(&a.values, i))
+                    .collect()
+            };
+            public_key = group.mul(&public_key, &powers[0]);
+            for (public_share, power) in public_shares.iter_mut().zip(&powers[1..]) {
+                *public_share = group.mul(public_share, power);
+            }
+        }
+        Ok(KeyShare {
+            index: self.index,
+            secret,
+            public_key,
+            public_shares,
+        })
+    }
+
+    // Qualified dealers with missing or malformed Feldman commitments, or
+    // with a valid accusation against them
+    fn exposed(
+        &self,
+        extraction: &[Commitments],
+        accusations: &[SharePair],
+    ) -> Result<Vec<usize>, TinySsError> {
+        let extraction = by_sender(self.sharing.n(), extraction, |c| c.sender)?;
+        Ok(self
+            .qualified
+            .iter()
+            .copied()
+            .filter(|&dealer| {
+                let Some(a) = extraction[dealer - 1] else {
+                    return true;
+                };
+                if a.values.len() != self.sharing.t()
+                    || !a.values.iter().all(|c| self.group.is_element(c))
+                {
+                    return true;
+                }
+                let commitments = self.commitments[dealer - 1]
+                    .as_ref()
+                    .expect("qualified dealers have commitments");
+                accusations.iter().any(|s| {
+                    s.sender == dealer
+                        && (1..=self.sharing.n()).contains(&s.receiver)
+                        && self.is_valid_at(commitments, s, s.receiver)
+                        && !self.is_feldman_valid(a, s, s.receiver)
+                })
+            })
+            .collect())
+    }
+
+    // Pedersen check of a share sent to this party
+    fn is_valid(&self, commitments: &Commitments, share: &SharePair) -> bool {
+        share.receiver == self.index && self.is_valid_at(commitments, share, self.index)
+    }
+
+    // Pedersen check g^{a(i)} h^{b(i)} = prod_j C_j^{i^j}
+    fn is_valid_at(&self, commitments: &Commitments, share: &SharePair, index: usize) -> bool {
+        commitments.values.len() == self.sharing.t()
+            && commitments.values.iter().all(|c| self.group.is_element(c))
+            && self.group.commit(&share.value, &share.blinding)
+                == self.group.eval_commitments(&commitments.values, index)
+    }
+
+    // Feldman check g^{a(i)} = prod_j A_j^{i^j}
+    fn is_feldman_valid(&self, extraction: &Commitments, share: &SharePair, index: usize) -> bool {
+        extraction.values.len() == self.sharing.t()
+            && self.group.exp(self.group.g(), &share.value)
+                == self.group.eval_commitments(&extraction.values, index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Share;
+
+    fn parties(t: usize, n: usize) -> Vec<Party> {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        (1..=n)
+            .map(|i| {
+                let mut next = i as u64 * 31;
+                Party::new_with(group.clone(), t, n, i, || {
+                    next = (next * 13 + 5) % 1019;
+                    BigInt::from(next)
+                })
+                .unwrap()
+            })
+            .collect()
+    }
+
+    // Runs every round, letting `tamper` corrupt round 1 shares and round 4
+    // commitments; `silent` dealers do not answer complaints
+    fn run(
+        parties: &mut [Party],
+        tamper_share: impl Fn(&mut SharePair),
+        tamper_extraction: impl Fn(&mut Commitments),
+        silent: &[usize],
+    ) -> Vec<KeyShare> {
+        let commitments: Vec<Commitments> = parties.iter().map(Party::commitments).collect();
+        let mut shares = Vec::new();
+        for dealer in parties.iter() {
+            for receiver in 1..=parties.len() {
+                let mut share = dealer.share_for(receiver).unwrap();
+                tamper_share(&mut share);
+                shares.push(share);
+            }
+        }
+        let complaints: Vec<Complaint> = parties
+            .iter_mut()
+            .flat_map(|p| p.receive(&commitments, &shares).unwrap())
+            .collect();
+        let justifications: Vec<SharePair> = parties
+            .iter()
+            .filter(|p| !silent.contains(&p.index()))
+            .flat_map(|p| p.justify(&complaints))
+            .collect();
+        for party in parties.iter_mut() {
+            party.qualify(&complaints, &justifications).unwrap();
+        }
+        let extraction: Vec<Commitments> = parties
+            .iter()
+            .map(|p| {
+                let mut a = p.extraction_commitments();
+                tamper_extraction(&mut a);
+                a
+            })
+            .collect();
+        let accusations: Vec<SharePair> = parties
+            .iter()
+            .flat_map(|p| p.check_extraction(&extraction).unwrap())
+            .collect();
+        let revealed: Vec<SharePair> = parties
+            .iter()
+            .flat_map(|p| p.reveal(&extraction, &accusations).unwrap())
+            .collect();
+        parties
+            .iter()
+            .map(|p| p.finish(&extraction, &accusations, &revealed).unwrap())
+            .collect()
+    }
+
+    // the joint secret recovered from the key shares, checked against the
+    // public key and the public shares
+    fn joint_secret(parties: &[Party], keys: &[KeyShare]) -> BigInt {
+        let group = parties[0].group();
+        let sharing = SecretShare::new(2, parties.len(), group.q().clone()).unwrap();
+        let shares: Vec<Share> = keys.iter().map(KeyShare::share).collect();
+        let secret = sharing.recover(&shares).unwrap();
+        for key in keys {
+            assert_eq!(key.public_key(), &group.exp(group.g(), &secret));
+            assert_eq!(
+                keys[0].public_share(key.index()),
+                Some(&group.exp(group.g(), key.secret()))
+            );
+        }
+        secret
+    }
+
+    fn sum_of(parties: &[Party], dealers: &[usize]) -> BigInt {
+        let scalars = parties[0].group().scalars();
+        dealers.iter().fold(BigInt::from(0), |sum, &d| {
+            scalars.add(&sum, &parties[d - 1].polynomial[0])
+        })
+    }
+
+    #[test]
+    fn honest_test() {
+        let mut parties = parties(2, 4);
+        let keys = run(&mut parties, |_| {}, |_| {}, &[]);
+        assert_eq!(parties[0].qualified(), [1, 2, 3, 4]);
+        assert_eq!(
+            joint_secret(&parties, &keys),
+            sum_of(&parties, &[1, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn justified_complaint_test() {
+        let mut parties = parties(2, 4);
+        let bad = |s: &mut SharePair| {
+            if s.sender == 3 && s.receiver == 1 {
+                s.value += 1;
+            }
+        };
+        let keys = run(&mut parties, bad, |_| {}, &[]);
+        assert!(parties.iter().all(|p| p.qualified() == [1, 2, 3, 4]));
+        assert_eq!(
+            joint_secret(&parties, &keys),
+            sum_of(&parties, &[1, 2, 3, 4])
+        );
+    }
+
+    #[test]
+    fn disqualified_test() {
+        let mut parties = parties(2, 4);
+        let bad = |s: &mut SharePair| {
+            if s.sender == 4 && s.receiver == 2 {
+                s.blinding += 1;
+            }
+        };
+        let keys = run(&mut parties, bad, |_| {}, &[4]);
+        assert!(parties.iter().all(|p| p.qualified() == [1, 2, 3]));
+        assert_eq!(joint_secret(&parties, &keys), sum_of(&parties, &[1, 2, 3]));
+    }
+
+    #[test]
+    fn exposed_test() {
+        let mut parties = parties(2, 4);
+        let group = parties[0].group().clone();
+        let bad = |a: &mut Commitments| {
+            if a.sender == 2 {
+                a.values[1] = group.mul(&a.values[1], group.g());
+            }
+        };
+        let keys = run(&mut parties, |_| {}, bad, &[]);
+        assert!(parties.iter().all(|p| p.qualified() == [1, 2, 3, 4]));
+        assert_eq!(
+            joint_secret(&parties, &keys),
+            sum_of(&parties, &[1, 2, 3, 4])
+        );
+    }
+}
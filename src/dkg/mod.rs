@@ -0,0 +1,449 @@
+//! Dealer-less distributed key generation after Pedersen (EUROCRYPT '91).
+//!
+//! Every party deals a random secret of its own with Feldman commitments
+//! `C_j = g^{a_j}`. A party's key share is the sum of the shares it
+//! received, the joint secret is the sum of all dealt secrets, which nobody
+//! learns, and the joint public key is the product of the commitments
+//! `C_0`.
+//!
+//! The module is sans-io: each round is a method that returns the messages
+//! to send and consumes the messages received, and the caller moves them
+//! over whatever transport it uses.
+//!
+//! 1. `Party::commitments` is broadcast to everyone, and `Party::share_for`
+//!    is sent privately to each receiver.
+//! 2. `Party::finish` verifies what was received and outputs the
+//!    `KeyShare`.
+//!
+//! Alongside its commitments, a dealer can broadcast
+//! `Party::prove_secret`, with which the others check that it knows the
+//! secret behind its `C_0` before accepting it into the public key.
+//!
+//! Joint-Feldman aborts on the first bad share. `gennaro` adds complaint
+//! rounds that exclude misbehaving dealers instead.
+
+pub mod gennaro;
+
+use crate::{
+    field::Field,
+    group::SchnorrGroup,
+    pok::SchnorrProof,
+    poly,
+    transcript::{Sha256Transcript, Transcript},
+    SecretShare, Share, TinySsError,
+};
+use alloc::vec::Vec;
+use core::fmt;
+use num_bigint::BigInt;
+
+/// Broadcast of a dealer's commitments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Commitments {
+    sender: usize,
+    /// g^{a_j}
+    values: Vec<BigInt>,
+}
+
+impl Commitments {
+    pub fn new(sender: usize, values: Vec<BigInt>) -> Self {
+        Commitments { sender, values }
+    }
+
+    pub fn sender(&self) -> usize {
+        self.sender
+    }
+
+    pub fn values(&self) -> &[BigInt] {
+        &self.values
+    }
+
+    /// Whether `proof` shows that the sender knows the secret behind `C_0`.
+    pub fn verify_secret_proof(&self, group: &SchnorrGroup, proof: &SchnorrProof) -> bool {
+        self.values.first().is_some_and(|c0| {
+            proof.verify(group, &mut proof_transcript(self.sender), (group.g(), c0))
+        })
+    }
+}
+
+/// Private message carrying the share a dealer computed for one receiver.
+#[derive(Clone, PartialEq, Eq)]
+pub struct DealtShare {
+    sender: usize,
+    receiver: usize,
+    value: BigInt,
+}
+
+impl fmt::Debug for DealtShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DealtShare")
+            .field("sender", &self.sender)
+            .field("receiver", &self.receiver)
+            .field("value", crate::debug::secret(&self.value))
+            .finish()
+    }
+}
+
+impl DealtShare {
+    pub fn new(sender: usize, receiver: usize, value: BigInt) -> Self {
+        DealtShare {
+            sender,
+            receiver,
+            value,
+        }
+    }
+
+    pub fn sender(&self) -> usize {
+        self.sender
+    }
+
+    pub fn receiver(&self) -> usize {
+        self.receiver
+    }
+
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+}
+
+/// A party's output of the key generation.
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyShare {
+    index: usize,
+    /// share of the joint secret
+    secret: BigInt,
+    /// g to the joint secret
+    public_key: BigInt,
+    /// g to every party's share, in index order
+    public_shares: Vec<BigInt>,
+}
+
+impl fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyShare")
+            .field("index", &self.index)
+            .field("secret", crate::debug::secret(&self.secret))
+            .field("public_key", &self.public_key)
+            .field("public_shares", &self.public_shares)
+            .finish()
+    }
+}
+
+impl KeyShare {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// share of the joint secret
+    pub fn secret(&self) -> &BigInt {
+        &self.secret
+    }
+
+    /// g to the joint secret
+    pub fn public_key(&self) -> &BigInt {
+        &self.public_key
+    }
+
+    /// `g^{x_i}` for the share `x_i` of the party at index `i`
+    pub fn public_share(&self, index: usize) -> Option<&BigInt> {
+        self.public_shares.get(index.checked_sub(1)?)
+    }
+
+    /// the key share as a plain Shamir share of the joint secret
+    pub fn share(&self) -> Share {
+        Share::new(self.index, self.secret.clone())
+    }
+}
+
+/// One participant of a `t`-of-`n` key generation.
+#[derive(Clone)]
+pub struct Party {
+    group: SchnorrGroup,
+    /// sharing over the exponents, modulo q
+    sharing: SecretShare,
+    index: usize,
+    /// coefficients of the dealt polynomial, constant term first
+    polynomial: Vec<BigInt>,
+}
+
+impl fmt::Debug for Party {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Party")
+            .field("group", &self.group)
+            .field("sharing", &self.sharing)
+            .field("index", &self.index)
+            .field("polynomial", crate::debug::secret(&self.polynomial))
+            .finish()
+    }
+}
+
+impl Party {
+    #[cfg(feature = "rand")]
+    pub fn new(group: SchnorrGroup, t: usize, n: usize, index: usize) -> Result<Self, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let scalars = group.scalars().clone();
+        Party::new_with(group, t, n, index, || {
+            scalars.random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Creates the party at `index` in `1..=n`, drawing its polynomial from
+    /// `sample`, which must return uniformly random elements modulo q.
+    pub fn new_with<S>(
+        group: SchnorrGroup,
+        t: usize,
+        n: usize,
+        index: usize,
+        mut sample: S,
+    ) -> Result<Self, TinySsError>
+    where
+        S: FnMut() -> BigInt,
+    {
+        let sharing = SecretShare::new(t, n, group.q().clone())?;
+        if index == 0 || index > n {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let polynomial = (0..t).map(|_| sample()).collect();
+        Ok(Party {
+            group,
+            sharing,
+            index,
+            polynomial,
+        })
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn group(&self) -> &SchnorrGroup {
+        &self.group
+    }
+
+    /// Round 1 broadcast.
+    pub fn commitments(&self) -> Commitments {
+        let values = self
+            .polynomial
+            .iter()
+            .map(|a| self.group.exp(self.group.g(), a))
+            .collect();
+        Commitments::new(self.index, values)
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn prove_secret(&self) -> SchnorrProof {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let nonce = self.group.scalars().random(&mut |buf| rng.fill_bytes(buf));
+        self.prove_secret_with(&nonce)
+    }
+
+    /// Round 1 broadcast proving knowledge of the dealt secret behind
+    /// `C_0`, using the uniformly random `nonce`.
+    pub fn prove_secret_with(&self, nonce: &BigInt) -> SchnorrProof {
+        let c0 = self.group.exp(self.group.g(), &self.polynomial[0]);
+        SchnorrProof::prove(
+            &self.group,
+            &mut proof_transcript(self.index),
+            (self.group.g(), &c0),
+            &self.polynomial[0],
+            nonce,
+        )
+    }
+
+    /// Round 1 private message to the party at `receiver`.
+    pub fn share_for(&self, receiver: usize) -> Result<DealtShare, TinySsError> {
+        if receiver == 0 || receiver > self.sharing.n() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let scalars = self.group.scalars();
+        let value = poly::evaluate(
+            scalars,
+            &self.polynomial,
+            &scalars.elem_from_index(receiver),
+        );
+        Ok(DealtShare::new(self.index, receiver, value))
+    }
+
+    /// Round 2: verifies the commitments and shares of all `n` dealers,
+    /// this party's own included, and combines them into its key share.
+    pub fn finish(
+        &self,
+        commitments: &[Commitments],
+        shares: &[DealtShare],
+    ) -> Result<KeyShare, TinySsError> {
+        let n = self.sharing.n();
+        let dealers = complete(by_sender(n, commitments, |c| c.sender)?)?;
+        let received = complete(by_sender(n, shares, |s| s.sender)?)?;
+        for (commitment, share) in dealers.iter().zip(&received) {
+            if !self.is_valid(commitment, share) {
+                return Err(TinySsError::InvalidShare {
+                    index: share.sender,
+                });
+            }
+        }
+        Ok(self.combine(&dealers, &received))
+    }
+
+    // Feldman check g^{s} = prod_j C_j^{i^j} of a share sent to this party
+    fn is_valid(&self, commitments: &Commitments, share: &DealtShare) -> bool {
+        commitments.values.len() == self.sharing.t()
+            && share.receiver == self.index
+            && commitments.values.iter().all(|c| self.group.is_element(c))
+            && self.group.exp(self.group.g(), &share.value)
+                == self.group.eval_commitments(&commitments.values, self.index)
+    }
+
+    fn combine(&self, dealers: &[&Commitments], received: &[&DealtShare]) -> KeyShare {
+        let group = &self.group;
+        let scalars = group.scalars();
+        let secret = received
+            .iter()
+            .fold(scalars.zero(), |sum, share| scalars.add(&sum, &share.value));
+        let product = |f: &dyn Fn(&Commitments) -> BigInt| {
+            dealers
+                .iter()
+                .fold(BigInt::from(1), |acc, c| group.mul(&acc, &f(c)))
+        };
+        let public_key = product(&|c| c.values[0].clone());
+        let public_shares = (1..=self.sharing.n())
+            .map(|i| product(&|c| group.eval_commitments(&c.values, i)))
+            .collect();
+        KeyShare {
+            index: self.index,
+            secret,
+            public_key,
+            public_shares,
+        }
+    }
+}
+
+// Slots messages by their dealer in `1..=n`, rejecting repeats
+fn by_sender<M>(
+    n: usize,
+    messages: &[M],
+    sender: impl Fn(&M) -> usize,
+) -> Result<Vec<Option<&M>>, TinySsError> {
+    let mut ordered: Vec<Option<&M>> = (0..n).map(|_| None).collect();
+    for message in messages {
+        let index = sender(message);
+        let slot = ordered
+            .get_mut(index.wrapping_sub(1))
+            .ok_or(TinySsError::InvalidIndex)?;
+        if slot.replace(message).is_some() {
+            return Err(TinySsError::ConflictingShares { index });
+        }
+    }
+    Ok(ordered)
+}
+
+// Requires a message from every dealer
+fn complete<M>(ordered: Vec<Option<&M>>) -> Result<Vec<&M>, TinySsError> {
+    let (required, got) = (ordered.len(), ordered.iter().flatten().count());
+    ordered
+        .into_iter()
+        .collect::<Option<Vec<_>>>()
+        .ok_or(TinySsError::NotEnoughShares { required, got })
+}
+
+// Transcript binding a proof of the dealt secret to its dealer
+fn proof_transcript(sender: usize) -> Sha256Transcript {
+    let mut transcript = Sha256Transcript::new(b"tiny-ss dkg secret");
+    transcript.append_message(b"sender", &(sender as u64).to_be_bytes());
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parties(t: usize, n: usize) -> Vec<Party> {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        (1..=n)
+            .map(|i| {
+                let mut next = i as u64 * 31;
+                Party::new_with(group.clone(), t, n, i, || {
+                    next = (next * 13 + 5) % 1019;
+                    BigInt::from(next)
+                })
+                .unwrap()
+            })
+            .collect()
+    }
+
+    fn run(parties: &[Party]) -> Vec<Result<KeyShare, TinySsError>> {
+        let commitments: Vec<Commitments> = parties.iter().map(Party::commitments).collect();
+        parties
+            .iter()
+            .map(|receiver| {
+                let shares: Vec<DealtShare> = parties
+                    .iter()
+                    .map(|dealer| dealer.share_for(receiver.index()).unwrap())
+                    .collect();
+                receiver.finish(&commitments, &shares)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn prove_secret_test() {
+        let parties = parties(2, 3);
+        let group = parties[0].group();
+        let commitments: Vec<Commitments> = parties.iter().map(Party::commitments).collect();
+        let proofs: Vec<SchnorrProof> = parties
+            .iter()
+            .map(|p| p.prove_secret_with(&BigInt::from(321)))
+            .collect();
+        for (c, proof) in commitments.iter().zip(&proofs) {
+            assert!(c.verify_secret_proof(group, proof));
+        }
+        // a proof cannot be claimed by another dealer
+        assert!(!commitments[1].verify_secret_proof(group, &proofs[0]));
+    }
+
+    #[test]
+    fn dkg_test() {
+        let parties = parties(2, 3);
+        let keys: Vec<KeyShare> = run(&parties).into_iter().map(Result::unwrap).collect();
+        let group = parties[0].group();
+
+        let secret = parties.iter().fold(BigInt::from(0), |sum, p| {
+            group.scalars().add(&sum, &p.polynomial[0])
+        });
+        assert_eq!(&group.exp(group.g(), &secret), keys[0].public_key());
+        assert!(keys.iter().all(|k| k.public_key() == keys[0].public_key()));
+        for key in &keys {
+            assert_eq!(
+                keys[0].public_share(key.index()),
+                Some(&group.exp(group.g(), key.secret()))
+            );
+        }
+
+        let sharing = SecretShare::new(2, 3, group.q().clone()).unwrap();
+        let shares: Vec<Share> = keys.iter().map(KeyShare::share).collect();
+        assert_eq!(sharing.recover(&shares[1..]).unwrap(), secret);
+    }
+
+    #[test]
+    fn invalid_share_test() {
+        let parties = parties(2, 3);
+        let commitments: Vec<Commitments> = parties.iter().map(Party::commitments).collect();
+        let mut shares: Vec<DealtShare> = parties
+            .iter()
+            .map(|dealer| dealer.share_for(1).unwrap())
+            .collect();
+        assert_eq!(
+            parties[0].finish(&commitments, &shares[1..]),
+            Err(TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            })
+        );
+        shares[2].value += 1;
+        assert_eq!(
+            parties[0].finish(&commitments, &shares),
+            Err(TinySsError::InvalidShare { index: 3 })
+        );
+        assert_eq!(parties[0].share_for(4), Err(TinySsError::InvalidIndex));
+    }
+}
@@ -0,0 +1,273 @@
+//! Export of secp256k1 dealings for threshold-ECDSA implementations.
+//!
+//! Threshold-ECDSA protocols such as GG18/GG20 or CGGMP start from Shamir
+//! shares of the signing key modulo the secp256k1 group order, and expect
+//! each party to hold its share as a 32-byte big-endian scalar, its public
+//! share and the group public key as compressed SEC1 points, and its
+//! party index. Implementations disagree on whether party indices start at
+//! 0 or 1, while the share itself is always the polynomial evaluated at a
+//! nonzero point, so the convention is chosen at export and the evaluation
+//! point is carried alongside.
+
+use crate::{field::Field, poly, presets, Inconsistency, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+use core::fmt;
+use num_bigint::{BigInt, Sign};
+use secp256k1::{PublicKey, Secp256k1, SecretKey};
+
+/// bytes of a big-endian scalar
+pub const SCALAR_BYTES: usize = 32;
+/// bytes of a compressed SEC1 point
+pub const POINT_BYTES: usize = 33;
+
+/// How a toolkit numbers its parties.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum IndexConvention {
+    /// party `i` holds the share at `x = i`
+    OneBased,
+    /// party `i` holds the share at `x = i + 1`
+    ZeroBased,
+}
+
+/// One party's key material in the layout of threshold-ECDSA toolkits.
+#[derive(Clone, PartialEq, Eq)]
+pub struct EcdsaKeyShare {
+    party_index: usize,
+    /// evaluation point of the share
+    x: usize,
+    /// big-endian share of the signing key
+    secret: [u8; SCALAR_BYTES],
+    /// compressed share times the generator
+    public_share: [u8; POINT_BYTES],
+    /// compressed group public key
+    public_key: [u8; POINT_BYTES],
+    threshold: usize,
+    parties: usize,
+}
+
+impl fmt::Debug for EcdsaKeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EcdsaKeyShare")
+            .field("party_index", &self.party_index)
+            .field("x", &self.x)
+            .field("secret", crate::debug::secret(&self.secret))
+            .field("public_share", &self.public_share)
+            .field("public_key", &self.public_key)
+            .field("threshold", &self.threshold)
+            .field("parties", &self.parties)
+            .finish()
+    }
+}
+
+impl EcdsaKeyShare {
+    pub fn party_index(&self) -> usize {
+        self.party_index
+    }
+
+    /// evaluation point of the share
+    pub fn x(&self) -> usize {
+        self.x
+    }
+
+    /// big-endian share of the signing key
+    pub fn secret(&self) -> &[u8; SCALAR_BYTES] {
+        &self.secret
+    }
+
+    /// compressed share times the generator
+    pub fn public_share(&self) -> &[u8; POINT_BYTES] {
+        &self.public_share
+    }
+
+    /// compressed group public key
+    pub fn public_key(&self) -> &[u8; POINT_BYTES] {
+        &self.public_key
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    pub fn parties(&self) -> usize {
+        self.parties
+    }
+}
+
+/// The order of the secp256k1 group, the modulus of a dealing.
+pub fn order() -> BigInt {
+    presets::modulus(presets::SECP256K1_ORDER)
+}
+
+/// A `t`-of-`n` scheme over the secp256k1 scalars.
+pub fn scheme(t: usize, n: usize) -> Result<SecretShare, TinySsError> {
+    SecretShare::secp256k1_order(t, n)
+}
+
+/// Exports the shares of a dealing under `scheme`, which must be over the
+/// secp256k1 scalars, after checking that they lie on one polynomial
+/// whose shares are all valid secret keys. Shares repeating an index are
+/// exported once.
+///
+/// Fails with `InvalidIndex` for indices outside `1..=n` and with
+/// `InvalidShare` for values that are not canonical scalars.
+pub fn export(
+    scheme: &SecretShare,
+    shares: &[Share],
+    convention: IndexConvention,
+) -> Result<Vec<EcdsaKeyShare>, TinySsError> {
+    let order = order();
+    if scheme.field().p() != &order {
+        return Err(TinySsError::InvalidModulus);
+    }
+    for share in shares {
+        if share.index() == 0 || share.index() > scheme.n() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        if share.value().sign() == Sign::Minus || share.value() >= &order {
+            return Err(TinySsError::InvalidShare {
+                index: share.index(),
+            });
+        }
+    }
+    scheme.verify_consistent(shares).map_err(|e| match e {
+        Inconsistency::OffPolynomial { indices } => TinySsError::InvalidShare { index: indices[0] },
+        Inconsistency::Unusable(e) => e,
+    })?;
+    let shares = poly::distinct_shares(shares, scheme.t())?;
+    let secp = Secp256k1::new();
+    let public_shares: Vec<PublicKey> = shares
+        .iter()
+        .map(|s| {
+            let secret = SecretKey::from_slice(&encode(s.value()))
+                .map_err(|_| TinySsError::InvalidShare { index: s.index() })?;
+            Ok(PublicKey::from_secret_key(&secp, &secret))
+        })
+        .collect::<Result<_, TinySsError>>()?;
+
+    // Y = sum of l_i Y_i over the first t shares
+    let first = &shares[..scheme.t()];
+    let field = scheme.field();
+    let xs: Vec<BigInt> = first
+        .iter()
+        .map(|s| field.elem_from_index(s.index()))
+        .collect();
+    let weights = poly::lagrange_coefficients(field, &xs, &field.zero())?;
+    let mut public_key: Option<PublicKey> = None;
+    for (weight, public_share) in weights.iter().zip(&public_shares) {
+        let mut term = *public_share;
+        term.mul_assign(&secp, &encode(weight))
+            .map_err(|_| TinySsError::NotInvertible)?;
+        public_key = Some(match public_key {
+            None => term,
+            Some(sum) => sum.combine(&term).map_err(|_| TinySsError::InvalidGroup)?,
+        });
+    }
+    let public_key = public_key.expect("a threshold of at least one").serialize();
+
+    shares
+        .iter()
+        .zip(&public_shares)
+        .map(|(share, public_share)| {
+            let party_index = match convention {
+                IndexConvention::OneBased => share.index(),
+                IndexConvention::ZeroBased => share.index() - 1,
+            };
+            Ok(EcdsaKeyShare {
+                party_index,
+                x: share.index(),
+                secret: encode(share.value()),
+                public_share: public_share.serialize(),
+                public_key,
+                threshold: scheme.t(),
+                parties: scheme.n(),
+            })
+        })
+        .collect()
+}
+
+// big-endian scalar, left padded to 32 bytes, of a value below the order
+fn encode(value: &BigInt) -> [u8; SCALAR_BYTES] {
+    let (sign, bytes) = value.to_bytes_be();
+    debug_assert!(sign != Sign::Minus && bytes.len() <= SCALAR_BYTES);
+    let mut encoded = [0u8; SCALAR_BYTES];
+    encoded[SCALAR_BYTES - bytes.len()..].copy_from_slice(&bytes);
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn export_test() {
+        let ss = scheme(2, 3).unwrap();
+        let secret = BigInt::from(777);
+        let shares = ss
+            .split_with_coefficients(secret.clone(), &[BigInt::from(13)])
+            .unwrap();
+        let exported = export(&ss, &shares, IndexConvention::ZeroBased).unwrap();
+        assert_eq!(exported.len(), 3);
+        assert_eq!(exported[0].party_index(), 0);
+        assert_eq!(exported[0].x(), 1);
+        // 777 + 3 * 13 = 0x330
+        assert_eq!(exported[2].secret()[30..], [0x03, 0x30]);
+
+        let secp = Secp256k1::new();
+        let expected =
+            PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&encode(&secret)).unwrap());
+        assert!(exported
+            .iter()
+            .all(|e| e.public_key() == &expected.serialize()));
+        assert_eq!(exported[1].threshold(), 2);
+        assert_eq!(
+            export(&ss, &shares, IndexConvention::OneBased).unwrap()[1].party_index(),
+            2
+        );
+
+        let mut tampered = shares.clone();
+        tampered[2] = Share::new(3, BigInt::from(5));
+        assert_eq!(
+            export(&ss, &tampered, IndexConvention::OneBased),
+            Err(TinySsError::InvalidShare { index: 3 })
+        );
+        let mut duplicated = shares.clone();
+        duplicated.insert(1, shares[0].clone());
+        let exported = export(&ss, &duplicated, IndexConvention::OneBased).unwrap();
+        assert_eq!(exported.len(), 3);
+        assert!(exported
+            .iter()
+            .all(|e| e.public_key() == &expected.serialize()));
+
+        // the same point, off by a multiple of the order
+        let oversized = Share::new(1, shares[0].value() + (order() << 300));
+        assert_eq!(
+            export(
+                &ss,
+                &[oversized, shares[1].clone()],
+                IndexConvention::OneBased
+            ),
+            Err(TinySsError::InvalidShare { index: 1 })
+        );
+        let negative = Share::new(1, shares[0].value() - order());
+        assert_eq!(
+            export(
+                &ss,
+                &[negative, shares[1].clone()],
+                IndexConvention::OneBased
+            ),
+            Err(TinySsError::InvalidShare { index: 1 })
+        );
+        for index in [0, 4] {
+            let stray = Share::new(index, BigInt::from(1));
+            assert_eq!(
+                export(&ss, &[stray, shares[1].clone()], IndexConvention::ZeroBased),
+                Err(TinySsError::InvalidIndex)
+            );
+        }
+        let small = SecretShare::new(2, 3, BigInt::from(1613)).unwrap();
+        assert_eq!(
+            export(&small, &shares, IndexConvention::OneBased),
+            Err(TinySsError::InvalidModulus)
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! Threshold ElGamal decryption.
+//!
+//! The decryption key `x` with public key `Y = g^x` is Shamir shared modulo
+//! q. A ciphertext `(c1, c2) = (g^r, m Y^r)` is opened without anybody
+//! learning `x`: each holder publishes `d_i = c1^{x_i}` with a DLEQ proof
+//! that it used the same exponent as in its public share `g^{x_i}`, and any
+//! `t` correct partials give `c1^x = prod_i d_i^{l_i}` with the Lagrange
+//! coefficients `l_i` at 0, and so `m = c2 / c1^x`.
+
+use crate::{
+    field::Field,
+    group::SchnorrGroup,
+    poly,
+    pvss::DleqProof,
+    transcript::{Sha256Transcript, Transcript},
+    SecretShare, Share, TinySsError,
+};
+use alloc::vec::Vec;
+use num_bigint::BigInt;
+use num_traits::One;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// An ElGamal ciphertext `(g^r, m Y^r)` of a group element `m`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Ciphertext {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    c1: BigInt,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    c2: BigInt,
+}
+
+impl Ciphertext {
+    pub fn new(c1: BigInt, c2: BigInt) -> Self {
+        Ciphertext { c1, c2 }
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn encrypt(
+        group: &SchnorrGroup,
+        public_key: &BigInt,
+        message: &BigInt,
+    ) -> Result<Self, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let r = group.scalars().random(&mut |buf| rng.fill_bytes(buf));
+        Ciphertext::encrypt_with(group, public_key, message, &r)
+    }
+
+    /// Encrypts the group element `message` under `public_key` with the
+    /// uniformly random exponent `r`.
+    pub fn encrypt_with(
+        group: &SchnorrGroup,
+        public_key: &BigInt,
+        message: &BigInt,
+        r: &BigInt,
+    ) -> Result<Self, TinySsError> {
+        if !group.is_element(message) {
+            return Err(TinySsError::SecretOutOfRange);
+        }
+        Ok(Ciphertext {
+            c1: group.exp(group.g(), r),
+            c2: group.mul(message, &group.exp(public_key, r)),
+        })
+    }
+
+    pub fn c1(&self) -> &BigInt {
+        &self.c1
+    }
+
+    pub fn c2(&self) -> &BigInt {
+        &self.c2
+    }
+}
+
+/// A holder's `c1^{x_i}` with its proof of correctness.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartialDecryption {
+    index: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    value: BigInt,
+    proof: DleqProof,
+}
+
+impl PartialDecryption {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    pub fn proof(&self) -> &DleqProof {
+        &self.proof
+    }
+}
+
+/// Decryption with a `t`-of-`n` sharing of an ElGamal key.
+#[derive(Clone, Debug)]
+pub struct ThresholdElGamal {
+    group: SchnorrGroup,
+    /// sharing over the exponents, modulo q
+    sharing: SecretShare,
+}
+
+impl ThresholdElGamal {
+    pub fn new(group: SchnorrGroup, t: usize, n: usize) -> Result<Self, TinySsError> {
+        let sharing = SecretShare::new(t, n, group.q().clone())?;
+        Ok(ThresholdElGamal { group, sharing })
+    }
+
+    pub fn group(&self) -> &SchnorrGroup {
+        &self.group
+    }
+
+    /// The sharing of the exponents.
+    pub fn shamir(&self) -> &SecretShare {
+        &self.sharing
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn partial_decrypt(
+        &self,
+        share: &Share,
+        ciphertext: &Ciphertext,
+    ) -> Result<PartialDecryption, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let nonce = self.group.scalars().random(&mut |buf| rng.fill_bytes(buf));
+        self.partial_decrypt_with(share, ciphertext, &nonce)
+    }
+
+    /// A holder's partial decryption from its key share, proved with the
+    /// uniformly random `nonce`.
+    pub fn partial_decrypt_with(
+        &self,
+        share: &Share,
+        ciphertext: &Ciphertext,
+        nonce: &BigInt,
+    ) -> Result<PartialDecryption, TinySsError> {
+        if share.index() == 0 || share.index() > self.sharing.n() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let group = &self.group;
+        if !group.is_element(&ciphertext.c1) {
+            return Err(TinySsError::InvalidShare {
+                index: share.index(),
+            });
+        }
+        let value = group.exp(&ciphertext.c1, share.value());
+        let proof = DleqProof::prove(
+            group,
+            &mut transcript(group, share.index(), ciphertext),
+            (group.g(), &group.exp(group.g(), share.value())),
+            (&ciphertext.c1, &value),
+            share.value(),
+            nonce,
+        );
+        Ok(PartialDecryption {
+            index: share.index(),
+            value,
+            proof,
+        })
+    }
+
+    /// Checks a partial decryption against the holder's public share
+    /// `g^{x_i}`.
+    pub fn verify_partial(
+        &self,
+        partial: &PartialDecryption,
+        public_share: &BigInt,
+        ciphertext: &Ciphertext,
+    ) -> bool {
+        let group = &self.group;
+        group.is_element(&partial.value)
+            && group.is_element(public_share)
+            && partial.proof.verify(
+                group,
+                &mut transcript(group, partial.index, ciphertext),
+                (group.g(), public_share),
+                (&ciphertext.c1, &partial.value),
+            )
+    }
+
+    /// Recovers the plaintext from at least `t` partial decryptions, which
+    /// the caller has checked with `verify_partial`.
+    pub fn combine(
+        &self,
+        ciphertext: &Ciphertext,
+        partials: &[PartialDecryption],
+    ) -> Result<BigInt, TinySsError> {
+        if partials
+            .iter()
+            .any(|p| p.index == 0 || p.index > self.sharing.n())
+        {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let shares: Vec<Share> = partials
+            .iter()
+            .map(|p| Share::new(p.index, p.value.clone()))
+            .collect();
+        let unique = poly::distinct_shares(&shares, self.sharing.t())?;
+        let group = &self.group;
+        let scalars = group.scalars();
+        let xs: Vec<BigInt> = unique
+            .iter()
+            .map(|s| scalars.elem_from_index(s.index()))
+            .collect();
+        let weights = poly::lagrange_coefficients(scalars, &xs, &scalars.zero())?;
+        let values: Vec<BigInt> = unique.iter().map(|s| s.value().clone()).collect();
+        let blinding = group.multi_exp(&values, &weights);
+        // dividing by an element of the order-q subgroup is raising it to q - 1
+        let unblinding = group.exp(&blinding, &(group.q() - BigInt::one()));
+        Ok(group.mul(&ciphertext.c2, &unblinding))
+    }
+}
+
+// Binds a decryption proof to the holder and the ciphertext
+fn transcript(group: &SchnorrGroup, index: usize, ciphertext: &Ciphertext) -> Sha256Transcript {
+    let mut transcript = Sha256Transcript::new(b"tiny-ss elgamal decryption");
+    transcript.append_message(b"index", &(index as u64).to_be_bytes());
+    group.append_element(&mut transcript, b"c1", &ciphertext.c1);
+    group.append_element(&mut transcript, b"c2", &ciphertext.c2);
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold_elgamal_test() {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        let elgamal = ThresholdElGamal::new(group.clone(), 2, 3).unwrap();
+        let x = BigInt::from(777);
+        let shares = elgamal
+            .shamir()
+            .split_with_coefficients(x.clone(), &[BigInt::from(13)])
+            .unwrap();
+        let y = group.exp(group.g(), &x);
+        let message = group.exp(group.g(), &BigInt::from(42));
+        let ciphertext =
+            Ciphertext::encrypt_with(&group, &y, &message, &BigInt::from(300)).unwrap();
+
+        let partials: Vec<PartialDecryption> = shares
+            .iter()
+            .map(|s| {
+                elgamal
+                    .partial_decrypt_with(s, &ciphertext, &BigInt::from(55))
+                    .unwrap()
+            })
+            .collect();
+        for (partial, share) in partials.iter().zip(&shares) {
+            let public_share = group.exp(group.g(), share.value());
+            assert!(elgamal.verify_partial(partial, &public_share, &ciphertext));
+        }
+        assert_eq!(
+            elgamal.combine(&ciphertext, &partials[1..]).unwrap(),
+            message
+        );
+        assert_eq!(
+            elgamal.combine(&ciphertext, &partials[..2]).unwrap(),
+            message
+        );
+
+        // a partial under another share fails its proof
+        let wrong = elgamal
+            .partial_decrypt_with(
+                &Share::new(1, BigInt::from(5)),
+                &ciphertext,
+                &BigInt::from(55),
+            )
+            .unwrap();
+        let public_share = group.exp(group.g(), shares[0].value());
+        assert!(!elgamal.verify_partial(&wrong, &public_share, &ciphertext));
+
+        assert_eq!(
+            Ciphertext::encrypt_with(&group, &y, &BigInt::from(2038), &BigInt::from(1)),
+            Err(TinySsError::SecretOutOfRange)
+        );
+    }
+}
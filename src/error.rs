@@ -0,0 +1,171 @@
+use alloc::vec::Vec;
+use core::fmt;
+
+/// Errors returned by the dealer and the combiner.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TinySsError {
+    /// threshold must be nonzero and smaller than the number of shares
+    InvalidThreshold,
+    /// modulus must be larger than the number of shares
+    InvalidModulus,
+    /// fewer distinct shares than the threshold were given to `recover`
+    NotEnoughShares { required: usize, got: usize },
+    /// two shares with the same index carry different values
+    ConflictingShares { index: usize },
+    /// number of caller-provided coefficients does not match `t - 1`
+    WrongCoefficientCount { expected: usize, got: usize },
+    /// a denominator has no inverse modulo p
+    NotInvertible,
+    /// shares of one secret have values of different lengths
+    LengthMismatch,
+    /// a share index is zero, which would reveal the secret
+    InvalidIndex,
+    /// group parameters do not describe a prime-order subgroup
+    InvalidGroup,
+    /// a share does not match the dealer's commitments
+    InvalidShare { index: usize },
+    /// a participant name is unknown or already taken
+    InvalidParticipant,
+    /// policy text is malformed
+    InvalidPolicy,
+    /// secret lies outside the range the scheme can share
+    SecretOutOfRange,
+    /// recovered data does not pass its integrity check
+    IntegrityCheckFailed,
+    /// more shares are corrupted than robust recovery can correct
+    TooManyErrors,
+    /// share sets combined position by position have different indices
+    IndexMismatch,
+    /// modulus failed the primality test
+    CompositeModulus,
+    /// share value is not a canonical field element
+    InvalidValue { index: usize },
+    /// two x-coordinates of dealt shares, or of recovered shares with
+    /// different values, coincide
+    DuplicatePoint,
+    /// shares come from different dealings or parameter sets
+    MixedDealings,
+    /// recovered text is not valid UTF-8
+    InvalidUtf8,
+    /// a full-degree scheme was given a zero coefficient of degree `t - 1`
+    ZeroLeadingCoefficient,
+}
+
+impl fmt::Display for TinySsError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TinySsError::InvalidThreshold => {
+                write!(f, "threshold must be nonzero and smaller than n")
+            }
+            TinySsError::InvalidModulus => write!(f, "modulus must be larger than n"),
+            TinySsError::NotEnoughShares { required, got } => {
+                write!(f, "not enough shares: required {}, got {}", required, got)
+            }
+            TinySsError::ConflictingShares { index } => {
+                write!(f, "conflicting shares at index {}", index)
+            }
+            TinySsError::WrongCoefficientCount { expected, got } => {
+                write!(f, "wrong coefficients: expected {}, got {}", expected, got)
+            }
+            TinySsError::NotInvertible => write!(f, "value is not invertible modulo p"),
+            TinySsError::LengthMismatch => write!(f, "share values differ in length"),
+            TinySsError::InvalidIndex => write!(f, "share index must be nonzero"),
+            TinySsError::InvalidGroup => write!(f, "invalid group parameters"),
+            TinySsError::InvalidShare { index } => {
+                write!(f, "share {} does not match the commitments", index)
+            }
+            TinySsError::InvalidParticipant => write!(f, "unknown or duplicate participant"),
+            TinySsError::InvalidPolicy => write!(f, "malformed policy"),
+            TinySsError::SecretOutOfRange => write!(f, "secret out of range"),
+            TinySsError::IntegrityCheckFailed => write!(f, "integrity check failed"),
+            TinySsError::TooManyErrors => write!(f, "too many corrupted shares"),
+            TinySsError::IndexMismatch => write!(f, "share indices do not line up"),
+            TinySsError::CompositeModulus => write!(f, "modulus is not prime"),
+            TinySsError::InvalidValue { index } => {
+                write!(f, "share {} has a value outside the field", index)
+            }
+            TinySsError::DuplicatePoint => write!(f, "shares at the same x-coordinate"),
+            TinySsError::MixedDealings => write!(f, "shares of different dealings"),
+            TinySsError::InvalidUtf8 => write!(f, "recovered text is not valid UTF-8"),
+            TinySsError::ZeroLeadingCoefficient => write!(f, "leading coefficient is zero"),
+        }
+    }
+}
+
+/// Reasons a set of shares fails `verify_consistent`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Inconsistency {
+    /// the shares could not be interpolated in the first place
+    Unusable(TinySsError),
+    /// indices of the shares that do not lie on the interpolated polynomial
+    OffPolynomial { indices: Vec<usize> },
+}
+
+impl From<TinySsError> for Inconsistency {
+    fn from(err: TinySsError) -> Self {
+        Inconsistency::Unusable(err)
+    }
+}
+
+impl fmt::Display for Inconsistency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Inconsistency::Unusable(err) => write!(f, "{}", err),
+            Inconsistency::OffPolynomial { indices } => {
+                write!(f, "shares off the polynomial: {:?}", indices)
+            }
+        }
+    }
+}
+
+/// Errors returned when parsing an encoded share.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// input does not start with the expected magic bytes
+    BadMagic,
+    /// input was written by an unknown format version
+    UnsupportedVersion(u8),
+    /// input was dealt by an unknown scheme
+    UnsupportedScheme(u8),
+    /// input ended before the encoded share did
+    Truncated,
+    /// input continues after the encoded share
+    TrailingBytes,
+    /// share index does not fit into `usize`
+    IndexOverflow,
+    /// text is not valid hex or base64 or has nonzero padding bits
+    InvalidEncoding,
+    /// a word is not in the wordlist
+    InvalidWord,
+    /// the embedded checksum does not match
+    InvalidChecksum,
+    /// chunks are not numbered consecutively from 0
+    MisorderedChunk,
+    /// share value is not a field element of the expected width
+    InvalidValue,
+    /// a test vector is malformed or incomplete at `line`
+    MalformedVector { line: usize },
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::BadMagic => write!(f, "bad magic bytes"),
+            DecodeError::UnsupportedVersion(version) => {
+                write!(f, "unsupported format version {}", version)
+            }
+            DecodeError::UnsupportedScheme(scheme) => write!(f, "unsupported scheme {}", scheme),
+            DecodeError::Truncated => write!(f, "truncated share"),
+            DecodeError::TrailingBytes => write!(f, "trailing bytes after share"),
+            DecodeError::IndexOverflow => write!(f, "share index does not fit into usize"),
+            DecodeError::InvalidEncoding => write!(f, "invalid text encoding"),
+            DecodeError::InvalidWord => write!(f, "word not in the wordlist"),
+            DecodeError::InvalidChecksum => write!(f, "invalid checksum"),
+            DecodeError::MisorderedChunk => write!(f, "chunks out of order"),
+            DecodeError::InvalidValue => write!(f, "share value is not a field element"),
+            DecodeError::MalformedVector { line } => {
+                write!(f, "malformed test vector at line {}", line)
+            }
+        }
+    }
+}
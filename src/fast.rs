@@ -0,0 +1,494 @@
+//! Near-linear evaluation and interpolation for large thresholds.
+//!
+//! Horner's rule evaluates a polynomial of `t` coefficients at the `n`
+//! share indices with `n * t` multiplications. The indices are consecutive,
+//! so once the values at the first `t` of them are known, those at the next
+//! `t` follow from a single product of polynomials of length `t` and `2t`,
+//! which the number-theoretic transform computes in `O(t log t)` when the
+//! field has the roots of unity for it, and Karatsuba's method in
+//! `O(t^1.58)` otherwise. Past the first block a dealing thus costs
+//! `O(n log t)` multiplications in such fields.
+//!
+//! Interpolating through `t` points needs the Lagrange denominators
+//! `prod_{k != j} (x_j - x_k)`, `t^2` multiplications when taken one by
+//! one. They are the values at the points of the derivative of
+//! `prod_k (x - x_k)`, which a subproduct tree, the products over ever
+//! larger groups of points, evaluates by reducing it modulo each node on
+//! the way down, with products as above and division by a Newton
+//! iteration.
+//!
+//! The constant factors are large, so evaluation only takes this path from
+//! `FAST_THRESHOLD` coefficients on, and interpolation from
+//! `TREE_THRESHOLD` points. The intermediate products of a dealing depend
+//! on the secret and, unlike the polynomial, are not wiped with the
+//! `zeroize` feature.
+
+use crate::field::Field;
+use alloc::{vec, vec::Vec};
+
+/// number of coefficients from which dealings are evaluated block by block
+pub(crate) const FAST_THRESHOLD: usize = 128;
+/// number of points from which interpolation uses a subproduct tree
+pub(crate) const TREE_THRESHOLD: usize = 2048;
+// shortest operands for which the faster products pay off
+const KARATSUBA_THRESHOLD: usize = 16;
+const NTT_THRESHOLD: usize = 64;
+// quotients shorter than this are computed by long division
+const NEWTON_THRESHOLD: usize = 32;
+// tree leaves hold this many points, evaluated by Horner's rule
+const LEAF_POINTS: usize = 8;
+
+/// Coefficients, constant term first.
+type Poly<E> = Vec<E>;
+
+// The product of `a` and `b`
+pub(crate) fn mul<F: Field>(field: &F, a: &[F::Elem], b: &[F::Elem]) -> Poly<F::Elem> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) >= NTT_THRESHOLD {
+        let log_size = (a.len() + b.len() - 1).next_power_of_two().trailing_zeros();
+        if let Some(root) = field.root_of_unity(log_size) {
+            return ntt_mul(field, a, b, &root);
+        }
+    }
+    karatsuba(field, a, b)
+}
+
+// Coefficient k is the dot product of a_i for i in [lo, hi] with b_{k-i},
+// which run down as i runs up, so it is taken with `b` reversed
+fn schoolbook<F: Field>(field: &F, a: &[F::Elem], b: &[F::Elem]) -> Poly<F::Elem> {
+    let reversed: Poly<F::Elem> = b.iter().rev().cloned().collect();
+    (0..a.len() + b.len() - 1)
+        .map(|k| {
+            let lo = (k + 1).saturating_sub(b.len());
+            let hi = k.min(a.len() - 1);
+            // b_{k-i} = reversed[b.len() - 1 - k + i]
+            let start = b.len() - 1 + lo - k;
+            field.dot(&a[lo..=hi], &reversed[start..start + hi + 1 - lo])
+        })
+        .collect()
+}
+
+// a0 b0 + ((a0 + a1)(b0 + b1) - a0 b0 - a1 b1) x^m + a1 b1 x^2m with both
+// operands split at m; an operand shorter than m instead multiplies the
+// other one slice by slice
+fn karatsuba<F: Field>(field: &F, a: &[F::Elem], b: &[F::Elem]) -> Poly<F::Elem> {
+    if a.len().min(b.len()) < KARATSUBA_THRESHOLD {
+        return schoolbook(field, a, b);
+    }
+    let mut product = vec![field.zero(); a.len() + b.len() - 1];
+    let m = a.len().max(b.len()) / 2;
+    if a.len() <= m || b.len() <= m {
+        let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+        for (k, slice) in long.chunks(short.len()).enumerate() {
+            add_into(
+                field,
+                &mut product[k * short.len()..],
+                &karatsuba(field, slice, short),
+            );
+        }
+        return product;
+    }
+    let (a0, a1) = a.split_at(m);
+    let (b0, b1) = b.split_at(m);
+    let low = karatsuba(field, a0, b0);
+    let high = karatsuba(field, a1, b1);
+    let mut middle = karatsuba(field, &add(field, a0, a1), &add(field, b0, b1));
+    sub_into(field, &mut middle, &low);
+    sub_into(field, &mut middle, &high);
+    add_into(field, &mut product, &low);
+    add_into(field, &mut product[m..], &middle);
+    add_into(field, &mut product[2 * m..], &high);
+    product
+}
+
+// Pointwise product of the transforms of `a` and `b`, with `root` a
+// primitive root of unity of an order at least their product's length
+fn ntt_mul<F: Field>(field: &F, a: &[F::Elem], b: &[F::Elem], root: &F::Elem) -> Poly<F::Elem> {
+    let len = a.len() + b.len() - 1;
+    let size = len.next_power_of_two();
+    let mut a = a.to_vec();
+    let mut b = b.to_vec();
+    a.resize(size, field.zero());
+    b.resize(size, field.zero());
+    ntt(field, &mut a, root);
+    ntt(field, &mut b, root);
+    for (x, y) in a.iter_mut().zip(&b) {
+        field.mul_assign(x, y);
+    }
+    let root_inv = field.inv(root).expect("a root of unity is invertible");
+    ntt(field, &mut a, &root_inv);
+    let size_inv = field
+        .inv(&field.elem_from_index(size))
+        .expect("the order of a root of unity divides p - 1");
+    a.truncate(len);
+    for x in a.iter_mut() {
+        field.mul_assign(x, &size_inv);
+    }
+    a
+}
+
+// In-place radix-2 transform of `values`, whose length is a power of two,
+// with `root` a primitive root of unity of that order
+fn ntt<F: Field>(field: &F, values: &mut [F::Elem], root: &F::Elem) {
+    let size = values.len();
+    if size <= 1 {
+        return;
+    }
+    let bits = size.trailing_zeros();
+    for i in 0..size {
+        let j = i.reverse_bits() >> (usize::BITS - bits);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    // twiddles[k] = root^k
+    let mut twiddles = Vec::with_capacity(size / 2);
+    let mut twiddle = field.one();
+    for _ in 0..size / 2 {
+        twiddles.push(twiddle.clone());
+        field.mul_assign(&mut twiddle, root);
+    }
+    let mut len = 2;
+    while len <= size {
+        let stride = size / len;
+        for start in (0..size).step_by(len) {
+            for k in 0..len / 2 {
+                let mut v = values[start + k + len / 2].clone();
+                field.mul_assign(&mut v, &twiddles[k * stride]);
+                let u = values[start + k].clone();
+                values[start + k + len / 2] = field.sub(&u, &v);
+                field.add_assign(&mut values[start + k], &v);
+            }
+        }
+        len <<= 1;
+    }
+}
+
+fn add<F: Field>(field: &F, a: &[F::Elem], b: &[F::Elem]) -> Poly<F::Elem> {
+    let (long, short) = if a.len() >= b.len() { (a, b) } else { (b, a) };
+    let mut sum = long.to_vec();
+    add_into(field, &mut sum, short);
+    sum
+}
+
+// dst += src, with dst at least as long as src
+fn add_into<F: Field>(field: &F, dst: &mut [F::Elem], src: &[F::Elem]) {
+    for (d, s) in dst[..src.len()].iter_mut().zip(src) {
+        field.add_assign(d, s);
+    }
+}
+
+// dst -= src, with dst at least as long as src
+fn sub_into<F: Field>(field: &F, dst: &mut [F::Elem], src: &[F::Elem]) {
+    for (d, s) in dst[..src.len()].iter_mut().zip(src) {
+        *d = field.sub(d, s);
+    }
+}
+
+/// Precomputed values for extending the values of a polynomial of `t`
+/// coefficients at `t` consecutive points to the next `t` points.
+///
+/// With `v_i = f(a + i)` for `i < t`, Lagrange's formula at `a + m`, for
+/// `t <= m < 2t`, reads `f(a + m) = P(m) * sum_i w_i v_i / (m - i)`, with
+/// `P(m) = m! / (m - t)!` and `w_i = (-1)^(t-1-i) / (i! (t-1-i)!)`. The sum
+/// is a convolution of `w_i v_i` with `1 / d`, and none of `P`, `w` or the
+/// `1 / d` depends on `a`, so every further block costs one product.
+struct Extension<E> {
+    /// 1 / d for d in 1..2t
+    reciprocals: Vec<E>,
+    /// w_i for i < t
+    weights: Vec<E>,
+    /// P(t + k) for k < t
+    scales: Vec<E>,
+}
+
+impl<E: Clone> Extension<E> {
+    // `None` if some of 1..2t is zero in the field
+    fn new<F: Field<Elem = E>>(field: &F, t: usize) -> Option<Self> {
+        let ds: Vec<E> = (1..2 * t).map(|d| field.elem_from_index(d)).collect();
+        let reciprocals = crate::poly::batch_inverse(field, &ds)?;
+        // inverse_factorials[i] = 1 / i!
+        let mut inverse_factorials = vec![field.one()];
+        for reciprocal in &reciprocals[..t - 1] {
+            let mut next = inverse_factorials.last().expect("starts at 0!").clone();
+            field.mul_assign(&mut next, reciprocal);
+            inverse_factorials.push(next);
+        }
+        let weights = (0..t)
+            .map(|i| {
+                let mut weight = field.mul(&inverse_factorials[i], &inverse_factorials[t - 1 - i]);
+                if (t - 1 - i) % 2 == 1 {
+                    weight = field.sub(&field.zero(), &weight);
+                }
+                weight
+            })
+            .collect();
+        // P(t) = t!, then P(m + 1) = P(m) (m + 1) / (m + 1 - t)
+        let mut scale = ds[..t]
+            .iter()
+            .fold(field.one(), |product, d| field.mul(&product, d));
+        let mut scales = Vec::with_capacity(t);
+        for m in t..2 * t {
+            scales.push(scale.clone());
+            if m + 1 < 2 * t {
+                field.mul_assign(&mut scale, &ds[m]);
+                field.mul_assign(&mut scale, &reciprocals[m - t]);
+            }
+        }
+        Some(Extension {
+            reciprocals,
+            weights,
+            scales,
+        })
+    }
+
+    // f(a + t + k) for k < t from f(a + i) for i < t
+    fn next<F: Field<Elem = E>>(&self, field: &F, values: &[E]) -> Vec<E> {
+        let t = values.len();
+        let weighted: Vec<E> = values
+            .iter()
+            .zip(&self.weights)
+            .map(|(v, w)| field.mul(v, w))
+            .collect();
+        // sum_i w_i v_i / (m - i) is coefficient m - 1 of the product
+        let sums = mul(field, &weighted, &self.reciprocals);
+        sums[t - 1..2 * t - 1]
+            .iter()
+            .zip(&self.scales)
+            .map(|(sum, scale)| field.mul(sum, scale))
+            .collect()
+    }
+}
+
+/// The values of `polynomial` at `1..=n`, from Horner's rule at the first
+/// `t` points, `t` the number of coefficients, and `Extension` block by
+/// block after that, falling back to Horner's rule at every point if the
+/// field is too small for the extension.
+pub(crate) fn evaluate_consecutive<F: Field>(
+    field: &F,
+    polynomial: &[F::Elem],
+    n: usize,
+) -> Vec<F::Elem> {
+    let horner = |x: usize| crate::poly::evaluate(field, polynomial, &field.elem_from_index(x));
+    let t = polynomial.len();
+    let extension = match Extension::new(field, t) {
+        Some(extension) if t > 0 && n > t => extension,
+        _ => return (1..=n).map(horner).collect(),
+    };
+    let mut values: Vec<F::Elem> = (1..=t).map(horner).collect();
+    while values.len() < n {
+        let next = extension.next(field, &values[values.len() - t..]);
+        values.extend(next);
+    }
+    values.truncate(n);
+    values
+}
+
+// 1 / f mod x^len, for f with an invertible constant term, by the Newton
+// iteration g <- g (2 - f g), which doubles the correct terms each step
+fn inverse_series<F: Field>(field: &F, f: &[F::Elem], len: usize) -> Option<Poly<F::Elem>> {
+    let mut g = vec![field.inv(&f[0])?];
+    let two = field.add(&field.one(), &field.one());
+    while g.len() < len {
+        let next = (2 * g.len()).min(len);
+        let mut error = mul(field, &f[..next.min(f.len())], &g);
+        error.resize(next, field.zero());
+        for e in error.iter_mut() {
+            *e = field.sub(&field.zero(), e);
+        }
+        field.add_assign(&mut error[0], &two);
+        g = mul(field, &g, &error);
+        g.truncate(next);
+    }
+    Some(g)
+}
+
+// a mod b, for a monic b of positive degree
+fn rem_monic<F: Field>(field: &F, a: &[F::Elem], b: &[F::Elem]) -> Poly<F::Elem> {
+    let degree = b.len() - 1;
+    if a.len() <= degree {
+        return a.to_vec();
+    }
+    let quotient_len = a.len() - degree;
+    if quotient_len < NEWTON_THRESHOLD || degree < NEWTON_THRESHOLD {
+        // long division, the same operations whatever the coefficients
+        let mut r = a.to_vec();
+        for i in (degree..r.len()).rev() {
+            let lead = r[i].clone();
+            for (j, bj) in b[..degree].iter().enumerate() {
+                r[i - degree + j] = field.sub(&r[i - degree + j], &field.mul(&lead, bj));
+            }
+        }
+        r.truncate(degree);
+        return r;
+    }
+    // the reversed quotient is rev(a) / rev(b) mod x^quotient_len
+    let reversed_a: Poly<F::Elem> = a.iter().rev().take(quotient_len).cloned().collect();
+    let reversed_b: Poly<F::Elem> = b.iter().rev().cloned().collect();
+    let inverse =
+        inverse_series(field, &reversed_b, quotient_len).expect("a monic divisor is invertible");
+    let mut quotient = mul(field, &reversed_a, &inverse);
+    quotient.resize(quotient_len, field.zero());
+    quotient.reverse();
+    let product = mul(field, &quotient, &b[..degree]);
+    // a - q b agrees with a - q (b - x^degree) below degree
+    let mut r = a[..degree].to_vec();
+    sub_into(field, &mut r, &product[..degree.min(product.len())]);
+    r
+}
+
+/// The products of `x - x_i` over groups of points, leaves first; every
+/// node of a level is the product of two adjacent nodes of the level below,
+/// or, last on an odd level, the carried up node itself.
+struct SubproductTree<'a, E> {
+    points: &'a [E],
+    levels: Vec<Vec<Poly<E>>>,
+}
+
+impl<'a, E: Clone> SubproductTree<'a, E> {
+    fn new<F: Field<Elem = E>>(field: &F, points: &'a [E]) -> Self {
+        let leaves: Vec<Poly<E>> = points
+            .chunks(LEAF_POINTS)
+            .map(|group| {
+                group.iter().fold(vec![field.one()], |product, x| {
+                    let factor = [field.sub(&field.zero(), x), field.one()];
+                    schoolbook(field, &product, &factor)
+                })
+            })
+            .collect();
+        let mut levels = vec![leaves];
+        while levels.last().expect("at least the leaves").len() > 1 {
+            let below = levels.last().expect("at least the leaves");
+            let level = below
+                .chunks(2)
+                .map(|pair| match pair {
+                    [left, right] => mul(field, left, right),
+                    [single] => single.clone(),
+                    _ => unreachable!("chunks of at most two"),
+                })
+                .collect();
+            levels.push(level);
+        }
+        SubproductTree { points, levels }
+    }
+
+    /// `prod (x - x_i)` over all points
+    fn root(&self) -> &[E] {
+        &self.levels.last().expect("at least the leaves")[0]
+    }
+
+    /// The values of `polynomial` at every point, in order.
+    fn evaluate<F: Field<Elem = E>>(&self, field: &F, polynomial: &[E]) -> Vec<E> {
+        let mut remainders = vec![rem_monic(field, polynomial, self.root())];
+        for level in self.levels.iter().rev().skip(1) {
+            remainders = level
+                .iter()
+                .enumerate()
+                .map(|(i, node)| rem_monic(field, &remainders[i / 2], node))
+                .collect();
+        }
+        self.points
+            .chunks(LEAF_POINTS)
+            .zip(&remainders)
+            .flat_map(|(group, remainder)| {
+                group
+                    .iter()
+                    .map(|x| crate::poly::evaluate(field, remainder, x))
+                    .collect::<Vec<E>>()
+            })
+            .collect()
+    }
+}
+
+/// `prod_{k != j} (x_j - x_k)` for every point `x_j`, the value at `x_j`
+/// of the derivative of `prod_k (x - x_k)`.
+pub(crate) fn denominators<F: Field>(field: &F, points: &[F::Elem]) -> Vec<F::Elem> {
+    let tree = SubproductTree::new(field, points);
+    let derivative: Poly<F::Elem> = tree
+        .root()
+        .iter()
+        .enumerate()
+        .skip(1)
+        .map(|(i, c)| field.mul(c, &field.elem_from_index(i)))
+        .collect();
+    tree.evaluate(field, &derivative)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field::PrimeField, poly};
+    use num_bigint::BigInt;
+
+    fn sequence(field: &PrimeField, len: usize, seed: u64) -> Vec<BigInt> {
+        (0..len as u64)
+            .map(|i| field.reduce(&BigInt::from(i * i * 7919 + seed * 104729 + 13)))
+            .collect()
+    }
+
+    #[test]
+    fn mul_test() {
+        // 65537 - 1 = 2^16 has the roots for the transform, 1613 - 1 = 4 * 403
+        // does not
+        for p in [65537, 1613] {
+            let field = PrimeField::new(BigInt::from(p)).unwrap();
+            for (len_a, len_b) in [(1, 1), (3, 70), (70, 70), (100, 33), (129, 200)] {
+                let a = sequence(&field, len_a, 1);
+                let b = sequence(&field, len_b, 2);
+                assert_eq!(mul(&field, &a, &b), schoolbook(&field, &a, &b));
+            }
+        }
+    }
+
+    #[test]
+    fn evaluate_consecutive_test() {
+        for p in [65537, 1613] {
+            let field = PrimeField::new(BigInt::from(p)).unwrap();
+            for (len, n) in [(1, 5), (3, 3), (5, 17), (40, 300), (150, 400)] {
+                let polynomial = sequence(&field, len, 3);
+                let expected: Vec<BigInt> = (1..=n)
+                    .map(|x| poly::evaluate(&field, &polynomial, &BigInt::from(x)))
+                    .collect();
+                assert_eq!(evaluate_consecutive(&field, &polynomial, n), expected);
+            }
+        }
+        // 2t - 1 reaches the modulus 5, so the extension does not apply
+        let field = PrimeField::new(BigInt::from(5)).unwrap();
+        let polynomial = sequence(&field, 3, 1);
+        let expected: Vec<BigInt> = (1..=4)
+            .map(|x| poly::evaluate(&field, &polynomial, &BigInt::from(x)))
+            .collect();
+        assert_eq!(evaluate_consecutive(&field, &polynomial, 4), expected);
+    }
+
+    #[test]
+    fn denominators_test() {
+        for p in [65537, 1613] {
+            let field = PrimeField::new(BigInt::from(p)).unwrap();
+            for len in [1, 2, 9, 100, 300] {
+                let points: Vec<BigInt> = (1..=len).map(|i| BigInt::from(3 * i + 1)).collect();
+                let expected: Vec<BigInt> = points
+                    .iter()
+                    .map(|xj| {
+                        points
+                            .iter()
+                            .filter(|xk| *xk != xj)
+                            .fold(field.one(), |d, xk| field.mul(&d, &field.sub(xj, xk)))
+                    })
+                    .collect();
+                assert_eq!(denominators(&field, &points), expected);
+            }
+        }
+        // through the tree, the weights at 0 still interpolate 1 and x
+        let field = PrimeField::new(BigInt::from(65537)).unwrap();
+        let points: Vec<BigInt> = (1..=TREE_THRESHOLD).map(|i| BigInt::from(2 * i)).collect();
+        let weights = poly::lagrange_coefficients(&field, &points, &field.zero()).unwrap();
+        assert_eq!(
+            field.dot(&weights, &vec![field.one(); points.len()]),
+            field.one()
+        );
+        assert_eq!(field.dot(&weights, &points), field.zero());
+    }
+}
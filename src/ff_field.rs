@@ -0,0 +1,186 @@
+//! Backend for the scalar fields of the `ff` ecosystem.
+//!
+//! `FfField<F>` lets `SecretShare` split scalars of curves such as
+//! bls12_381 or pallas/vesta directly, using their fixed-width constant-time
+//! arithmetic instead of `BigInt`.
+
+use crate::{field::Field, SecretShare, TinySsError};
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use ff::PrimeField;
+use rand_core::{impls, CryptoRng, RngCore};
+
+/// The field `F` as a `Field`, with elements as `F` itself.
+#[derive(Debug)]
+pub struct FfField<F>(PhantomData<F>);
+
+impl<F> FfField<F> {
+    pub fn new() -> Self {
+        FfField(PhantomData)
+    }
+}
+
+impl<F> Default for FfField<F> {
+    fn default() -> Self {
+        FfField::new()
+    }
+}
+
+impl<F> Clone for FfField<F> {
+    fn clone(&self) -> Self {
+        FfField::new()
+    }
+}
+
+impl<F: PrimeField> SecretShare<FfField<F>> {
+    /// Creates a `t`-of-`n` scheme over the scalar field `F`.
+    pub fn ff(t: usize, n: usize) -> Result<Self, TinySsError> {
+        SecretShare::with_field(t, n, FfField::new())
+    }
+}
+
+impl<F: PrimeField> Field for FfField<F> {
+    type Elem = F;
+
+    fn zero(&self) -> F {
+        F::ZERO
+    }
+
+    fn one(&self) -> F {
+        F::ONE
+    }
+
+    fn add(&self, a: &F, b: &F) -> F {
+        *a + b
+    }
+
+    fn sub(&self, a: &F, b: &F) -> F {
+        *a - b
+    }
+
+    fn mul(&self, a: &F, b: &F) -> F {
+        *a * b
+    }
+
+    fn inv(&self, a: &F) -> Option<F> {
+        a.invert().into()
+    }
+
+    fn root_of_unity(&self, log_order: u32) -> Option<F> {
+        if log_order > F::S {
+            return None;
+        }
+        Some((log_order..F::S).fold(F::ROOT_OF_UNITY, |root, _| root.square()))
+    }
+
+    fn elem_from_index(&self, index: usize) -> F {
+        F::from(index as u64)
+    }
+
+    fn has_indices(&self, n: usize) -> bool {
+        // every usize is below a modulus of more than 64 bits; otherwise
+        // n < p exactly when no index is a multiple of p
+        F::NUM_BITS > 64 || (1..=n).all(|i| !bool::from(F::from(i as u64).is_zero()))
+    }
+
+    fn random(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> F {
+        F::random(FillRng(fill_random))
+    }
+
+    // the canonical `F::Repr`, whose byte order is field specific
+    fn encode(&self, a: &F) -> Vec<u8> {
+        a.to_repr().as_ref().to_vec()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<F> {
+        let mut repr = F::Repr::default();
+        if repr.as_ref().len() != bytes.len() {
+            return None;
+        }
+        repr.as_mut().copy_from_slice(bytes);
+        F::from_repr(repr).into()
+    }
+}
+
+// Adapts a byte source to the `RngCore` expected by `ff::Field::random`
+struct FillRng<'a>(&'a mut dyn FnMut(&mut [u8]));
+
+impl RngCore for FillRng<'_> {
+    fn next_u32(&mut self) -> u32 {
+        impls::next_u32_via_fill(self)
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        impls::next_u64_via_fill(self)
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        (self.0)(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+        (self.0)(dest);
+        Ok(())
+    }
+}
+
+// the byte source is required to be uniformly random
+impl CryptoRng for FillRng<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bls12_381::Scalar;
+
+    #[test]
+    fn bls12_381_test() {
+        let ss = SecretShare::<FfField<Scalar>>::ff(3, 5).unwrap();
+        let secret = Scalar::from(1234u64);
+        let shares = ss
+            .split_with_coefficients(secret, &[Scalar::from(166u64), Scalar::from(94u64)])
+            .unwrap();
+        assert_eq!(shares[0].value(), &Scalar::from(1234u64 + 166 + 94));
+        assert_eq!(ss.recover(&shares[2..]).unwrap(), secret);
+        assert_eq!(ss.verify_consistent(&shares), Ok(()));
+
+        let mut counter = 0u8;
+        let shares = ss
+            .split_with(-secret, || {
+                ss.field().random(&mut |buf| {
+                    buf.fill(counter);
+                    counter += 1;
+                })
+            })
+            .unwrap();
+        assert_eq!(ss.recover(&shares[..3]).unwrap(), -secret);
+    }
+
+    #[test]
+    fn large_threshold_test() {
+        // past the threshold the dealing is evaluated with the transform,
+        // for which the scalar field has roots of unity of order 2^32
+        let ss = SecretShare::<FfField<Scalar>>::ff(200, 500).unwrap();
+        let polynomial: Vec<Scalar> = (1..=200u64).map(|i| Scalar::from(i * i + 7)).collect();
+        let shares = ss
+            .split_with_coefficients(polynomial[0], &polynomial[1..])
+            .unwrap();
+        for index in [1, 200, 201, 499, 500] {
+            assert_eq!(
+                shares[index - 1],
+                ss.share_from_polynomial(&polynomial, index).unwrap()
+            );
+        }
+        assert_eq!(ss.recover(&shares[300..]).unwrap(), polynomial[0]);
+    }
+
+    #[test]
+    fn encoding_test() {
+        let field = FfField::<Scalar>::new();
+        let a = Scalar::from(0x0102u64);
+        let bytes = field.encode(&a);
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(field.decode(&bytes), Some(a));
+        assert_eq!(field.decode(&[0xff; 32]), None);
+        assert_eq!(field.decode(&bytes[1..]), None);
+    }
+}
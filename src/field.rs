@@ -0,0 +1,733 @@
+//! Finite fields the sharing schemes compute in.
+//!
+//! Splitting and recovery only need the operations of `Field`, so every
+//! backend implementing it shares the same code paths.
+
+use crate::TinySsError;
+use alloc::{borrow::Cow, vec, vec::Vec};
+use core::{fmt, mem};
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
+
+/// Arithmetic of a finite field whose elements are `Elem`.
+///
+/// Operations take `&self` so that fields can carry runtime parameters such
+/// as the modulus. Every operation must return the canonical representative
+/// of its result, so that equal elements compare equal, and may assume that
+/// its operands are canonical. Values from elsewhere, such as a caller's
+/// secret or shares read back in, are brought there with `canonical`.
+pub trait Field {
+    type Elem: Clone + PartialEq + fmt::Debug;
+
+    fn zero(&self) -> Self::Elem;
+
+    fn one(&self) -> Self::Elem;
+
+    fn add(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+
+    fn sub(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+
+    fn mul(&self, a: &Self::Elem, b: &Self::Elem) -> Self::Elem;
+
+    /// `a = a + b`; backends with heap-allocated elements override it to
+    /// reuse the storage of `a`
+    fn add_assign(&self, a: &mut Self::Elem, b: &Self::Elem) {
+        *a = self.add(a, b);
+    }
+
+    /// `a = a * b`, reusing the storage of `a` where the backend can
+    fn mul_assign(&self, a: &mut Self::Elem, b: &Self::Elem) {
+        *a = self.mul(a, b);
+    }
+
+    /// `sum a_i b_i` over the pairs of `a` and `b`, which have the same
+    /// length; backends can defer the reduction to the end of the sum
+    fn dot(&self, a: &[Self::Elem], b: &[Self::Elem]) -> Self::Elem {
+        let mut sum = self.zero();
+        for (ai, bi) in a.iter().zip(b) {
+            self.add_assign(&mut sum, &self.mul(ai, bi));
+        }
+        sum
+    }
+
+    /// multiplicative inverse, `None` if `a` has none
+    fn inv(&self, a: &Self::Elem) -> Option<Self::Elem>;
+
+    /// whether `a` is the canonical representative of its element, which
+    /// every value returned by the other operations is
+    fn is_canonical(&self, a: &Self::Elem) -> bool {
+        let _ = a;
+        true
+    }
+
+    /// the canonical representative of `a`; backends whose every value is
+    /// canonical return it unchanged
+    fn canonical(&self, a: &Self::Elem) -> Self::Elem {
+        a.clone()
+    }
+
+    /// a primitive `2^log_order`-th root of unity, `None` if the field has
+    /// none or the backend does not say; with one, large polynomial
+    /// products are computed by the number-theoretic transform
+    fn root_of_unity(&self, log_order: u32) -> Option<Self::Elem> {
+        let _ = log_order;
+        None
+    }
+
+    /// maps the share index into the field
+    fn elem_from_index(&self, index: usize) -> Self::Elem;
+
+    /// whether the indices `1..=n` map to distinct nonzero elements
+    fn has_indices(&self, n: usize) -> bool;
+
+    /// uniformly random element, built from bytes written by `fill_random`
+    fn random(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> Self::Elem;
+
+    /// fixed-width canonical encoding
+    fn encode(&self, a: &Self::Elem) -> Vec<u8>;
+
+    /// inverse of `encode`, `None` for input of the wrong width or out of
+    /// range
+    fn decode(&self, bytes: &[u8]) -> Option<Self::Elem>;
+
+    /// overwrites `a` in place so that its value does not outlive it; the
+    /// default suits elements without heap storage
+    #[cfg(feature = "zeroize")]
+    fn zeroize(&self, a: &mut Self::Elem) {
+        *a = self.zero();
+        core::hint::black_box(&*a);
+    }
+}
+
+/// Barrett reduction modulo a fixed `p`.
+///
+/// The constant `mu = 4^k / p`, with `k` the bit length of `p`, is computed
+/// once, after which reducing a product of two residues costs two
+/// multiplications and shifts instead of a `BigInt` division.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldCtx {
+    /// prime in ff
+    p: BigInt,
+    /// bit length of p
+    k: u64,
+    /// floor(4^k / p)
+    mu: BigInt,
+}
+
+impl FieldCtx {
+    pub fn new(p: BigInt) -> Result<Self, TinySsError> {
+        if p <= BigInt::one() {
+            return Err(TinySsError::InvalidModulus);
+        }
+        let k = p.bits();
+        let mu = (BigInt::one() << (2 * k)) / &p;
+        Ok(FieldCtx { p, k, mu })
+    }
+
+    /// prime in ff
+    pub fn p(&self) -> &BigInt {
+        &self.p
+    }
+
+    /// Maps any integer into the canonical range `[0, p)`.
+    ///
+    /// Values below `4^k` in magnitude, which covers every sum, difference
+    /// and product of two residues, take the Barrett path; anything larger
+    /// falls back to division.
+    pub fn reduce(&self, value: &BigInt) -> BigInt {
+        if value.sign() == Sign::Minus {
+            let r = self.reduce(&-value);
+            return if r.is_zero() { r } else { &self.p - r };
+        }
+        let mut r = value.clone();
+        self.reduce_assign(&mut r);
+        r
+    }
+
+    /// `reduce` in place, without allocating a new value for a
+    /// non-negative input.
+    pub fn reduce_assign(&self, value: &mut BigInt) {
+        if value.sign() == Sign::Minus {
+            *value = self.reduce(value);
+            return;
+        }
+        if value.bits() > 2 * self.k {
+            *value %= &self.p;
+            return;
+        }
+        let mut q = &*value >> (self.k - 1);
+        q *= &self.mu;
+        q >>= self.k + 1;
+        q *= &self.p;
+        *value -= q;
+        while *value >= self.p {
+            *value -= &self.p;
+        }
+    }
+
+    /// `base^exponent mod p` by a Montgomery ladder, for a non-negative
+    /// exponent, possibly secret.
+    ///
+    /// The ladder takes one multiplication and one squaring per bit over
+    /// at least the bit length of `p`, and picks its operands by masking
+    /// rather than branching, so which operations run does not depend on
+    /// the bits of any exponent below `p`. `BigInt` arithmetic still takes
+    /// time that varies with the size of its operands; use the fixed-width
+    /// `ct_field` backend where that matters too.
+    pub fn pow(&self, base: &BigInt, exponent: &BigInt) -> BigInt {
+        assert!(exponent.sign() != Sign::Minus, "negative exponent");
+        // r1 = r0 * base throughout
+        let (mut r0, mut r1) = (BigInt::one(), self.reduce(base));
+        for i in (0..exponent.bits().max(self.k)).rev() {
+            let mask = -BigInt::from(u8::from(exponent.bit(i)));
+            conditional_swap(&mask, &mut r0, &mut r1);
+            r1 *= &r0;
+            self.reduce_assign(&mut r1);
+            r0 = &r0 * &r0;
+            self.reduce_assign(&mut r0);
+            conditional_swap(&mask, &mut r0, &mut r1);
+        }
+        r0
+    }
+
+    /// `base^exponent mod p`, faster than `pow` but in time that depends
+    /// on the exponent, for public exponents only.
+    pub fn pow_vartime(&self, base: &BigInt, exponent: &BigInt) -> BigInt {
+        assert!(exponent.sign() != Sign::Minus, "negative exponent");
+        self.reduce(base).modpow(exponent, &self.p)
+    }
+}
+
+// Swaps a and b if mask is -1, all ones, and leaves them if it is 0
+fn conditional_swap(mask: &BigInt, a: &mut BigInt, b: &mut BigInt) {
+    let difference = (&*a ^ &*b) & mask;
+    *a ^= &difference;
+    *b ^= &difference;
+}
+
+/// The prime field of order `p`, with elements as `BigInt`s in `[0, p)`.
+///
+/// Its operations also accept integers outside that range, and reduce them
+/// before computing, so that every intermediate value is a residue in
+/// `[0, p)` as well.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrimeField {
+    /// reduction context for p
+    ctx: FieldCtx,
+}
+
+impl PrimeField {
+    pub fn new(p: BigInt) -> Result<Self, TinySsError> {
+        Ok(PrimeField {
+            ctx: FieldCtx::new(p)?,
+        })
+    }
+
+    /// prime in ff
+    pub fn p(&self) -> &BigInt {
+        self.ctx.p()
+    }
+
+    /// reduction context for p
+    pub fn ctx(&self) -> &FieldCtx {
+        &self.ctx
+    }
+
+    /// Maps any integer into the canonical range `[0, p)`.
+    pub fn reduce(&self, value: &BigInt) -> BigInt {
+        self.ctx.reduce(value)
+    }
+
+    // bytes needed for the largest element
+    fn byte_len(&self) -> usize {
+        self.p().bits().div_ceil(8) as usize
+    }
+
+    // `a` itself if it already lies in `[0, p)`, its residue otherwise
+    fn residue<'a>(&self, a: &'a BigInt) -> Cow<'a, BigInt> {
+        if self.is_canonical(a) {
+            Cow::Borrowed(a)
+        } else {
+            Cow::Owned(self.reduce(a))
+        }
+    }
+
+    // The extended Euclidean algorithm on (p, a), keeping only the
+    // coefficient of a, modulo p, so that s_j * a = r_j (mod p) holds with
+    // every s_j a field element.
+    fn euclid_inv(&self, a: &BigInt) -> Option<BigInt> {
+        let (mut r_last, mut r) = (self.p().clone(), self.reduce(a));
+        let (mut s_last, mut s) = (BigInt::zero(), BigInt::one());
+        while !r.is_zero() {
+            let quotient = self.reduce(&(&r_last / &r));
+            let remainder = &r_last % &r;
+            let next = self.sub(&s_last, &self.mul(&quotient, &s));
+            r_last = mem::replace(&mut r, remainder);
+            s_last = mem::replace(&mut s, next);
+        }
+        if r_last.is_one() {
+            Some(s_last)
+        } else {
+            None
+        }
+    }
+}
+
+impl Field for PrimeField {
+    type Elem = BigInt;
+
+    fn zero(&self) -> BigInt {
+        BigInt::zero()
+    }
+
+    fn one(&self) -> BigInt {
+        BigInt::one()
+    }
+
+    // The operands are first brought into `[0, p)`, after which no
+    // intermediate value is negative or needs more than one correction.
+    fn add(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        let sum = &*self.residue(a) + &*self.residue(b);
+        if &sum >= self.p() {
+            sum - self.p()
+        } else {
+            sum
+        }
+    }
+
+    fn sub(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        let (a, b) = (self.residue(a), self.residue(b));
+        if a >= b {
+            &*a - &*b
+        } else {
+            &*a + self.p() - &*b
+        }
+    }
+
+    fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        let mut product = &*self.residue(a) * &*self.residue(b);
+        self.ctx.reduce_assign(&mut product);
+        product
+    }
+
+    fn add_assign(&self, a: &mut BigInt, b: &BigInt) {
+        if !self.is_canonical(a) {
+            self.ctx.reduce_assign(a);
+        }
+        *a += &*self.residue(b);
+        if &*a >= self.p() {
+            *a -= self.p();
+        }
+    }
+
+    fn mul_assign(&self, a: &mut BigInt, b: &BigInt) {
+        if !self.is_canonical(a) {
+            self.ctx.reduce_assign(a);
+        }
+        *a *= &*self.residue(b);
+        self.ctx.reduce_assign(a);
+    }
+
+    // the products are summed as they are and reduced once
+    fn dot(&self, a: &[BigInt], b: &[BigInt]) -> BigInt {
+        let mut sum = BigInt::zero();
+        for (ai, bi) in a.iter().zip(b) {
+            sum += &*self.residue(ai) * &*self.residue(bi);
+        }
+        self.ctx.reduce_assign(&mut sum);
+        sum
+    }
+
+    // By the binary extended GCD, which only shifts and subtracts, for an
+    // odd p; nothing halves modulo an even one, which Euclid's algorithm
+    // is kept for.
+    fn inv(&self, a: &BigInt) -> Option<BigInt> {
+        if self.p().bit(0) {
+            crate::bingcd::inverse(&self.reduce(a), self.p())
+        } else {
+            self.euclid_inv(a)
+        }
+    }
+
+    fn is_canonical(&self, a: &BigInt) -> bool {
+        a.sign() != Sign::Minus && a < self.p()
+    }
+
+    fn canonical(&self, a: &BigInt) -> BigInt {
+        self.reduce(a)
+    }
+
+    // g^q for a quadratic non-residue g and p - 1 = q * 2^s with q odd
+    // generates the 2^s-th roots of unity
+    fn root_of_unity(&self, log_order: u32) -> Option<BigInt> {
+        if log_order == 0 {
+            return Some(BigInt::one());
+        }
+        let p_minus_one = self.p() - BigInt::one();
+        let s = p_minus_one.trailing_zeros()?;
+        if u64::from(log_order) > s {
+            return None;
+        }
+        let half = &p_minus_one >> 1;
+        // a composite modulus may have no non-residue, so the search is
+        // bounded
+        let g = (2u32..1 << 16)
+            .map(BigInt::from)
+            .find(|g| g.modpow(&half, self.p()) == p_minus_one)?;
+        let mut root = g.modpow(&(&p_minus_one >> s), self.p());
+        for _ in u64::from(log_order)..s {
+            root = self.mul(&root, &root);
+        }
+        Some(root)
+    }
+
+    fn elem_from_index(&self, index: usize) -> BigInt {
+        self.reduce(&BigInt::from(index))
+    }
+
+    fn has_indices(&self, n: usize) -> bool {
+        BigInt::from(n) < *self.p()
+    }
+
+    // Rejection sampling over the bit length of p
+    fn random(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> BigInt {
+        let mut bytes = vec![0u8; self.byte_len()];
+        let excess_bits = bytes.len() * 8 - self.p().bits() as usize;
+        loop {
+            fill_random(&mut bytes);
+            bytes[0] &= 0xff >> excess_bits;
+            let value = BigInt::from_bytes_be(Sign::Plus, &bytes);
+            if value < *self.p() {
+                return value;
+            }
+        }
+    }
+
+    // big-endian, padded to the byte length of p
+    fn encode(&self, a: &BigInt) -> Vec<u8> {
+        let (_, magnitude) = self.reduce(a).to_bytes_be();
+        let mut bytes = vec![0u8; self.byte_len()];
+        let start = bytes.len() - magnitude.len();
+        bytes[start..].copy_from_slice(&magnitude);
+        bytes
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<BigInt> {
+        if bytes.len() != self.byte_len() {
+            return None;
+        }
+        let value = BigInt::from_bytes_be(Sign::Plus, bytes);
+        if value < *self.p() {
+            Some(value)
+        } else {
+            None
+        }
+    }
+
+    #[cfg(feature = "zeroize")]
+    fn zeroize(&self, a: &mut BigInt) {
+        crate::zeroize::zeroize_bigint(a)
+    }
+}
+
+/// The prime field of order `p < 2^61`, with elements as `u64`s in `[0, p)`.
+///
+/// Products fit in a `u128`, so this avoids allocating `BigInt`s for small
+/// moduli such as Mersenne primes up to `2^61 - 1`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SmallPrimeField {
+    /// prime in ff
+    p: u64,
+}
+
+impl SmallPrimeField {
+    /// largest accepted modulus
+    pub const MAX_MODULUS: u64 = (1 << 61) - 1;
+
+    pub fn new(p: u64) -> Result<Self, TinySsError> {
+        if p <= 1 || p > Self::MAX_MODULUS {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(SmallPrimeField { p })
+    }
+
+    /// prime in ff
+    pub fn p(&self) -> u64 {
+        self.p
+    }
+
+    // bytes needed for the largest element
+    fn byte_len(&self) -> usize {
+        (u64::BITS - self.p.leading_zeros()).div_ceil(8) as usize
+    }
+
+    // Computes a^e mod p by square and multiply
+    fn pow(&self, mut a: u64, mut e: u64) -> u64 {
+        let mut result = 1;
+        while e > 0 {
+            if e & 1 == 1 {
+                result = self.mul(&result, &a);
+            }
+            a = self.mul(&a, &a);
+            e >>= 1;
+        }
+        result
+    }
+}
+
+impl Field for SmallPrimeField {
+    type Elem = u64;
+
+    fn zero(&self) -> u64 {
+        0
+    }
+
+    fn one(&self) -> u64 {
+        1
+    }
+
+    // both operands are below 2^61, so the sum cannot overflow
+    fn add(&self, a: &u64, b: &u64) -> u64 {
+        (a + b) % self.p
+    }
+
+    fn sub(&self, a: &u64, b: &u64) -> u64 {
+        (a + self.p - b % self.p) % self.p
+    }
+
+    fn mul(&self, a: &u64, b: &u64) -> u64 {
+        ((*a as u128 * *b as u128) % self.p as u128) as u64
+    }
+
+    // Fermat's little theorem, a^(p - 2) = a^-1 for prime p
+    fn inv(&self, a: &u64) -> Option<u64> {
+        let a = a % self.p;
+        if a == 0 {
+            return None;
+        }
+        let inverse = self.pow(a, self.p - 2);
+        if self.mul(&a, &inverse) == 1 {
+            Some(inverse)
+        } else {
+            None
+        }
+    }
+
+    fn is_canonical(&self, a: &u64) -> bool {
+        *a < self.p
+    }
+
+    fn root_of_unity(&self, log_order: u32) -> Option<u64> {
+        if log_order == 0 {
+            return Some(1);
+        }
+        let s = (self.p - 1).trailing_zeros();
+        if log_order > s {
+            return None;
+        }
+        let half = (self.p - 1) / 2;
+        let g = (2..self.p.min(1 << 16)).find(|&g| self.pow(g, half) == self.p - 1)?;
+        let mut root = self.pow(g, (self.p - 1) >> s);
+        for _ in log_order..s {
+            root = self.mul(&root, &root);
+        }
+        Some(root)
+    }
+
+    fn canonical(&self, a: &u64) -> u64 {
+        a % self.p
+    }
+
+    fn elem_from_index(&self, index: usize) -> u64 {
+        (index as u64) % self.p
+    }
+
+    fn has_indices(&self, n: usize) -> bool {
+        (n as u64) < self.p
+    }
+
+    // Rejection sampling over the bit length of p
+    fn random(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> u64 {
+        let mut bytes = [0u8; 8];
+        let start = 8 - self.byte_len();
+        let mask = u64::MAX >> self.p.leading_zeros();
+        loop {
+            fill_random(&mut bytes[start..]);
+            let value = u64::from_be_bytes(bytes) & mask;
+            if value < self.p {
+                return value;
+            }
+        }
+    }
+
+    // big-endian, padded to the byte length of p
+    fn encode(&self, a: &u64) -> Vec<u8> {
+        (a % self.p).to_be_bytes()[8 - self.byte_len()..].to_vec()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<u64> {
+        if bytes.len() != self.byte_len() {
+            return None;
+        }
+        let mut padded = [0u8; 8];
+        padded[8 - bytes.len()..].copy_from_slice(bytes);
+        let value = u64::from_be_bytes(padded);
+        if value < self.p {
+            Some(value)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prime_field_test() {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        assert_eq!(
+            field.sub(&BigInt::from(3), &BigInt::from(5)),
+            BigInt::from(1611)
+        );
+        assert_eq!(
+            field.mul(&BigInt::from(-2), &BigInt::from(3)),
+            BigInt::from(1607)
+        );
+        let a = BigInt::from(166);
+        assert_eq!(field.mul(&a, &field.inv(&a).unwrap()), BigInt::one());
+        assert_eq!(field.inv(&BigInt::zero()), None);
+        assert_eq!(field.inv(&BigInt::one()), Some(BigInt::one()));
+        assert_eq!(field.inv(&BigInt::from(-1)), Some(BigInt::from(1612)));
+        assert!((1..1613).all(|a| {
+            let inverse = field.inv(&BigInt::from(a)).unwrap();
+            field.is_canonical(&inverse) && field.mul(&BigInt::from(a), &inverse).is_one()
+        }));
+        // both algorithms agree, including on moduli with common divisors
+        for p in [9, 1000, 65537] {
+            let ring = PrimeField::new(BigInt::from(p)).unwrap();
+            assert!((0..p).all(|a| {
+                let a = BigInt::from(a);
+                let inverse = ring.inv(&a);
+                inverse == ring.euclid_inv(&a)
+            }));
+        }
+        assert!(field.is_canonical(&BigInt::from(1612)));
+        assert!(!field.is_canonical(&BigInt::from(1613)));
+        assert!(!field.is_canonical(&BigInt::from(-1)));
+        assert_eq!(field.canonical(&BigInt::from(-1)), BigInt::from(1612));
+        assert_eq!(
+            field.add(&BigInt::from(1612), &BigInt::from(3226)),
+            BigInt::from(1612)
+        );
+        assert_eq!(field.root_of_unity(3), None);
+        let field_65537 = PrimeField::new(BigInt::from(65537)).unwrap();
+        let root = field_65537.root_of_unity(16).unwrap();
+        let order = |exponent: u32| root.modpow(&(BigInt::one() << exponent), field_65537.p());
+        assert_eq!((order(15), order(16)), (BigInt::from(65536), BigInt::one()));
+        assert_eq!(field_65537.root_of_unity(0), Some(BigInt::one()));
+        assert_eq!(field_65537.root_of_unity(17), None);
+        let a = [3, 5].map(BigInt::from);
+        assert_eq!(
+            field.dot(&a, &[BigInt::from(1000), BigInt::from(-1)]),
+            BigInt::from(1382)
+        );
+        let mut sum = BigInt::from(-1);
+        field.add_assign(&mut sum, &BigInt::from(3226));
+        assert_eq!(sum, BigInt::from(1612));
+        let mut product = BigInt::from(4000);
+        field.mul_assign(&mut product, &BigInt::from(-3));
+        assert_eq!(product, field.mul(&BigInt::from(4000), &BigInt::from(-3)));
+        assert_eq!(
+            PrimeField::new(BigInt::one()),
+            Err(TinySsError::InvalidModulus)
+        );
+    }
+
+    #[test]
+    fn field_ctx_test() {
+        let ctx = FieldCtx::new(BigInt::from(1613)).unwrap();
+        for v in [
+            0i64,
+            1,
+            1612,
+            1613,
+            1614,
+            1612 * 1612,
+            -1,
+            -1613,
+            -5000,
+            1 << 40,
+        ] {
+            let expected = ((v % 1613) + 1613) % 1613;
+            assert_eq!(ctx.reduce(&BigInt::from(v)), BigInt::from(expected));
+        }
+        assert_eq!(
+            FieldCtx::new(BigInt::zero()),
+            Err(TinySsError::InvalidModulus)
+        );
+        for (base, exponent) in [
+            (0, 0),
+            (0, 5),
+            (3, 0),
+            (-2, 7),
+            (166, 1611),
+            (5, 1i64 << 40),
+        ] {
+            let (base, exponent) = (BigInt::from(base), BigInt::from(exponent));
+            let expected = ctx.reduce(&base).modpow(&exponent, ctx.p());
+            assert_eq!(ctx.pow(&base, &exponent), expected);
+            assert_eq!(ctx.pow_vartime(&base, &exponent), expected);
+        }
+    }
+
+    #[test]
+    fn prime_field_bytes_test() {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        assert_eq!(field.encode(&BigInt::from(0)), [0, 0]);
+        assert_eq!(field.encode(&BigInt::from(77)), [0, 77]);
+        assert_eq!(field.decode(&[6, 0x4c]), Some(BigInt::from(1612)));
+        assert_eq!(field.decode(&[6, 0x4d]), None);
+        assert_eq!(field.decode(&[77]), None);
+
+        // 0xff.. first tries 0x7ff, out of range, then 0x000
+        let mut byte = 0xff;
+        let value = field.random(&mut |buf| {
+            buf.fill(byte);
+            byte = 0;
+        });
+        assert_eq!(value, BigInt::zero());
+        // the whole range up to p - 1 is reachable
+        let value = field.random(&mut |buf| buf.copy_from_slice(&[6, 0x4c]));
+        assert_eq!(value, BigInt::from(1612));
+    }
+
+    #[test]
+    fn small_prime_field_test() {
+        let field = SmallPrimeField::new(1613).unwrap();
+        assert_eq!(field.sub(&3, &5), 1611);
+        assert_eq!(field.mul(&1612, &3), 1610);
+        assert_eq!(field.mul(&166, &field.inv(&166).unwrap()), 1);
+        assert_eq!(field.inv(&0), None);
+        assert_eq!(field.encode(&77), [0, 77]);
+        assert_eq!(field.decode(&[6, 0x4c]), Some(1612));
+        assert_eq!(field.decode(&[6, 0x4d]), None);
+        let root = field.root_of_unity(2).unwrap();
+        assert_eq!(field.mul(&root, &root), 1612);
+        assert_eq!(field.root_of_unity(3), None);
+
+        let mersenne = SmallPrimeField::new(SmallPrimeField::MAX_MODULUS).unwrap();
+        let a = SmallPrimeField::MAX_MODULUS - 1;
+        assert_eq!(mersenne.mul(&a, &a), 1);
+        assert_eq!(mersenne.add(&a, &a), a - 1);
+        assert_eq!(mersenne.inv(&a), Some(a));
+        assert_eq!(mersenne.encode(&a).len(), 8);
+
+        assert_eq!(SmallPrimeField::new(1), Err(TinySsError::InvalidModulus));
+        assert_eq!(
+            SmallPrimeField::new(1 << 61),
+            Err(TinySsError::InvalidModulus)
+        );
+    }
+}
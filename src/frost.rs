@@ -0,0 +1,462 @@
+//! Key packages in the layout of FROST signers.
+//!
+//! FROST (RFC 9591) expects every signer to hold a key package with its
+//! identifier, its signing share, its verification share `g^{x_i}`, the
+//! group verifying key and the threshold, and the coordinator to hold the
+//! public package of all verification shares. This module assembles them
+//! from a trusted dealing with Feldman commitments or from a `dkg` output,
+//! with the share index as the identifier. `KeyPackage` and
+//! `PublicKeyPackage` live in the crate's Schnorr group and encode as
+//! big-endian integers: they follow the layout, not the encoding, of any
+//! FROST ciphersuite.
+//!
+//! With the `frost-core` feature, `frost_core_packages` instead turns a
+//! dealing over the scalars of a `frost-core` ciphersuite into that
+//! crate's own key packages, which its signers use as they are.
+
+use crate::{dkg::KeyShare, group::SchnorrGroup, Share, TinySsError};
+#[cfg(feature = "frost-core")]
+use crate::{field::Field, poly, Inconsistency, SecretShare};
+#[cfg(feature = "frost-core")]
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "frost-core")]
+use frost_core::{Ciphersuite, Field as _, Group as _, Identifier};
+use num_bigint::BigInt;
+#[cfg(feature = "frost-core")]
+use num_bigint::Sign;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A signer's secret key package.
+#[derive(Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct KeyPackage {
+    identifier: usize,
+    /// the signer's share x_i
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    signing_share: BigInt,
+    /// g^{x_i}
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    verifying_share: BigInt,
+    /// g to the shared key
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    verifying_key: BigInt,
+    min_signers: usize,
+}
+
+impl fmt::Debug for KeyPackage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("KeyPackage")
+            .field("identifier", &self.identifier)
+            .field("signing_share", crate::debug::secret(&self.signing_share))
+            .field("verifying_share", &self.verifying_share)
+            .field("verifying_key", &self.verifying_key)
+            .field("min_signers", &self.min_signers)
+            .finish()
+    }
+}
+
+impl KeyPackage {
+    pub fn new(
+        identifier: usize,
+        signing_share: BigInt,
+        verifying_share: BigInt,
+        verifying_key: BigInt,
+        min_signers: usize,
+    ) -> Self {
+        KeyPackage {
+            identifier,
+            signing_share,
+            verifying_share,
+            verifying_key,
+            min_signers,
+        }
+    }
+
+    /// The package of a `dkg` participant, which ran with threshold
+    /// `min_signers`.
+    pub fn from_key_share(key_share: &KeyShare, min_signers: usize) -> Self {
+        let verifying_share = key_share
+            .public_share(key_share.index())
+            .expect("a key share holds its own public share")
+            .clone();
+        KeyPackage::new(
+            key_share.index(),
+            key_share.secret().clone(),
+            verifying_share,
+            key_share.public_key().clone(),
+            min_signers,
+        )
+    }
+
+    pub fn identifier(&self) -> usize {
+        self.identifier
+    }
+
+    /// the signer's share x_i
+    pub fn signing_share(&self) -> &BigInt {
+        &self.signing_share
+    }
+
+    /// g^{x_i}
+    pub fn verifying_share(&self) -> &BigInt {
+        &self.verifying_share
+    }
+
+    /// g to the shared key
+    pub fn verifying_key(&self) -> &BigInt {
+        &self.verifying_key
+    }
+
+    pub fn min_signers(&self) -> usize {
+        self.min_signers
+    }
+
+    /// the signing share as a plain Shamir share
+    pub fn share(&self) -> Share {
+        Share::new(self.identifier, self.signing_share.clone())
+    }
+}
+
+/// The public package: every verification share and the verifying key.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PublicKeyPackage {
+    /// g^{x_i} in identifier order, starting at 1
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint::vec"))]
+    verifying_shares: Vec<BigInt>,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    verifying_key: BigInt,
+}
+
+impl PublicKeyPackage {
+    pub fn new(verifying_shares: Vec<BigInt>, verifying_key: BigInt) -> Self {
+        PublicKeyPackage {
+            verifying_shares,
+            verifying_key,
+        }
+    }
+
+    /// The public package seen by a `dkg` participant.
+    pub fn from_key_share(key_share: &KeyShare) -> Self {
+        let verifying_shares = (1..)
+            .map_while(|i| key_share.public_share(i).cloned())
+            .collect();
+        PublicKeyPackage::new(verifying_shares, key_share.public_key().clone())
+    }
+
+    /// g^{x_i} of the signer with `identifier`
+    pub fn verifying_share(&self, identifier: usize) -> Option<&BigInt> {
+        self.verifying_shares.get(identifier.checked_sub(1)?)
+    }
+
+    pub fn verifying_key(&self) -> &BigInt {
+        &self.verifying_key
+    }
+}
+
+/// Turns the `n` shares of a trusted dealing, in index order, into key
+/// packages for threshold `min_signers`, checking each share against the
+/// Feldman commitments `C_j = g^{a_j}` of the dealt polynomial.
+pub fn key_packages(
+    group: &SchnorrGroup,
+    min_signers: usize,
+    shares: &[Share],
+    commitments: &[BigInt],
+) -> Result<(Vec<KeyPackage>, PublicKeyPackage), TinySsError> {
+    if min_signers == 0 || commitments.len() != min_signers {
+        return Err(TinySsError::WrongCoefficientCount {
+            expected: min_signers,
+            got: commitments.len(),
+        });
+    }
+    if shares.iter().enumerate().any(|(i, s)| s.index() != i + 1) {
+        return Err(TinySsError::InvalidIndex);
+    }
+    if let Some(bad) = shares
+        .iter()
+        .find(|s| !group.verify_feldman(commitments, s))
+    {
+        return Err(TinySsError::InvalidShare { index: bad.index() });
+    }
+    let verifying_key = commitments[0].clone();
+    let packages: Vec<KeyPackage> = shares
+        .iter()
+        .map(|s| {
+            KeyPackage::new(
+                s.index(),
+                s.value().clone(),
+                group.exp(group.g(), s.value()),
+                verifying_key.clone(),
+                min_signers,
+            )
+        })
+        .collect();
+    let verifying_shares = packages.iter().map(|p| p.verifying_share.clone()).collect();
+    Ok((
+        packages,
+        PublicKeyPackage::new(verifying_shares, verifying_key),
+    ))
+}
+
+/// Key packages of a `frost-core` ciphersuite `C`, by identifier, and the
+/// public package of their verifying shares.
+#[cfg(feature = "frost-core")]
+pub type FrostCorePackages<C> = (
+    BTreeMap<Identifier<C>, frost_core::keys::KeyPackage<C>>,
+    frost_core::keys::PublicKeyPackage<C>,
+);
+
+/// Turns the shares of a trusted dealing under `scheme`, which must be over
+/// the scalars of the ciphersuite `C`, into `frost-core` key packages for
+/// threshold `t`, after checking that they lie on one polynomial. Shares
+/// repeating an index are converted once.
+///
+/// Fails with `InvalidModulus` if the modulus is not the group order of
+/// `C`, with `InvalidIndex` for indices outside `1..=n` and with
+/// `InvalidShare` for values that are not canonical scalars.
+#[cfg(feature = "frost-core")]
+pub fn frost_core_packages<C: Ciphersuite>(
+    scheme: &SecretShare,
+    shares: &[Share],
+) -> Result<FrostCorePackages<C>, TinySsError> {
+    use frost_core::keys::{KeyPackage, PublicKeyPackage, SigningShare, VerifyingShare};
+    use frost_core::VerifyingKey;
+
+    let order = suite_order::<C>();
+    if scheme.field().p() != &order {
+        return Err(TinySsError::InvalidModulus);
+    }
+    let min_signers = u16::try_from(scheme.t()).map_err(|_| TinySsError::InvalidThreshold)?;
+    for share in shares {
+        if share.index() == 0 || share.index() > scheme.n() || share.index() > u16::MAX.into() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        if share.value().sign() == Sign::Minus || share.value() >= &order {
+            return Err(TinySsError::InvalidShare {
+                index: share.index(),
+            });
+        }
+    }
+    scheme.verify_consistent(shares).map_err(|e| match e {
+        Inconsistency::OffPolynomial { indices } => TinySsError::InvalidShare { index: indices[0] },
+        Inconsistency::Unusable(e) => e,
+    })?;
+    let shares = poly::distinct_shares(shares, scheme.t())?;
+    let signing_shares: Vec<SigningShare<C>> = shares
+        .iter()
+        .map(|s| {
+            SigningShare::deserialize(&suite_scalar_bytes::<C>(s.value()))
+                .map_err(|_| TinySsError::InvalidShare { index: s.index() })
+        })
+        .collect::<Result<_, TinySsError>>()?;
+
+    // Y = sum of l_i Y_i over the first t shares
+    let first = &shares[..scheme.t()];
+    let field = scheme.field();
+    let xs: Vec<BigInt> = first
+        .iter()
+        .map(|s| field.elem_from_index(s.index()))
+        .collect();
+    let weights = poly::lagrange_coefficients(field, &xs, &field.zero())?;
+    let mut public_key = C::Group::identity();
+    for (weight, share) in weights.iter().zip(first) {
+        let x = suite_scalar::<C>(share.value()).ok_or(TinySsError::InvalidShare {
+            index: share.index(),
+        })?;
+        let l = suite_scalar::<C>(weight).ok_or(TinySsError::NotInvertible)?;
+        public_key = public_key + C::Group::generator() * x * l;
+    }
+    let public_key = C::Group::serialize(&public_key)
+        .ok()
+        .and_then(|bytes| VerifyingKey::<C>::deserialize(bytes.as_ref()).ok())
+        .ok_or(TinySsError::InvalidGroup)?;
+
+    let mut packages = BTreeMap::new();
+    let mut verifying_shares = BTreeMap::new();
+    for (share, signing_share) in shares.iter().zip(signing_shares) {
+        let identifier = Identifier::<C>::try_from(share.index() as u16)
+            .map_err(|_| TinySsError::InvalidIndex)?;
+        let verifying_share = VerifyingShare::from(signing_share);
+        verifying_shares.insert(identifier, verifying_share);
+        packages.insert(
+            identifier,
+            KeyPackage::new(
+                identifier,
+                signing_share,
+                verifying_share,
+                public_key,
+                min_signers,
+            ),
+        );
+    }
+    Ok((
+        packages,
+        PublicKeyPackage::new(verifying_shares, public_key),
+    ))
+}
+
+#[cfg(feature = "frost-core")]
+type SuiteField<C> = <<C as Ciphersuite>::Group as frost_core::Group>::Field;
+
+// The group order of `C`, one more than its largest scalar
+#[cfg(feature = "frost-core")]
+fn suite_order<C: Ciphersuite>() -> BigInt {
+    let largest = SuiteField::<C>::zero() - SuiteField::<C>::one();
+    BigInt::from_bytes_le(
+        Sign::Plus,
+        SuiteField::<C>::little_endian_serialize(&largest).as_ref(),
+    ) + 1
+}
+
+// `value`, below the order of `C`, in the scalar encoding of `C`, which is
+// big- or little-endian depending on the ciphersuite
+#[cfg(feature = "frost-core")]
+fn suite_scalar_bytes<C: Ciphersuite>(value: &BigInt) -> Vec<u8> {
+    let one = SuiteField::<C>::serialize(&SuiteField::<C>::one());
+    let one = one.as_ref();
+    let mut bytes = value.to_bytes_le().1;
+    bytes.resize(one.len(), 0);
+    if one[0] != 1 {
+        bytes.reverse();
+    }
+    bytes
+}
+
+#[cfg(feature = "frost-core")]
+fn suite_scalar<C: Ciphersuite>(value: &BigInt) -> Option<frost_core::Scalar<C>> {
+    SuiteField::<C>::deserialize(&suite_scalar_bytes::<C>(value).try_into().ok()?).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::SecretShare;
+
+    #[test]
+    fn key_packages_test() {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        let ss = SecretShare::new(2, 3, group.q().clone()).unwrap();
+        let polynomial = [BigInt::from(777), BigInt::from(13)];
+        let shares = ss
+            .split_with_coefficients(polynomial[0].clone(), &polynomial[1..])
+            .unwrap();
+        let commitments: Vec<BigInt> = polynomial.iter().map(|a| group.exp(group.g(), a)).collect();
+        let (packages, public) = key_packages(&group, 2, &shares, &commitments).unwrap();
+        assert_eq!(packages.len(), 3);
+        assert_eq!(
+            public.verifying_key(),
+            &group.exp(group.g(), &polynomial[0])
+        );
+        for package in &packages {
+            assert_eq!(package.min_signers(), 2);
+            assert_eq!(
+                public.verifying_share(package.identifier()),
+                Some(package.verifying_share())
+            );
+        }
+        let signing_shares: Vec<Share> = packages[1..].iter().map(KeyPackage::share).collect();
+        assert_eq!(ss.recover(&signing_shares).unwrap(), polynomial[0]);
+
+        let mut tampered = shares.clone();
+        tampered[1] = Share::new(2, BigInt::from(5));
+        assert_eq!(
+            key_packages(&group, 2, &tampered, &commitments),
+            Err(TinySsError::InvalidShare { index: 2 })
+        );
+        tampered.swap(0, 1);
+        assert_eq!(
+            key_packages(&group, 2, &tampered, &commitments),
+            Err(TinySsError::InvalidIndex)
+        );
+        assert_eq!(
+            key_packages(&group, 3, &shares, &commitments),
+            Err(TinySsError::WrongCoefficientCount {
+                expected: 3,
+                got: 2
+            })
+        );
+    }
+
+    #[cfg(all(feature = "frost-core", feature = "rand"))]
+    #[test]
+    fn frost_core_test() {
+        use frost_secp256k1::{self as frost, Secp256K1Sha256};
+
+        let ss = SecretShare::secp256k1_order(2, 3).unwrap();
+        let secret = BigInt::from(0x5eed_u32);
+        let shares = ss
+            .split_with_coefficients(secret.clone(), &[BigInt::from(77)])
+            .unwrap();
+        let (packages, public) = frost_core_packages::<Secp256K1Sha256>(&ss, &shares).unwrap();
+        assert_eq!(packages.len(), 3);
+        let key = frost::SigningKey::deserialize(&suite_scalar_bytes::<Secp256K1Sha256>(&secret))
+            .unwrap();
+        assert_eq!(public.verifying_key(), &frost::VerifyingKey::from(&key));
+
+        // signers 2 and 3 run both FROST rounds with their packages
+        let mut rng = rand::thread_rng();
+        let signers: Vec<_> = packages.values().skip(1).collect();
+        let mut nonces = BTreeMap::new();
+        let mut commitments = BTreeMap::new();
+        for package in &signers {
+            let (nonce, commitment) = frost::round1::commit(package.signing_share(), &mut rng);
+            nonces.insert(*package.identifier(), nonce);
+            commitments.insert(*package.identifier(), commitment);
+        }
+        let message = b"dealt by tiny-ss";
+        let signing_package = frost::SigningPackage::new(commitments, message);
+        let signature_shares = signers
+            .iter()
+            .map(|package| {
+                let nonce = &nonces[package.identifier()];
+                let share = frost::round2::sign(&signing_package, nonce, package).unwrap();
+                (*package.identifier(), share)
+            })
+            .collect();
+        let signature = frost::aggregate(&signing_package, &signature_shares, &public).unwrap();
+        assert!(public.verifying_key().verify(message, &signature).is_ok());
+
+        let mut tampered = shares.clone();
+        tampered[2] = Share::new(3, BigInt::from(5));
+        assert_eq!(
+            frost_core_packages::<Secp256K1Sha256>(&ss, &tampered).unwrap_err(),
+            TinySsError::InvalidShare { index: 3 }
+        );
+        tampered[2] = Share::new(3, ss.field().p().clone());
+        assert_eq!(
+            frost_core_packages::<Secp256K1Sha256>(&ss, &tampered).unwrap_err(),
+            TinySsError::InvalidShare { index: 3 }
+        );
+        tampered[2] = Share::new(4, BigInt::from(5));
+        assert_eq!(
+            frost_core_packages::<Secp256K1Sha256>(&ss, &tampered).unwrap_err(),
+            TinySsError::InvalidIndex
+        );
+        let other = SecretShare::new(2, 3, BigInt::from(2039)).unwrap();
+        assert_eq!(
+            frost_core_packages::<Secp256K1Sha256>(&other, &shares).unwrap_err(),
+            TinySsError::InvalidModulus
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        use alloc::vec;
+
+        let public =
+            PublicKeyPackage::new(vec![BigInt::from(10), BigInt::from(255)], BigInt::from(16));
+        let json = serde_json::to_string(&public).unwrap();
+        assert_eq!(
+            json,
+            r#"{"verifying_shares":["a","ff"],"verifying_key":"10"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<PublicKeyPackage>(&json).unwrap(),
+            public
+        );
+    }
+}
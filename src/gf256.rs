@@ -0,0 +1,299 @@
+//! Byte-oriented secret sharing over GF(2^8).
+//!
+//! Every byte of the secret is shared with its own polynomial, so secrets of
+//! any length can be split and every share value is as long as the secret.
+//! Arithmetic is modulo the AES polynomial x^8 + x^4 + x^3 + x + 1, with 3 as
+//! the generator for the log/exp tables.
+
+use crate::{field::Field, poly, Share, TinySsError};
+use alloc::{vec, vec::Vec};
+
+mod slice;
+
+const fn tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut x: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = x as u8;
+        log[x as usize] = i as u8;
+        // multiply by the generator 3 = x + 1
+        x ^= x << 1;
+        if x & 0x100 != 0 {
+            x ^= 0x11b;
+        }
+        i += 1;
+    }
+    (exp, log)
+}
+
+const EXP: [u8; 255] = tables().0;
+const LOG: [u8; 256] = tables().1;
+
+pub(crate) fn mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    EXP[(LOG[a as usize] as usize + LOG[b as usize] as usize) % 255]
+}
+
+// Inverse of a nonzero element
+pub(crate) fn inv(a: u8) -> u8 {
+    debug_assert!(a != 0);
+    EXP[(255 - LOG[a as usize] as usize) % 255]
+}
+
+/// GF(2^8) as a `Field`, with elements as bytes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Gf256;
+
+impl Field for Gf256 {
+    type Elem = u8;
+
+    fn zero(&self) -> u8 {
+        0
+    }
+
+    fn one(&self) -> u8 {
+        1
+    }
+
+    fn add(&self, a: &u8, b: &u8) -> u8 {
+        a ^ b
+    }
+
+    fn sub(&self, a: &u8, b: &u8) -> u8 {
+        a ^ b
+    }
+
+    fn mul(&self, a: &u8, b: &u8) -> u8 {
+        mul(*a, *b)
+    }
+
+    fn inv(&self, a: &u8) -> Option<u8> {
+        if *a == 0 {
+            None
+        } else {
+            Some(inv(*a))
+        }
+    }
+
+    fn elem_from_index(&self, index: usize) -> u8 {
+        index as u8
+    }
+
+    fn has_indices(&self, n: usize) -> bool {
+        n <= 255
+    }
+
+    fn random(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> u8 {
+        let mut byte = [0u8];
+        fill_random(&mut byte);
+        byte[0]
+    }
+
+    fn encode(&self, a: &u8) -> Vec<u8> {
+        vec![*a]
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<u8> {
+        match bytes {
+            [byte] => Some(*byte),
+            _ => None,
+        }
+    }
+}
+
+// Evaluates at `x` the polynomials through `points`, one per byte position.
+// The x-coordinates must be distinct and all values of the same length.
+pub(crate) fn interpolate(points: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    let xs: Vec<u8> = points.iter().map(|(xi, _)| *xi).collect();
+    let coefficients =
+        poly::lagrange_coefficients(&Gf256, &xs, &x).expect("distinct x-coordinates");
+    let len = points.first().map_or(0, |(_, y)| y.len());
+    let mut result = vec![0u8; len];
+    for (c, (_, ys)) in coefficients.iter().zip(points) {
+        slice::add_mul(&mut result, *c, ys);
+    }
+    result
+}
+
+/// A share of a byte string: one GF(256) y-coordinate per secret byte.
+pub type ByteShare = Share<Vec<u8>>;
+
+/// A `t`-of-`n` scheme splitting byte strings over GF(256).
+#[derive(Clone, Debug)]
+pub struct Gf256Sharing {
+    /// threshold
+    t: usize,
+    /// total number of shares
+    n: usize,
+}
+
+impl Gf256Sharing {
+    /// Creates a `t`-of-`n` scheme with `0 < t < n`. Since share indices are
+    /// the nonzero field elements, `n` is at most 255.
+    pub fn new(t: usize, n: usize) -> Result<Self, TinySsError> {
+        if t == 0 || t >= n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if !Gf256.has_indices(n) {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(Gf256Sharing { t, n })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: &[u8]) -> Vec<ByteShare> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits `secret` with coefficients drawn from `fill_random`, which must
+    /// fill the buffer with uniformly random bytes: the coefficients of
+    /// degree 1 of every byte, then those of degree 2, and so on.
+    pub fn split_with<F>(&self, secret: &[u8], mut fill_random: F) -> Vec<ByteShare>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        // one polynomial per byte, the secret byte being degree 0;
+        // coefficient j + 1 of every byte fills row j, so that Horner's
+        // rule runs over whole rows
+        let len = secret.len();
+        let mut coefficients = vec![0u8; (self.t - 1) * len];
+        fill_random(&mut coefficients);
+        (1..=self.n)
+            .map(|x| {
+                let x_elem = Gf256.elem_from_index(x);
+                let mut rows = coefficients.chunks(len.max(1)).rev().chain([secret]);
+                // Horner's rule from the top row, which a zero start would
+                // only multiply by x and add to
+                let mut value = rows.next().expect("the secret row").to_vec();
+                for row in rows {
+                    slice::mul_add(&mut value, x_elem, row);
+                }
+                Share::new(x, value)
+            })
+            .collect()
+    }
+
+    /// Recovers the secret from at least `t` shares of equal length.
+    ///
+    /// Shares repeating an index are ignored as long as they carry the same
+    /// value; the first `t` distinct shares are used for interpolation.
+    pub fn recover(&self, shares: &[ByteShare]) -> Result<Vec<u8>, TinySsError> {
+        for share in shares {
+            if share.index() == 0 || !Gf256.has_indices(share.index()) {
+                return Err(TinySsError::InvalidIndex);
+            }
+            if share.value().len() != shares[0].value().len() {
+                return Err(TinySsError::LengthMismatch);
+            }
+        }
+        let unique = poly::distinct_shares(shares, self.t)?;
+        let points: Vec<(u8, &[u8])> = unique
+            .iter()
+            .take(self.t)
+            .map(|share| {
+                (
+                    Gf256.elem_from_index(share.index()),
+                    share.value().as_slice(),
+                )
+            })
+            .collect();
+        Ok(interpolate(&points, 0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_test() {
+        // FIPS-197 section 4.2
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        for a in 1..=255u8 {
+            assert_eq!(mul(a, inv(a)), 1);
+        }
+    }
+
+    #[test]
+    fn interpolate_test() {
+        // y = 7 + 5x per byte
+        let points: [(u8, &[u8]); 2] = [(1, &[7 ^ 5]), (2, &[7 ^ mul(5, 2)])];
+        assert_eq!(interpolate(&points, 0), [7]);
+        assert_eq!(interpolate(&points, 2), [7 ^ mul(5, 2)]);
+    }
+
+    #[test]
+    fn split_with_test() {
+        let gf = Gf256Sharing::new(2, 3).unwrap();
+        let shares = gf.split_with(b"ss", |buf| buf.copy_from_slice(&[5, 9]));
+        assert_eq!(shares[0], Share::new(1, vec![b's' ^ 5, b's' ^ 9]));
+        assert_eq!(
+            shares[1],
+            Share::new(2, vec![b's' ^ mul(5, 2), b's' ^ mul(9, 2)])
+        );
+        assert_eq!(gf.recover(&shares[1..]).unwrap(), b"ss");
+
+        // degree 1 of both bytes first, then degree 2
+        let gf = Gf256Sharing::new(3, 4).unwrap();
+        let shares = gf.split_with(b"ab", |buf| buf.copy_from_slice(&[1, 2, 3, 4]));
+        let y = |s: u8, c1: u8, c2: u8| s ^ mul(c1, 2) ^ mul(c2, mul(2, 2));
+        assert_eq!(shares[1].value(), &[y(b'a', 1, 3), y(b'b', 2, 4)]);
+        assert_eq!(gf.recover(&shares[1..]).unwrap(), b"ab");
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn split_test() {
+        let gf = Gf256Sharing::new(3, 5).unwrap();
+        let secret = b"correct horse battery staple";
+        let shares = gf.split(secret);
+        assert!(shares.iter().all(|s| s.value().len() == secret.len()));
+        assert_eq!(gf.recover(&shares[2..]).unwrap(), secret);
+        assert_eq!(
+            gf.recover(&[shares[0].clone(), shares[0].clone()])
+                .unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 1
+            }
+        );
+        assert_eq!(gf.split(b"").len(), 5);
+    }
+
+    #[test]
+    fn error_test() {
+        assert_eq!(
+            Gf256Sharing::new(3, 3).unwrap_err(),
+            TinySsError::InvalidThreshold
+        );
+        let gf = Gf256Sharing::new(2, 3).unwrap();
+        let shares = [Share::new(1, vec![1, 2]), Share::new(2, vec![3])];
+        assert_eq!(gf.recover(&shares), Err(TinySsError::LengthMismatch));
+        let shares = [Share::new(0, vec![1]), Share::new(2, vec![3])];
+        assert_eq!(gf.recover(&shares), Err(TinySsError::InvalidIndex));
+
+        // 256 would alias x = 0 and 257 x = 1 as bytes
+        let ss = crate::SecretShare::with_field(2, 3, Gf256).unwrap();
+        for index in [256, 257] {
+            let shares = [Share::new(index, 0x99), Share::new(2, 0x42)];
+            assert_eq!(ss.recover(&shares), Err(TinySsError::InvalidIndex));
+        }
+    }
+}
@@ -0,0 +1,210 @@
+//! GF(2^8) arithmetic over whole byte slices.
+//!
+//! Splitting evaluates every byte's polynomial at the same `x`, and
+//! recovery scales every byte of a share by the same Lagrange coefficient,
+//! so both reduce to multiplying a slice by one constant `c`. Split into
+//! nibbles, `c * v = c * (v & 15) ^ c * (v & 240)`, two lookups in tables of
+//! 16 products, which SSSE3's `pshufb` and NEON's `tbl` perform for 16
+//! bytes at once. Elsewhere, 8 bytes held in a `u64` are multiplied by
+//! shifts and masks. Neither path indexes memory by the data, unlike the
+//! log and exponent tables of single multiplications.
+
+use super::mul;
+
+/// `dst = dst * c ^ src`, one step of Horner's rule for every byte.
+pub(crate) fn mul_add(dst: &mut [u8], c: u8, src: &[u8]) {
+    assert_eq!(dst.len(), src.len());
+    kernel(Op::MulAdd, dst, c, src);
+}
+
+/// `dst ^= c * src`.
+pub(crate) fn add_mul(dst: &mut [u8], c: u8, src: &[u8]) {
+    assert_eq!(dst.len(), src.len());
+    kernel(Op::AddMul, dst, c, src);
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Op {
+    /// multiplies `dst`
+    MulAdd,
+    /// multiplies `src`
+    AddMul,
+}
+
+// the products of c with every low nibble and with every high nibble
+fn nibble_tables(c: u8) -> ([u8; 16], [u8; 16]) {
+    let (mut low, mut high) = ([0u8; 16], [0u8; 16]);
+    for v in 0..16u8 {
+        low[v as usize] = mul(c, v);
+        high[v as usize] = mul(c, v << 4);
+    }
+    (low, high)
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+cpufeatures::new!(ssse3, "ssse3");
+
+fn kernel(op: Op, dst: &mut [u8], c: u8, src: &[u8]) {
+    let done = vectorized(op, dst, c, src);
+    portable(op, &mut dst[done..], c, &src[done..]);
+}
+
+// Runs the vector unit over a prefix of the slices, returning its length
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+fn vectorized(op: Op, dst: &mut [u8], c: u8, src: &[u8]) -> usize {
+    if ssse3::get() {
+        // SAFETY: the CPU supports SSSE3
+        unsafe { x86::kernel(op, dst, c, src) }
+    } else {
+        0
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+fn vectorized(op: Op, dst: &mut [u8], c: u8, src: &[u8]) -> usize {
+    // SAFETY: NEON is enabled for the target
+    unsafe { neon::kernel(op, dst, c, src) }
+}
+
+#[cfg(not(any(
+    target_arch = "x86",
+    target_arch = "x86_64",
+    all(target_arch = "aarch64", target_feature = "neon")
+)))]
+fn vectorized(_: Op, _: &mut [u8], _: u8, _: &[u8]) -> usize {
+    0
+}
+
+// 8 bytes at a time in a u64, the last ones padded to a word
+fn portable(op: Op, dst: &mut [u8], c: u8, src: &[u8]) {
+    let step = |d: u64, s: u64| match op {
+        Op::MulAdd => mul_word(d, c) ^ s,
+        Op::AddMul => d ^ mul_word(s, c),
+    };
+    let mut dst_words = dst.chunks_exact_mut(8);
+    let mut src_words = src.chunks_exact(8);
+    for (d, s) in (&mut dst_words).zip(&mut src_words) {
+        let word = step(
+            u64::from_le_bytes(d.try_into().expect("8 bytes")),
+            u64::from_le_bytes(s.try_into().expect("8 bytes")),
+        );
+        d.copy_from_slice(&word.to_le_bytes());
+    }
+    let (d, s) = (dst_words.into_remainder(), src_words.remainder());
+    let (mut d_word, mut s_word) = ([0u8; 8], [0u8; 8]);
+    d_word[..d.len()].copy_from_slice(d);
+    s_word[..s.len()].copy_from_slice(s);
+    let word = step(u64::from_le_bytes(d_word), u64::from_le_bytes(s_word));
+    d.copy_from_slice(&word.to_le_bytes()[..d.len()]);
+}
+
+// c times each byte of a, by shift-and-add over the bits of c, doubling
+// every byte at once modulo the AES polynomial
+fn mul_word(mut a: u64, c: u8) -> u64 {
+    const HIGH_BITS: u64 = 0x8080_8080_8080_8080;
+    let mut product = 0;
+    for bit in 0..8 {
+        product ^= a & 0u64.wrapping_sub(u64::from((c >> bit) & 1));
+        let overflow = (a & HIGH_BITS) >> 7;
+        a = ((a & !HIGH_BITS) << 1) ^ (overflow * 0x1b);
+    }
+    product
+}
+
+#[cfg(any(target_arch = "x86", target_arch = "x86_64"))]
+mod x86 {
+    use super::{nibble_tables, Op};
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::*;
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::*;
+
+    // Processes the whole blocks of 16 bytes and returns how many bytes
+    // that covered.
+    #[target_feature(enable = "ssse3")]
+    pub(super) unsafe fn kernel(op: Op, dst: &mut [u8], c: u8, src: &[u8]) -> usize {
+        let (low, high) = nibble_tables(c);
+        let low = _mm_loadu_si128(low.as_ptr().cast());
+        let high = _mm_loadu_si128(high.as_ptr().cast());
+        let mask = _mm_set1_epi8(0x0f);
+        let blocks = dst.len() / 16;
+        for i in 0..blocks {
+            let d_ptr = dst.as_mut_ptr().add(16 * i).cast::<__m128i>();
+            let d = _mm_loadu_si128(d_ptr);
+            let s = _mm_loadu_si128(src.as_ptr().add(16 * i).cast());
+            let (factor, other) = match op {
+                Op::MulAdd => (d, s),
+                Op::AddMul => (s, d),
+            };
+            let product = _mm_xor_si128(
+                _mm_shuffle_epi8(low, _mm_and_si128(factor, mask)),
+                _mm_shuffle_epi8(high, _mm_and_si128(_mm_srli_epi64(factor, 4), mask)),
+            );
+            _mm_storeu_si128(d_ptr, _mm_xor_si128(product, other));
+        }
+        16 * blocks
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+mod neon {
+    use super::{nibble_tables, Op};
+    use core::arch::aarch64::*;
+
+    // Processes the whole blocks of 16 bytes and returns how many bytes
+    // that covered.
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn kernel(op: Op, dst: &mut [u8], c: u8, src: &[u8]) -> usize {
+        let (low, high) = nibble_tables(c);
+        let low = vld1q_u8(low.as_ptr());
+        let high = vld1q_u8(high.as_ptr());
+        let mask = vdupq_n_u8(0x0f);
+        let blocks = dst.len() / 16;
+        for i in 0..blocks {
+            let d_ptr = dst.as_mut_ptr().add(16 * i);
+            let d = vld1q_u8(d_ptr);
+            let s = vld1q_u8(src.as_ptr().add(16 * i));
+            let (factor, other) = match op {
+                Op::MulAdd => (d, s),
+                Op::AddMul => (s, d),
+            };
+            let product = veorq_u8(
+                vqtbl1q_u8(low, vandq_u8(factor, mask)),
+                vqtbl1q_u8(high, vshrq_n_u8::<4>(factor)),
+            );
+            vst1q_u8(d_ptr, veorq_u8(product, other));
+        }
+        16 * blocks
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec::Vec;
+
+    #[test]
+    fn kernels_test() {
+        let src: Vec<u8> = (0..100u32).map(|i| (i * 37 + 11) as u8).collect();
+        let start: Vec<u8> = (0..100u32).map(|i| (i * 91 + 5) as u8).collect();
+        for c in [0, 1, 2, 0x53, 0xca, 0xff] {
+            for len in [0, 7, 8, 16, 33, 100] {
+                let (src, start) = (&src[..len], &start[..len]);
+                let mut dst = start.to_vec();
+                mul_add(&mut dst, c, src);
+                let expected: Vec<u8> =
+                    start.iter().zip(src).map(|(d, s)| mul(*d, c) ^ s).collect();
+                assert_eq!(dst, expected);
+                let mut dst = start.to_vec();
+                add_mul(&mut dst, c, src);
+                let expected: Vec<u8> =
+                    start.iter().zip(src).map(|(d, s)| d ^ mul(c, *s)).collect();
+                assert_eq!(dst, expected);
+                // the fallback on its own, whatever the CPU
+                let mut dst = start.to_vec();
+                portable(Op::AddMul, &mut dst, c, src);
+                assert_eq!(dst, expected);
+            }
+        }
+    }
+}
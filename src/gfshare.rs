@@ -0,0 +1,197 @@
+//! Share files of [libgfshare](https://www.digital-scurf.org/software/libgfshare)'s
+//! `gfsplit` and `gfcombine`, for restoring backups made with them and for
+//! writing shares they restore.
+//!
+//! libgfshare shares files byte by byte over GF(256) modulo
+//! x^8 + x^4 + x^3 + x^2 + 1, the field of `sharks`, at x-coordinates drawn
+//! at random from 1 to 255. `gfsplit` writes every share to a file of its
+//! own holding nothing but the value bytes, and records x in the name: the
+//! output stem, a dot and x in three decimal digits, as in `secret.txt.042`.
+
+use crate::{
+    field::Field,
+    gf256::ByteShare,
+    poly,
+    sharks::{SharksField, SharksSharing},
+    DecodeError, Share, TinySsError,
+};
+use alloc::{format, string::String, vec, vec::Vec};
+
+/// The name `gfsplit` gives the file of `share` for the output `stem`.
+/// Fails with `InvalidIndex` for an index outside `1..=255`.
+pub fn file_name(stem: &str, share: &ByteShare) -> Result<String, TinySsError> {
+    if share.index() == 0 || share.index() > 255 {
+        return Err(TinySsError::InvalidIndex);
+    }
+    Ok(format!("{}.{:03}", stem, share.index()))
+}
+
+/// Splits a share file name into the output stem and x, as `gfcombine` does
+/// to name the file it restores; the suffix must be three decimal digits
+/// naming a nonzero byte.
+pub fn parse_file_name(name: &str) -> Result<(&str, usize), DecodeError> {
+    let (stem, suffix) = name.rsplit_once('.').ok_or(DecodeError::InvalidEncoding)?;
+    if suffix.len() != 3 || !suffix.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(DecodeError::InvalidEncoding);
+    }
+    match suffix.parse::<usize>() {
+        Ok(x @ 1..=255) => Ok((stem, x)),
+        _ => Err(DecodeError::InvalidValue),
+    }
+}
+
+/// The share stored under `name` with `contents`; a share file without
+/// bytes has nothing to recover.
+pub fn from_file(name: &str, contents: &[u8]) -> Result<ByteShare, DecodeError> {
+    let (_, x) = parse_file_name(name)?;
+    if contents.is_empty() {
+        return Err(DecodeError::Truncated);
+    }
+    Ok(Share::new(x, contents.to_vec()))
+}
+
+/// A `t`-of-`n` scheme dealing as `gfsplit -n t -m n` does.
+#[derive(Clone, Debug)]
+pub struct GfshareSharing {
+    /// threshold
+    t: usize,
+    /// total number of shares
+    n: usize,
+}
+
+impl GfshareSharing {
+    /// Creates a `t`-of-`n` scheme with `2 <= t <= n <= 255`, the parameters
+    /// `gfsplit` accepts.
+    pub fn new(t: usize, n: usize) -> Result<Self, TinySsError> {
+        if t < 2 || t > n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if !SharksField.has_indices(n) {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(GfshareSharing { t, n })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: &[u8]) -> Vec<ByteShare> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits `secret` with the x-coordinates and then the coefficients
+    /// drawn from `fill_random`; zero and repeated x-coordinates are drawn
+    /// again.
+    pub fn split_with<F>(&self, secret: &[u8], mut fill_random: F) -> Vec<ByteShare>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        let mut xs: Vec<u8> = Vec::with_capacity(self.n);
+        while xs.len() < self.n {
+            let x = SharksField.random(&mut fill_random);
+            if x != 0 && !xs.contains(&x) {
+                xs.push(x);
+            }
+        }
+        let degree = self.t - 1;
+        let mut coefficients = vec![0u8; degree * secret.len()];
+        fill_random(&mut coefficients);
+        // coefficient j + 1 of byte i at i * degree + j
+        let polynomials: Vec<Vec<u8>> = secret
+            .iter()
+            .enumerate()
+            .map(|(i, &byte)| {
+                let mut polynomial = Vec::from([byte]);
+                polynomial.extend_from_slice(&coefficients[i * degree..(i + 1) * degree]);
+                polynomial
+            })
+            .collect();
+        xs.into_iter()
+            .map(|x| {
+                let value = polynomials
+                    .iter()
+                    .map(|polynomial| poly::evaluate(&SharksField, polynomial, &x))
+                    .collect();
+                Share::new(x.into(), value)
+            })
+            .collect()
+    }
+
+    /// Recovers the secret from at least `t` shares of equal length, as
+    /// `gfcombine` does from their files.
+    ///
+    /// Shares repeating an index are ignored as long as they carry the same
+    /// value; the first `t` distinct shares are used for interpolation.
+    pub fn recover(&self, shares: &[ByteShare]) -> Result<Vec<u8>, TinySsError> {
+        SharksSharing::new(self.t, self.n)?.recover(shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn file_name_test() {
+        let share = Share::new(42, b"abc".to_vec());
+        let name = file_name("secret.txt", &share).unwrap();
+        assert_eq!(name, "secret.txt.042");
+        assert_eq!(parse_file_name(&name).unwrap(), ("secret.txt", 42));
+        assert_eq!(from_file(&name, b"abc").unwrap(), share);
+        assert_eq!(from_file(&name, b""), Err(DecodeError::Truncated));
+        assert_eq!(
+            parse_file_name("secret.42"),
+            Err(DecodeError::InvalidEncoding)
+        );
+        assert_eq!(parse_file_name("secret"), Err(DecodeError::InvalidEncoding));
+        assert_eq!(
+            parse_file_name("secret.+42"),
+            Err(DecodeError::InvalidEncoding)
+        );
+        assert_eq!(
+            parse_file_name("secret.000"),
+            Err(DecodeError::InvalidValue)
+        );
+        assert_eq!(
+            parse_file_name("secret.256"),
+            Err(DecodeError::InvalidValue)
+        );
+        let share = Share::new(256, b"abc".to_vec());
+        assert_eq!(file_name("secret", &share), Err(TinySsError::InvalidIndex));
+    }
+
+    #[test]
+    fn split_with_test() {
+        let ss = GfshareSharing::new(2, 3).unwrap();
+        // x = 7, 7 again, 0, 200, 1, then the coefficients
+        let mut draws = [&[7][..], &[7], &[0], &[200], &[1], &[5, 9]].into_iter();
+        let shares = ss.split_with(b"gf", |buf| buf.copy_from_slice(draws.next().unwrap()));
+        let indices: Vec<usize> = shares.iter().map(|s| s.index()).collect();
+        assert_eq!(indices, [7, 200, 1]);
+        assert_eq!(shares[2], Share::new(1, vec![b'g' ^ 5, b'f' ^ 9]));
+        let y = |c: u8| SharksField.mul(&c, &200);
+        assert_eq!(shares[1].value(), &[b'g' ^ y(5), b'f' ^ y(9)]);
+        assert_eq!(ss.recover(&shares[..2]).unwrap(), b"gf");
+
+        let files: Vec<(String, Vec<u8>)> = shares
+            .iter()
+            .map(|s| (file_name("key", s).unwrap(), s.value().clone()))
+            .collect();
+        let read: Vec<ByteShare> = files
+            .iter()
+            .map(|(name, contents)| from_file(name, contents).unwrap())
+            .collect();
+        assert_eq!(ss.recover(&read[1..]).unwrap(), b"gf");
+        assert!(GfshareSharing::new(1, 3).is_err());
+    }
+}
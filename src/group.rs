@@ -0,0 +1,436 @@
+//! Prime-order subgroups of `Z_p^*` for the verifiable schemes.
+//!
+//! `p = 2q + 1` is a safe prime and the group is the subgroup of quadratic
+//! residues, of prime order `q`. Share values and exponents live in the
+//! scalar field of order `q`; commitments are group elements.
+
+use crate::{
+    field::{Field, FieldCtx, PrimeField},
+    transcript::Transcript,
+    Share, TinySsError,
+};
+use alloc::{vec, vec::Vec};
+use num_bigint::{BigInt, Sign};
+use num_traits::One;
+use sha2::{Digest, Sha256};
+
+// RFC 3526, 2048-bit MODP group
+const MODP_2048: &[u8] = b"\
+    ffffffffffffffffc90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea6\
+    3b139b22514a08798e3404ddef9519b3cd3a431b302b0a6df25f14374fe1356d6d51c245\
+    e485b576625e7ec6f44c42e9a637ed6b0bff5cb6f406b7edee386bfb5a899fa5ae9f2411\
+    7c4b1fe649286651ece45b3dc2007cb8a163bf0598da48361c55d39a69163fa8fd24cf5f\
+    83655d23dca3ad961c62f356208552bb9ed529077096966d670c354e4abc9804f1746c08\
+    ca18217c32905e462e36ce3be39e772c180e86039b2783a2ec07a28fb5c55df06f4c52c9\
+    de2bcbf6955817183995497cea956ae515d2261898fa051015728e5a8aacaa68ffffffff\
+    ffffffff";
+
+/// domain separating the derivation of the second generator
+const H_DOMAIN: &[u8] = b"tiny-ss pedersen generator h";
+
+/// The order-`q` subgroup of `Z_p^*` with two independent generators.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SchnorrGroup {
+    /// reduction context for the safe prime modulus
+    ctx: FieldCtx,
+    /// generator
+    g: BigInt,
+    /// second generator with unknown discrete log to base g
+    h: BigInt,
+    /// field of exponents, of order q = (p - 1) / 2
+    scalars: PrimeField,
+}
+
+impl SchnorrGroup {
+    /// Creates the group of the safe prime `p` with generators `g` and `h`.
+    ///
+    /// Primality of `p` and `q` is the caller's responsibility; the
+    /// generators are checked to be distinct, nontrivial subgroup elements.
+    pub fn new(p: BigInt, g: BigInt, h: BigInt) -> Result<Self, TinySsError> {
+        if p <= BigInt::from(5) || !p.bit(0) {
+            return Err(TinySsError::InvalidGroup);
+        }
+        let q: BigInt = (&p - 1u32) >> 1;
+        let scalars = PrimeField::new(q).map_err(|_| TinySsError::InvalidGroup)?;
+        let ctx = FieldCtx::new(p).map_err(|_| TinySsError::InvalidGroup)?;
+        let group = SchnorrGroup { ctx, g, h, scalars };
+        let nontrivial = |x: &BigInt| group.is_element(x) && !x.is_one();
+        if !nontrivial(&group.g) || !nontrivial(&group.h) || group.g == group.h {
+            return Err(TinySsError::InvalidGroup);
+        }
+        Ok(group)
+    }
+
+    /// Creates the group of the safe prime `p` with `g = 4` and `h` derived
+    /// by hashing, so that nobody knows `log_g h`.
+    pub fn from_safe_prime(p: BigInt) -> Result<Self, TinySsError> {
+        let g = BigInt::from(4);
+        let h = hash_to_element(&p, H_DOMAIN);
+        SchnorrGroup::new(p, g, h)
+    }
+
+    /// The 2048-bit MODP group of RFC 3526.
+    pub fn modp2048() -> Self {
+        let p = BigInt::parse_bytes(MODP_2048, 16).expect("valid hex");
+        SchnorrGroup::from_safe_prime(p).expect("RFC 3526 prime is safe")
+    }
+
+    /// safe prime modulus
+    pub fn p(&self) -> &BigInt {
+        self.ctx.p()
+    }
+
+    /// order of the group
+    pub fn q(&self) -> &BigInt {
+        self.scalars.p()
+    }
+
+    /// generator
+    pub fn g(&self) -> &BigInt {
+        &self.g
+    }
+
+    /// second generator
+    pub fn h(&self) -> &BigInt {
+        &self.h
+    }
+
+    /// field of exponents
+    pub fn scalars(&self) -> &PrimeField {
+        &self.scalars
+    }
+
+    /// `base^e mod p`, with the exponent reduced modulo `q`, by the
+    /// ladder of `FieldCtx::pow` as exponents are often secret
+    pub fn exp(&self, base: &BigInt, e: &BigInt) -> BigInt {
+        self.ctx.pow(base, &self.scalars.reduce(e))
+    }
+
+    pub fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        (a * b) % self.p()
+    }
+
+    /// Pedersen commitment `g^a h^b`.
+    pub fn commit(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        self.mul(&self.exp(&self.g, a), &self.exp(&self.h, b))
+    }
+
+    /// Whether `x` lies in the order-`q` subgroup.
+    pub fn is_element(&self, x: &BigInt) -> bool {
+        x.sign() == Sign::Plus && x < self.p() && self.ctx.pow_vartime(x, self.q()).is_one()
+    }
+
+    /// Evaluates the committed polynomial in the exponent,
+    /// `prod_j C_j^{index^j}`.
+    pub fn eval_commitments(&self, commitments: &[BigInt], index: usize) -> BigInt {
+        let x = self.scalars.elem_from_index(index);
+        let (mut result, mut power) = (BigInt::one(), BigInt::one());
+        for commitment in commitments {
+            // the powers of the index are public
+            result = self.mul(&result, &self.ctx.pow_vartime(commitment, &power));
+            power = self.scalars.mul(&power, &x);
+        }
+        result
+    }
+
+    /// `prod_i bases_i^{exponents_i}` over one shared chain of squarings,
+    /// with the exponents reduced modulo `q`.
+    pub fn multi_exp(&self, bases: &[BigInt], exponents: &[BigInt]) -> BigInt {
+        let exponents: Vec<BigInt> = exponents.iter().map(|e| self.scalars.reduce(e)).collect();
+        let bits = exponents.iter().map(BigInt::bits).max().unwrap_or(0);
+        let mut result = BigInt::one();
+        for bit in (0..bits).rev() {
+            result = self.mul(&result, &result);
+            for (base, e) in bases.iter().zip(&exponents) {
+                if e.bit(bit) {
+                    result = self.mul(&result, base);
+                }
+            }
+        }
+        result
+    }
+
+    /// Checks `g^{s_i} = prod_j C_j^{i^j}` for one share against the Feldman
+    /// commitments `C_j = g^{a_j}`.
+    pub fn verify_feldman(&self, commitments: &[BigInt], share: &Share) -> bool {
+        share.index() != 0
+            && commitments.iter().all(|c| self.is_element(c))
+            && self.exp(&self.g, share.value()) == self.eval_commitments(commitments, share.index())
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn verify_feldman_batch(
+        &self,
+        commitments: &[BigInt],
+        shares: &[Share],
+    ) -> Result<(), TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.verify_feldman_batch_with(commitments, shares, &mut |buf| rng.fill_bytes(buf))
+    }
+
+    /// Checks all of `shares` against the Feldman commitments at once.
+    ///
+    /// Weighting share `i` with a random `r_i`, a single multi-exponentiation
+    /// checks `g^{sum_i r_i s_i} = prod_j C_j^{sum_i r_i i^j}`, which a bad
+    /// share passes with probability at most `2^-128` or `1 / q`. Only on
+    /// failure are the shares checked one by one, to name the first bad one.
+    /// The weights are built from `fill_random`, which must fill the buffer
+    /// with uniformly random bytes.
+    pub fn verify_feldman_batch_with(
+        &self,
+        commitments: &[BigInt],
+        shares: &[Share],
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<(), TinySsError> {
+        if shares.iter().any(|s| s.index() == 0) {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let weights = self.batch_weights(shares.len(), fill_random);
+        // g^{-sum_i r_i s_i} prod_j C_j^{sum_i r_i i^j} must be one
+        let mut bases = vec![self.g.clone()];
+        bases.extend_from_slice(commitments);
+        let mut exponents = vec![self.scalars.sub(
+            &self.scalars.zero(),
+            &self.weighted_sum(&weights, shares.iter().map(Share::value)),
+        )];
+        exponents.extend(self.weighted_powers(
+            &weights,
+            shares.iter().map(Share::index),
+            commitments.len(),
+        ));
+        if commitments.iter().all(|c| self.is_element(c))
+            && self.multi_exp(&bases, &exponents).is_one()
+        {
+            return Ok(());
+        }
+        match shares.iter().find(|s| !self.verify_feldman(commitments, s)) {
+            Some(bad) => Err(TinySsError::InvalidShare { index: bad.index() }),
+            None => Ok(()),
+        }
+    }
+
+    // Random weights of at most 128 bits for a batch of `n` checks
+    pub(crate) fn batch_weights(
+        &self,
+        n: usize,
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Vec<BigInt> {
+        (0..n)
+            .map(|_| {
+                let mut bytes = [0u8; 16];
+                fill_random(&mut bytes);
+                self.scalars
+                    .reduce(&BigInt::from_bytes_be(Sign::Plus, &bytes))
+            })
+            .collect()
+    }
+
+    // sum_i r_i v_i modulo q
+    pub(crate) fn weighted_sum<'a>(
+        &self,
+        weights: &[BigInt],
+        values: impl Iterator<Item = &'a BigInt>,
+    ) -> BigInt {
+        weights
+            .iter()
+            .zip(values)
+            .fold(self.scalars.zero(), |sum, (r, v)| {
+                self.scalars
+                    .add(&sum, &self.scalars.mul(r, &self.scalars.reduce(v)))
+            })
+    }
+
+    // sum_i r_i i^j modulo q for every j below `t`
+    pub(crate) fn weighted_powers(
+        &self,
+        weights: &[BigInt],
+        indices: impl Iterator<Item = usize>,
+        t: usize,
+    ) -> Vec<BigInt> {
+        let mut sums = vec![self.scalars.zero(); t];
+        for (r, index) in weights.iter().zip(indices) {
+            let x = self.scalars.elem_from_index(index);
+            let mut term = r.clone();
+            for sum in sums.iter_mut() {
+                *sum = self.scalars.add(sum, &term);
+                term = self.scalars.mul(&term, &x);
+            }
+        }
+        sums
+    }
+
+    /// Hashes `domain` to an element of the group whose discrete log is
+    /// unknown.
+    pub fn hash_to_element(&self, domain: &[u8]) -> BigInt {
+        hash_to_element(self.p(), domain)
+    }
+
+    /// Fiat-Shamir challenge over the fixed-width encodings of `elements`.
+    pub fn hash_to_scalar(&self, domain: &[u8], elements: &[&BigInt]) -> BigInt {
+        let mut data = domain.to_vec();
+        for element in elements {
+            data.extend(self.encode(element));
+        }
+        let digest = expand(self.q(), &data, 0);
+        self.scalars
+            .reduce(&BigInt::from_bytes_be(Sign::Plus, &digest))
+    }
+
+    /// Absorbs the fixed-width encoding of `x` into `transcript`.
+    pub fn append_element(
+        &self,
+        transcript: &mut dyn Transcript,
+        label: &'static [u8],
+        x: &BigInt,
+    ) {
+        transcript.append_message(label, &self.encode(x));
+    }
+
+    /// Draws a challenge modulo q from `transcript`, 16 bytes longer than q
+    /// to keep the reduction close to uniform.
+    pub fn challenge_scalar(
+        &self,
+        transcript: &mut dyn Transcript,
+        label: &'static [u8],
+    ) -> BigInt {
+        let mut bytes = vec![0u8; self.q().bits().div_ceil(8) as usize + 16];
+        transcript.challenge_bytes(label, &mut bytes);
+        self.scalars
+            .reduce(&BigInt::from_bytes_be(Sign::Plus, &bytes))
+    }
+
+    // big-endian residue of x, padded to the width of p
+    fn encode(&self, x: &BigInt) -> Vec<u8> {
+        let width = self.p().bits().div_ceil(8) as usize;
+        let (_, bytes) = self.canonical(x).to_bytes_be();
+        let mut encoded = vec![0u8; width - bytes.len()];
+        encoded.extend_from_slice(&bytes);
+        encoded
+    }
+
+    // residue of x in [0, p)
+    fn canonical(&self, x: &BigInt) -> BigInt {
+        let x = x % self.p();
+        if x.sign() == Sign::Minus {
+            x + self.p()
+        } else {
+            x
+        }
+    }
+}
+
+// Squares a hash-derived residue, retrying until it is not 0 or 1
+fn hash_to_element(p: &BigInt, domain: &[u8]) -> BigInt {
+    for counter in 0u32.. {
+        let x = BigInt::from_bytes_be(Sign::Plus, &expand(p, domain, counter)) % p;
+        let element = (&x * &x) % p;
+        if element > BigInt::one() {
+            return element;
+        }
+    }
+    unreachable!("a hash hits a nontrivial residue")
+}
+
+// SHA-256 in counter mode, 16 bytes longer than p to keep the reduction
+// close to uniform
+fn expand(p: &BigInt, domain: &[u8], counter: u32) -> Vec<u8> {
+    let len = p.bits().div_ceil(8) as usize + 16;
+    let mut bytes = Vec::with_capacity(len);
+    for block in 0u32.. {
+        if bytes.len() >= len {
+            break;
+        }
+        let digest = Sha256::new()
+            .chain_update(counter.to_be_bytes())
+            .chain_update(block.to_be_bytes())
+            .chain_update(domain)
+            .finalize();
+        bytes.extend_from_slice(&digest);
+    }
+    bytes.truncate(len);
+    bytes
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+
+    #[test]
+    fn group_test() {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        assert_eq!(group.q(), &BigInt::from(1019));
+        assert!(group.is_element(group.h()));
+        assert_ne!(group.h(), group.g());
+        assert_eq!(group.exp(group.g(), group.q()), BigInt::one());
+        // exponents are taken modulo q
+        assert_eq!(
+            group.commit(&BigInt::from(3), &BigInt::from(5)),
+            group.commit(&BigInt::from(3), &BigInt::from(5 + 1019))
+        );
+
+        let p = BigInt::from(2039);
+        let non_residue = BigInt::from(2038);
+        assert_eq!(
+            SchnorrGroup::new(p.clone(), non_residue, BigInt::from(9)),
+            Err(TinySsError::InvalidGroup)
+        );
+        assert_eq!(
+            SchnorrGroup::new(p, BigInt::from(4), BigInt::from(4)),
+            Err(TinySsError::InvalidGroup)
+        );
+    }
+
+    #[test]
+    fn feldman_batch_test() {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        let (a, b) = (BigInt::from(5), BigInt::from(7));
+        assert_eq!(
+            group.multi_exp(
+                &[a.clone(), b.clone()],
+                &[BigInt::from(3), BigInt::from(1020)]
+            ),
+            group.mul(&group.exp(&a, &BigInt::from(3)), &b)
+        );
+
+        let ss = crate::SecretShare::new(3, 6, group.q().clone()).unwrap();
+        let polynomial = [BigInt::from(1000), BigInt::from(17), BigInt::from(400)];
+        let commitments: Vec<BigInt> = polynomial.iter().map(|a| group.exp(group.g(), a)).collect();
+        let mut shares = ss
+            .split_with_coefficients(polynomial[0].clone(), &polynomial[1..])
+            .unwrap();
+        let mut fill = counter(29);
+        assert_eq!(
+            group.verify_feldman_batch_with(&commitments, &shares, &mut fill),
+            Ok(())
+        );
+        shares[4] = Share::new(5, group.scalars().add(shares[4].value(), &BigInt::from(1)));
+        assert!(!group.verify_feldman(&commitments, &shares[4]));
+        assert_eq!(
+            group.verify_feldman_batch_with(&commitments, &shares, &mut fill),
+            Err(TinySsError::InvalidShare { index: 5 })
+        );
+    }
+
+    #[test]
+    fn modp2048_test() {
+        let group = SchnorrGroup::modp2048();
+        assert_eq!(group.p().bits(), 2048);
+        assert!(group.is_element(group.h()));
+    }
+
+    #[test]
+    fn hash_to_scalar_test() {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        let (a, b) = (BigInt::from(5), BigInt::from(7));
+        let c = group.hash_to_scalar(b"test", &[&a, &b]);
+        assert!(&c < group.q());
+        assert_eq!(c, group.hash_to_scalar(b"test", &[&a, &b]));
+        assert_ne!(c, group.hash_to_scalar(b"test", &[&b, &a]));
+        assert_eq!(
+            group.eval_commitments(&[a.clone(), b.clone()], 2),
+            group.mul(&a, &group.exp(&b, &BigInt::from(2)))
+        );
+    }
+}
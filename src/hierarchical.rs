@@ -0,0 +1,267 @@
+//! Hierarchical threshold sharing after Tassa (J. Cryptology 2007).
+//!
+//! Holders are ranked in levels with increasing cumulative thresholds
+//! `t_0 < t_1 < ... < t_m = t`: a set of holders is authorized if, for every
+//! level `j`, at least `t_j` of them come from levels `0..=j`. For example,
+//! levels `[(1, 2), (3, 5)]` mean "at least 3 in total, including at least 1
+//! of the 2 directors".
+//!
+//! A holder of level `j` receives the derivative of order `t_{j-1}` of the
+//! dealt polynomial at its index, and recovery solves the resulting Birkhoff
+//! interpolation problem. The field's characteristic must exceed `t`, which
+//! rules out `Gf256`.
+
+use crate::{
+    field::{Field, PrimeField},
+    poly, Share, TinySsError,
+};
+use alloc::{vec, vec::Vec};
+
+/// A hierarchical scheme; holders are numbered consecutively, level by
+/// level, starting at the most senior.
+#[derive(Clone, Debug)]
+pub struct HierarchicalSharing<F: Field = PrimeField> {
+    /// cumulative threshold of every level
+    thresholds: Vec<usize>,
+    /// number of holders on every level
+    sizes: Vec<usize>,
+    field: F,
+}
+
+impl<F: Field> HierarchicalSharing<F> {
+    /// Creates a scheme from `(cumulative threshold, holders)` per level,
+    /// most senior first. Fails with `InvalidModulus` if the field cannot
+    /// tell the holders apart or its characteristic is at most `t`.
+    pub fn new(levels: &[(usize, usize)], field: F) -> Result<Self, TinySsError> {
+        let (thresholds, sizes): (Vec<usize>, Vec<usize>) = levels.iter().copied().unzip();
+        let increasing = thresholds.windows(2).all(|w| w[0] < w[1]);
+        if levels.is_empty() || thresholds[0] == 0 || !increasing || sizes.contains(&0) {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        // every level must be reachable, and the whole set must not be the
+        // only authorized one
+        let mut holders = 0;
+        for (threshold, size) in levels {
+            holders += size;
+            if holders < *threshold {
+                return Err(TinySsError::InvalidThreshold);
+            }
+        }
+        if holders <= thresholds[thresholds.len() - 1] {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if !field.has_indices(holders) {
+            return Err(TinySsError::InvalidModulus);
+        }
+        // the derivatives pick up factors up to t - 1, and the Birkhoff
+        // system is singular once one of 1, ..., t vanishes
+        let mut k = field.zero();
+        for _ in 0..thresholds[thresholds.len() - 1] {
+            field.add_assign(&mut k, &field.one());
+            if k == field.zero() {
+                return Err(TinySsError::InvalidModulus);
+            }
+        }
+        Ok(HierarchicalSharing {
+            thresholds,
+            sizes,
+            field,
+        })
+    }
+
+    /// overall threshold
+    pub fn t(&self) -> usize {
+        self.thresholds[self.thresholds.len() - 1]
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.sizes.iter().sum()
+    }
+
+    pub fn field(&self) -> &F {
+        &self.field
+    }
+
+    /// Level of the holder at `index`, `None` outside `1..=n`.
+    pub fn level(&self, index: usize) -> Option<usize> {
+        let mut last = 0;
+        for (level, size) in self.sizes.iter().enumerate() {
+            last += size;
+            if index != 0 && index <= last {
+                return Some(level);
+            }
+        }
+        None
+    }
+
+    /// Whether the holders at `indices` may recover the secret.
+    pub fn is_authorized(&self, indices: &[usize]) -> bool {
+        let mut counts = vec![0; self.sizes.len()];
+        for (i, &index) in indices.iter().enumerate() {
+            if indices[..i].contains(&index) {
+                continue;
+            }
+            match self.level(index) {
+                Some(level) => counts[level] += 1,
+                None => return false,
+            }
+        }
+        let mut cumulative = 0;
+        counts
+            .iter()
+            .zip(&self.thresholds)
+            .all(|(count, threshold)| {
+                cumulative += count;
+                cumulative >= *threshold
+            })
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: F::Elem) -> Result<Vec<Share<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, || self.field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Splits `secret` drawing the random coefficients from `sample`, which
+    /// must return uniformly random field elements.
+    pub fn split_with<S>(
+        &self,
+        secret: F::Elem,
+        mut sample: S,
+    ) -> Result<Vec<Share<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let mut polynomial = vec![secret];
+        polynomial.extend((1..self.t()).map(|_| sample()));
+        Ok((1..=self.n())
+            .map(|index| {
+                let row = self.row(index);
+                let value = row
+                    .iter()
+                    .zip(&polynomial)
+                    .fold(self.field.zero(), |sum, (r, a)| {
+                        self.field.add(&sum, &self.field.mul(r, a))
+                    });
+                Share::new(index, value)
+            })
+            .collect())
+    }
+
+    /// Recovers the secret from an authorized set of shares.
+    ///
+    /// A few authorized sets give a singular interpolation problem, which
+    /// fails with `NotInvertible`; Tassa shows this is unlikely for large
+    /// fields.
+    pub fn recover(&self, shares: &[Share<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        let mut unique = poly::distinct_field_shares(&self.field, shares, self.t())?;
+        let indices: Vec<usize> = unique.iter().map(|s| s.index()).collect();
+        if !self.is_authorized(&indices) {
+            return Err(TinySsError::NotEnoughShares {
+                required: self.t(),
+                got: unique.len(),
+            });
+        }
+        // the t most senior shares are still authorized
+        unique.sort_by_key(|s| s.index());
+        let chosen = &unique[..self.t()];
+        let matrix = chosen.iter().map(|s| self.row(s.index())).collect();
+        let values = chosen.iter().map(|s| s.value().clone()).collect();
+        let coefficients = poly::solve(&self.field, matrix, values)?;
+        Ok(coefficients[0].clone())
+    }
+
+    // Coefficients of the derivative of order d = t_{level - 1} at x,
+    // j! / (j - d)! * x^(j - d) for the term of degree j
+    fn row(&self, index: usize) -> Vec<F::Elem> {
+        let field = &self.field;
+        let level = self.level(index).expect("index of a holder");
+        let order = if level == 0 {
+            0
+        } else {
+            self.thresholds[level - 1]
+        };
+        let x = field.elem_from_index(index);
+        (0..self.t())
+            .map(|j| {
+                if j < order {
+                    return field.zero();
+                }
+                let falling = (j - order + 1..=j)
+                    .fold(field.one(), |f, k| field.mul(&f, &field.elem_from_index(k)));
+                let power = (0..j - order).fold(field.one(), |p, _| field.mul(&p, &x));
+                field.mul(&falling, &power)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    fn scheme() -> HierarchicalSharing {
+        // at least 3 in total, including 1 of the 2 directors
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        HierarchicalSharing::new(&[(1, 2), (3, 5)], field).unwrap()
+    }
+
+    #[test]
+    fn hierarchical_test() {
+        let ss = scheme();
+        assert_eq!((ss.t(), ss.n()), (3, 7));
+        assert_eq!(ss.level(2), Some(0));
+        assert_eq!(ss.level(3), Some(1));
+        assert_eq!(ss.level(8), None);
+        assert!(ss.is_authorized(&[1, 4, 7]));
+        assert!(!ss.is_authorized(&[3, 4, 7]));
+
+        let mut next = 40;
+        let shares = ss
+            .split_with(BigInt::from(1234), || {
+                next += 333;
+                BigInt::from(next)
+            })
+            .unwrap();
+        let pick = |indices: &[usize]| -> Vec<Share> {
+            indices.iter().map(|&i| shares[i - 1].clone()).collect()
+        };
+        assert_eq!(ss.recover(&pick(&[1, 4, 7])).unwrap(), BigInt::from(1234));
+        assert_eq!(
+            ss.recover(&pick(&[2, 3, 5, 6])).unwrap(),
+            BigInt::from(1234)
+        );
+        assert_eq!(
+            ss.recover(&pick(&[3, 4, 7])).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 3
+            }
+        );
+    }
+
+    #[test]
+    fn levels_test() {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        for levels in [
+            &[][..],
+            &[(0, 2)],
+            &[(2, 2), (2, 3)],
+            &[(3, 1), (4, 5)],
+            &[(2, 2)],
+        ] {
+            assert_eq!(
+                HierarchicalSharing::new(levels, field.clone()).unwrap_err(),
+                TinySsError::InvalidThreshold
+            );
+        }
+        // characteristic 2 is below the overall threshold 3
+        assert_eq!(
+            HierarchicalSharing::new(&[(1, 2), (3, 5)], crate::gf256::Gf256).unwrap_err(),
+            TinySsError::InvalidModulus
+        );
+    }
+}
@@ -0,0 +1,113 @@
+// Linear operations on whole share sets.
+//
+// Shamir sharing is linear: adding the shares of `a` and `b` index by index
+// gives shares of `a + b` on the sum of the two polynomials, and adding or
+// multiplying by a public constant acts on every share alike. Holders can
+// therefore compute any linear function of shared secrets without talking
+// to each other.
+
+use crate::{field::Field, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+
+impl<F: Field> SecretShare<F> {
+    /// Shares of `a + b` from shares of `a` and `b` with the same indices
+    /// in the same order.
+    pub fn add_shares(
+        &self,
+        a: &[Share<F::Elem>],
+        b: &[Share<F::Elem>],
+    ) -> Result<Vec<Share<F::Elem>>, TinySsError> {
+        self.zip_shares(a, b, |x, y| self.field().add(x, y))
+    }
+
+    /// Shares of `a - b` from shares of `a` and `b` with the same indices
+    /// in the same order.
+    pub fn sub_shares(
+        &self,
+        a: &[Share<F::Elem>],
+        b: &[Share<F::Elem>],
+    ) -> Result<Vec<Share<F::Elem>>, TinySsError> {
+        self.zip_shares(a, b, |x, y| self.field().sub(x, y))
+    }
+
+    /// Shares of `s + constant` from shares of `s`.
+    pub fn add_constant(
+        &self,
+        shares: &[Share<F::Elem>],
+        constant: &F::Elem,
+    ) -> Vec<Share<F::Elem>> {
+        shares
+            .iter()
+            .map(|s| Share::new(s.index(), self.field().add(s.value(), constant)))
+            .collect()
+    }
+
+    /// Shares of `scalar * s` from shares of `s`.
+    pub fn mul_scalar(&self, shares: &[Share<F::Elem>], scalar: &F::Elem) -> Vec<Share<F::Elem>> {
+        shares
+            .iter()
+            .map(|s| Share::new(s.index(), self.field().mul(s.value(), scalar)))
+            .collect()
+    }
+
+    // Combines two share sets position by position, requiring equal indices
+    fn zip_shares<O>(
+        &self,
+        a: &[Share<F::Elem>],
+        b: &[Share<F::Elem>],
+        op: O,
+    ) -> Result<Vec<Share<F::Elem>>, TinySsError>
+    where
+        O: Fn(&F::Elem, &F::Elem) -> F::Elem,
+    {
+        if a.len() != b.len() {
+            return Err(TinySsError::LengthMismatch);
+        }
+        a.iter()
+            .zip(b)
+            .map(|(x, y)| {
+                if x.index() != y.index() {
+                    return Err(TinySsError::IndexMismatch);
+                }
+                Ok(Share::new(x.index(), op(x.value(), y.value())))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn homomorphic_test() {
+        let ss = SecretShare::new(2, 4, BigInt::from(1613)).unwrap();
+        let a = ss
+            .split_with_coefficients(BigInt::from(1000), &[BigInt::from(5)])
+            .unwrap();
+        let b = ss
+            .split_with_coefficients(BigInt::from(700), &[BigInt::from(9)])
+            .unwrap();
+        let sum = ss.add_shares(&a, &b).unwrap();
+        assert_eq!(ss.recover(&sum[2..]).unwrap(), BigInt::from(87));
+        let difference = ss.sub_shares(&a, &b).unwrap();
+        assert_eq!(ss.recover(&difference[..2]).unwrap(), BigInt::from(300));
+
+        let shifted = ss.add_constant(&a, &BigInt::from(13));
+        assert_eq!(ss.recover(&shifted[1..3]).unwrap(), BigInt::from(1013));
+        let scaled = ss.mul_scalar(&a, &BigInt::from(2));
+        assert_eq!(ss.recover(&scaled[1..3]).unwrap(), BigInt::from(387));
+        // linear combinations compose
+        let combined = ss
+            .add_shares(&scaled, &ss.mul_scalar(&b, &BigInt::from(3)))
+            .unwrap();
+        assert_eq!(ss.recover(&combined[..2]).unwrap(), BigInt::from(874));
+
+        assert_eq!(
+            ss.add_shares(&a[..2], &b[1..3]),
+            Err(TinySsError::IndexMismatch)
+        );
+        assert_eq!(ss.add_shares(&a, &b[1..]), Err(TinySsError::LengthMismatch));
+    }
+}
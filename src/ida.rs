@@ -0,0 +1,193 @@
+//! Information dispersal after Rabin, with a systematic Reed-Solomon code
+//! over GF(256).
+//!
+//! The data is cut into `t` pieces, which are the values at `1..=t` of one
+//! polynomial of degree `t - 1` per byte position. Fragment `i` is the value
+//! at `i`, so the first `t` fragments are the data itself and any `t`
+//! fragments determine the rest. Each fragment is `1 / t` of the data, but
+//! unlike a secret sharing every fragment reveals part of it; use the `css`
+//! module for confidentiality.
+
+use crate::{gf256, poly, Share, TinySsError};
+use alloc::vec::Vec;
+
+/// One fragment of dispersed data.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Fragment {
+    index: usize,
+    /// length of the dispersed data
+    len: usize,
+    bytes: Vec<u8>,
+}
+
+impl Fragment {
+    pub fn new(index: usize, len: usize, bytes: Vec<u8>) -> Self {
+        Fragment { index, len, bytes }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// length of the dispersed data
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+}
+
+/// Dispersal of data into `n` fragments, any `t` of which restore it.
+#[derive(Clone, Debug)]
+pub struct Ida {
+    /// fragments needed to restore the data
+    t: usize,
+    /// total number of fragments
+    n: usize,
+}
+
+impl Ida {
+    /// Creates a dispersal with `0 < t <= n <= 255`.
+    pub fn new(t: usize, n: usize) -> Result<Self, TinySsError> {
+        if t == 0 || t > n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if n > 255 {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(Ida { t, n })
+    }
+
+    /// fragments needed to restore the data
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of fragments
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// Splits `data` into `n` fragments of `ceil(len / t)` bytes.
+    pub fn disperse(&self, data: &[u8]) -> Vec<Fragment> {
+        disperse(data, self.t, self.n)
+            .into_iter()
+            .enumerate()
+            .map(|(i, bytes)| Fragment::new(i + 1, data.len(), bytes))
+            .collect()
+    }
+
+    /// Restores the data from at least `t` fragments.
+    pub fn restore(&self, fragments: &[Fragment]) -> Result<Vec<u8>, TinySsError> {
+        let len = fragments.first().map_or(0, |f| f.len);
+        let width = len.div_ceil(self.t);
+        for fragment in fragments {
+            if fragment.index == 0 || fragment.index > self.n {
+                return Err(TinySsError::InvalidIndex);
+            }
+            if fragment.len != len || fragment.bytes.len() != width {
+                return Err(TinySsError::LengthMismatch);
+            }
+        }
+        let shares: Vec<Share<&[u8]>> = fragments
+            .iter()
+            .map(|f| Share::new(f.index, f.bytes.as_slice()))
+            .collect();
+        let unique = poly::distinct_shares(&shares, self.t)?;
+        let points: Vec<(u8, &[u8])> = unique
+            .iter()
+            .take(self.t)
+            .map(|s| (s.index() as u8, *s.value()))
+            .collect();
+        Ok(restore(&points, self.t, len))
+    }
+}
+
+// Splits `data` into `n` fragments of `ceil(len / t)` bytes each
+fn disperse(data: &[u8], t: usize, n: usize) -> Vec<Vec<u8>> {
+    let width = data.len().div_ceil(t);
+    let mut padded = data.to_vec();
+    padded.resize(width * t, 0);
+    let pieces: Vec<(u8, &[u8])> = padded
+        .chunks(width.max(1))
+        .take(t)
+        .enumerate()
+        .map(|(i, piece)| (i as u8 + 1, piece))
+        .collect();
+    (1..=n)
+        .map(|i| {
+            if i <= t {
+                padded[(i - 1) * width..i * width].to_vec()
+            } else {
+                gf256::interpolate(&pieces, i as u8)
+            }
+        })
+        .collect()
+}
+
+// Restores `len` bytes of data from `t` fragments with distinct nonzero
+// indices
+fn restore(fragments: &[(u8, &[u8])], t: usize, len: usize) -> Vec<u8> {
+    let mut data = Vec::with_capacity(len.div_ceil(t) * t);
+    for i in 1..=t {
+        match fragments.iter().find(|(x, _)| usize::from(*x) == i) {
+            Some((_, piece)) => data.extend_from_slice(piece),
+            None => data.extend(gf256::interpolate(fragments, i as u8)),
+        }
+    }
+    data.truncate(len);
+    data
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn dispersal_test() {
+        let data = b"information dispersal";
+        let fragments = disperse(data, 3, 5);
+        assert!(fragments.iter().all(|f| f.len() == 7));
+        assert_eq!(fragments[0], b"informa");
+        let chosen: Vec<(u8, &[u8])> = [4, 1, 5]
+            .iter()
+            .map(|&i| (i as u8, fragments[i - 1].as_slice()))
+            .collect();
+        assert_eq!(restore(&chosen, 3, data.len()), data);
+        assert_eq!(disperse(b"", 2, 3), vec![Vec::<u8>::new(); 3]);
+    }
+
+    #[test]
+    fn ida_test() {
+        let ida = Ida::new(4, 6).unwrap();
+        let data: Vec<u8> = (0..=255).collect();
+        let fragments = ida.disperse(&data);
+        assert_eq!(fragments[5].bytes().len(), 64);
+        assert_eq!(ida.restore(&fragments[2..]).unwrap(), data);
+        assert_eq!(
+            ida.restore(&fragments[3..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 4,
+                got: 3
+            }
+        );
+
+        let mut tampered = fragments[2].clone();
+        tampered.bytes[0] ^= 1;
+        assert_eq!(
+            ida.restore(&[&fragments[2..], &[tampered]].concat()),
+            Err(TinySsError::ConflictingShares { index: 3 })
+        );
+        let short = Fragment::new(1, 300, fragments[0].bytes().to_vec());
+        assert_eq!(ida.restore(&[short]), Err(TinySsError::LengthMismatch));
+        assert_eq!(Ida::new(3, 2).unwrap_err(), TinySsError::InvalidThreshold);
+        assert_eq!(Ida::new(3, 256).unwrap_err(), TinySsError::InvalidModulus);
+    }
+}
@@ -0,0 +1,235 @@
+//! Information checking after Rabin and Ben-Or (STOC '89).
+//!
+//! For every ordered pair of holders `i` and `j`, the dealer draws a random
+//! `b != 0` and `y`, gives `y` to holder `i` as a tag and the check key
+//! `(b, c = s_i + b * y)` to holder `j`. When `i` later presents its share
+//! to `j`, a forged share needs a tag matching the unknown `b` and passes
+//! with probability `1 / |F|`, while `c` alone is uniform and tells `j`
+//! nothing about `s_i`. No computational assumption is involved.
+
+use crate::{
+    field::{Field, PrimeField},
+    SecretShare, Share, TinySsError,
+};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+/// A holder's share with its tags for, and its keys against, every other
+/// holder, the entries at its own index being unused.
+#[derive(Clone, PartialEq, Eq)]
+pub struct IcShare<V = num_bigint::BigInt> {
+    share: Share<V>,
+    /// tag to present to holder `j` at position `j - 1`
+    tags: Vec<V>,
+    /// key `(b, c)` checking holder `i` at position `i - 1`
+    keys: Vec<(V, V)>,
+}
+
+impl<V: fmt::Debug> fmt::Debug for IcShare<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("IcShare")
+            .field("share", &self.share)
+            .field("tags", crate::debug::secret(&self.tags))
+            .field("keys", crate::debug::secret(&self.keys))
+            .finish()
+    }
+}
+
+impl<V> IcShare<V> {
+    pub fn new(share: Share<V>, tags: Vec<V>, keys: Vec<(V, V)>) -> Self {
+        IcShare { share, tags, keys }
+    }
+
+    pub fn index(&self) -> usize {
+        self.share.index()
+    }
+
+    pub fn share(&self) -> &Share<V> {
+        &self.share
+    }
+
+    /// tag to present along with the share to the holder at `verifier`
+    pub fn tag_for(&self, verifier: usize) -> Option<&V> {
+        self.tags.get(verifier.checked_sub(1)?)
+    }
+
+    pub fn tags(&self) -> &[V] {
+        &self.tags
+    }
+
+    pub fn keys(&self) -> &[(V, V)] {
+        &self.keys
+    }
+}
+
+/// A `t`-of-`n` Shamir scheme whose shares carry information-checking tags.
+#[derive(Clone, Debug)]
+pub struct InformationChecking<F: Field = PrimeField> {
+    scheme: SecretShare<F>,
+}
+
+impl<F: Field> InformationChecking<F> {
+    /// Creates a `t`-of-`n` scheme with `0 < t < n`.
+    pub fn new(t: usize, n: usize, field: F) -> Result<Self, TinySsError> {
+        Ok(InformationChecking {
+            scheme: SecretShare::with_field(t, n, field)?,
+        })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.scheme.t()
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.scheme.n()
+    }
+
+    /// The underlying Shamir scheme.
+    pub fn shamir(&self) -> &SecretShare<F> {
+        &self.scheme
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: F::Elem) -> Result<Vec<IcShare<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let field = self.scheme.field();
+        self.split_with(secret, || field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Splits `secret` drawing the coefficients, tags and keys from
+    /// `sample`, which must return uniformly random field elements.
+    pub fn split_with<S>(
+        &self,
+        secret: F::Elem,
+        mut sample: S,
+    ) -> Result<Vec<IcShare<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let field = self.scheme.field();
+        let n = self.n();
+        let shares = self.scheme.split_with(secret, &mut sample)?;
+        let mut tags = vec![vec![field.zero(); n]; n];
+        let mut keys = vec![vec![(field.zero(), field.zero()); n]; n];
+        for (i, share) in shares.iter().enumerate() {
+            for j in (0..n).filter(|&j| j != i) {
+                // a zero b would hand the share itself to the verifier
+                let b = loop {
+                    let b = sample();
+                    if b != field.zero() {
+                        break b;
+                    }
+                };
+                let y = sample();
+                let c = field.add(share.value(), &field.mul(&b, &y));
+                tags[i][j] = y;
+                keys[j][i] = (b, c);
+            }
+        }
+        Ok(shares
+            .into_iter()
+            .zip(tags)
+            .zip(keys)
+            .map(|((share, tags), keys)| IcShare::new(share, tags, keys))
+            .collect())
+    }
+
+    /// Whether `share` with `tag` passes the check key that `verifier`
+    /// holds for its index.
+    pub fn verify(
+        &self,
+        verifier: &IcShare<F::Elem>,
+        share: &Share<F::Elem>,
+        tag: &F::Elem,
+    ) -> bool {
+        let field = self.scheme.field();
+        let Some((b, c)) = share
+            .index()
+            .checked_sub(1)
+            .filter(|_| share.index() != verifier.index())
+            .and_then(|i| verifier.keys.get(i))
+        else {
+            return false;
+        };
+        field.sub(&field.add(share.value(), &field.mul(b, tag)), c) == field.zero()
+    }
+
+    /// Recovers the secret as `verifier`, from its own share and the shares
+    /// presented by others with their tags for it, failing with
+    /// `InvalidShare` at the first presented share that does not check out.
+    pub fn recover(
+        &self,
+        verifier: &IcShare<F::Elem>,
+        presented: &[(Share<F::Elem>, F::Elem)],
+    ) -> Result<F::Elem, TinySsError> {
+        let mut shares = Vec::with_capacity(presented.len() + 1);
+        shares.push(verifier.share.clone());
+        for (share, tag) in presented {
+            if share.index() == verifier.index() {
+                continue;
+            }
+            if !self.verify(verifier, share, tag) {
+                return Err(TinySsError::InvalidShare {
+                    index: share.index(),
+                });
+            }
+            shares.push(share.clone());
+        }
+        self.scheme.recover(&shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    fn sampler() -> impl FnMut() -> BigInt {
+        let mut next = 3;
+        move || {
+            next = (next * next + 7) % 1613;
+            BigInt::from(next)
+        }
+    }
+
+    #[test]
+    fn information_checking_test() {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        let ic = InformationChecking::new(3, 4, field).unwrap();
+        let shares = ic.split_with(BigInt::from(1234), sampler()).unwrap();
+        #[cfg(not(feature = "danger_debug"))]
+        assert!(alloc::format!("{:?}", shares[0]).contains("[REDACTED]"));
+        for holder in &shares {
+            for verifier in shares.iter().filter(|v| v.index() != holder.index()) {
+                let tag = holder.tag_for(verifier.index()).unwrap();
+                assert!(ic.verify(verifier, holder.share(), tag));
+            }
+        }
+        let presented: Vec<(Share, BigInt)> = shares[1..3]
+            .iter()
+            .map(|s| (s.share().clone(), s.tag_for(1).unwrap().clone()))
+            .collect();
+        assert_eq!(
+            ic.recover(&shares[0], &presented).unwrap(),
+            BigInt::from(1234)
+        );
+
+        // a holder lying about its share is caught
+        let forged = ic
+            .scheme
+            .field()
+            .add(shares[2].share().value(), &BigInt::from(1));
+        let lie = vec![
+            presented[0].clone(),
+            (Share::new(3, forged), presented[1].1.clone()),
+        ];
+        assert_eq!(
+            ic.recover(&shares[0], &lie),
+            Err(TinySsError::InvalidShare { index: 3 })
+        );
+        assert!(!ic.verify(&shares[0], shares[0].share(), &BigInt::from(0)));
+    }
+}
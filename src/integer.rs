@@ -0,0 +1,201 @@
+//! Shamir sharing over the integers, for secrets such as RSA private
+//! exponents that live in groups of unknown order.
+//!
+//! With `delta = n!`, the dealer picks `f(x) = delta * s + a_1 x + ... +
+//! a_{t-1} x^{t-1}` with random non-negative coefficients far larger than
+//! the secret, and share `i` is `f(i)` over `Z`. The coefficients
+//! `delta * l_i(0)` of any `t` holders are integers, so the holders can
+//! combine their shares without ever inverting modulo anything: the
+//! weighted sum is `delta^2 * s`. The slack of `SECURITY_BITS` in the
+//! coefficients bounds what `t - 1` shares leak about the secret.
+
+use crate::{crt::random_below, poly, Share, TinySsError};
+use alloc::{vec, vec::Vec};
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
+
+/// statistical security parameter of the coefficients
+pub const SECURITY_BITS: u64 = 128;
+
+/// A `t`-of-`n` sharing of non-negative integers below `2^secret_bits`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IntegerSharing {
+    /// threshold
+    t: usize,
+    /// total number of shares
+    n: usize,
+    /// bit length bound of the secrets
+    secret_bits: u64,
+    /// n!, which clears the denominators of the Lagrange coefficients
+    delta: BigInt,
+}
+
+impl IntegerSharing {
+    /// Creates a `t`-of-`n` scheme with `0 < t < n`.
+    pub fn new(t: usize, n: usize, secret_bits: u64) -> Result<Self, TinySsError> {
+        if t == 0 || t >= n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        let delta = (1..=n).map(BigInt::from).product();
+        Ok(IntegerSharing {
+            t,
+            n,
+            secret_bits,
+            delta,
+        })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// `n!`, the scaling factor of the secret in the polynomial
+    pub fn delta(&self) -> &BigInt {
+        &self.delta
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: &BigInt) -> Result<Vec<Share>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, &mut |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits `secret`, drawing the coefficients from `fill_random`, which
+    /// must fill the buffer with uniformly random bytes.
+    pub fn split_with(
+        &self,
+        secret: &BigInt,
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<Vec<Share>, TinySsError> {
+        if secret.sign() == Sign::Minus || secret.bits() > self.secret_bits {
+            return Err(TinySsError::SecretOutOfRange);
+        }
+        // coefficients below delta^2 * 2^(secret_bits + SECURITY_BITS)
+        let bound = (&self.delta * &self.delta) << (self.secret_bits + SECURITY_BITS);
+        let mut polynomial = vec![&self.delta * secret];
+        polynomial.extend((1..self.t).map(|_| random_below(&bound, fill_random)));
+        Ok((1..=self.n)
+            .map(|index| {
+                let x = BigInt::from(index);
+                let value = polynomial
+                    .iter()
+                    .rev()
+                    .fold(BigInt::zero(), |acc, a| acc * &x + a);
+                Share::new(index, value)
+            })
+            .collect())
+    }
+
+    /// The integers `delta * l_i(0)` for the holders at `indices`, with
+    /// which threshold protocols can combine shares in the exponent.
+    pub fn lagrange_coefficients(&self, indices: &[usize]) -> Result<Vec<BigInt>, TinySsError> {
+        for (k, &i) in indices.iter().enumerate() {
+            if i == 0 || i > self.n {
+                return Err(TinySsError::InvalidIndex);
+            }
+            if indices[..k].contains(&i) {
+                return Err(TinySsError::ConflictingShares { index: i });
+            }
+        }
+        Ok(indices
+            .iter()
+            .map(|&i| {
+                let (numerator, denominator) = indices
+                    .iter()
+                    .filter(|&&j| j != i)
+                    .fold((self.delta.clone(), BigInt::one()), |(num, den), &j| {
+                        (num * j, den * (BigInt::from(j) - i))
+                    });
+                numerator / denominator
+            })
+            .collect())
+    }
+
+    /// Recovers the secret from at least `t` shares.
+    pub fn recover(&self, shares: &[Share]) -> Result<BigInt, TinySsError> {
+        let unique = poly::distinct_shares(shares, self.t)?;
+        let unique = &unique[..self.t];
+        let indices: Vec<usize> = unique.iter().map(|s| s.index()).collect();
+        let lambdas = self.lagrange_coefficients(&indices)?;
+        let sum: BigInt = unique
+            .iter()
+            .zip(&lambdas)
+            .map(|(s, lambda)| s.value() * lambda)
+            .sum();
+        // the sum is delta^2 * s for consistent shares
+        let scale = &self.delta * &self.delta;
+        let secret = &sum / &scale;
+        if &secret * scale != sum
+            || secret.sign() == Sign::Minus
+            || secret.bits() > self.secret_bits
+        {
+            return Err(TinySsError::SecretOutOfRange);
+        }
+        Ok(secret)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+
+    #[test]
+    fn integer_test() {
+        let integer = IntegerSharing::new(3, 5, 256).unwrap();
+        assert_eq!(integer.delta(), &BigInt::from(120));
+        let secret = (BigInt::one() << 255) + 12345;
+        let shares = integer.split_with(&secret, &mut counter(101)).unwrap();
+        assert_eq!(integer.recover(&shares[2..]).unwrap(), secret);
+        assert_eq!(integer.recover(&shares[..3]).unwrap(), secret);
+        assert_eq!(
+            integer.split_with(&(BigInt::one() << 256), &mut counter(101)),
+            Err(TinySsError::SecretOutOfRange)
+        );
+        assert_eq!(
+            integer.split_with(&BigInt::from(-1), &mut counter(101)),
+            Err(TinySsError::SecretOutOfRange)
+        );
+    }
+
+    #[test]
+    fn lagrange_coefficients_test() {
+        let integer = IntegerSharing::new(2, 3, 8).unwrap();
+        // delta = 6, l_1(0) = 3/2 and l_3(0) = -1/2 for the holders 1 and 3
+        assert_eq!(
+            integer.lagrange_coefficients(&[1, 3]).unwrap(),
+            [BigInt::from(9), BigInt::from(-3)]
+        );
+        assert_eq!(
+            integer.lagrange_coefficients(&[1, 4]),
+            Err(TinySsError::InvalidIndex)
+        );
+
+        // combining in the exponent: y^(delta^2 s) from y^(s_i) alone
+        let shares = integer
+            .split_with(&BigInt::from(200), &mut counter(101))
+            .unwrap();
+        let (y, m) = (BigInt::from(5), BigInt::from(1_000_003));
+        let lambdas = integer.lagrange_coefficients(&[1, 3]).unwrap();
+        let combined = [&shares[0], &shares[2]].iter().zip(&lambdas).fold(
+            BigInt::one(),
+            |acc, (s, lambda)| {
+                let e = s.value() * lambda;
+                let base = if e.sign() == Sign::Minus {
+                    y.modinv(&m).unwrap()
+                } else {
+                    y.clone()
+                };
+                acc * base.modpow(&e.magnitude().clone().into(), &m) % &m
+            },
+        );
+        assert_eq!(combined, y.modpow(&BigInt::from(36 * 200), &m));
+    }
+}
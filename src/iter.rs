@@ -0,0 +1,107 @@
+//! Lazy generation of shares.
+//!
+//! `split` evaluates the polynomial at all `n` indices before returning.
+//! `split_iter` draws the polynomial up front but evaluates it only as the
+//! shares are taken, so that a dealing with a very large `n` can be handed
+//! out one share at a time without holding all of them. The polynomial is
+//! wiped, with the `zeroize` feature, when the iterator is dropped.
+
+use crate::{field::Field, poly, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+use core::{fmt, ops::Range};
+
+/// The shares of one dealing, evaluated on demand in order of index.
+pub struct ShareIter<'a, F: Field> {
+    scheme: &'a SecretShare<F>,
+    polynomial: Vec<F::Elem>,
+    indices: Range<usize>,
+}
+
+impl<F: Field> fmt::Debug for ShareIter<'_, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ShareIter")
+            .field("polynomial", crate::debug::secret(&self.polynomial))
+            .field("indices", &self.indices)
+            .finish()
+    }
+}
+
+impl<F: Field> Iterator for ShareIter<'_, F> {
+    type Item = Share<F::Elem>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.indices.next()?;
+        let x = self.scheme.field().elem_from_index(index);
+        let value = poly::evaluate(self.scheme.field(), &self.polynomial, &x);
+        Some(Share::new(index, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.indices.size_hint()
+    }
+}
+
+impl<F: Field> ExactSizeIterator for ShareIter<'_, F> {}
+
+impl<F: Field> Drop for ShareIter<'_, F> {
+    fn drop(&mut self) {
+        self.scheme.wipe(&mut self.polynomial);
+    }
+}
+
+impl<F: Field> SecretShare<F> {
+    #[cfg(feature = "rand")]
+    pub fn split_iter(&self, secret: F::Elem) -> Result<ShareIter<'_, F>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_iter_with(secret, || {
+            self.field().random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Draws the coefficients from `sample` as `split_with` does and returns
+    /// the shares at `1..=n` as an iterator evaluating each when taken.
+    pub fn split_iter_with<S>(
+        &self,
+        secret: F::Elem,
+        sample: S,
+    ) -> Result<ShareIter<'_, F>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        Ok(ShareIter {
+            scheme: self,
+            polynomial: self.random_polynomial(secret, sample),
+            indices: 1..self.n() + 1,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn split_iter_test() {
+        let ss = SecretShare::new(2, 1000, BigInt::from(65537)).unwrap();
+        let mut shares = ss
+            .split_iter_with(BigInt::from(1000), || BigInt::from(5))
+            .unwrap();
+        assert_eq!(shares.len(), 1000);
+        let first: Vec<Share> = shares.by_ref().take(2).collect();
+        assert_eq!(first[1], Share::new(2, BigInt::from(1010)));
+        assert_eq!(shares.len(), 998);
+        assert_eq!(shares.last(), Some(Share::new(1000, BigInt::from(6000))));
+        assert_eq!(ss.recover(&first).unwrap(), BigInt::from(1000));
+
+        let lazy: Vec<Share> = ss
+            .split_iter_with(BigInt::from(7), || BigInt::from(3))
+            .unwrap()
+            .collect();
+        assert_eq!(
+            lazy,
+            ss.split_with(BigInt::from(7), || BigInt::from(3)).unwrap()
+        );
+    }
+}
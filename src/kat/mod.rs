@@ -0,0 +1,400 @@
+//! Known-answer test vectors for implementations of the scheme.
+//!
+//! A vector fixes a prime field, a threshold, a secret and the other
+//! coefficients of the dealing polynomial, and lists the shares they give,
+//! each with its `Share::to_bytes` encoding, along with sets of share
+//! indices that must recover the secret. `vectors` returns those shipped
+//! with the crate, which reimplementations can check themselves against;
+//! `Vector::generate` writes new ones.
+//!
+//! Vectors are stored as text, one `key = value` pair per line, with
+//! numbers other than counts and indices in lowercase hex:
+//!
+//! ```text
+//! name = 2-of-3 over 1613
+//! p = 64d
+//! t = 2
+//! n = 3
+//! secret = 4d2
+//! coefficients = a6
+//! share = 1 578 545353010000000000000001000000020578
+//! share = 2 61e 54535301000000000000000200000002061e
+//! share = 3 77 5453530100000000000000030000000177
+//! recover = 1 2
+//! recover = 2 3
+//! ```
+//!
+//! Every vector starts with its `name`; `coefficients` lists the terms of
+//! degree 1 to `t - 1` and is empty for `t = 1`. Blank lines and lines
+//! starting with `#` are ignored.
+
+use crate::{field::Field, DecodeError, SecretShare, Share, TinySsError};
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+use num_bigint::BigInt;
+
+/// The vectors shipped with the crate.
+const VECTORS: &str = include_str!("vectors.txt");
+
+/// One dealing with its expected shares and recoveries.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Vector {
+    name: String,
+    p: BigInt,
+    t: usize,
+    n: usize,
+    secret: BigInt,
+    /// terms of degree 1 to t - 1
+    coefficients: Vec<BigInt>,
+    shares: Vec<Share>,
+    /// index sets that recover the secret
+    recoveries: Vec<Vec<usize>>,
+}
+
+/// Where an implementation departs from a vector.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Mismatch {
+    /// the parameters describe no scheme
+    Scheme(TinySsError),
+    /// the share at `index` has another value or encoding
+    Share { index: usize },
+    /// the shares at `indices` do not recover the secret
+    Recovery { indices: Vec<usize> },
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Mismatch::Scheme(err) => write!(f, "{}", err),
+            Mismatch::Share { index } => write!(f, "share {} differs", index),
+            Mismatch::Recovery { indices } => {
+                write!(f, "shares {:?} do not recover the secret", indices)
+            }
+        }
+    }
+}
+
+impl Vector {
+    /// Deals `secret` under `scheme` with the given coefficients of degree 1
+    /// to `t - 1`, recording all `n` shares and the recoveries from the
+    /// first and from the last `t` of them.
+    pub fn generate(
+        name: &str,
+        scheme: &SecretShare,
+        secret: BigInt,
+        coefficients: &[BigInt],
+    ) -> Result<Self, TinySsError> {
+        let shares = scheme.split_with_coefficients(secret.clone(), coefficients)?;
+        let (t, n) = (scheme.t(), scheme.n());
+        let mut recoveries = Vec::from([(1..=t).collect::<Vec<usize>>()]);
+        if t < n {
+            recoveries.push((n - t + 1..=n).collect());
+        }
+        Ok(Vector {
+            name: name.to_string(),
+            p: scheme.p().clone(),
+            t,
+            n,
+            secret: scheme.field().canonical(&secret),
+            coefficients: coefficients
+                .iter()
+                .map(|c| scheme.field().canonical(c))
+                .collect(),
+            shares,
+            recoveries,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn p(&self) -> &BigInt {
+        &self.p
+    }
+
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn secret(&self) -> &BigInt {
+        &self.secret
+    }
+
+    /// terms of degree 1 to `t - 1`
+    pub fn coefficients(&self) -> &[BigInt] {
+        &self.coefficients
+    }
+
+    /// expected shares, by index
+    pub fn shares(&self) -> &[Share] {
+        &self.shares
+    }
+
+    /// index sets that recover the secret
+    pub fn recoveries(&self) -> &[Vec<usize>] {
+        &self.recoveries
+    }
+
+    /// Checks this crate against the vector: the dealing must give the
+    /// listed shares, and every listed set of them must recover the secret.
+    pub fn check(&self) -> Result<(), Mismatch> {
+        let scheme = SecretShare::new(self.t, self.n, self.p.clone()).map_err(Mismatch::Scheme)?;
+        let shares = scheme
+            .split_with_coefficients(self.secret.clone(), &self.coefficients)
+            .map_err(Mismatch::Scheme)?;
+        for (expected, share) in self.shares.iter().zip(&shares) {
+            if expected != share {
+                return Err(Mismatch::Share {
+                    index: expected.index(),
+                });
+            }
+        }
+        if self.shares.len() != shares.len() {
+            return Err(Mismatch::Share {
+                index: self.shares.len().min(shares.len()) + 1,
+            });
+        }
+        for indices in &self.recoveries {
+            let subset: Vec<Share> = self
+                .shares
+                .iter()
+                .filter(|share| indices.contains(&share.index()))
+                .cloned()
+                .collect();
+            if scheme.recover(&subset).as_ref() != Ok(&self.secret) {
+                return Err(Mismatch::Recovery {
+                    indices: indices.clone(),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Writes the vector in the text format, ending in a newline.
+impl fmt::Display for Vector {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "name = {}", self.name)?;
+        writeln!(f, "p = {}", self.p.to_str_radix(16))?;
+        writeln!(f, "t = {}", self.t)?;
+        writeln!(f, "n = {}", self.n)?;
+        writeln!(f, "secret = {}", self.secret.to_str_radix(16))?;
+        write!(f, "coefficients =")?;
+        for coefficient in &self.coefficients {
+            write!(f, " {}", coefficient.to_str_radix(16))?;
+        }
+        writeln!(f)?;
+        for share in &self.shares {
+            writeln!(
+                f,
+                "share = {} {} {}",
+                share.index(),
+                share.value().to_str_radix(16),
+                share.to_hex()
+            )?;
+        }
+        for indices in &self.recoveries {
+            writeln!(
+                f,
+                "recover = {}",
+                join(indices.iter().map(usize::to_string))
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The vectors shipped with the crate.
+pub fn vectors() -> Vec<Vector> {
+    parse(VECTORS).expect("well-formed shipped vectors")
+}
+
+/// Writes `vectors` in the text format, separated by blank lines.
+pub fn render(vectors: &[Vector]) -> String {
+    join_with(vectors.iter().map(Vector::to_string), "\n")
+}
+
+/// Parses vectors in the text format. A malformed or missing line fails
+/// with `MalformedVector` and its number, counting from 1; shares whose
+/// encoding disagrees with their index and value are malformed too.
+pub fn parse(text: &str) -> Result<Vec<Vector>, DecodeError> {
+    let mut vectors: Vec<Vector> = Vec::new();
+    // line at which the vector being read started
+    let mut start = 0;
+    for (number, line) in text
+        .lines()
+        .enumerate()
+        .map(|(i, line)| (i + 1, line.trim()))
+    {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let malformed = DecodeError::MalformedVector { line: number };
+        let (key, value) = line.split_once('=').ok_or(malformed.clone())?;
+        let (key, value) = (key.trim(), value.trim());
+        if key == "name" {
+            if let Some(last) = vectors.last() {
+                complete(last, start)?;
+            }
+            vectors.push(Vector {
+                name: value.to_string(),
+                p: BigInt::default(),
+                t: 0,
+                n: 0,
+                secret: BigInt::default(),
+                coefficients: Vec::new(),
+                shares: Vec::new(),
+                recoveries: Vec::new(),
+            });
+            start = number;
+            continue;
+        }
+        let vector = vectors.last_mut().ok_or(malformed.clone())?;
+        let hex = |s: &str| BigInt::parse_bytes(s.as_bytes(), 16).ok_or(malformed.clone());
+        let count = |s: &str| s.parse::<usize>().map_err(|_| malformed.clone());
+        match key {
+            "p" => vector.p = hex(value)?,
+            "t" => vector.t = count(value)?,
+            "n" => vector.n = count(value)?,
+            "secret" => vector.secret = hex(value)?,
+            "coefficients" => {
+                vector.coefficients = value
+                    .split_whitespace()
+                    .map(hex)
+                    .collect::<Result<_, _>>()?
+            }
+            "share" => {
+                let fields: Vec<&str> = value.split_whitespace().collect();
+                let [index, share_value, encoding] = fields[..] else {
+                    return Err(malformed);
+                };
+                let share = Share::new(count(index)?, hex(share_value)?);
+                if Share::from_hex(encoding).as_ref() != Ok(&share) {
+                    return Err(malformed);
+                }
+                vector.shares.push(share);
+            }
+            "recover" => {
+                let indices = value
+                    .split_whitespace()
+                    .map(count)
+                    .collect::<Result<_, _>>()?;
+                vector.recoveries.push(indices);
+            }
+            _ => return Err(malformed),
+        }
+    }
+    if let Some(last) = vectors.last() {
+        complete(last, start)?;
+    }
+    Ok(vectors)
+}
+
+// A vector read up to its end must have had every parameter line
+fn complete(vector: &Vector, start: usize) -> Result<(), DecodeError> {
+    if vector.p == BigInt::default() || vector.t == 0 || vector.n == 0 || vector.shares.is_empty() {
+        return Err(DecodeError::MalformedVector { line: start });
+    }
+    Ok(())
+}
+
+fn join(items: impl Iterator<Item = String>) -> String {
+    join_with(items, " ")
+}
+
+fn join_with(items: impl Iterator<Item = String>, separator: &str) -> String {
+    let mut joined = String::new();
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            joined.push_str(separator);
+        }
+        joined.push_str(&item);
+    }
+    joined
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::presets;
+
+    // The definitions of the shipped vectors
+    fn generated() -> Vec<Vector> {
+        let order = presets::modulus(presets::SECP256K1_ORDER);
+        let mersenne127 = SecretShare::mersenne127(3, 5).unwrap();
+        let m127 = mersenne127.p().clone();
+        [
+            (
+                "2-of-3 over 1613",
+                SecretShare::new(2, 3, BigInt::from(1613)).unwrap(),
+                BigInt::from(1234),
+                Vec::from([BigInt::from(166)]),
+            ),
+            (
+                "1-of-2 over 1613",
+                SecretShare::new(1, 2, BigInt::from(1613)).unwrap(),
+                BigInt::from(42),
+                Vec::new(),
+            ),
+            (
+                "3-of-5 over 2^127 - 1",
+                mersenne127,
+                &m127 - 2u32,
+                Vec::from([&m127 - 1u32, BigInt::from(1) << 100]),
+            ),
+            (
+                "4-of-7 over the secp256k1 order",
+                SecretShare::secp256k1_order(4, 7).unwrap(),
+                &order - 12345u32,
+                Vec::from([&order - 1u32, BigInt::from(7), &order >> 1]),
+            ),
+            (
+                "3-of-4 over the ed25519 order",
+                SecretShare::ed25519(3, 4).unwrap(),
+                BigInt::from(0),
+                Vec::from([BigInt::from(1), BigInt::from(2)]),
+            ),
+        ]
+        .into_iter()
+        .map(|(name, ss, secret, coefficients)| {
+            Vector::generate(name, &ss, secret, &coefficients).unwrap()
+        })
+        .collect()
+    }
+
+    #[test]
+    fn vectors_test() {
+        let shipped = vectors();
+        assert_eq!(shipped, generated());
+        assert_eq!(render(&shipped), VECTORS);
+        assert!(shipped.iter().all(|vector| vector.check().is_ok()));
+
+        let mut wrong = shipped[0].clone();
+        wrong.shares[1] = Share::new(2, BigInt::from(1));
+        assert_eq!(wrong.check(), Err(Mismatch::Share { index: 2 }));
+        wrong.shares[1] = shipped[0].shares[1].clone();
+        wrong.secret = BigInt::from(1);
+        assert_eq!(wrong.check(), Err(Mismatch::Share { index: 1 }));
+
+        assert_eq!(
+            parse("name = x\np = 64d\nt = 2\nn = 3\nshare = 1 5 00\n"),
+            Err(DecodeError::MalformedVector { line: 5 })
+        );
+        assert_eq!(
+            parse("name = x\np = 64d\n"),
+            Err(DecodeError::MalformedVector { line: 1 })
+        );
+        assert_eq!(
+            parse("t = 2\n"),
+            Err(DecodeError::MalformedVector { line: 1 })
+        );
+    }
+}
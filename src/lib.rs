@@ -2,157 +2,519 @@
 
 extern crate alloc;
 
+pub mod additive;
+#[cfg(feature = "css")]
+pub mod aont;
+pub mod barycentric;
+pub mod beaver;
+mod bingcd;
+pub mod blakley;
+mod blinding;
+#[cfg(feature = "bls")]
+pub mod bls;
+pub mod bundle;
+pub mod chunked;
+mod codec;
+pub mod combiner;
+pub mod compartmented;
+pub mod crt;
+#[cfg(feature = "css")]
+pub mod css;
+#[cfg(feature = "crypto-bigint")]
+pub mod ct_field;
+pub mod dealer;
+mod debug;
+pub mod described;
+#[cfg(feature = "vss")]
+pub mod dkg;
+#[cfg(feature = "ecdsa")]
+pub mod ecdsa;
+#[cfg(feature = "vss")]
+pub mod elgamal;
+mod error;
+mod fast;
+#[cfg(feature = "ff")]
+pub mod ff_field;
+pub mod field;
+#[cfg(feature = "vss")]
+pub mod frost;
+pub mod gf256;
+pub mod gfshare;
+#[cfg(feature = "vss")]
+pub mod group;
+pub mod hierarchical;
+mod homomorphic;
+pub mod ida;
+pub mod infocheck;
+pub mod integer;
+pub mod iter;
+pub mod kat;
+#[cfg(feature = "mac")]
+pub mod mac;
+#[cfg(feature = "merkle")]
+pub mod merkle;
+#[cfg(feature = "mnemonic")]
+mod mnemonic;
+pub mod montgomery;
+pub mod packed;
+#[cfg(feature = "vss")]
+pub mod pedersen;
+pub mod points;
+#[cfg(feature = "vss")]
+pub mod pok;
+pub mod policy;
+mod poly;
+pub mod presets;
+pub mod prime;
+#[cfg(feature = "prss")]
+pub mod prss;
+#[cfg(feature = "vss")]
+pub mod pvss;
+mod recovery;
+pub mod replicated;
+mod reshare;
+mod robust;
+mod scheme;
+pub mod secret;
+pub mod secrets_js;
+#[cfg(feature = "serde")]
+mod serde_bigint;
+mod share;
+pub mod sharks;
+#[cfg(feature = "vss")]
+pub mod signing;
+#[cfg(feature = "slip39")]
+pub mod slip39;
+#[cfg(feature = "sss")]
+pub mod sss;
+pub mod ssss;
+#[cfg(test)]
+mod test_util;
+#[cfg(feature = "vss")]
+pub mod transcript;
+pub mod vault;
+#[cfg(feature = "vsss-rs")]
+pub mod vsss_rs;
+pub mod weighted;
+pub mod xor;
+#[cfg(feature = "zeroize")]
+pub mod zeroize;
+
+pub use error::{DecodeError, Inconsistency, TinySsError};
+pub use recovery::{RecoveryMessage, ShareRecovery};
+pub use reshare::Subshare;
+pub use scheme::ThresholdScheme;
+pub use share::Share;
+
 use alloc::{vec, vec::Vec};
-use core::{mem, ops::SubAssign};
-use num_bigint::{BigInt, RandBigInt};
-use num_traits::{One, Zero};
-use rand::thread_rng;
+use field::{Field, PrimeField, SmallPrimeField};
+use num_bigint::BigInt;
+#[cfg(feature = "serde")]
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
 
+/// A `t`-of-`n` Shamir scheme over the field `F`, by default the prime field
+/// of `BigInt`s.
 #[derive(Clone, Debug)]
-pub struct SecretShare {
+pub struct SecretShare<F: Field = PrimeField> {
     /// threshold
-    pub t: usize,
+    t: usize,
     /// total number of shares
-    pub n: usize,
-    /// prime in ff
-    pub p: BigInt,
+    n: usize,
+    /// field the polynomial lives in
+    field: F,
+    /// whether the coefficient of degree `t - 1` must be nonzero
+    full_degree: bool,
+}
+
+// Wire form of `SecretShare`, validated through `SecretShare::new` on
+// deserialize
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct RawSecretShare {
+    t: usize,
+    n: usize,
+    #[serde(with = "serde_bigint")]
+    p: BigInt,
+    #[serde(default, skip_serializing_if = "core::ops::Not::not")]
+    full_degree: bool,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for SecretShare {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawSecretShare {
+            t: self.t,
+            n: self.n,
+            p: self.p().clone(),
+            full_degree: self.full_degree,
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for SecretShare {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawSecretShare::deserialize(deserializer)?;
+        let ss = SecretShare::new(raw.t, raw.n, raw.p).map_err(D::Error::custom)?;
+        Ok(if raw.full_degree {
+            ss.require_full_degree()
+        } else {
+            ss
+        })
+    }
 }
 
 impl SecretShare {
-    pub fn split(&self, secret: BigInt) -> Vec<(usize, BigInt)> {
-        assert!(self.t < self.n);
-        let polynomial = self.sample_polynomial(secret);
-        self.evaluate_polynomial(polynomial)
-    }
-
-    fn sample_polynomial(&self, secret: BigInt) -> Vec<BigInt> {
-        let mut coeff: Vec<BigInt> = vec![secret];
-        let mut rng = thread_rng();
-        let low = BigInt::zero();
-        let high = &self.p - BigInt::one();
-        let random_coeffs: Vec<BigInt> = (0..(self.t - 1))
-            .map(|_| rng.gen_bigint_range(&low, &high))
-            .collect();
-        coeff.extend(random_coeffs);
-        coeff
+    /// Creates a `t`-of-`n` scheme over the prime field of order `p`.
+    ///
+    /// The threshold must satisfy `0 < t < n`, and `p` must be larger than
+    /// `n` so that every share index is a distinct nonzero field element.
+    /// With the `primality` feature, `p` must also pass
+    /// `prime::is_probable_prime`.
+    pub fn new(t: usize, n: usize, p: BigInt) -> Result<Self, TinySsError> {
+        let ss = SecretShare::with_field(t, n, PrimeField::new(p)?)?;
+        prime::check_modulus(ss.p())?;
+        Ok(ss)
     }
 
-    fn evaluate_polynomial(&self, polynomial: Vec<BigInt>) -> Vec<(usize, BigInt)> {
-        (1..=self.n)
-            .map(|x| (x, self.mod_evaluate_at(&polynomial, x)))
-            .collect()
+    /// prime in ff
+    pub fn p(&self) -> &BigInt {
+        self.field.p()
     }
+}
 
-    fn mod_evaluate_at(&self, polynomial: &[BigInt], x: usize) -> BigInt {
-        let x_bigint = BigInt::from(x);
-        polynomial
-            .iter()
-            .rev()
-            .fold(Zero::zero(), |sum, item| (&x_bigint * sum + item) % &self.p)
+impl SecretShare<SmallPrimeField> {
+    /// Creates a `t`-of-`n` scheme over a prime `p < 2^61` using native
+    /// integer arithmetic.
+    pub fn small(t: usize, n: usize, p: u64) -> Result<Self, TinySsError> {
+        let ss = SecretShare::with_field(t, n, SmallPrimeField::new(p)?)?;
+        prime::check_modulus(&BigInt::from(p))?;
+        Ok(ss)
     }
+}
 
-    pub fn recover(&self, shares: &[(usize, BigInt)]) -> BigInt {
-        assert!(shares.len() == self.t, "wrong shares");
-        let (xs, ys): (Vec<usize>, Vec<BigInt>) = shares.iter().cloned().unzip();
-        let result = self.lagrange_interpolation(Zero::zero(), xs, ys);
-        if result < Zero::zero() {
-            result + &self.p
-        } else {
-            result
+impl<F: Field> SecretShare<F> {
+    /// Creates a `t`-of-`n` scheme over `field`, which must have at least
+    /// `n` nonzero elements.
+    pub fn with_field(t: usize, n: usize, field: F) -> Result<Self, TinySsError> {
+        if t == 0 || t >= n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if !field.has_indices(n) {
+            return Err(TinySsError::InvalidModulus);
         }
+        Ok(SecretShare {
+            t,
+            n,
+            field,
+            full_degree: false,
+        })
     }
 
-    // Lagrange interpolation algorithm for polynomial evaluation
-    fn lagrange_interpolation(&self, x: BigInt, xs: Vec<usize>, ys: Vec<BigInt>) -> BigInt {
-        let len = xs.len();
-        // Create a vector of BigInts from a vector of usize
-        let xs_bigint: Vec<BigInt> = xs.iter().map(|x| BigInt::from(*x as i64)).collect();
-        // Iterate over the range of 0 to len
-        (0..len).fold(Zero::zero(), |sum, item| {
-            // Define the numerator
-            let numerator = (0..len).fold(One::one(), |product: BigInt, i| {
-                if i == item {
-                    product
-                } else {
-                    product * (&x - &xs_bigint[i]) % &self.p
+    /// Requires the coefficient of degree `t - 1` to be nonzero, so that the
+    /// polynomial has exactly degree `t - 1` and no `t - 1` shares determine
+    /// it. `split_with` draws that coefficient again until it is nonzero, and
+    /// `split_with_coefficients` rejects a zero one.
+    ///
+    /// The leading coefficient is then uniform over the nonzero elements
+    /// only, which reveals nothing about the secret to fewer than `t`
+    /// holders, as it is independent of the secret.
+    pub fn require_full_degree(mut self) -> Self {
+        self.full_degree = true;
+        self
+    }
+
+    /// whether the coefficient of degree `t - 1` must be nonzero
+    pub fn is_full_degree(&self) -> bool {
+        self.full_degree
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    pub fn field(&self) -> &F {
+        &self.field
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: F::Elem) -> Result<Vec<Share<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, || self.field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Splits `secret` drawing the random coefficients from `sample`, which
+    /// must return uniformly random field elements. A secret outside the
+    /// field is shared as its canonical representative; `split_chunked`
+    /// shares byte strings of any length.
+    pub fn split_with<S>(
+        &self,
+        secret: F::Elem,
+        sample: S,
+    ) -> Result<Vec<Share<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let polynomial = self.random_polynomial(secret, sample);
+        Ok(self.evaluate_polynomial(polynomial))
+    }
+
+    /// Splits `secret` using caller-provided random coefficients for the
+    /// terms of degree `1..t`.
+    pub fn split_with_coefficients(
+        &self,
+        secret: F::Elem,
+        coefficients: &[F::Elem],
+    ) -> Result<Vec<Share<F::Elem>>, TinySsError> {
+        let polynomial = self.polynomial(secret, coefficients)?;
+        Ok(self.evaluate_polynomial(polynomial))
+    }
+
+    // The dealing polynomial, secret first, with the `t - 1` coefficients
+    // drawn from `sample`
+    fn random_polynomial<S>(&self, secret: F::Elem, mut sample: S) -> Vec<F::Elem>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let mut coefficients: Vec<F::Elem> = (0..(self.t - 1)).map(|_| sample()).collect();
+        if self.full_degree {
+            if let Some(leading) = coefficients.last_mut() {
+                while self.is_zero(leading) {
+                    *leading = sample();
                 }
+            }
+        }
+        let polynomial = self
+            .polynomial(secret, &coefficients)
+            .expect("t - 1 coefficients, the leading one nonzero if required");
+        self.wipe(&mut coefficients);
+        polynomial
+    }
+
+    // The dealing polynomial, secret first, with caller-provided coefficients
+    fn polynomial(
+        &self,
+        secret: F::Elem,
+        coefficients: &[F::Elem],
+    ) -> Result<Vec<F::Elem>, TinySsError> {
+        if coefficients.len() != self.t - 1 {
+            return Err(TinySsError::WrongCoefficientCount {
+                expected: self.t - 1,
+                got: coefficients.len(),
             });
-            // Define the denominator
-            let denominator = (0..len).fold(One::one(), |product: BigInt, i| {
-                if i == item {
-                    product
-                } else {
-                    product * (&xs_bigint[item] - &xs_bigint[i]) % &self.p
-                }
+        }
+        if self.full_degree && coefficients.last().is_some_and(|c| self.is_zero(c)) {
+            return Err(TinySsError::ZeroLeadingCoefficient);
+        }
+        let mut polynomial: Vec<F::Elem> = vec![self.field.canonical(&secret)];
+        polynomial.extend(coefficients.iter().map(|c| self.field.canonical(c)));
+        Ok(polynomial)
+    }
+
+    fn evaluate_polynomial(&self, mut polynomial: Vec<F::Elem>) -> Vec<Share<F::Elem>> {
+        let shares = if self.t >= fast::FAST_THRESHOLD {
+            let values = fast::evaluate_consecutive(&self.field, &polynomial, self.n);
+            (1..=self.n).zip(values).map(Share::from).collect()
+        } else {
+            (1..=self.n)
+                .map(|x| {
+                    let x_elem = self.field.elem_from_index(x);
+                    Share::new(x, poly::evaluate(&self.field, &polynomial, &x_elem))
+                })
+                .collect()
+        };
+        self.wipe(&mut polynomial);
+        shares
+    }
+
+    /// Recovers the secret from at least `t` shares.
+    ///
+    /// Shares repeating an index are ignored as long as they carry the same
+    /// value; the first `t` distinct shares are used for interpolation. The
+    /// secret comes back as its canonical representative. Indices that are
+    /// zero or too large for the field to tell apart fail with
+    /// `InvalidIndex`.
+    pub fn recover(&self, shares: &[Share<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        self.recover_at(self.field.zero(), shares)
+    }
+
+    /// Evaluates the dealt polynomial at `x` from at least `t` shares, taken
+    /// as `recover` takes them; at zero this is the secret, at an index the
+    /// share there.
+    pub fn recover_at(
+        &self,
+        x: F::Elem,
+        shares: &[Share<F::Elem>],
+    ) -> Result<F::Elem, TinySsError> {
+        let unique = poly::distinct_field_shares(&self.field, shares, self.t)?;
+        let (xs, ys) = self.interpolation_points(&unique);
+        self.lagrange_interpolation(x, xs, ys)
+    }
+
+    /// The weights `l_i(x)` of the holders at `indices`, with which the
+    /// value of the dealt polynomial at `x` is the sum of their shares
+    /// weighted, so that threshold protocols can combine shares in the
+    /// exponent. They depend on the indices alone; at zero, `t` indices
+    /// give the weights recovering the secret.
+    pub fn lagrange_coefficients_at(
+        &self,
+        x: F::Elem,
+        indices: &[usize],
+    ) -> Result<Vec<F::Elem>, TinySsError> {
+        for (k, &i) in indices.iter().enumerate() {
+            self.check_new_index(i)?;
+            if indices[..k].contains(&i) {
+                return Err(TinySsError::ConflictingShares { index: i });
+            }
+        }
+        let xs: Vec<F::Elem> = indices
+            .iter()
+            .map(|&i| self.field.elem_from_index(i))
+            .collect();
+        poly::lagrange_coefficients(&self.field, &xs, &x)
+    }
+
+    /// Mints the share at a new `index` from at least `t` existing shares,
+    /// so that a holder can be added without dealing again.
+    pub fn share_at(
+        &self,
+        shares: &[Share<F::Elem>],
+        index: usize,
+    ) -> Result<Share<F::Elem>, TinySsError> {
+        self.check_new_index(index)?;
+        let value = self.recover_at(self.field.elem_from_index(index), shares)?;
+        Ok(Share::new(index, value))
+    }
+
+    /// Mints the share at `index` from the `t` coefficients of the dealt
+    /// polynomial, secret first, as kept by the dealer.
+    pub fn share_from_polynomial(
+        &self,
+        polynomial: &[F::Elem],
+        index: usize,
+    ) -> Result<Share<F::Elem>, TinySsError> {
+        if polynomial.len() != self.t {
+            return Err(TinySsError::WrongCoefficientCount {
+                expected: self.t,
+                got: polynomial.len(),
             });
-            // Calculate the sum
-            (sum + numerator * self.mod_inv(denominator) * &ys[item]) % &self.p
-        })
+        }
+        self.check_new_index(index)?;
+        let x = self.field.elem_from_index(index);
+        let mut polynomial: Vec<F::Elem> =
+            polynomial.iter().map(|c| self.field.canonical(c)).collect();
+        let value = poly::evaluate(&self.field, &polynomial, &x);
+        self.wipe(&mut polynomial);
+        Ok(Share::new(index, value))
+    }
+
+    // Indices beyond n must still map to distinct nonzero elements
+    fn check_new_index(&self, index: usize) -> Result<(), TinySsError> {
+        if index == 0 {
+            return Err(TinySsError::InvalidIndex);
+        }
+        if !self.field.has_indices(index) {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(())
     }
 
-    // Computes the modular inverse of a mod p
-    #[inline]
-    fn mod_inv(&self, a: BigInt) -> BigInt {
-        let m = self.p.clone();
-        let num = if a < Zero::zero() { a + &self.p } else { a };
-        let (g, x, _) = SecretShare::xgcd(num, m);
-        assert!(g.is_one());
-        (x + &self.p) % &self.p
-    }
-
-    // The extended Euclidean algorithm.
-    // Compute the greatest common divisor of a and b, as well as the
-    // coefficients sj and tj such that sj * a + tj * b = gcd(a, b).
-    fn xgcd(a: BigInt, b: BigInt) -> (BigInt, BigInt, BigInt) {
-        let (mut sj, mut sj_last) = (BigInt::zero(), BigInt::one());
-        let (mut tj, mut tj_last) = (BigInt::one(), BigInt::zero());
-        let (mut rj, mut rj_last) = (b, a);
-
-        while !rj.is_zero() {
-            let quotient = &rj_last / &rj;
-            rj_last.sub_assign(&quotient * &rj);
-            sj_last.sub_assign(&quotient * &sj);
-            tj_last.sub_assign(&quotient * &tj);
-            mem::swap(&mut rj, &mut rj_last);
-            mem::swap(&mut sj, &mut sj_last);
-            mem::swap(&mut tj, &mut tj_last);
+    /// Checks that all given shares lie on a single polynomial of degree
+    /// `t - 1`.
+    ///
+    /// The polynomial is interpolated from the first `t` distinct shares and
+    /// every remaining share is evaluated against it.
+    pub fn verify_consistent(&self, shares: &[Share<F::Elem>]) -> Result<(), Inconsistency> {
+        let unique = poly::distinct_field_shares(&self.field, shares, self.t)?;
+        let (xs, ys) = self.interpolation_points(&unique);
+        let mut indices = Vec::new();
+        for share in &unique[self.t..] {
+            let x = self.field.elem_from_index(share.index());
+            let expected = self.lagrange_interpolation(x, xs.clone(), ys.clone())?;
+            if expected != self.field.canonical(share.value()) {
+                indices.push(share.index());
+            }
         }
-        (rj_last, sj_last, tj_last)
+        if indices.is_empty() {
+            Ok(())
+        } else {
+            Err(Inconsistency::OffPolynomial { indices })
+        }
+    }
+
+    // Splits the first t shares into x and y coordinates, the latter
+    // canonical
+    fn interpolation_points(&self, shares: &[&Share<F::Elem>]) -> (Vec<usize>, Vec<F::Elem>) {
+        shares
+            .iter()
+            .take(self.t)
+            .map(|share| (share.index(), self.field.canonical(share.value())))
+            .unzip()
+    }
+
+    // Lagrange interpolation algorithm for polynomial evaluation
+    fn lagrange_interpolation(
+        &self,
+        x: F::Elem,
+        xs: Vec<usize>,
+        ys: Vec<F::Elem>,
+    ) -> Result<F::Elem, TinySsError> {
+        let mut ys = ys;
+        let xs: Vec<F::Elem> = xs.iter().map(|x| self.field.elem_from_index(*x)).collect();
+        let value = poly::interpolate(&self.field, &xs, &ys, &x);
+        self.wipe(&mut ys);
+        value
+    }
+
+    fn is_zero(&self, a: &F::Elem) -> bool {
+        self.field.canonical(a) == self.field.zero()
+    }
+
+    // Overwrites secret-dependent scratch values once they are used up
+    fn wipe(&self, values: &mut [F::Elem]) {
+        #[cfg(feature = "zeroize")]
+        values.iter_mut().for_each(|v| self.field.zeroize(v));
+        #[cfg(not(feature = "zeroize"))]
+        let _ = values;
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    #[cfg(feature = "rand")]
     use num_traits::FromPrimitive;
 
+    #[cfg(feature = "rand")]
     fn get<T: FromPrimitive>(x: i32) -> T {
         T::from_i32(x).unwrap()
     }
 
+    #[cfg(feature = "rand")]
     #[test]
     fn test_lagrange() {
-        let ss = SecretShare {
-            t: 2,
-            n: 3,
-            p: BigInt::from(7),
-        };
+        let ss = SecretShare::new(2, 3, BigInt::from(7)).unwrap();
 
-        let shares = ss.split(BigInt::from(2));
+        let shares = ss.split(BigInt::from(2)).unwrap();
 
-        let (xs, ys): (Vec<usize>, Vec<BigInt>) = shares.iter().cloned().unzip();
-        let result = ss.lagrange_interpolation(BigInt::from(0), xs, ys);
+        let (xs, ys): (Vec<usize>, Vec<BigInt>) =
+            shares.into_iter().map(<(usize, BigInt)>::from).unzip();
+        let result = ss.lagrange_interpolation(BigInt::from(0), xs, ys).unwrap();
 
         assert_eq!(result, BigInt::from(2));
     }
 
     #[test]
     fn recover_test() {
-        let ss = SecretShare {
-            t: 3,
-            n: 6,
-            p: BigInt::from(1613),
-        };
+        let ss = SecretShare::new(3, 6, BigInt::from(1613)).unwrap();
 
         let shares = ss.evaluate_polynomial(vec![
             BigInt::from(1234),
@@ -163,49 +525,331 @@ mod tests {
         assert_eq!(
             shares,
             [
-                (1, BigInt::from(1494)),
-                (2, BigInt::from(329)),
-                (3, BigInt::from(965)),
-                (4, BigInt::from(176)),
-                (5, BigInt::from(1188)),
-                (6, BigInt::from(775)),
+                Share::new(1, BigInt::from(1494)),
+                Share::new(2, BigInt::from(329)),
+                Share::new(3, BigInt::from(965)),
+                Share::new(4, BigInt::from(176)),
+                Share::new(5, BigInt::from(1188)),
+                Share::new(6, BigInt::from(775)),
             ]
         );
-        let r = ss.recover(&[
-            (1, BigInt::from(1494)),
-            (2, BigInt::from(329)),
-            (3, BigInt::from(965)),
-        ]);
+        let r = ss
+            .recover(&[
+                Share::new(1, BigInt::from(1494)),
+                Share::new(2, BigInt::from(329)),
+                Share::new(3, BigInt::from(965)),
+            ])
+            .unwrap();
         assert_eq!(r, BigInt::from(1234))
     }
 
+    #[cfg(feature = "rand")]
     #[test]
     fn large_parime_test() {
-        let ss = SecretShare {
-            t: 3,
-            n: 6,
-            p: BigInt::parse_bytes(
-                b"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
-                16,
-            )
-            .unwrap(),
-        };
+        let p = BigInt::parse_bytes(
+            b"fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f",
+            16,
+        )
+        .unwrap();
+        let ss = SecretShare::new(3, 6, p).unwrap();
         let secret = BigInt::parse_bytes(b"ffffffffffffffffffffffffffffffffffffff", 16).unwrap();
-        let shares = ss.split(secret.clone());
-        assert_eq!(secret, ss.recover(&shares[0..ss.t]));
+        let shares = ss.split(secret.clone()).unwrap();
+        assert_eq!(secret, ss.recover(&shares[0..ss.t()]).unwrap());
     }
 
+    #[cfg(feature = "rand")]
     #[test]
     fn diffferent_type_split_test() {
-        let ss = SecretShare {
-            t: 3,
-            n: 4,
-            p: BigInt::from(11),
-        };
+        let ss = SecretShare::new(3, 4, BigInt::from(11)).unwrap();
 
         let secret: i32 = 4;
-        let shares = ss.split(get(secret));
+        let shares = ss.split(get(secret)).unwrap();
+
+        assert_eq!(
+            BigInt::from(secret),
+            ss.recover(&shares[0..ss.t()]).unwrap()
+        );
+    }
+
+    #[test]
+    fn split_with_test() {
+        let ss = SecretShare::new(3, 6, BigInt::from(1613)).unwrap();
+        let shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+        assert_eq!(shares[0], Share::new(1, BigInt::from(1494)));
+        assert_eq!(ss.recover(&shares[3..6]).unwrap(), BigInt::from(1234));
+        // out-of-range secrets and coefficients deal the same shares
+        let wrapped = ss
+            .split_with_coefficients(
+                BigInt::from(1234 - 1613),
+                &[BigInt::from(166 + 1613), BigInt::from(94)],
+            )
+            .unwrap();
+        assert_eq!(wrapped, shares);
+
+        let mut coefficients = vec![BigInt::from(94), BigInt::from(166)];
+        let shares = ss
+            .split_with(BigInt::from(1234), || coefficients.pop().unwrap())
+            .unwrap();
+        assert_eq!(shares[5], Share::new(6, BigInt::from(775)));
+    }
+
+    #[test]
+    fn full_degree_test() {
+        let ss = SecretShare::new(3, 6, BigInt::from(1613))
+            .unwrap()
+            .require_full_degree();
+        // p reduces to a zero leading coefficient as well
+        assert_eq!(
+            ss.split_with_coefficients(
+                BigInt::from(1234),
+                &[BigInt::from(166), BigInt::from(1613)]
+            ),
+            Err(TinySsError::ZeroLeadingCoefficient)
+        );
+        let mut draws = vec![
+            BigInt::from(94),
+            BigInt::from(0),
+            BigInt::from(0),
+            BigInt::from(166),
+        ];
+        let shares = ss
+            .split_with(BigInt::from(1234), || draws.pop().unwrap())
+            .unwrap();
+        assert!(draws.is_empty());
+        assert_eq!(shares[5], Share::new(6, BigInt::from(775)));
+
+        // the threshold 1 has no coefficient to constrain
+        let ss = SecretShare::new(1, 3, BigInt::from(1613))
+            .unwrap()
+            .require_full_degree();
+        assert_eq!(
+            ss.split_with_coefficients(BigInt::from(5), &[]).unwrap()[2],
+            Share::new(3, BigInt::from(5))
+        );
+    }
+
+    #[test]
+    fn recover_more_shares_test() {
+        let ss = SecretShare::new(3, 6, BigInt::from(1613)).unwrap();
+        let shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+        assert_eq!(ss.recover(&shares).unwrap(), BigInt::from(1234));
+
+        let duplicated = [
+            shares[0].clone(),
+            shares[0].clone(),
+            shares[1].clone(),
+            shares[4].clone(),
+        ];
+        assert_eq!(ss.recover(&duplicated).unwrap(), BigInt::from(1234));
+
+        let not_enough = [shares[0].clone(), shares[0].clone(), shares[1].clone()];
+        assert_eq!(
+            ss.recover(&not_enough),
+            Err(TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            })
+        );
+
+        let conflicting = [
+            shares[0].clone(),
+            shares[1].clone(),
+            Share::new(1, BigInt::from(1)),
+            shares[2].clone(),
+        ];
+        assert_eq!(
+            ss.recover(&conflicting),
+            Err(TinySsError::ConflictingShares { index: 1 })
+        );
+    }
+
+    #[test]
+    fn share_at_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        let polynomial = [BigInt::from(1234), BigInt::from(166), BigInt::from(94)];
+        let shares = ss
+            .split_with_coefficients(polynomial[0].clone(), &polynomial[1..])
+            .unwrap();
+        let minted = ss.share_at(&shares[2..], 7).unwrap();
+        assert_eq!(minted, ss.share_from_polynomial(&polynomial, 7).unwrap());
+        assert_eq!(ss.share_at(&shares[..3], 4).unwrap(), shares[3]);
+
+        // 1234 + 166 * 1612 + 94 * 1612^2 = 1234 - 166 + 94 (mod 1613)
+        assert_eq!(
+            ss.recover_at(BigInt::from(-1), &shares[1..4]).unwrap(),
+            BigInt::from(1162)
+        );
+        assert_eq!(
+            ss.recover_at(BigInt::from(0), &shares[..3]).unwrap(),
+            ss.recover(&shares[2..]).unwrap()
+        );
+
+        let weights = ss
+            .lagrange_coefficients_at(BigInt::from(0), &[5, 2, 3])
+            .unwrap();
+        let weighted = [&shares[4], &shares[1], &shares[2]]
+            .iter()
+            .zip(&weights)
+            .fold(BigInt::from(0), |sum, (share, weight)| {
+                (sum + share.value() * weight) % 1613
+            });
+        assert_eq!(weighted, BigInt::from(1234));
+        assert_eq!(
+            ss.lagrange_coefficients_at(BigInt::from(0), &[1, 2, 1]),
+            Err(TinySsError::ConflictingShares { index: 1 })
+        );
+        assert_eq!(
+            ss.lagrange_coefficients_at(BigInt::from(0), &[0, 2]),
+            Err(TinySsError::InvalidIndex)
+        );
+
+        let mut extended = shares[..2].to_vec();
+        extended.push(minted);
+        assert_eq!(ss.recover(&extended).unwrap(), BigInt::from(1234));
 
-        assert_eq!(BigInt::from(secret), ss.recover(&shares[0..ss.t]));
+        assert_eq!(
+            ss.share_at(&shares, 0).unwrap_err(),
+            TinySsError::InvalidIndex
+        );
+        assert_eq!(
+            ss.share_at(&shares, 1613).unwrap_err(),
+            TinySsError::InvalidModulus
+        );
+        assert_eq!(
+            ss.share_from_polynomial(&polynomial[1..], 7).unwrap_err(),
+            TinySsError::WrongCoefficientCount {
+                expected: 3,
+                got: 2
+            }
+        );
+    }
+
+    #[test]
+    fn verify_consistent_test() {
+        let ss = SecretShare::new(3, 6, BigInt::from(1613)).unwrap();
+        let mut shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+        assert_eq!(ss.verify_consistent(&shares), Ok(()));
+        assert_eq!(ss.verify_consistent(&shares[1..4]), Ok(()));
+
+        shares[4] = Share::new(5, BigInt::from(1));
+        assert_eq!(
+            ss.verify_consistent(&shares),
+            Err(Inconsistency::OffPolynomial { indices: vec![5] })
+        );
+        assert_eq!(
+            ss.verify_consistent(&shares[0..2]),
+            Err(Inconsistency::Unusable(TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let ss = SecretShare::new(3, 6, BigInt::from(1613)).unwrap();
+        let json = serde_json::to_string(&ss).unwrap();
+        assert_eq!(json, r#"{"t":3,"n":6,"p":"64d"}"#);
+        let decoded: SecretShare = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded.p(), ss.p());
+        assert!(serde_json::from_str::<SecretShare>(r#"{"t":6,"n":3,"p":"64d"}"#).is_err());
+        let json = serde_json::to_string(&ss.require_full_degree()).unwrap();
+        assert_eq!(json, r#"{"t":3,"n":6,"p":"64d","full_degree":true}"#);
+        assert!(serde_json::from_str::<SecretShare>(&json)
+            .unwrap()
+            .is_full_degree());
+    }
+
+    // the same dealing round-trips through every field backend
+    fn round_trip<F: Field>(ss: SecretShare<F>, secret: F::Elem, coefficients: &[F::Elem]) {
+        let shares = ss
+            .split_with_coefficients(secret.clone(), coefficients)
+            .unwrap();
+        assert_eq!(shares.len(), ss.n());
+        assert_eq!(ss.recover(&shares).unwrap(), secret);
+        assert_eq!(ss.recover(&shares[ss.n() - ss.t()..]).unwrap(), secret);
+        assert_eq!(ss.verify_consistent(&shares), Ok(()));
+    }
+
+    #[test]
+    fn field_backends_test() {
+        let p = BigInt::from(1613);
+        round_trip(
+            SecretShare::new(3, 6, p).unwrap(),
+            BigInt::from(1234),
+            &[BigInt::from(166), BigInt::from(94)],
+        );
+        round_trip(
+            SecretShare::with_field(3, 255, gf256::Gf256).unwrap(),
+            0x42,
+            &[0x17, 0xe9],
+        );
+        round_trip(
+            SecretShare::small(3, 6, (1 << 61) - 1).unwrap(),
+            1 << 60,
+            &[166, 94],
+        );
+        assert_eq!(
+            SecretShare::with_field(3, 256, gf256::Gf256).unwrap_err(),
+            TinySsError::InvalidModulus
+        );
+    }
+
+    #[test]
+    fn error_test() {
+        assert_eq!(
+            SecretShare::new(3, 3, BigInt::from(11)).unwrap_err(),
+            TinySsError::InvalidThreshold
+        );
+        assert_eq!(
+            SecretShare::new(0, 3, BigInt::from(11)).unwrap_err(),
+            TinySsError::InvalidThreshold
+        );
+        assert_eq!(
+            SecretShare::new(2, 7, BigInt::from(7)).unwrap_err(),
+            TinySsError::InvalidModulus
+        );
+        #[cfg(feature = "primality")]
+        {
+            assert_eq!(
+                SecretShare::new(2, 7, BigInt::from(561)).unwrap_err(),
+                TinySsError::CompositeModulus
+            );
+            assert_eq!(
+                SecretShare::small(2, 7, 1613 * 1619).unwrap_err(),
+                TinySsError::CompositeModulus
+            );
+        }
+
+        let ss = SecretShare::new(2, 3, BigInt::from(11)).unwrap();
+        assert_eq!(
+            ss.split_with_coefficients(BigInt::from(4), &[]),
+            Err(TinySsError::WrongCoefficientCount {
+                expected: 1,
+                got: 0
+            })
+        );
+        let shares = ss
+            .split_with_coefficients(BigInt::from(4), &[BigInt::from(5)])
+            .unwrap();
+        assert_eq!(
+            ss.recover(&shares[0..1]),
+            Err(TinySsError::NotEnoughShares {
+                required: 2,
+                got: 1
+            })
+        );
+        // an index that reduces onto another field element
+        assert_eq!(
+            ss.recover(&[shares[0].clone(), Share::new(12, shares[0].value().clone())]),
+            Err(TinySsError::InvalidIndex)
+        );
     }
 }
@@ -0,0 +1,228 @@
+//! Per-share authentication tags and a salted digest of the secret.
+//!
+//! The dealer tags every share with HMAC-SHA256 over its index and encoded
+//! value, under a verification key of its choice. The combiner checks the
+//! tags before interpolating, so a corrupted or forged share fails recovery
+//! instead of yielding a wrong secret. Anyone holding the key can forge
+//! tags, so it must stay with the combiner, or be split separately, for
+//! example with `Gf256Sharing`, rather than be handed to the holders.
+//!
+//! Without a key, the dealer can instead publish `SHA-256(salt || secret)`,
+//! against which the combiner checks the interpolated secret. This detects
+//! mismatched shares after the fact but not which share was bad, and the
+//! digest is only as hiding as the secret is hard to guess.
+
+use crate::{field::Field, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+
+/// bytes of an authentication tag
+pub const TAG_BYTES: usize = 32;
+
+/// bytes of the salt of a `SecretDigest`
+pub const SALT_BYTES: usize = 16;
+
+/// A salted SHA-256 digest of a secret, published alongside the shares.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecretDigest {
+    salt: [u8; SALT_BYTES],
+    hash: [u8; 32],
+}
+
+impl SecretDigest {
+    pub fn new(salt: [u8; SALT_BYTES], hash: [u8; 32]) -> Self {
+        SecretDigest { salt, hash }
+    }
+
+    pub fn salt(&self) -> &[u8; SALT_BYTES] {
+        &self.salt
+    }
+
+    pub fn hash(&self) -> &[u8; 32] {
+        &self.hash
+    }
+}
+
+/// shares of a secret with the digest to check its recovery against
+pub type CheckedShares<V> = (Vec<Share<V>>, SecretDigest);
+
+/// A share with its authentication tag.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaggedShare<V> {
+    share: Share<V>,
+    tag: [u8; TAG_BYTES],
+}
+
+impl<V> TaggedShare<V> {
+    pub fn new(share: Share<V>, tag: [u8; TAG_BYTES]) -> Self {
+        TaggedShare { share, tag }
+    }
+
+    pub fn share(&self) -> &Share<V> {
+        &self.share
+    }
+
+    pub fn tag(&self) -> &[u8; TAG_BYTES] {
+        &self.tag
+    }
+}
+
+impl<F: Field> SecretShare<F> {
+    #[cfg(feature = "rand")]
+    pub fn split_tagged(
+        &self,
+        secret: F::Elem,
+        key: &[u8],
+    ) -> Result<Vec<TaggedShare<F::Elem>>, TinySsError> {
+        Ok(self.tag_shares(key, self.split(secret)?))
+    }
+
+    /// Tags every share under the verification `key`.
+    pub fn tag_shares(&self, key: &[u8], shares: Vec<Share<F::Elem>>) -> Vec<TaggedShare<F::Elem>> {
+        shares
+            .into_iter()
+            .map(|share| {
+                let tag = self.mac(key, &share).finalize().into_bytes().into();
+                TaggedShare::new(share, tag)
+            })
+            .collect()
+    }
+
+    /// Checks the tag of `share` in constant time.
+    pub fn verify_tag(&self, key: &[u8], share: &TaggedShare<F::Elem>) -> bool {
+        self.mac(key, &share.share).verify_slice(&share.tag).is_ok()
+    }
+
+    /// Recovers the secret from at least `t` tagged shares, failing with
+    /// `InvalidShare` at the first share whose tag does not verify.
+    pub fn recover_tagged(
+        &self,
+        key: &[u8],
+        shares: &[TaggedShare<F::Elem>],
+    ) -> Result<F::Elem, TinySsError> {
+        if let Some(bad) = shares.iter().find(|s| !self.verify_tag(key, s)) {
+            return Err(TinySsError::InvalidShare {
+                index: bad.share.index(),
+            });
+        }
+        let shares: Vec<Share<F::Elem>> = shares.iter().map(|s| s.share.clone()).collect();
+        self.recover(&shares)
+    }
+
+    /// Splits `secret` and digests it under a fresh random salt.
+    #[cfg(feature = "rand")]
+    pub fn split_checked(&self, secret: F::Elem) -> Result<CheckedShares<F::Elem>, TinySsError> {
+        use rand::RngCore;
+        let mut salt = [0u8; SALT_BYTES];
+        rand::thread_rng().fill_bytes(&mut salt);
+        let digest = self.digest(&secret, salt);
+        Ok((self.split(secret)?, digest))
+    }
+
+    /// The digest of `secret` under `salt`.
+    pub fn digest(&self, secret: &F::Elem, salt: [u8; SALT_BYTES]) -> SecretDigest {
+        let hash = Sha256::new()
+            .chain_update(salt)
+            .chain_update(self.field().encode(secret))
+            .finalize()
+            .into();
+        SecretDigest::new(salt, hash)
+    }
+
+    /// Recovers the secret from at least `t` shares, failing with
+    /// `IntegrityCheckFailed` if it does not match `digest`.
+    pub fn recover_checked(
+        &self,
+        shares: &[Share<F::Elem>],
+        digest: &SecretDigest,
+    ) -> Result<F::Elem, TinySsError> {
+        let secret = self.recover(shares)?;
+        let expected = self.digest(&secret, digest.salt);
+        // constant time in the digest, which may be public anyway
+        let diff = expected
+            .hash
+            .iter()
+            .zip(&digest.hash)
+            .fold(0, |acc, (a, b)| acc | (a ^ b));
+        if diff != 0 {
+            return Err(TinySsError::IntegrityCheckFailed);
+        }
+        Ok(secret)
+    }
+
+    // HMAC-SHA256 over the big-endian index and the encoded value
+    fn mac(&self, key: &[u8], share: &Share<F::Elem>) -> Hmac<Sha256> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+        mac.update(&(share.index() as u64).to_be_bytes());
+        mac.update(&self.field().encode(share.value()));
+        mac
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn tagged_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        let shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+        let mut tagged = ss.tag_shares(b"verification key", shares);
+        assert!(tagged.iter().all(|s| ss.verify_tag(b"verification key", s)));
+        assert_eq!(
+            ss.recover_tagged(b"verification key", &tagged[2..])
+                .unwrap(),
+            BigInt::from(1234)
+        );
+        assert_eq!(
+            ss.recover_tagged(b"another key", &tagged[2..]),
+            Err(TinySsError::InvalidShare { index: 3 })
+        );
+
+        tagged[3] = TaggedShare::new(Share::new(4, BigInt::from(1)), *tagged[3].tag());
+        assert_eq!(
+            ss.recover_tagged(b"verification key", &tagged[2..]),
+            Err(TinySsError::InvalidShare { index: 4 })
+        );
+        // the tag binds the index as well as the value
+        let moved = TaggedShare::new(
+            Share::new(2, tagged[0].share().value().clone()),
+            *tagged[0].tag(),
+        );
+        assert!(!ss.verify_tag(b"verification key", &moved));
+    }
+
+    #[test]
+    fn checked_test() {
+        let ss = SecretShare::new(2, 4, BigInt::from(1613)).unwrap();
+        let mut shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166)])
+            .unwrap();
+        let digest = ss.digest(&BigInt::from(1234), [7; SALT_BYTES]);
+        assert_ne!(digest, ss.digest(&BigInt::from(1234), [8; SALT_BYTES]));
+        assert_eq!(
+            ss.recover_checked(&shares[1..3], &digest).unwrap(),
+            BigInt::from(1234)
+        );
+        shares[1] = Share::new(2, BigInt::from(5));
+        assert_eq!(
+            ss.recover_checked(&shares[1..3], &digest),
+            Err(TinySsError::IntegrityCheckFailed)
+        );
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn split_checked_test() {
+        let ss = SecretShare::new(2, 3, BigInt::from(1613)).unwrap();
+        let (shares, digest) = ss.split_checked(BigInt::from(99)).unwrap();
+        assert_eq!(
+            ss.recover_checked(&shares[1..], &digest).unwrap(),
+            BigInt::from(99)
+        );
+    }
+}
@@ -0,0 +1,220 @@
+//! A Merkle manifest over one dealing.
+//!
+//! Every share is committed as `SHA-256(0 || index || salt || value)` with a
+//! fresh salt, so that the commitments reveal nothing about the values, and
+//! the dealer publishes the root of the tree over all `n` commitments. Each
+//! holder keeps its salt and authentication path, and a combiner that knows
+//! the root accepts only shares that belong to that very dealing, whoever
+//! submits them.
+
+use crate::{field::Field, SecretShare, Share, TinySsError};
+use alloc::{vec, vec::Vec};
+use sha2::{Digest, Sha256};
+
+/// bytes of the salt of a share commitment
+pub const SALT_BYTES: usize = 16;
+
+/// The published root over the commitments of a dealing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Manifest {
+    root: [u8; 32],
+    /// number of shares in the dealing
+    n: usize,
+}
+
+impl Manifest {
+    pub fn new(root: [u8; 32], n: usize) -> Self {
+        Manifest { root, n }
+    }
+
+    pub fn root(&self) -> &[u8; 32] {
+        &self.root
+    }
+
+    /// number of shares in the dealing
+    pub fn n(&self) -> usize {
+        self.n
+    }
+}
+
+/// A share with the salt of its commitment and its path to the root.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestShare<V> {
+    share: Share<V>,
+    salt: [u8; SALT_BYTES],
+    /// sibling hashes from the leaf upwards
+    path: Vec<[u8; 32]>,
+}
+
+impl<V> ManifestShare<V> {
+    pub fn new(share: Share<V>, salt: [u8; SALT_BYTES], path: Vec<[u8; 32]>) -> Self {
+        ManifestShare { share, salt, path }
+    }
+
+    pub fn share(&self) -> &Share<V> {
+        &self.share
+    }
+
+    pub fn salt(&self) -> &[u8; SALT_BYTES] {
+        &self.salt
+    }
+
+    /// sibling hashes from the leaf upwards
+    pub fn path(&self) -> &[[u8; 32]] {
+        &self.path
+    }
+}
+
+impl<F: Field> SecretShare<F> {
+    #[cfg(feature = "rand")]
+    pub fn manifest(&self, shares: Vec<Share<F::Elem>>) -> (Manifest, Vec<ManifestShare<F::Elem>>) {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.manifest_with(shares, &mut |buf| rng.fill_bytes(buf))
+    }
+
+    /// Commits to the `n` shares of a dealing, in index order, drawing the
+    /// salts from `fill_random`, which must fill the buffer with uniformly
+    /// random bytes.
+    pub fn manifest_with(
+        &self,
+        shares: Vec<Share<F::Elem>>,
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> (Manifest, Vec<ManifestShare<F::Elem>>) {
+        let salts: Vec<[u8; SALT_BYTES]> = shares
+            .iter()
+            .map(|_| {
+                let mut salt = [0u8; SALT_BYTES];
+                fill_random(&mut salt);
+                salt
+            })
+            .collect();
+        let mut level: Vec<[u8; 32]> = shares
+            .iter()
+            .zip(&salts)
+            .map(|(share, salt)| self.leaf(share, salt))
+            .collect();
+        level.resize(level.len().next_power_of_two(), [0; 32]);
+        let mut paths = vec![Vec::new(); shares.len()];
+        while level.len() > 1 {
+            for (position, path) in paths.iter_mut().enumerate() {
+                path.push(level[(position >> path.len()) ^ 1]);
+            }
+            level = level
+                .chunks(2)
+                .map(|pair| node(&pair[0], &pair[1]))
+                .collect();
+        }
+        let manifest = Manifest::new(level[0], shares.len());
+        let shares = shares
+            .into_iter()
+            .zip(salts)
+            .zip(paths)
+            .map(|((share, salt), path)| ManifestShare::new(share, salt, path))
+            .collect();
+        (manifest, shares)
+    }
+
+    /// Whether `share` is part of the dealing committed to by `manifest`.
+    pub fn verify_inclusion(&self, manifest: &Manifest, share: &ManifestShare<F::Elem>) -> bool {
+        let index = share.share.index();
+        let depth = manifest.n.next_power_of_two().trailing_zeros() as usize;
+        if index == 0 || index > manifest.n || share.path.len() != depth {
+            return false;
+        }
+        let position = index - 1;
+        let root = share.path.iter().enumerate().fold(
+            self.leaf(&share.share, &share.salt),
+            |hash, (height, sibling)| {
+                if (position >> height) & 1 == 0 {
+                    node(&hash, sibling)
+                } else {
+                    node(sibling, &hash)
+                }
+            },
+        );
+        root == manifest.root
+    }
+
+    /// Recovers the secret from at least `t` shares of the dealing, failing
+    /// with `InvalidShare` at the first share not included in `manifest`.
+    pub fn recover_manifest(
+        &self,
+        manifest: &Manifest,
+        shares: &[ManifestShare<F::Elem>],
+    ) -> Result<F::Elem, TinySsError> {
+        if let Some(bad) = shares.iter().find(|s| !self.verify_inclusion(manifest, s)) {
+            return Err(TinySsError::InvalidShare {
+                index: bad.share.index(),
+            });
+        }
+        let shares: Vec<Share<F::Elem>> = shares.iter().map(|s| s.share.clone()).collect();
+        self.recover(&shares)
+    }
+
+    // Salted commitment to a share, domain separated from inner nodes
+    fn leaf(&self, share: &Share<F::Elem>, salt: &[u8; SALT_BYTES]) -> [u8; 32] {
+        Sha256::new()
+            .chain_update([0])
+            .chain_update((share.index() as u64).to_be_bytes())
+            .chain_update(salt)
+            .chain_update(self.field().encode(share.value()))
+            .finalize()
+            .into()
+    }
+}
+
+// Inner node over two children
+fn node(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    Sha256::new()
+        .chain_update([1])
+        .chain_update(left)
+        .chain_update(right)
+        .finalize()
+        .into()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn manifest_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        let shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+        let (manifest, committed) = ss.manifest_with(shares, &mut counter(37));
+        assert_eq!(manifest.n(), 5);
+        assert!(committed.iter().all(|s| s.path().len() == 3));
+        assert!(committed.iter().all(|s| ss.verify_inclusion(&manifest, s)));
+        assert_eq!(
+            ss.recover_manifest(&manifest, &committed[2..]).unwrap(),
+            BigInt::from(1234)
+        );
+
+        // a share of another dealing is rejected
+        let other = ss
+            .split_with_coefficients(BigInt::from(1), &[BigInt::from(2), BigInt::from(3)])
+            .unwrap();
+        let (_, foreign) = ss.manifest_with(other, &mut counter(37));
+        let mixed = [
+            committed[0].clone(),
+            committed[1].clone(),
+            foreign[2].clone(),
+        ];
+        assert_eq!(
+            ss.recover_manifest(&manifest, &mixed),
+            Err(TinySsError::InvalidShare { index: 3 })
+        );
+        // and so is a path presented for another position
+        let moved = ManifestShare::new(
+            Share::new(2, committed[0].share().value().clone()),
+            *committed[0].salt(),
+            committed[0].path().to_vec(),
+        );
+        assert!(!ss.verify_inclusion(&manifest, &moved));
+    }
+}
@@ -0,0 +1,141 @@
+// Renders arbitrary bytes as words from the BIP-0039 English wordlist.
+//
+// The first two words hold a 22-bit header: the parity of the byte length
+// followed by the first 21 bits of SHA-256 over the bytes. The remaining
+// words carry the bytes themselves, 11 bits per word, zero padded at the
+// end. The word count leaves at most two candidate lengths, which the parity
+// bit tells apart.
+
+mod wordlist;
+
+use crate::DecodeError;
+use alloc::{string::String, vec::Vec};
+use sha2::{Digest, Sha256};
+use wordlist::WORDLIST;
+
+/// bits encoded by a single word
+const RADIX_BITS: usize = 11;
+/// words taken by the length parity and checksum
+const HEADER_WORDS: usize = 2;
+/// bits of SHA-256 kept as checksum
+const CHECKSUM_BITS: usize = 21;
+
+pub fn encode(data: &[u8]) -> String {
+    let header = ((data.len() as u32 & 1) << CHECKSUM_BITS) | checksum(data);
+    let mut words: Vec<u16> = Vec::with_capacity(HEADER_WORDS + data_words(data.len()));
+    words.push((header >> RADIX_BITS) as u16);
+    words.push((header & 0x7ff) as u16);
+
+    let (mut acc, mut bits) = (0u32, 0);
+    for &byte in data {
+        acc = acc << 8 | byte as u32;
+        bits += 8;
+        if bits >= RADIX_BITS {
+            bits -= RADIX_BITS;
+            words.push((acc >> bits) as u16);
+            acc &= (1 << bits) - 1;
+        }
+    }
+    if bits > 0 {
+        words.push((acc << (RADIX_BITS - bits)) as u16);
+    }
+
+    let mut mnemonic = String::new();
+    for (i, &w) in words.iter().enumerate() {
+        if i > 0 {
+            mnemonic.push(' ');
+        }
+        mnemonic.push_str(WORDLIST[w as usize]);
+    }
+    mnemonic
+}
+
+pub fn decode(mnemonic: &str) -> Result<Vec<u8>, DecodeError> {
+    let words = mnemonic
+        .split_whitespace()
+        .map(|word| {
+            WORDLIST
+                .binary_search(&word)
+                .map(|i| i as u32)
+                .map_err(|_| DecodeError::InvalidWord)
+        })
+        .collect::<Result<Vec<u32>, _>>()?;
+    if words.len() < HEADER_WORDS {
+        return Err(DecodeError::Truncated);
+    }
+    let header = words[0] << RADIX_BITS | words[1];
+    let parity = (header >> CHECKSUM_BITS) as usize;
+
+    let body = &words[HEADER_WORDS..];
+    let longest = body.len() * RADIX_BITS / 8;
+    let len = [longest, longest.saturating_sub(1)]
+        .into_iter()
+        .find(|&len| len & 1 == parity && data_words(len) == body.len())
+        .ok_or(DecodeError::InvalidEncoding)?;
+
+    let mut data = Vec::with_capacity(len);
+    let (mut acc, mut bits) = (0u32, 0);
+    for &w in body {
+        acc = acc << RADIX_BITS | w;
+        bits += RADIX_BITS;
+        while bits >= 8 && data.len() < len {
+            bits -= 8;
+            data.push((acc >> bits) as u8);
+        }
+        acc &= (1 << bits) - 1;
+    }
+    // only the zero padding of the last word may remain
+    if acc != 0 {
+        return Err(DecodeError::InvalidEncoding);
+    }
+    if checksum(&data) != header & ((1 << CHECKSUM_BITS) - 1) {
+        return Err(DecodeError::InvalidChecksum);
+    }
+    Ok(data)
+}
+
+// Words needed for `len` bytes of data
+fn data_words(len: usize) -> usize {
+    (len * 8).div_ceil(RADIX_BITS)
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    let hash = Sha256::digest(data);
+    u32::from_be_bytes([hash[0], hash[1], hash[2], hash[3]]) >> (32 - CHECKSUM_BITS)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip_test() {
+        let data: Vec<u8> = (0..64u8).map(|b| b.wrapping_mul(37)).collect();
+        for len in 0..data.len() {
+            let mnemonic = encode(&data[..len]);
+            assert_eq!(mnemonic.split(' ').count(), HEADER_WORDS + data_words(len));
+            assert_eq!(decode(&mnemonic).unwrap(), &data[..len]);
+        }
+    }
+
+    #[test]
+    fn invalid_mnemonic_test() {
+        let mnemonic = encode(b"tiny-ss");
+        let mut words: Vec<&str> = mnemonic.split(' ').collect();
+        assert_eq!(decode(&words[..1].join(" ")), Err(DecodeError::Truncated));
+
+        let last = words.len() - 1;
+        words[last] = if words[last] == "zoo" {
+            "abandon"
+        } else {
+            "zoo"
+        };
+        assert!(matches!(
+            decode(&words.join(" ")),
+            Err(DecodeError::InvalidChecksum | DecodeError::InvalidEncoding)
+        ));
+
+        words[last] = "tinyss";
+        assert_eq!(decode(&words.join(" ")), Err(DecodeError::InvalidWord));
+    }
+}
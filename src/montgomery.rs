@@ -0,0 +1,225 @@
+//! Montgomery arithmetic for repeated operations modulo one prime.
+//!
+//! `MontgomeryField` precomputes its constants once, when the scheme is
+//! created, and then multiplies with shifts and masks instead of dividing
+//! `BigInt`s by `p`. Elements are kept in Montgomery form `a * R mod p`;
+//! convert secrets in with `to_montgomery` and recovered values back out
+//! with `from_montgomery`. `encode` and `decode` work on the ordinary
+//! value, so encoded shares match those of `PrimeField`.
+
+use crate::{
+    field::{Field, PrimeField},
+    prime, SecretShare, TinySsError,
+};
+use alloc::vec::Vec;
+use num_bigint::BigInt;
+use num_traits::{One, Zero};
+
+/// The prime field of an odd order `p`, with elements in Montgomery form.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MontgomeryField {
+    /// the same field in ordinary form
+    field: PrimeField,
+    /// bits of R = 2^shift > p
+    shift: u64,
+    /// R - 1
+    mask: BigInt,
+    /// -p^-1 mod R
+    p_neg_inv: BigInt,
+    /// R^2 mod p
+    r2: BigInt,
+}
+
+impl MontgomeryField {
+    /// `p` must be an odd prime; even moduli have no Montgomery form.
+    pub fn new(p: BigInt) -> Result<Self, TinySsError> {
+        let field = PrimeField::new(p)?;
+        let p = field.p();
+        if !p.bit(0) {
+            return Err(TinySsError::InvalidModulus);
+        }
+        // R as a whole number of 64-bit digits
+        let shift = p.bits().div_ceil(64) * 64;
+        let r = BigInt::one() << shift;
+        let mask = &r - BigInt::one();
+        let r_field = PrimeField::new(r.clone()).expect("R is larger than 1");
+        let p_inv = r_field.inv(p).expect("p is odd and R a power of two");
+        let p_neg_inv = r_field.sub(&BigInt::zero(), &p_inv);
+        let r2 = field.reduce(&(&r * &r));
+        Ok(MontgomeryField {
+            field,
+            shift,
+            mask,
+            p_neg_inv,
+            r2,
+        })
+    }
+
+    /// prime in ff
+    pub fn p(&self) -> &BigInt {
+        self.field.p()
+    }
+
+    /// Converts an integer to Montgomery form.
+    pub fn to_montgomery(&self, value: &BigInt) -> BigInt {
+        self.redc(self.field.reduce(value) * &self.r2)
+    }
+
+    /// Converts an element in Montgomery form back to `[0, p)`.
+    pub fn from_montgomery(&self, value: &BigInt) -> BigInt {
+        self.redc(value.clone())
+    }
+
+    // Montgomery reduction, t * R^-1 mod p for 0 <= t < p * R
+    fn redc(&self, t: BigInt) -> BigInt {
+        let m = ((&t & &self.mask) * &self.p_neg_inv) & &self.mask;
+        let u = (t + m * self.p()) >> self.shift;
+        if &u >= self.p() {
+            u - self.p()
+        } else {
+            u
+        }
+    }
+}
+
+impl SecretShare<MontgomeryField> {
+    /// Creates a `t`-of-`n` scheme over the odd prime `p` that computes in
+    /// Montgomery form.
+    pub fn montgomery(t: usize, n: usize, p: BigInt) -> Result<Self, TinySsError> {
+        let ss = SecretShare::with_field(t, n, MontgomeryField::new(p)?)?;
+        prime::check_modulus(ss.field().p())?;
+        Ok(ss)
+    }
+}
+
+impl Field for MontgomeryField {
+    type Elem = BigInt;
+
+    fn zero(&self) -> BigInt {
+        BigInt::zero()
+    }
+
+    fn one(&self) -> BigInt {
+        self.to_montgomery(&BigInt::one())
+    }
+
+    // operands are in `[0, p)`, so one conditional subtraction reduces
+    fn add(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        let sum = a + b;
+        if &sum >= self.p() {
+            sum - self.p()
+        } else {
+            sum
+        }
+    }
+
+    fn sub(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        if a >= b {
+            a - b
+        } else {
+            a + self.p() - b
+        }
+    }
+
+    fn mul(&self, a: &BigInt, b: &BigInt) -> BigInt {
+        self.redc(a * b)
+    }
+
+    fn inv(&self, a: &BigInt) -> Option<BigInt> {
+        let inverse = self.field.inv(&self.from_montgomery(a))?;
+        Some(self.to_montgomery(&inverse))
+    }
+
+    fn is_canonical(&self, a: &BigInt) -> bool {
+        self.field.is_canonical(a)
+    }
+
+    fn canonical(&self, a: &BigInt) -> BigInt {
+        self.field.canonical(a)
+    }
+
+    fn elem_from_index(&self, index: usize) -> BigInt {
+        self.to_montgomery(&BigInt::from(index))
+    }
+
+    fn has_indices(&self, n: usize) -> bool {
+        self.field.has_indices(n)
+    }
+
+    // uniform in `[0, p)` in either form
+    fn random(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> BigInt {
+        self.field.random(fill_random)
+    }
+
+    fn encode(&self, a: &BigInt) -> Vec<u8> {
+        self.field.encode(&self.from_montgomery(a))
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<BigInt> {
+        self.field.decode(bytes).map(|a| self.to_montgomery(&a))
+    }
+
+    #[cfg(feature = "zeroize")]
+    fn zeroize(&self, a: &mut BigInt) {
+        crate::zeroize::zeroize_bigint(a)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn montgomery_field_test() {
+        let field = MontgomeryField::new(BigInt::from(1613)).unwrap();
+        let elem = |v: i64| field.to_montgomery(&BigInt::from(v));
+        assert_eq!(field.from_montgomery(&elem(-2)), BigInt::from(1611));
+        assert_eq!(field.sub(&elem(3), &elem(5)), elem(1611));
+        assert_eq!(field.mul(&elem(-2), &elem(3)), elem(1607));
+        assert_eq!(
+            field.mul(&elem(166), &field.inv(&elem(166)).unwrap()),
+            field.one()
+        );
+        assert_eq!(field.inv(&field.zero()), None);
+        assert_eq!(field.encode(&elem(77)), [0, 77]);
+        assert_eq!(field.decode(&[6, 0x4c]), Some(elem(1612)));
+
+        assert_eq!(
+            MontgomeryField::new(BigInt::from(1614)),
+            Err(TinySsError::InvalidModulus)
+        );
+    }
+
+    #[test]
+    fn matches_prime_field_test() {
+        let p = BigInt::parse_bytes(
+            b"fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141",
+            16,
+        )
+        .unwrap();
+        let plain = SecretShare::new(3, 5, p.clone()).unwrap();
+        let ss = SecretShare::montgomery(3, 5, p).unwrap();
+        let field = ss.field();
+
+        let secret = BigInt::from(1234);
+        let coefficients = [BigInt::from(166), plain.p() - 94];
+        let expected = plain
+            .split_with_coefficients(secret.clone(), &coefficients)
+            .unwrap();
+        let coefficients: Vec<BigInt> = coefficients
+            .iter()
+            .map(|c| field.to_montgomery(c))
+            .collect();
+        let shares = ss
+            .split_with_coefficients(field.to_montgomery(&secret), &coefficients)
+            .unwrap();
+        for (share, expected) in shares.iter().zip(&expected) {
+            assert_eq!(
+                field.encode(share.value()),
+                plain.field().encode(expected.value())
+            );
+        }
+        let recovered = ss.recover(&shares[2..]).unwrap();
+        assert_eq!(field.from_montgomery(&recovered), secret);
+    }
+}
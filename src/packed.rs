@@ -0,0 +1,190 @@
+//! Packed secret sharing after Franklin and Yung (STOC '92).
+//!
+//! `k` secrets sit at the points `n + 1..=n + k` of one polynomial of degree
+//! `t + k - 2`, and share `i` is its value at `i`. Any `t - 1` shares still
+//! reveal nothing, but recovery needs `t + k - 1` of them: a ramp scheme that
+//! stores `k` secrets in the space of one.
+
+use crate::{
+    field::{Field, PrimeField},
+    poly, Share, TinySsError,
+};
+use alloc::vec::Vec;
+
+/// A ramp scheme packing `k` secrets into shares for `n` holders.
+#[derive(Clone, Debug)]
+pub struct PackedSharing<F: Field = PrimeField> {
+    /// privacy threshold, fewer shares reveal nothing
+    t: usize,
+    /// secrets per polynomial
+    k: usize,
+    /// total number of shares
+    n: usize,
+    field: F,
+}
+
+impl<F: Field> PackedSharing<F> {
+    /// The threshold must satisfy `0 < t` and `t + k - 1 <= n`, and the
+    /// field needs `n + k` distinct nonzero elements.
+    pub fn new(t: usize, k: usize, n: usize, field: F) -> Result<Self, TinySsError> {
+        if t == 0 || k == 0 || t + k - 1 > n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if !field.has_indices(n + k) {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(PackedSharing { t, k, n, field })
+    }
+
+    /// privacy threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// secrets per polynomial
+    pub fn k(&self) -> usize {
+        self.k
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// shares needed for recovery, `t + k - 1`
+    pub fn recovery_threshold(&self) -> usize {
+        self.t + self.k - 1
+    }
+
+    pub fn field(&self) -> &F {
+        &self.field
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secrets: &[F::Elem]) -> Result<Vec<Share<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secrets, || {
+            self.field.random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Splits exactly `k` secrets, drawing the `t - 1` random values from
+    /// `sample`, which must return uniformly random field elements.
+    pub fn split_with<S>(
+        &self,
+        secrets: &[F::Elem],
+        mut sample: S,
+    ) -> Result<Vec<Share<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        if secrets.len() != self.k {
+            return Err(TinySsError::LengthMismatch);
+        }
+        // f = L + Z * R, with L through the secrets, Z vanishing on the
+        // secret points and R random
+        let field = &self.field;
+        let points = self.secret_points();
+        let random: Vec<F::Elem> = (1..self.t).map(|_| sample()).collect();
+        (1..=self.n)
+            .map(|i| {
+                let x = field.elem_from_index(i);
+                let lambdas = poly::lagrange_coefficients(field, &points, &x)?;
+                let l = lambdas
+                    .iter()
+                    .zip(secrets)
+                    .fold(field.zero(), |sum, (lambda, s)| {
+                        field.add(&sum, &field.mul(lambda, s))
+                    });
+                let z = points.iter().fold(field.one(), |product, e| {
+                    field.mul(&product, &field.sub(&x, e))
+                });
+                let r = poly::evaluate(field, &random, &x);
+                Ok(Share::new(i, field.add(&l, &field.mul(&z, &r))))
+            })
+            .collect()
+    }
+
+    /// Recovers all `k` secrets from at least `t + k - 1` shares.
+    pub fn recover(&self, shares: &[Share<F::Elem>]) -> Result<Vec<F::Elem>, TinySsError> {
+        let unique = poly::distinct_shares(shares, self.recovery_threshold())?;
+        let unique = &unique[..self.recovery_threshold()];
+        if unique.iter().any(|s| s.index() == 0 || s.index() > self.n) {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let xs: Vec<F::Elem> = unique
+            .iter()
+            .map(|s| self.field.elem_from_index(s.index()))
+            .collect();
+        let ys: Vec<F::Elem> = unique.iter().map(|s| s.value().clone()).collect();
+        self.secret_points()
+            .iter()
+            .map(|e| poly::interpolate(&self.field, &xs, &ys, e))
+            .collect()
+    }
+
+    // where the secrets sit, past the share indices
+    fn secret_points(&self) -> Vec<F::Elem> {
+        (self.n + 1..=self.n + self.k)
+            .map(|i| self.field.elem_from_index(i))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf256::Gf256;
+    use crate::test_util::counter;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn packed_test() {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        let packed = PackedSharing::new(2, 3, 6, field).unwrap();
+        assert_eq!(packed.recovery_threshold(), 4);
+        let secrets = [BigInt::from(10), BigInt::from(20), BigInt::from(1612)];
+        let shares = packed.split_with(&secrets, || BigInt::from(555)).unwrap();
+        assert_eq!(packed.recover(&shares[2..]).unwrap(), secrets);
+        assert_eq!(
+            packed.recover(&shares[3..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 4,
+                got: 3
+            }
+        );
+
+        // each single share is masked by the random value
+        let other = packed.split_with(&secrets, || BigInt::from(556)).unwrap();
+        assert!(shares.iter().zip(&other).all(|(a, b)| a != b));
+    }
+
+    #[test]
+    fn gf256_test() {
+        let packed = PackedSharing::new(3, 4, 10, Gf256).unwrap();
+        let secrets = [1, 2, 3, 4];
+        let mut fill = counter(77);
+        let shares = packed
+            .split_with(&secrets, || {
+                let mut byte = [0];
+                fill(&mut byte);
+                byte[0]
+            })
+            .unwrap();
+        assert_eq!(packed.recover(&shares[4..]).unwrap(), secrets);
+    }
+
+    #[test]
+    fn parameters_test() {
+        let field = PrimeField::new(BigInt::from(7)).unwrap();
+        assert_eq!(
+            PackedSharing::new(2, 3, 3, field.clone()).unwrap_err(),
+            TinySsError::InvalidThreshold
+        );
+        assert_eq!(
+            PackedSharing::new(2, 3, 5, field).unwrap_err(),
+            TinySsError::InvalidModulus
+        );
+    }
+}
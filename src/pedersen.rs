@@ -0,0 +1,280 @@
+//! Pedersen verifiable secret sharing.
+//!
+//! The dealer shares the secret with a polynomial `a` and a random blinding
+//! polynomial `b` of the same degree, and publishes the commitments
+//! `C_j = g^{a_j} h^{b_j}`. Each shareholder checks its pair `(a(i), b(i))`
+//! against them. Since `b` is random the commitments are perfectly hiding:
+//! they reveal nothing about the secret, even to an unbounded adversary.
+
+use crate::{field::Field, group::SchnorrGroup, SecretShare, Share, TinySsError};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+use num_bigint::BigInt;
+use num_traits::One;
+
+/// A share of the secret together with the matching share of the blinding
+/// polynomial.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PedersenShare {
+    index: usize,
+    /// a(index)
+    value: BigInt,
+    /// b(index)
+    blinding: BigInt,
+}
+
+impl fmt::Debug for PedersenShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PedersenShare")
+            .field("index", &self.index)
+            .field("value", crate::debug::secret(&self.value))
+            .field("blinding", crate::debug::secret(&self.blinding))
+            .finish()
+    }
+}
+
+impl PedersenShare {
+    pub fn new(index: usize, value: BigInt, blinding: BigInt) -> Self {
+        PedersenShare {
+            index,
+            value,
+            blinding,
+        }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    pub fn blinding(&self) -> &BigInt {
+        &self.blinding
+    }
+
+    /// the plain Shamir share, without the blinding
+    pub fn share(&self) -> Share {
+        Share::new(self.index, self.value.clone())
+    }
+}
+
+/// A `t`-of-`n` Pedersen scheme over a Schnorr group.
+#[derive(Clone, Debug)]
+pub struct PedersenVss {
+    group: SchnorrGroup,
+    /// sharing over the exponents, modulo q
+    sharing: SecretShare,
+}
+
+impl PedersenVss {
+    pub fn new(t: usize, n: usize, group: SchnorrGroup) -> Result<Self, TinySsError> {
+        let sharing = SecretShare::new(t, n, group.q().clone())?;
+        Ok(PedersenVss { group, sharing })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.sharing.t()
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.sharing.n()
+    }
+
+    pub fn group(&self) -> &SchnorrGroup {
+        &self.group
+    }
+
+    /// Splits `secret`, returning the shares and the `t` commitments.
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: BigInt) -> Result<(Vec<PedersenShare>, Vec<BigInt>), TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let scalars = self.group.scalars();
+        self.split_with(secret, || scalars.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Splits `secret` drawing both polynomials from `sample`, which must
+    /// return uniformly random elements modulo q.
+    pub fn split_with<S>(
+        &self,
+        secret: BigInt,
+        mut sample: S,
+    ) -> Result<(Vec<PedersenShare>, Vec<BigInt>), TinySsError>
+    where
+        S: FnMut() -> BigInt,
+    {
+        let t = self.t();
+        let coefficients: Vec<BigInt> = (1..t).map(|_| sample()).collect();
+        let blinding: Vec<BigInt> = (0..t).map(|_| sample()).collect();
+        let values = self
+            .sharing
+            .split_with_coefficients(secret.clone(), &coefficients)?;
+        let blindings = self
+            .sharing
+            .split_with_coefficients(blinding[0].clone(), &blinding[1..])?;
+
+        let commitments = core::iter::once(&secret)
+            .chain(&coefficients)
+            .zip(&blinding)
+            .map(|(a, b)| self.group.commit(a, b))
+            .collect();
+        let shares = values
+            .into_iter()
+            .zip(blindings)
+            .map(|(value, blinding)| {
+                PedersenShare::new(
+                    value.index(),
+                    value.value().clone(),
+                    blinding.value().clone(),
+                )
+            })
+            .collect();
+        Ok((shares, commitments))
+    }
+
+    /// Checks `g^{a(i)} h^{b(i)} = prod_j C_j^{i^j}` for the share at index
+    /// `i`.
+    pub fn verify(&self, share: &PedersenShare, commitments: &[BigInt]) -> Result<(), TinySsError> {
+        if commitments.len() != self.t() {
+            return Err(TinySsError::WrongCoefficientCount {
+                expected: self.t(),
+                got: commitments.len(),
+            });
+        }
+        if share.index == 0 {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let expected = self.group.eval_commitments(commitments, share.index);
+        if self.group.commit(&share.value, &share.blinding) == expected {
+            Ok(())
+        } else {
+            Err(TinySsError::InvalidShare { index: share.index })
+        }
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn verify_batch(
+        &self,
+        shares: &[PedersenShare],
+        commitments: &[BigInt],
+    ) -> Result<(), TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.verify_batch_with(shares, commitments, &mut |buf| rng.fill_bytes(buf))
+    }
+
+    /// Checks all of `shares` with one multi-exponentiation of random
+    /// linear combinations, `g^{sum_i r_i a(i)} h^{sum_i r_i b(i)} = prod_j
+    /// C_j^{sum_i r_i i^j}`, falling back to `verify` to name the first bad
+    /// share. The weights are built from `fill_random`, which must fill the
+    /// buffer with uniformly random bytes.
+    pub fn verify_batch_with(
+        &self,
+        shares: &[PedersenShare],
+        commitments: &[BigInt],
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<(), TinySsError> {
+        if commitments.len() != self.t() {
+            return Err(TinySsError::WrongCoefficientCount {
+                expected: self.t(),
+                got: commitments.len(),
+            });
+        }
+        if shares.iter().any(|s| s.index == 0) {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let group = &self.group;
+        let scalars = group.scalars();
+        let weights = group.batch_weights(shares.len(), fill_random);
+        let negate = |x: BigInt| scalars.sub(&scalars.zero(), &x);
+        let mut bases = vec![group.g().clone(), group.h().clone()];
+        bases.extend_from_slice(commitments);
+        let mut exponents = vec![
+            negate(group.weighted_sum(&weights, shares.iter().map(|s| &s.value))),
+            negate(group.weighted_sum(&weights, shares.iter().map(|s| &s.blinding))),
+        ];
+        exponents.extend(group.weighted_powers(
+            &weights,
+            shares.iter().map(|s| s.index),
+            commitments.len(),
+        ));
+        if group.multi_exp(&bases, &exponents).is_one() {
+            return Ok(());
+        }
+        shares
+            .iter()
+            .try_for_each(|share| self.verify(share, commitments))
+    }
+
+    /// Recovers the secret from at least `t` shares.
+    pub fn recover(&self, shares: &[PedersenShare]) -> Result<BigInt, TinySsError> {
+        let shares: Vec<Share> = shares.iter().map(PedersenShare::share).collect();
+        self.sharing.recover(&shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+
+    fn vss() -> PedersenVss {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        PedersenVss::new(3, 5, group).unwrap()
+    }
+
+    #[test]
+    fn split_with_test() {
+        let vss = vss();
+        let mut next = 100;
+        let (shares, commitments) = vss
+            .split_with(BigInt::from(1000), || {
+                next += 77;
+                BigInt::from(next)
+            })
+            .unwrap();
+        assert_eq!(commitments.len(), 3);
+        for share in &shares {
+            assert_eq!(vss.verify(share, &commitments), Ok(()));
+        }
+        assert_eq!(vss.recover(&shares[1..4]).unwrap(), BigInt::from(1000));
+
+        let mut forged = shares[2].clone();
+        forged.value += 1;
+        assert_eq!(
+            vss.verify(&forged, &commitments),
+            Err(TinySsError::InvalidShare { index: 3 })
+        );
+        let mut fill = counter(61);
+        assert_eq!(
+            vss.verify_batch_with(&shares, &commitments, &mut fill),
+            Ok(())
+        );
+        let mut batch = shares.clone();
+        batch[2] = forged.clone();
+        assert_eq!(
+            vss.verify_batch_with(&batch, &commitments, &mut fill),
+            Err(TinySsError::InvalidShare { index: 3 })
+        );
+        assert_eq!(
+            vss.verify(&shares[0], &commitments[1..]),
+            Err(TinySsError::WrongCoefficientCount {
+                expected: 3,
+                got: 2
+            })
+        );
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn split_test() {
+        let vss = vss();
+        let (shares, commitments) = vss.split(BigInt::from(42)).unwrap();
+        assert!(shares.iter().all(|s| vss.verify(s, &commitments).is_ok()));
+        assert_eq!(vss.recover(&shares[2..]).unwrap(), BigInt::from(42));
+    }
+}
@@ -0,0 +1,232 @@
+//! Shares at arbitrary x-coordinates.
+//!
+//! `Share` places holder `i` at `x = i`. `split_at` instead deals at
+//! caller-chosen nonzero field elements, such as identifiers hashed into
+//! the field, so that a share is bound to its holder's identity;
+//! `recover_points` interpolates such shares. `split_at_random` draws the
+//! x-coordinates at random instead.
+
+use crate::{field::Field, poly, SecretShare, TinySsError};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A share at the x-coordinate `x`, any nonzero field element.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PointShare<V = num_bigint::BigInt> {
+    /// x-coordinate
+    x: V,
+    /// y-coordinate
+    value: V,
+}
+
+impl<V: fmt::Debug> fmt::Debug for PointShare<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PointShare")
+            .field("x", &self.x)
+            .field("value", crate::debug::secret(&self.value))
+            .finish()
+    }
+}
+
+impl<V> PointShare<V> {
+    pub fn new(x: V, value: V) -> Self {
+        PointShare { x, value }
+    }
+
+    /// x-coordinate
+    pub fn x(&self) -> &V {
+        &self.x
+    }
+
+    /// y-coordinate
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+}
+
+impl<F: Field> SecretShare<F> {
+    #[cfg(feature = "rand")]
+    pub fn split_at(
+        &self,
+        secret: F::Elem,
+        xs: &[F::Elem],
+    ) -> Result<Vec<PointShare<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_at_with(secret, xs, || {
+            self.field().random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Splits `secret` into one share per x-coordinate in `xs`, drawing the
+    /// coefficients from `sample` as `split_with` does. The x-coordinates
+    /// must be nonzero, failing with `InvalidIndex`, and distinct, failing
+    /// with `DuplicatePoint`.
+    pub fn split_at_with<S>(
+        &self,
+        secret: F::Elem,
+        xs: &[F::Elem],
+        sample: S,
+    ) -> Result<Vec<PointShare<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let field = self.field();
+        let xs: Vec<F::Elem> = xs.iter().map(|x| field.canonical(x)).collect();
+        for (i, x) in xs.iter().enumerate() {
+            if *x == field.zero() {
+                return Err(TinySsError::InvalidIndex);
+            }
+            if xs[..i].contains(x) {
+                return Err(TinySsError::DuplicatePoint);
+            }
+        }
+        let mut polynomial = self.random_polynomial(secret, sample);
+        let shares = xs
+            .into_iter()
+            .map(|x| {
+                let value = poly::evaluate(field, &polynomial, &x);
+                PointShare::new(x, value)
+            })
+            .collect();
+        self.wipe(&mut polynomial);
+        Ok(shares)
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split_at_random(
+        &self,
+        secret: F::Elem,
+    ) -> Result<Vec<PointShare<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_at_random_with(secret, || {
+            self.field().random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Splits `secret` into `n` shares at distinct nonzero x-coordinates
+    /// drawn from `sample`, which also supplies the coefficients. Unlike
+    /// the indices `1..=n`, such coordinates reveal neither a holder's
+    /// position in the dealing nor the number of holders.
+    pub fn split_at_random_with<S>(
+        &self,
+        secret: F::Elem,
+        mut sample: S,
+    ) -> Result<Vec<PointShare<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let field = self.field();
+        let mut xs: Vec<F::Elem> = Vec::with_capacity(self.n());
+        while xs.len() < self.n() {
+            let x = field.canonical(&sample());
+            if x != field.zero() && !xs.contains(&x) {
+                xs.push(x);
+            }
+        }
+        self.split_at_with(secret, &xs, sample)
+    }
+
+    /// Recovers the secret from at least `t` shares made by `split_at`.
+    ///
+    /// Shares repeating an x-coordinate are ignored as long as they carry
+    /// the same value, and fail with `DuplicatePoint` otherwise.
+    pub fn recover_points(&self, shares: &[PointShare<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        let field = self.field();
+        let mut xs: Vec<F::Elem> = Vec::with_capacity(self.t());
+        let mut ys: Vec<F::Elem> = Vec::with_capacity(self.t());
+        for share in shares {
+            let x = field.canonical(&share.x);
+            let y = field.canonical(&share.value);
+            if x == field.zero() {
+                return Err(TinySsError::InvalidIndex);
+            }
+            match xs.iter().position(|other| *other == x) {
+                Some(i) if ys[i] != y => return Err(TinySsError::DuplicatePoint),
+                Some(_) => {}
+                None => {
+                    xs.push(x);
+                    ys.push(y);
+                }
+            }
+        }
+        if xs.len() < self.t() {
+            return Err(TinySsError::NotEnoughShares {
+                required: self.t(),
+                got: xs.len(),
+            });
+        }
+        xs.truncate(self.t());
+        ys.truncate(self.t());
+        let secret = poly::interpolate(field, &xs, &ys, &field.zero());
+        self.wipe(&mut ys);
+        secret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn split_at_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        let xs: Vec<BigInt> = [977, 12, 1600, 404].into_iter().map(BigInt::from).collect();
+        let mut coefficients = vec![BigInt::from(94), BigInt::from(166)];
+        let shares = ss
+            .split_at_with(BigInt::from(1234), &xs, || coefficients.pop().unwrap())
+            .unwrap();
+        // 1234 + 166 * 12 + 94 * 144 = 16762 = 632 (mod 1613)
+        assert_eq!(
+            shares[1],
+            PointShare::new(BigInt::from(12), BigInt::from(632))
+        );
+        assert_eq!(ss.recover_points(&shares[1..]).unwrap(), BigInt::from(1234));
+        assert_eq!(
+            ss.recover_points(&[
+                shares[3].clone(),
+                shares[0].clone(),
+                shares[3].clone(),
+                shares[2].clone()
+            ])
+            .unwrap(),
+            BigInt::from(1234)
+        );
+
+        let zero = [BigInt::from(5), BigInt::from(1613)];
+        assert_eq!(
+            ss.split_at_with(BigInt::from(1), &zero, || BigInt::from(1)),
+            Err(TinySsError::InvalidIndex)
+        );
+        let colliding = [BigInt::from(5), BigInt::from(1618)];
+        assert_eq!(
+            ss.split_at_with(BigInt::from(1), &colliding, || BigInt::from(1)),
+            Err(TinySsError::DuplicatePoint)
+        );
+        let mut draws = [977, 0, 12, 977, 1600, 404, 7, 166, 94]
+            .map(BigInt::from)
+            .to_vec();
+        let random = ss
+            .split_at_random_with(BigInt::from(1234), || draws.remove(0))
+            .unwrap();
+        assert_eq!(random.len(), 5);
+        assert_eq!(random[..4], shares);
+        assert_eq!(ss.recover_points(&random[2..]).unwrap(), BigInt::from(1234));
+
+        let conflicting = PointShare::new(BigInt::from(12), BigInt::from(633));
+        assert_eq!(
+            ss.recover_points(&[shares[1].clone(), conflicting, shares[2].clone()]),
+            Err(TinySsError::DuplicatePoint)
+        );
+        assert_eq!(
+            ss.recover_points(&shares[..2]),
+            Err(TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,128 @@
+//! Schnorr proofs of knowledge of a discrete logarithm.
+//!
+//! A dealer proves that it knows the `x` behind a published `y = g^x`, such
+//! as the coefficient-0 commitment of its polynomial, without revealing
+//! anything about `x`. The challenge is drawn from a transcript, so that a
+//! proof made for one dealer or ceremony cannot be replayed for another.
+
+use crate::{field::Field, group::SchnorrGroup, transcript::Transcript};
+use num_bigint::BigInt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Non-interactive proof of knowledge of `log_g y`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct SchnorrProof {
+    /// challenge
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    c: BigInt,
+    /// response
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    r: BigInt,
+}
+
+impl SchnorrProof {
+    pub fn new(c: BigInt, r: BigInt) -> Self {
+        SchnorrProof { c, r }
+    }
+
+    /// Proves knowledge of `x` with `y = g^x` under `transcript`, using the
+    /// uniformly random `nonce`.
+    pub fn prove(
+        group: &SchnorrGroup,
+        transcript: &mut dyn Transcript,
+        (g, y): (&BigInt, &BigInt),
+        x: &BigInt,
+        nonce: &BigInt,
+    ) -> Self {
+        let a = group.exp(g, nonce);
+        let c = challenge(group, transcript, g, y, &a);
+        let scalars = group.scalars();
+        let r = scalars.sub(nonce, &scalars.mul(x, &c));
+        SchnorrProof { c, r }
+    }
+
+    /// Checks the proof against a transcript in the state the prover used.
+    pub fn verify(
+        &self,
+        group: &SchnorrGroup,
+        transcript: &mut dyn Transcript,
+        (g, y): (&BigInt, &BigInt),
+    ) -> bool {
+        if !group.is_element(y) {
+            return false;
+        }
+        let a = group.mul(&group.exp(g, &self.r), &group.exp(y, &self.c));
+        self.c == challenge(group, transcript, g, y, &a)
+    }
+
+    /// challenge
+    pub fn c(&self) -> &BigInt {
+        &self.c
+    }
+
+    /// response
+    pub fn r(&self) -> &BigInt {
+        &self.r
+    }
+}
+
+// Absorbs the statement and the commitment and draws the challenge
+fn challenge(
+    group: &SchnorrGroup,
+    transcript: &mut dyn Transcript,
+    g: &BigInt,
+    y: &BigInt,
+    a: &BigInt,
+) -> BigInt {
+    group.append_element(transcript, b"g", g);
+    group.append_element(transcript, b"y", y);
+    group.append_element(transcript, b"a", a);
+    group.challenge_scalar(transcript, b"c")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::transcript::Sha256Transcript;
+
+    #[test]
+    fn schnorr_proof_test() {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        let transcript = || Sha256Transcript::new(b"ceremony");
+        let x = BigInt::from(777);
+        let y = group.exp(group.g(), &x);
+        let proof = SchnorrProof::prove(
+            &group,
+            &mut transcript(),
+            (group.g(), &y),
+            &x,
+            &BigInt::from(95),
+        );
+        assert!(proof.verify(&group, &mut transcript(), (group.g(), &y)));
+        let mut another = Sha256Transcript::new(b"another");
+        assert!(!proof.verify(&group, &mut another, (group.g(), &y)));
+        let other = group.exp(group.g(), &BigInt::from(778));
+        assert!(!proof.verify(&group, &mut transcript(), (group.g(), &other)));
+
+        // a proof for the wrong exponent fails
+        let wrong = SchnorrProof::prove(
+            &group,
+            &mut transcript(),
+            (group.g(), &y),
+            &BigInt::from(5),
+            &BigInt::from(95),
+        );
+        assert!(!wrong.verify(&group, &mut transcript(), (group.g(), &y)));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let proof = SchnorrProof::new(BigInt::from(255), BigInt::from(16));
+        let json = serde_json::to_string(&proof).unwrap();
+        assert_eq!(json, r#"{"c":"ff","r":"10"}"#);
+        assert_eq!(serde_json::from_str::<SchnorrProof>(&json).unwrap(), proof);
+    }
+}
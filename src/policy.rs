@@ -0,0 +1,454 @@
+//! Monotone access structures over named participants.
+//!
+//! A [`Policy`] is a tree of threshold gates, AND and OR being the `n`-of-`n`
+//! and `1`-of-`n` cases, with participants at the leaves. Dealing shares the
+//! secret at every gate with a Shamir polynomial of degree `t - 1` among its
+//! children, so a participant receives one share per leaf naming it.
+//!
+//! Policies can also be written as text, e.g.
+//! `and(ceo, 2 of (alice, bob, carol))` or `or(root, and(ops, audit))`.
+
+use crate::{
+    field::{Field, PrimeField},
+    poly, TinySsError,
+};
+use alloc::{string::String, vec, vec::Vec};
+use core::{fmt, str::FromStr};
+
+/// A monotone policy: who may recover the secret together.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// a single named participant
+    Participant(String),
+    /// at least `t` of the sub-policies
+    Threshold(usize, Vec<Policy>),
+}
+
+impl Policy {
+    pub fn participant(name: &str) -> Self {
+        Policy::Participant(String::from(name))
+    }
+
+    /// all of `policies`
+    pub fn and(policies: Vec<Policy>) -> Self {
+        Policy::Threshold(policies.len(), policies)
+    }
+
+    /// any of `policies`
+    pub fn or(policies: Vec<Policy>) -> Self {
+        Policy::Threshold(1, policies)
+    }
+
+    /// at least `t` of `policies`
+    pub fn threshold(t: usize, policies: Vec<Policy>) -> Self {
+        Policy::Threshold(t, policies)
+    }
+
+    /// Whether the named participants satisfy the policy together.
+    pub fn is_satisfied_by(&self, names: &[&str]) -> bool {
+        match self {
+            Policy::Participant(name) => names.contains(&name.as_str()),
+            Policy::Threshold(t, policies) => {
+                policies.iter().filter(|p| p.is_satisfied_by(names)).count() >= *t
+            }
+        }
+    }
+
+    // Every gate needs `0 < t <= children` and indices for its children
+    fn validate<F: Field>(&self, field: &F) -> Result<(), TinySsError> {
+        match self {
+            Policy::Participant(_) => Ok(()),
+            Policy::Threshold(t, policies) => {
+                if *t == 0 || *t > policies.len() {
+                    return Err(TinySsError::InvalidThreshold);
+                }
+                if !field.has_indices(policies.len()) {
+                    return Err(TinySsError::InvalidModulus);
+                }
+                policies.iter().try_for_each(|p| p.validate(field))
+            }
+        }
+    }
+
+    // Participant at the leaf reached by `path`
+    fn leaf(&self, path: &[usize]) -> Option<&str> {
+        match (self, path.split_first()) {
+            (Policy::Participant(name), None) => Some(name),
+            (Policy::Threshold(_, policies), Some((first, rest))) => {
+                policies.get(*first)?.leaf(rest)
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Policy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Policy::Participant(name) => write!(f, "{}", name),
+            Policy::Threshold(t, policies) => {
+                if *t == policies.len() {
+                    write!(f, "and(")?;
+                } else if *t == 1 {
+                    write!(f, "or(")?;
+                } else {
+                    write!(f, "{} of (", t)?;
+                }
+                for (i, policy) in policies.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", policy)?;
+                }
+                write!(f, ")")
+            }
+        }
+    }
+}
+
+impl FromStr for Policy {
+    type Err = TinySsError;
+
+    /// Parses `name`, `and(p, ...)`, `or(p, ...)` and `t of (p, ...)`, where
+    /// names consist of alphanumerics and `_-.@`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser { rest: s };
+        let policy = parser.policy()?;
+        parser.skip_whitespace();
+        if !parser.rest.is_empty() {
+            return Err(TinySsError::InvalidPolicy);
+        }
+        Ok(policy)
+    }
+}
+
+// Recursive descent over the policy text
+struct Parser<'a> {
+    rest: &'a str,
+}
+
+impl<'a> Parser<'a> {
+    fn policy(&mut self) -> Result<Policy, TinySsError> {
+        let word = self.word()?;
+        if let Ok(t) = word.parse::<usize>() {
+            if self.word()? != "of" {
+                return Err(TinySsError::InvalidPolicy);
+            }
+            return Ok(Policy::threshold(t, self.list()?));
+        }
+        self.skip_whitespace();
+        match word {
+            "and" if self.rest.starts_with('(') => Ok(Policy::and(self.list()?)),
+            "or" if self.rest.starts_with('(') => Ok(Policy::or(self.list()?)),
+            _ => Ok(Policy::participant(word)),
+        }
+    }
+
+    // `(policy, ...)`
+    fn list(&mut self) -> Result<Vec<Policy>, TinySsError> {
+        self.expect('(')?;
+        let mut policies = vec![self.policy()?];
+        loop {
+            self.skip_whitespace();
+            if self.rest.starts_with(')') {
+                self.rest = &self.rest[1..];
+                return Ok(policies);
+            }
+            self.expect(',')?;
+            policies.push(self.policy()?);
+        }
+    }
+
+    fn word(&mut self) -> Result<&'a str, TinySsError> {
+        self.skip_whitespace();
+        let end = self
+            .rest
+            .find(|c: char| !(c.is_alphanumeric() || "_-.@".contains(c)))
+            .unwrap_or(self.rest.len());
+        if end == 0 {
+            return Err(TinySsError::InvalidPolicy);
+        }
+        let (word, rest) = self.rest.split_at(end);
+        self.rest = rest;
+        Ok(word)
+    }
+
+    fn expect(&mut self, c: char) -> Result<(), TinySsError> {
+        self.skip_whitespace();
+        self.rest = self
+            .rest
+            .strip_prefix(c)
+            .ok_or(TinySsError::InvalidPolicy)?;
+        Ok(())
+    }
+
+    fn skip_whitespace(&mut self) {
+        self.rest = self.rest.trim_start();
+    }
+}
+
+/// A share dealt to the participant at one leaf of a policy.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PolicyShare<V = num_bigint::BigInt> {
+    name: String,
+    /// positions of the gates' children leading to the leaf
+    path: Vec<usize>,
+    value: V,
+}
+
+impl<V: fmt::Debug> fmt::Debug for PolicyShare<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PolicyShare")
+            .field("name", &self.name)
+            .field("path", &self.path)
+            .field("value", crate::debug::secret(&self.value))
+            .finish()
+    }
+}
+
+impl<V> PolicyShare<V> {
+    pub fn new(name: &str, path: Vec<usize>, value: V) -> Self {
+        PolicyShare {
+            name: String::from(name),
+            path,
+            value,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn path(&self) -> &[usize] {
+        &self.path
+    }
+
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+}
+
+/// A policy compiled to nested Shamir sharings over `F`.
+#[derive(Clone, Debug)]
+pub struct PolicySharing<F: Field = PrimeField> {
+    policy: Policy,
+    field: F,
+}
+
+impl<F: Field> PolicySharing<F> {
+    /// Checks every gate of `policy` against `field`.
+    pub fn new(policy: Policy, field: F) -> Result<Self, TinySsError> {
+        policy.validate(&field)?;
+        Ok(PolicySharing { policy, field })
+    }
+
+    pub fn policy(&self) -> &Policy {
+        &self.policy
+    }
+
+    pub fn field(&self) -> &F {
+        &self.field
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: F::Elem) -> Vec<PolicyShare<F::Elem>> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, || self.field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Splits `secret` into one share per leaf, in the order the leaves
+    /// appear, drawing the coefficients of every gate from `sample`, which
+    /// must return uniformly random field elements.
+    pub fn split_with<S>(&self, secret: F::Elem, mut sample: S) -> Vec<PolicyShare<F::Elem>>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let mut shares = Vec::new();
+        self.deal(
+            &self.policy,
+            &mut Vec::new(),
+            secret,
+            &mut sample,
+            &mut shares,
+        );
+        shares
+    }
+
+    /// Recovers the secret from the shares of a set of participants
+    /// satisfying the policy.
+    ///
+    /// Only the first share for every leaf is used.
+    pub fn recover(&self, shares: &[PolicyShare<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        if shares
+            .iter()
+            .any(|s| self.policy.leaf(&s.path) != Some(s.name.as_str()))
+        {
+            return Err(TinySsError::InvalidParticipant);
+        }
+        self.combine(&self.policy, &mut Vec::new(), shares)
+    }
+
+    fn deal<S>(
+        &self,
+        policy: &Policy,
+        path: &mut Vec<usize>,
+        secret: F::Elem,
+        sample: &mut S,
+        shares: &mut Vec<PolicyShare<F::Elem>>,
+    ) where
+        S: FnMut() -> F::Elem,
+    {
+        match policy {
+            Policy::Participant(name) => shares.push(PolicyShare::new(name, path.clone(), secret)),
+            Policy::Threshold(t, policies) => {
+                let mut polynomial = vec![secret];
+                polynomial.extend((1..*t).map(|_| sample()));
+                for (i, child) in policies.iter().enumerate() {
+                    let x = self.field.elem_from_index(i + 1);
+                    let value = poly::evaluate(&self.field, &polynomial, &x);
+                    path.push(i);
+                    self.deal(child, path, value, sample, shares);
+                    path.pop();
+                }
+            }
+        }
+    }
+
+    fn combine(
+        &self,
+        policy: &Policy,
+        path: &mut Vec<usize>,
+        shares: &[PolicyShare<F::Elem>],
+    ) -> Result<F::Elem, TinySsError> {
+        match policy {
+            Policy::Participant(_) => shares
+                .iter()
+                .find(|s| s.path == *path)
+                .map(|s| s.value.clone())
+                .ok_or(TinySsError::NotEnoughShares {
+                    required: 1,
+                    got: 0,
+                }),
+            Policy::Threshold(t, policies) => {
+                let mut xs = Vec::with_capacity(*t);
+                let mut ys = Vec::with_capacity(*t);
+                for (i, child) in policies.iter().enumerate() {
+                    if ys.len() == *t {
+                        break;
+                    }
+                    path.push(i);
+                    if let Ok(value) = self.combine(child, path, shares) {
+                        xs.push(self.field.elem_from_index(i + 1));
+                        ys.push(value);
+                    }
+                    path.pop();
+                }
+                if ys.len() < *t {
+                    return Err(TinySsError::NotEnoughShares {
+                        required: *t,
+                        got: ys.len(),
+                    });
+                }
+                poly::interpolate(&self.field, &xs, &ys, &self.field.zero())
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn parse_test() {
+        let policy: Policy = "and(ceo, 2 of (alice, bob, carol))".parse().unwrap();
+        assert_eq!(
+            policy,
+            Policy::and(vec![
+                Policy::participant("ceo"),
+                Policy::threshold(
+                    2,
+                    vec![
+                        Policy::participant("alice"),
+                        Policy::participant("bob"),
+                        Policy::participant("carol"),
+                    ]
+                ),
+            ])
+        );
+        assert_eq!(policy.to_string().parse::<Policy>().unwrap(), policy);
+        assert_eq!(
+            "or(root, and(ops, audit))"
+                .parse::<Policy>()
+                .unwrap()
+                .to_string(),
+            "or(root, and(ops, audit))"
+        );
+        for bad in ["", "and(", "and()", "or(a b)", "2 (a, b)", "a)"] {
+            assert_eq!(bad.parse::<Policy>(), Err(TinySsError::InvalidPolicy));
+        }
+    }
+
+    #[test]
+    fn policy_test() {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        let policy = "or(root, and(ceo, 2 of (alice, bob, root)))"
+            .parse()
+            .unwrap();
+        let sharing = PolicySharing::new(policy, field).unwrap();
+        assert!(sharing.policy().is_satisfied_by(&["ceo", "bob", "alice"]));
+        assert!(!sharing.policy().is_satisfied_by(&["ceo", "bob"]));
+
+        let mut next = 3;
+        let shares = sharing.split_with(BigInt::from(1234), || {
+            next += 404;
+            BigInt::from(next)
+        });
+        assert_eq!(shares.len(), 5);
+        assert_eq!(shares[4].name(), "root");
+        assert_eq!(shares[4].path(), [1, 1, 2]);
+        #[cfg(not(feature = "danger_debug"))]
+        assert!(alloc::format!("{:?}", shares[4]).contains("[REDACTED]"));
+
+        let of = |names: &[&str]| -> Vec<PolicyShare> {
+            shares
+                .iter()
+                .filter(|s| names.contains(&s.name()))
+                .cloned()
+                .collect()
+        };
+        let secret = BigInt::from(1234);
+        assert_eq!(sharing.recover(&of(&["root"])).unwrap(), secret);
+        assert_eq!(
+            sharing.recover(&of(&["ceo", "bob", "alice"])).unwrap(),
+            secret
+        );
+        assert_eq!(
+            sharing.recover(&of(&["ceo", "bob"])).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 1,
+                got: 0
+            }
+        );
+
+        // shares are bound to their leaf
+        let moved = PolicyShare::new("bob", vec![0], shares[2].value().clone());
+        assert_eq!(
+            sharing.recover(&[moved]).unwrap_err(),
+            TinySsError::InvalidParticipant
+        );
+    }
+
+    #[test]
+    fn gate_test() {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        let policy = Policy::threshold(3, vec![Policy::participant("a"), Policy::participant("b")]);
+        assert_eq!(
+            PolicySharing::new(policy, field).unwrap_err(),
+            TinySsError::InvalidThreshold
+        );
+    }
+}
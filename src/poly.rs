@@ -0,0 +1,189 @@
+// Polynomial arithmetic shared by every `Field` backend.
+
+use crate::{fast, field::Field, Share, TinySsError};
+use alloc::vec::Vec;
+
+// Horner's rule, coefficients ordered from degree 0 upwards. The loop runs
+// the same operations whatever the coefficients; their timing is the
+// field's, constant only for `CtField`
+pub(crate) fn evaluate<F: Field>(field: &F, polynomial: &[F::Elem], x: &F::Elem) -> F::Elem {
+    let mut sum = field.zero();
+    for item in polynomial.iter().rev() {
+        field.mul_assign(&mut sum, x);
+        field.add_assign(&mut sum, item);
+    }
+    sum
+}
+
+// The Lagrange basis polynomials through `xs`, evaluated at `x`; they are
+// computed from the indices alone, which are public
+pub(crate) fn lagrange_coefficients<F: Field>(
+    field: &F,
+    xs: &[F::Elem],
+    x: &F::Elem,
+) -> Result<Vec<F::Elem>, TinySsError> {
+    let offsets: Vec<F::Elem> = xs.iter().map(|xj| field.sub(x, xj)).collect();
+    let denominators = if xs.len() >= fast::TREE_THRESHOLD {
+        fast::denominators(field, xs)
+    } else {
+        xs.iter()
+            .enumerate()
+            .map(|(item, xi)| {
+                let mut denominator = field.one();
+                for (_, xj) in xs.iter().enumerate().filter(|(j, _)| *j != item) {
+                    field.mul_assign(&mut denominator, &field.sub(xi, xj));
+                }
+                denominator
+            })
+            .collect()
+    };
+    let mut coefficients = batch_inverse(field, &denominators).ok_or(TinySsError::NotInvertible)?;
+    // the numerators prod_{j != i} (x - x_j), as the products of the
+    // offsets before and after i
+    let mut before = field.one();
+    for (coefficient, offset) in coefficients.iter_mut().zip(&offsets) {
+        field.mul_assign(coefficient, &before);
+        field.mul_assign(&mut before, offset);
+    }
+    let mut after = field.one();
+    for (coefficient, offset) in coefficients.iter_mut().zip(&offsets).rev() {
+        field.mul_assign(coefficient, &after);
+        field.mul_assign(&mut after, offset);
+    }
+    Ok(coefficients)
+}
+
+// The inverses of all `values` by Montgomery's trick: one inversion of their
+// product and three multiplications per value; `None` if any has none
+pub(crate) fn batch_inverse<F: Field>(field: &F, values: &[F::Elem]) -> Option<Vec<F::Elem>> {
+    // prefixes[i] = values[0] * ... * values[i - 1]
+    let mut prefixes: Vec<F::Elem> = Vec::with_capacity(values.len());
+    let mut product = field.one();
+    for value in values {
+        prefixes.push(product.clone());
+        field.mul_assign(&mut product, value);
+    }
+    // (values[0] * ... * values[i])^-1, walking i down
+    let mut inverse = field.inv(&product)?;
+    let mut inverses = prefixes;
+    for (prefix, value) in inverses.iter_mut().zip(values).rev() {
+        field.mul_assign(prefix, &inverse);
+        field.mul_assign(&mut inverse, value);
+    }
+    Some(inverses)
+}
+
+// Lagrange interpolation algorithm for polynomial evaluation; the share
+// values only meet field operations, constant time only for `CtField`
+pub(crate) fn interpolate<F: Field>(
+    field: &F,
+    xs: &[F::Elem],
+    ys: &[F::Elem],
+    x: &F::Elem,
+) -> Result<F::Elem, TinySsError> {
+    let mut sum = field.zero();
+    for (mut term, y) in lagrange_coefficients(field, xs, x)?.into_iter().zip(ys) {
+        field.mul_assign(&mut term, y);
+        field.add_assign(&mut sum, &term);
+    }
+    Ok(sum)
+}
+
+// Deduplicates shares by index, rejecting conflicting values and sets
+// smaller than the threshold
+pub(crate) fn distinct_shares<V: PartialEq>(
+    shares: &[Share<V>],
+    t: usize,
+) -> Result<Vec<&Share<V>>, TinySsError> {
+    let mut unique: Vec<&Share<V>> = Vec::with_capacity(shares.len());
+    for share in shares {
+        match unique.iter().find(|s| s.index() == share.index()) {
+            Some(s) if s.value() != share.value() => {
+                return Err(TinySsError::ConflictingShares {
+                    index: share.index(),
+                });
+            }
+            Some(_) => {}
+            None => unique.push(share),
+        }
+    }
+    if unique.len() < t {
+        return Err(TinySsError::NotEnoughShares {
+            required: t,
+            got: unique.len(),
+        });
+    }
+    Ok(unique)
+}
+
+// `distinct_shares` for shares interpolated over `field`, rejecting with
+// `InvalidIndex` the indices that are zero or that the field cannot tell
+// apart, which would otherwise alias another x-coordinate
+pub(crate) fn distinct_field_shares<'a, F: Field, V: PartialEq>(
+    field: &F,
+    shares: &'a [Share<V>],
+    t: usize,
+) -> Result<Vec<&'a Share<V>>, TinySsError> {
+    if shares
+        .iter()
+        .any(|share| share.index() == 0 || !field.has_indices(share.index()))
+    {
+        return Err(TinySsError::InvalidIndex);
+    }
+    distinct_shares(shares, t)
+}
+
+// Gaussian elimination for the square system `matrix * x = rhs`, failing
+// with `NotInvertible` if the matrix is singular
+pub(crate) fn solve<F: Field>(
+    field: &F,
+    mut matrix: Vec<Vec<F::Elem>>,
+    mut rhs: Vec<F::Elem>,
+) -> Result<Vec<F::Elem>, TinySsError> {
+    let size = rhs.len();
+    for col in 0..size {
+        let pivot = (col..size)
+            .find(|&row| matrix[row][col] != field.zero())
+            .ok_or(TinySsError::NotInvertible)?;
+        matrix.swap(col, pivot);
+        rhs.swap(col, pivot);
+        let inverse = field
+            .inv(&matrix[col][col])
+            .ok_or(TinySsError::NotInvertible)?;
+        for row in 0..size {
+            if row == col || matrix[row][col] == field.zero() {
+                continue;
+            }
+            let factor = field.mul(&matrix[row][col], &inverse);
+            let pivot_row = matrix[col].clone();
+            for (entry, p) in matrix[row].iter_mut().zip(&pivot_row).skip(col) {
+                *entry = field.sub(entry, &field.mul(&factor, p));
+            }
+            let delta = field.mul(&factor, &rhs[col]);
+            rhs[row] = field.sub(&rhs[row], &delta);
+        }
+    }
+    Ok((0..size)
+        .map(|i| field.mul(&rhs[i], &field.inv(&matrix[i][i]).expect("nonzero pivot")))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::PrimeField;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn batch_inverse_test() {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        let values = [3, 1612, 166, 1].map(BigInt::from);
+        let inverses = batch_inverse(&field, &values).unwrap();
+        for (value, inverse) in values.iter().zip(&inverses) {
+            assert_eq!(Some(inverse.clone()), field.inv(value));
+        }
+        assert_eq!(batch_inverse(&field, &[]), Some(Vec::new()));
+        let singular = [3, 0, 5].map(BigInt::from);
+        assert_eq!(batch_inverse(&field, &singular), None);
+    }
+}
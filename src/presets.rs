@@ -0,0 +1,171 @@
+//! Moduli of standard curves and Mersenne primes.
+//!
+//! Keys of an existing curve are shared modulo the order of its group, so
+//! that shares and recovered keys are valid scalars of that curve. The
+//! constants are big-endian hexadecimal, as they appear in the standards.
+//! Being known primes, they skip the primality test of `SecretShare::new`.
+//!
+//! `SecretShare::for_secret_bytes` picks the smallest prime above every
+//! secret of a byte length, from a table of the offsets `c` of the smallest
+//! primes `2^(8 len) + c`.
+
+use crate::{
+    field::{PrimeField, SmallPrimeField},
+    SecretShare, TinySsError,
+};
+use num_bigint::BigInt;
+use num_traits::One;
+
+/// order of the secp256k1 group, from SEC 2
+pub const SECP256K1_ORDER: &str =
+    "fffffffffffffffffffffffffffffffebaaedce6af48a03bbfd25e8cd0364141";
+
+/// order of the P-256 group, from FIPS 186-4
+pub const P256_ORDER: &str = "ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551";
+
+/// order `2^252 + 27742317777372353535851937790883648493` of the prime
+/// subgroup of edwards25519, from RFC 8032, which Ristretto255 shares
+pub const ED25519_ORDER: &str = "1000000000000000000000000000000014def9dea2f79cd65812631a5cf5d3ed";
+
+/// longest secret `SecretShare::for_secret_bytes` picks a modulus for
+pub const MAX_SECRET_BYTES: usize = 64;
+
+// c for the smallest prime 2^(8 len) + c, for len in 1..=64
+const NEXT_PRIME_OFFSETS: [u16; MAX_SECRET_BYTES] = [
+    1, 1, 43, 15, 15, 21, 81, 13, 15, 13, 7, 61, 111, 25, 451, 51, 85, 175, 253, 7, 87, 427, 27,
+    133, 235, 375, 423, 735, 357, 115, 81, 297, 175, 57, 45, 127, 61, 37, 91, 27, 15, 241, 231, 55,
+    105, 127, 115, 231, 207, 181, 37, 235, 163, 1093, 187, 211, 21, 841, 445, 165, 777, 583, 133,
+    75,
+];
+
+/// Parses one of the constants of this module.
+pub fn modulus(hex: &str) -> BigInt {
+    BigInt::parse_bytes(hex.as_bytes(), 16).expect("the preset is valid hexadecimal")
+}
+
+// 2^bits - 1
+fn mersenne(bits: u64) -> BigInt {
+    (BigInt::one() << bits) - BigInt::one()
+}
+
+impl SecretShare {
+    /// A `t`-of-`n` scheme over the secp256k1 scalars.
+    pub fn secp256k1_order(t: usize, n: usize) -> Result<Self, TinySsError> {
+        SecretShare::preset(t, n, modulus(SECP256K1_ORDER))
+    }
+
+    /// A `t`-of-`n` scheme over the P-256 scalars.
+    pub fn p256(t: usize, n: usize) -> Result<Self, TinySsError> {
+        SecretShare::preset(t, n, modulus(P256_ORDER))
+    }
+
+    /// A `t`-of-`n` scheme over the Ed25519 scalars.
+    ///
+    /// An Ed25519 private key is a seed hashed into the scalar, so it is the
+    /// clamped scalar, reduced modulo the order, that is shared here.
+    pub fn ed25519(t: usize, n: usize) -> Result<Self, TinySsError> {
+        SecretShare::preset(t, n, modulus(ED25519_ORDER))
+    }
+
+    /// A `t`-of-`n` scheme modulo the Mersenne prime `2^127 - 1`.
+    pub fn mersenne127(t: usize, n: usize) -> Result<Self, TinySsError> {
+        SecretShare::preset(t, n, mersenne(127))
+    }
+
+    /// A `t`-of-`n` scheme modulo the Mersenne prime `2^521 - 1`.
+    pub fn mersenne521(t: usize, n: usize) -> Result<Self, TinySsError> {
+        SecretShare::preset(t, n, mersenne(521))
+    }
+
+    /// A `t`-of-`n` scheme over the smallest prime larger than every secret
+    /// of `len` bytes, read as a big-endian integer; `len` must lie in
+    /// `1..=MAX_SECRET_BYTES`.
+    ///
+    /// The prime exceeds `2^(8 len)` only slightly, so its encoded elements,
+    /// and with them the shares, take `len + 1` bytes.
+    pub fn for_secret_bytes(len: usize, t: usize, n: usize) -> Result<Self, TinySsError> {
+        if len == 0 || len > MAX_SECRET_BYTES {
+            return Err(TinySsError::SecretOutOfRange);
+        }
+        let p = (BigInt::one() << (8 * len)) + BigInt::from(NEXT_PRIME_OFFSETS[len - 1]);
+        SecretShare::preset(t, n, p)
+    }
+
+    fn preset(t: usize, n: usize, p: BigInt) -> Result<Self, TinySsError> {
+        SecretShare::with_field(t, n, PrimeField::new(p)?)
+    }
+}
+
+impl SecretShare<SmallPrimeField> {
+    /// A `t`-of-`n` scheme modulo the Mersenne prime `2^61 - 1` in native
+    /// integers.
+    pub fn mersenne61(t: usize, n: usize) -> Result<Self, TinySsError> {
+        SecretShare::with_field(t, n, SmallPrimeField::new(SmallPrimeField::MAX_MODULUS)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prime;
+
+    #[test]
+    fn presets_test() {
+        let schemes = [
+            SecretShare::secp256k1_order(2, 3).unwrap(),
+            SecretShare::p256(2, 3).unwrap(),
+            SecretShare::ed25519(2, 3).unwrap(),
+            SecretShare::mersenne127(2, 3).unwrap(),
+            SecretShare::mersenne521(2, 3).unwrap(),
+        ];
+        let bits = [256, 256, 253, 127, 521];
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut fill = |buf: &mut [u8]| {
+            for b in buf {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                *b = state as u8;
+            }
+        };
+        for (ss, bits) in schemes.iter().zip(bits) {
+            assert_eq!(ss.p().bits(), bits);
+            assert!(prime::is_probable_prime_with(ss.p(), 8, &mut fill));
+            let shares = ss
+                .split_with_coefficients(BigInt::from(777), &[ss.p() - BigInt::one()])
+                .unwrap();
+            assert_eq!(ss.recover(&shares[1..]).unwrap(), BigInt::from(777));
+        }
+        assert_eq!(
+            SecretShare::ed25519(3, 3).unwrap_err(),
+            TinySsError::InvalidThreshold
+        );
+        for len in 1..=MAX_SECRET_BYTES {
+            let ss = SecretShare::for_secret_bytes(len, 2, 3).unwrap();
+            let largest = (BigInt::one() << (8 * len)) - BigInt::one();
+            assert!(ss.p() > &largest);
+            assert!(prime::is_probable_prime_with(ss.p(), 2, &mut fill));
+        }
+        // 2^32 + 15 and 2^256 + 297 are the smallest primes of their kind
+        assert_eq!(
+            SecretShare::for_secret_bytes(4, 2, 3).unwrap().p(),
+            &BigInt::from(4_294_967_311u64)
+        );
+        assert_eq!(
+            SecretShare::for_secret_bytes(32, 2, 3).unwrap().p() - (BigInt::one() << 256),
+            BigInt::from(297)
+        );
+        assert_eq!(
+            SecretShare::for_secret_bytes(65, 2, 3).unwrap_err(),
+            TinySsError::SecretOutOfRange
+        );
+        assert_eq!(
+            SecretShare::for_secret_bytes(0, 2, 3).unwrap_err(),
+            TinySsError::SecretOutOfRange
+        );
+        assert_eq!(
+            SecretShare::mersenne61(2, 3).unwrap().field().p(),
+            (1 << 61) - 1
+        );
+    }
+}
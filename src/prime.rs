@@ -0,0 +1,254 @@
+//! Primality testing and generation of moduli.
+//!
+//! A composite modulus leaves elements without inverses and gives the
+//! shares less entropy than the field suggests. `is_probable_prime_with`
+//! runs trial division by the primes below 100, then the Miller-Rabin test
+//! to the bases 2 to 37, which no composite below 3.3 * 10^24 passes, then
+//! to `rounds` random bases, each of which lets a composite pass with
+//! probability at most 1/4. With the `primality` feature, the constructors
+//! of `BigInt` and `u64` moduli schemes reject moduli failing the test.
+//!
+//! `generate_prime` and `generate_safe_prime` draw fresh moduli of a given
+//! bit length. Safe primes `p = 2q + 1` are far rarer than primes, so
+//! generating one of 2048 bits takes minutes rather than seconds.
+
+use crate::TinySsError;
+use alloc::vec;
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
+
+/// random bases tried by `is_probable_prime` and the generators, for an
+/// error probability below 2^-80 on adversarial input
+pub const ROUNDS: usize = 40;
+
+const SMALL_PRIMES: [u32; 25] = [
+    2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53, 59, 61, 67, 71, 73, 79, 83, 89, 97,
+];
+
+// 3317044064679887385961981, the smallest composite passing the bases up to
+// 37
+const DETERMINISTIC_BOUND: &[u8] = b"3317044064679887385961981";
+
+#[cfg(feature = "rand")]
+pub fn is_probable_prime(n: &BigInt) -> bool {
+    use rand::RngCore;
+    let mut rng = rand::thread_rng();
+    is_probable_prime_with(n, ROUNDS, &mut |buf| rng.fill_bytes(buf))
+}
+
+/// Whether `n` is prime, up to the Miller-Rabin error on `rounds` bases
+/// drawn from the uniformly random bytes written by `fill_random`; below
+/// 3.3 * 10^24 the answer is exact and no randomness is drawn.
+pub fn is_probable_prime_with(
+    n: &BigInt,
+    rounds: usize,
+    fill_random: &mut dyn FnMut(&mut [u8]),
+) -> bool {
+    if *n < BigInt::from(2) {
+        return false;
+    }
+    for &p in &SMALL_PRIMES {
+        let p = BigInt::from(p);
+        if *n == p {
+            return true;
+        }
+        if (n % &p).is_zero() {
+            return false;
+        }
+    }
+    // n - 1 = d * 2^s with d odd
+    let n_minus_one = n - BigInt::one();
+    let s = n_minus_one.trailing_zeros().expect("n is larger than 1");
+    let d = &n_minus_one >> s;
+    if !SMALL_PRIMES[..12]
+        .iter()
+        .all(|&a| passes(n, &d, s, &BigInt::from(a)))
+    {
+        return false;
+    }
+    let bound = BigInt::parse_bytes(DETERMINISTIC_BOUND, 10).expect("the bound is decimal");
+    if *n < bound {
+        return true;
+    }
+    // bases in [2, n - 2]
+    let span = n - BigInt::from(3);
+    (0..rounds).all(|_| {
+        let a = crate::crt::random_below(&span, fill_random) + BigInt::from(2);
+        passes(n, &d, s, &a)
+    })
+}
+
+// Whether n passes the Miller-Rabin test to the base a
+fn passes(n: &BigInt, d: &BigInt, s: u64, a: &BigInt) -> bool {
+    let n_minus_one = n - BigInt::one();
+    let mut x = a.modpow(d, n);
+    if x.is_one() || x == n_minus_one {
+        return true;
+    }
+    for _ in 1..s {
+        x = &x * &x % n;
+        if x == n_minus_one {
+            return true;
+        }
+    }
+    false
+}
+
+#[cfg(feature = "rand")]
+pub fn generate_prime(bits: u64) -> Result<BigInt, TinySsError> {
+    use rand::RngCore;
+    let mut rng = rand::thread_rng();
+    generate_prime_with(bits, &mut |buf| rng.fill_bytes(buf))
+}
+
+/// A random prime of exactly `bits` bits, at least 2, drawn from the
+/// uniformly random bytes written by `fill_random`.
+pub fn generate_prime_with(
+    bits: u64,
+    fill_random: &mut dyn FnMut(&mut [u8]),
+) -> Result<BigInt, TinySsError> {
+    if bits < 2 {
+        return Err(TinySsError::InvalidModulus);
+    }
+    loop {
+        let candidate = random_odd(bits, fill_random);
+        if is_probable_prime_with(&candidate, ROUNDS, fill_random) {
+            return Ok(candidate);
+        }
+    }
+}
+
+#[cfg(feature = "rand")]
+pub fn generate_safe_prime(bits: u64) -> Result<BigInt, TinySsError> {
+    use rand::RngCore;
+    let mut rng = rand::thread_rng();
+    generate_safe_prime_with(bits, &mut |buf| rng.fill_bytes(buf))
+}
+
+/// A random safe prime `p = 2q + 1`, with `q` prime, of exactly `bits`
+/// bits, at least 3, drawn from the bytes written by `fill_random`.
+pub fn generate_safe_prime_with(
+    bits: u64,
+    fill_random: &mut dyn FnMut(&mut [u8]),
+) -> Result<BigInt, TinySsError> {
+    if bits < 3 {
+        return Err(TinySsError::InvalidModulus);
+    }
+    loop {
+        let q = random_odd(bits - 1, fill_random);
+        let p = (&q << 1) + BigInt::one();
+        // p is tested first, as it fails a cheap trial division more often
+        if is_probable_prime_with(&p, ROUNDS, fill_random)
+            && is_probable_prime_with(&q, ROUNDS, fill_random)
+        {
+            return Ok(p);
+        }
+    }
+}
+
+// An odd value of exactly `bits` bits, at least 2
+fn random_odd(bits: u64, fill_random: &mut dyn FnMut(&mut [u8])) -> BigInt {
+    let mut bytes = vec![0u8; bits.div_ceil(8) as usize];
+    fill_random(&mut bytes);
+    let top = ((bits - 1) % 8) as u32;
+    bytes[0] &= 0xff >> (7 - top);
+    bytes[0] |= 1 << top;
+    let last = bytes.len() - 1;
+    bytes[last] |= 1;
+    BigInt::from_bytes_be(Sign::Plus, &bytes)
+}
+
+// Rejects a composite modulus if the `primality` feature is enabled
+pub(crate) fn check_modulus(p: &BigInt) -> Result<(), TinySsError> {
+    #[cfg(feature = "primality")]
+    if !is_probable_prime(p) {
+        return Err(TinySsError::CompositeModulus);
+    }
+    #[cfg(not(feature = "primality"))]
+    let _ = p;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+
+    fn no_randomness(_: &mut [u8]) {
+        panic!("drew randomness below the deterministic bound")
+    }
+
+    #[test]
+    fn is_probable_prime_test() {
+        let primes = [2u64, 3, 97, 101, 1613, 65537, (1 << 61) - 1];
+        for p in primes {
+            assert!(is_probable_prime_with(
+                &BigInt::from(p),
+                0,
+                &mut no_randomness
+            ));
+        }
+        // 561 is a Carmichael number, 3215031751 a strong pseudoprime to the
+        // bases 2, 3, 5 and 7
+        let composites = [0u64, 1, 4, 561, 1612, 3215031751, 1 << 61];
+        for c in composites {
+            assert!(!is_probable_prime_with(
+                &BigInt::from(c),
+                0,
+                &mut no_randomness
+            ));
+        }
+        assert!(!is_probable_prime_with(
+            &BigInt::from(-7),
+            0,
+            &mut no_randomness
+        ));
+
+        // beyond the bound, where the random bases are drawn
+        let mersenne = (BigInt::one() << 127) - BigInt::one();
+        let mut fill = counter(89);
+        assert!(is_probable_prime_with(&mersenne, 8, &mut fill));
+        assert!(!is_probable_prime_with(
+            &(&mersenne * BigInt::from(1613)),
+            8,
+            &mut fill
+        ));
+        // the deterministic bases alone let this strong pseudoprime through
+        let pseudoprime = BigInt::parse_bytes(DETERMINISTIC_BOUND, 10).unwrap();
+        assert!(!is_probable_prime_with(&pseudoprime, 8, &mut fill));
+    }
+
+    #[test]
+    fn generate_test() {
+        // xorshift, as a byte counter repeats too soon to reach a prime
+        let mut state = 0x2545_f491_4f6c_dd1du64;
+        let mut fill = |buf: &mut [u8]| {
+            for b in buf {
+                state ^= state << 13;
+                state ^= state >> 7;
+                state ^= state << 17;
+                *b = state as u8;
+            }
+        };
+        for bits in [2, 3, 17, 64, 100] {
+            let p = generate_prime_with(bits, &mut fill).unwrap();
+            assert_eq!(p.bits(), bits);
+            assert!(is_probable_prime_with(&p, ROUNDS, &mut fill));
+        }
+        for bits in [3, 5, 40, 90] {
+            let p = generate_safe_prime_with(bits, &mut fill).unwrap();
+            assert_eq!(p.bits(), bits);
+            let q: BigInt = (&p - BigInt::one()) >> 1;
+            assert!(is_probable_prime_with(&p, ROUNDS, &mut fill));
+            assert!(is_probable_prime_with(&q, ROUNDS, &mut fill));
+        }
+        assert_eq!(
+            generate_prime_with(1, &mut fill),
+            Err(TinySsError::InvalidModulus)
+        );
+        assert_eq!(
+            generate_safe_prime_with(2, &mut fill),
+            Err(TinySsError::InvalidModulus)
+        );
+    }
+}
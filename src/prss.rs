@@ -0,0 +1,229 @@
+//! Pseudo-random secret sharing.
+//!
+//! Following Cramer, Damgård and Ishai, a setup hands one key to every set
+//! `A` of `n - t + 1` holders, that is, to the complement of every maximal
+//! unqualified set. For each such `A`, the polynomial `f_A` of degree `t - 1`
+//! with `f_A(0) = 1` that vanishes on the holders outside `A` is public. With
+//! a fresh public nonce, holder `i` computes its share
+//! `sum over A containing i of PRF(key_A, nonce) * f_A(i)` on its own, and the
+//! shares lie on a degree `t - 1` polynomial through the pseudorandom value
+//! `sum over A of PRF(key_A, nonce)`. Sharings of zero come out the same
+//! way, on polynomials of degree `2 (t - 1)`, as used to re-randomize the
+//! product of two sharings.
+//!
+//! Every holder keeps `C(n - 1, t - 1)` keys, so this suits small `n` only.
+
+use crate::{field::Field, replicated::subsets, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+use core::fmt;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+/// bytes of a set key
+pub const KEY_BYTES: usize = 32;
+
+/// The keys of one holder, one per set of holders it belongs to.
+#[derive(Clone, PartialEq, Eq)]
+pub struct PrssKeys {
+    index: usize,
+    /// each set in increasing index order, with its key
+    keys: Vec<(Vec<usize>, [u8; KEY_BYTES])>,
+}
+
+impl fmt::Debug for PrssKeys {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let keys: Vec<_> = self
+            .keys
+            .iter()
+            .map(|(set, key)| (set, crate::debug::secret(key)))
+            .collect();
+        f.debug_struct("PrssKeys")
+            .field("index", &self.index)
+            .field("keys", &keys)
+            .finish()
+    }
+}
+
+impl PrssKeys {
+    pub fn new(index: usize, keys: Vec<(Vec<usize>, [u8; KEY_BYTES])>) -> Self {
+        PrssKeys { index, keys }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// each set in increasing index order, with its key
+    pub fn keys(&self) -> &[(Vec<usize>, [u8; KEY_BYTES])] {
+        &self.keys
+    }
+}
+
+/// Pseudo-random sharing for a `t`-of-`n` Shamir scheme.
+#[derive(Clone, Debug)]
+pub struct Prss<F: Field = crate::field::PrimeField> {
+    scheme: SecretShare<F>,
+}
+
+impl<F: Field> Prss<F> {
+    pub fn new(scheme: SecretShare<F>) -> Self {
+        Prss { scheme }
+    }
+
+    /// The Shamir scheme the shares belong to.
+    pub fn shamir(&self) -> &SecretShare<F> {
+        &self.scheme
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn deal_keys(&self) -> Vec<PrssKeys> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.deal_keys_with(&mut |buf| rng.fill_bytes(buf))
+    }
+
+    /// Draws a key for every set of `n - t + 1` holders from `fill_random`,
+    /// which must fill the buffer with uniformly random bytes, and hands
+    /// each holder the keys of its sets.
+    pub fn deal_keys_with(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> Vec<PrssKeys> {
+        let n = self.scheme.n();
+        let mut holders: Vec<PrssKeys> = (1..=n).map(|i| PrssKeys::new(i, Vec::new())).collect();
+        for set in subsets(n, n - self.scheme.t() + 1) {
+            let mut key = [0u8; KEY_BYTES];
+            fill_random(&mut key);
+            for &i in &set {
+                holders[i - 1].keys.push((set.clone(), key));
+            }
+        }
+        holders
+    }
+
+    /// The holder's share of the pseudorandom value for `nonce`, on a
+    /// polynomial of degree `t - 1`.
+    pub fn share(&self, keys: &PrssKeys, nonce: &[u8]) -> Result<Share<F::Elem>, TinySsError> {
+        let field = self.scheme.field();
+        let value = self.combine(keys, |set, key| {
+            let r = self.prf(key, b"prss", nonce, 0);
+            Ok(field.mul(&r, &self.vanishing(set, keys.index)?))
+        })?;
+        Ok(Share::new(keys.index, value))
+    }
+
+    /// The holder's share of zero for `nonce`, on a polynomial of degree
+    /// `2 (t - 1)`; `2 t - 1` of these shares recover it.
+    pub fn zero_share(&self, keys: &PrssKeys, nonce: &[u8]) -> Result<Share<F::Elem>, TinySsError> {
+        let field = self.scheme.field();
+        let x = field.elem_from_index(keys.index);
+        let value = self.combine(keys, |set, key| {
+            // sum of r_l x^l for l in 1..t, which is 0 at x = 0
+            let mut power = field.one();
+            let mut sum = field.zero();
+            for l in 1..self.scheme.t() {
+                power = field.mul(&power, &x);
+                let r = self.prf(key, b"przs", nonce, l as u64);
+                sum = field.add(&sum, &field.mul(&r, &power));
+            }
+            Ok(field.mul(&sum, &self.vanishing(set, keys.index)?))
+        })?;
+        Ok(Share::new(keys.index, value))
+    }
+
+    // Sums `term` over the holder's sets, after checking its keys fit
+    fn combine<T>(&self, keys: &PrssKeys, mut term: T) -> Result<F::Elem, TinySsError>
+    where
+        T: FnMut(&[usize], &[u8; KEY_BYTES]) -> Result<F::Elem, TinySsError>,
+    {
+        let (t, n) = (self.scheme.t(), self.scheme.n());
+        if keys.index == 0 || keys.index > n {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let field = self.scheme.field();
+        let mut sum = field.zero();
+        for (set, key) in &keys.keys {
+            if set.len() != n - t + 1 || !set.contains(&keys.index) || set.iter().any(|&j| j > n) {
+                return Err(TinySsError::InvalidParticipant);
+            }
+            sum = field.add(&sum, &term(set, key)?);
+        }
+        Ok(sum)
+    }
+
+    // f_A(x) = prod over j outside A of (j - x) / j, evaluated at holder i
+    fn vanishing(&self, set: &[usize], i: usize) -> Result<F::Elem, TinySsError> {
+        let field = self.scheme.field();
+        let x = field.elem_from_index(i);
+        (1..=self.scheme.n())
+            .filter(|j| !set.contains(j))
+            .try_fold(field.one(), |acc, j| {
+                let j = field.elem_from_index(j);
+                let inv = field.inv(&j).ok_or(TinySsError::NotInvertible)?;
+                Ok(field.mul(&acc, &field.mul(&field.sub(&j, &x), &inv)))
+            })
+    }
+
+    // Field element derived from HMAC-SHA256 blocks over the nonce
+    fn prf(&self, key: &[u8; KEY_BYTES], label: &[u8], nonce: &[u8], l: u64) -> F::Elem {
+        let mut counter = 0u64;
+        self.scheme.field().random(&mut |buf| {
+            for chunk in buf.chunks_mut(32) {
+                let mut mac =
+                    Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+                mac.update(label);
+                mac.update(&(nonce.len() as u64).to_be_bytes());
+                mac.update(nonce);
+                mac.update(&l.to_be_bytes());
+                mac.update(&counter.to_be_bytes());
+                counter += 1;
+                let block = mac.finalize().into_bytes();
+                chunk.copy_from_slice(&block[..chunk.len()]);
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn prss_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        let prss = Prss::new(ss.clone());
+        let keys = prss.deal_keys_with(&mut counter(29));
+        #[cfg(not(feature = "danger_debug"))]
+        assert!(alloc::format!("{:?}", keys[0]).contains("[REDACTED]"));
+        // C(4, 2) sets of three holders contain each holder
+        assert!(keys.iter().all(|k| k.keys().len() == 6));
+
+        let shares: Vec<Share> = keys
+            .iter()
+            .map(|k| prss.share(k, b"round 1").unwrap())
+            .collect();
+        let value = ss.recover(&shares[..3]).unwrap();
+        assert_eq!(ss.recover(&shares[2..]).unwrap(), value);
+        let next: Vec<Share> = keys
+            .iter()
+            .map(|k| prss.share(k, b"round 2").unwrap())
+            .collect();
+        assert_ne!(ss.recover(&next[1..4]).unwrap(), value);
+
+        // zero sharings of a 2-of-4 scheme have degree 2 and need three shares
+        let small = Prss::new(SecretShare::new(2, 4, BigInt::from(1613)).unwrap());
+        let zeros: Vec<Share> = small
+            .deal_keys_with(&mut counter(29))
+            .iter()
+            .map(|k| small.zero_share(k, b"round 1").unwrap())
+            .collect();
+        let wide = SecretShare::new(3, 4, BigInt::from(1613)).unwrap();
+        assert_eq!(wide.recover(&zeros[1..]).unwrap(), BigInt::from(0));
+        assert!(zeros.iter().any(|s| *s.value() != BigInt::from(0)));
+
+        let foreign = PrssKeys::new(1, keys[1].keys().to_vec());
+        assert_eq!(
+            prss.share(&foreign, b"round 1"),
+            Err(TinySsError::InvalidParticipant)
+        );
+    }
+}
@@ -0,0 +1,451 @@
+//! Publicly verifiable secret sharing after Schoenmakers (CRYPTO '99).
+//!
+//! Participant `i` holds a key pair `(x_i, y_i = h^{x_i})`. The dealer
+//! commits to its polynomial with `C_j = g^{a_j}` and encrypts each share as
+//! `Y_i = y_i^{a(i)}`, proving with a DLEQ proof that `log_g X_i = log_{y_i}
+//! Y_i` for `X_i = prod_j C_j^{i^j}`. Anyone can check these proofs without
+//! learning a share. Participants decrypt `S_i = Y_i^{1/x_i} = h^{a(i)}`,
+//! again with a proof, and any `t` decrypted shares give the secret
+//! `h^{a(0)}`. Every proof draws its challenge from a transcript bound to
+//! its step and share index.
+
+use crate::{
+    field::Field,
+    group::SchnorrGroup,
+    poly,
+    transcript::{Sha256Transcript, Transcript},
+    SecretShare, Share, TinySsError,
+};
+use alloc::vec::Vec;
+use num_bigint::BigInt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// domain separating the DLEQ challenges
+const DLEQ_DOMAIN: &[u8] = b"tiny-ss pvss dleq";
+
+/// labels of the statement and commitments of a DLEQ proof
+const DLEQ_LABELS: [&[u8]; 6] = [b"g1", b"h1", b"g2", b"h2", b"a1", b"a2"];
+
+/// Chaum-Pedersen proof that `log_{g1} h1 = log_{g2} h2`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct DleqProof {
+    /// challenge
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    c: BigInt,
+    /// response
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    r: BigInt,
+}
+
+impl DleqProof {
+    /// Proves knowledge of `alpha` with `h1 = g1^alpha` and `h2 = g2^alpha`
+    /// under `transcript`, using the uniformly random `nonce`.
+    pub fn prove(
+        group: &SchnorrGroup,
+        transcript: &mut dyn Transcript,
+        (g1, h1): (&BigInt, &BigInt),
+        (g2, h2): (&BigInt, &BigInt),
+        alpha: &BigInt,
+        nonce: &BigInt,
+    ) -> Self {
+        let (a1, a2) = (group.exp(g1, nonce), group.exp(g2, nonce));
+        let c = dleq_challenge(group, transcript, [g1, h1, g2, h2, &a1, &a2]);
+        let scalars = group.scalars();
+        let r = scalars.sub(nonce, &scalars.mul(alpha, &c));
+        DleqProof { c, r }
+    }
+
+    /// Checks the proof against a transcript in the state the prover used.
+    pub fn verify(
+        &self,
+        group: &SchnorrGroup,
+        transcript: &mut dyn Transcript,
+        (g1, h1): (&BigInt, &BigInt),
+        (g2, h2): (&BigInt, &BigInt),
+    ) -> bool {
+        let a1 = group.mul(&group.exp(g1, &self.r), &group.exp(h1, &self.c));
+        let a2 = group.mul(&group.exp(g2, &self.r), &group.exp(h2, &self.c));
+        self.c == dleq_challenge(group, transcript, [g1, h1, g2, h2, &a1, &a2])
+    }
+
+    /// challenge
+    pub fn c(&self) -> &BigInt {
+        &self.c
+    }
+
+    /// response
+    pub fn r(&self) -> &BigInt {
+        &self.r
+    }
+}
+
+/// A share encrypted to one participant, `Y_i = y_i^{a(i)}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EncryptedShare {
+    index: usize,
+    value: BigInt,
+    proof: DleqProof,
+}
+
+impl EncryptedShare {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    pub fn proof(&self) -> &DleqProof {
+        &self.proof
+    }
+}
+
+/// A share decrypted by its participant, `S_i = h^{a(i)}`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecryptedShare {
+    index: usize,
+    value: BigInt,
+    proof: DleqProof,
+}
+
+impl DecryptedShare {
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn value(&self) -> &BigInt {
+        &self.value
+    }
+
+    pub fn proof(&self) -> &DleqProof {
+        &self.proof
+    }
+}
+
+/// Everything the dealer publishes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Dealing {
+    /// C_j = g^{a_j}
+    commitments: Vec<BigInt>,
+    shares: Vec<EncryptedShare>,
+}
+
+impl Dealing {
+    pub fn commitments(&self) -> &[BigInt] {
+        &self.commitments
+    }
+
+    pub fn shares(&self) -> &[EncryptedShare] {
+        &self.shares
+    }
+}
+
+/// A `t`-of-`n` PVSS scheme over a Schnorr group.
+#[derive(Clone, Debug)]
+pub struct Pvss {
+    group: SchnorrGroup,
+    /// sharing over the exponents, modulo q
+    sharing: SecretShare,
+}
+
+impl Pvss {
+    pub fn new(t: usize, n: usize, group: SchnorrGroup) -> Result<Self, TinySsError> {
+        let sharing = SecretShare::new(t, n, group.q().clone())?;
+        Ok(Pvss { group, sharing })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.sharing.t()
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.sharing.n()
+    }
+
+    pub fn group(&self) -> &SchnorrGroup {
+        &self.group
+    }
+
+    /// Public key `h^x` of a participant with secret key `x`.
+    pub fn public_key(&self, secret_key: &BigInt) -> BigInt {
+        self.group.exp(self.group.h(), secret_key)
+    }
+
+    /// The group element `h^s` that recovery yields for the secret `s`.
+    pub fn secret_element(&self, secret: &BigInt) -> BigInt {
+        self.group.exp(self.group.h(), secret)
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn deal(&self, secret: BigInt, public_keys: &[BigInt]) -> Result<Dealing, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let scalars = self.group.scalars();
+        self.deal_with(secret, public_keys, || {
+            scalars.random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Shares `secret` to the holders of `public_keys`, the key at position
+    /// `i` receiving share `i + 1`. Coefficients and proof nonces are drawn
+    /// from `sample`, which must return uniformly random elements modulo q.
+    pub fn deal_with<S>(
+        &self,
+        secret: BigInt,
+        public_keys: &[BigInt],
+        mut sample: S,
+    ) -> Result<Dealing, TinySsError>
+    where
+        S: FnMut() -> BigInt,
+    {
+        if public_keys.len() != self.n() {
+            return Err(TinySsError::LengthMismatch);
+        }
+        let coefficients: Vec<BigInt> = (1..self.t()).map(|_| sample()).collect();
+        let values = self
+            .sharing
+            .split_with_coefficients(secret.clone(), &coefficients)?;
+        let group = &self.group;
+        let commitments: Vec<BigInt> = core::iter::once(&secret)
+            .chain(&coefficients)
+            .map(|a| group.exp(group.g(), a))
+            .collect();
+
+        let shares = values
+            .iter()
+            .zip(public_keys)
+            .map(|(share, y)| {
+                let x = group.exp(group.g(), share.value());
+                let value = group.exp(y, share.value());
+                let proof = DleqProof::prove(
+                    group,
+                    &mut transcript(b"encrypt", share.index()),
+                    (group.g(), &x),
+                    (y, &value),
+                    share.value(),
+                    &sample(),
+                );
+                EncryptedShare {
+                    index: share.index(),
+                    value,
+                    proof,
+                }
+            })
+            .collect();
+        Ok(Dealing {
+            commitments,
+            shares,
+        })
+    }
+
+    /// Checks every encrypted share against the commitments; anyone holding
+    /// the public keys can run this.
+    pub fn verify_dealing(
+        &self,
+        dealing: &Dealing,
+        public_keys: &[BigInt],
+    ) -> Result<(), TinySsError> {
+        if dealing.commitments.len() != self.t() {
+            return Err(TinySsError::WrongCoefficientCount {
+                expected: self.t(),
+                got: dealing.commitments.len(),
+            });
+        }
+        if dealing.shares.len() != self.n() || public_keys.len() != self.n() {
+            return Err(TinySsError::LengthMismatch);
+        }
+        let group = &self.group;
+        for (i, (share, y)) in dealing.shares.iter().zip(public_keys).enumerate() {
+            let x = group.eval_commitments(&dealing.commitments, i + 1);
+            let valid = share.index == i + 1
+                && group.is_element(&share.value)
+                && share.proof.verify(
+                    group,
+                    &mut transcript(b"encrypt", i + 1),
+                    (group.g(), &x),
+                    (y, &share.value),
+                );
+            if !valid {
+                return Err(TinySsError::InvalidShare { index: i + 1 });
+            }
+        }
+        Ok(())
+    }
+
+    /// Decrypts the share encrypted to `secret_key`, proving the decryption
+    /// correct with the uniformly random `nonce`.
+    pub fn decrypt(
+        &self,
+        share: &EncryptedShare,
+        secret_key: &BigInt,
+        nonce: &BigInt,
+    ) -> Result<DecryptedShare, TinySsError> {
+        let group = &self.group;
+        let inverse = group
+            .scalars()
+            .inv(secret_key)
+            .ok_or(TinySsError::NotInvertible)?;
+        let value = group.exp(&share.value, &inverse);
+        let y = self.public_key(secret_key);
+        // S_i^{x_i} = Y_i, so log_h y_i = log_{S_i} Y_i = x_i
+        let proof = DleqProof::prove(
+            group,
+            &mut transcript(b"decrypt", share.index),
+            (group.h(), &y),
+            (&value, &share.value),
+            secret_key,
+            nonce,
+        );
+        Ok(DecryptedShare {
+            index: share.index,
+            value,
+            proof,
+        })
+    }
+
+    /// Checks that `decrypted` is the decryption of `encrypted` under the
+    /// key belonging to `public_key`.
+    pub fn verify_decryption(
+        &self,
+        encrypted: &EncryptedShare,
+        decrypted: &DecryptedShare,
+        public_key: &BigInt,
+    ) -> Result<(), TinySsError> {
+        let group = &self.group;
+        let valid = encrypted.index == decrypted.index
+            && group.is_element(&decrypted.value)
+            && decrypted.proof.verify(
+                group,
+                &mut transcript(b"decrypt", decrypted.index),
+                (group.h(), public_key),
+                (&decrypted.value, &encrypted.value),
+            );
+        if valid {
+            Ok(())
+        } else {
+            Err(TinySsError::InvalidShare {
+                index: decrypted.index,
+            })
+        }
+    }
+
+    /// Recovers `h^s` from at least `t` decrypted shares by interpolating in
+    /// the exponent.
+    pub fn recover(&self, shares: &[DecryptedShare]) -> Result<BigInt, TinySsError> {
+        let shares: Vec<Share> = shares
+            .iter()
+            .map(|s| Share::new(s.index, s.value.clone()))
+            .collect();
+        let shares = poly::distinct_shares(&shares, self.t())?;
+        let shares = &shares[..self.t()];
+        let scalars = self.group.scalars();
+        let xs: Vec<BigInt> = shares
+            .iter()
+            .map(|s| scalars.elem_from_index(s.index()))
+            .collect();
+        let lambdas = poly::lagrange_coefficients(scalars, &xs, &scalars.zero())?;
+        Ok(shares
+            .iter()
+            .zip(&lambdas)
+            .fold(BigInt::from(1), |acc, (share, lambda)| {
+                self.group.mul(&acc, &self.group.exp(share.value(), lambda))
+            }))
+    }
+}
+
+// Absorbs the statement and commitments of a DLEQ proof and draws its
+// challenge
+fn dleq_challenge(
+    group: &SchnorrGroup,
+    transcript: &mut dyn Transcript,
+    elements: [&BigInt; 6],
+) -> BigInt {
+    for (label, element) in DLEQ_LABELS.into_iter().zip(elements) {
+        group.append_element(transcript, label, element);
+    }
+    group.challenge_scalar(transcript, b"c")
+}
+
+// Transcript of the proof for one step and share of the scheme
+fn transcript(step: &'static [u8], index: usize) -> Sha256Transcript {
+    let mut transcript = Sha256Transcript::new(DLEQ_DOMAIN);
+    transcript.append_message(b"step", step);
+    transcript.append_message(b"index", &(index as u64).to_be_bytes());
+    transcript
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // deterministic stand-in for a random source
+    fn counter(start: u64) -> impl FnMut() -> BigInt {
+        let mut next = start;
+        move || {
+            next = next * 7 + 3;
+            BigInt::from(next % 1019)
+        }
+    }
+
+    #[test]
+    fn pvss_test() {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        let pvss = Pvss::new(3, 5, group).unwrap();
+        let secret_keys: Vec<BigInt> = (0..5).map(|i| BigInt::from(100 + 17 * i)).collect();
+        let public_keys: Vec<BigInt> = secret_keys.iter().map(|x| pvss.public_key(x)).collect();
+
+        let secret = BigInt::from(777);
+        let dealing = pvss
+            .deal_with(secret.clone(), &public_keys, counter(1))
+            .unwrap();
+        assert_eq!(pvss.verify_dealing(&dealing, &public_keys), Ok(()));
+
+        let mut nonces = counter(2);
+        let decrypted: Vec<DecryptedShare> = dealing
+            .shares()
+            .iter()
+            .zip(&secret_keys)
+            .map(|(share, x)| pvss.decrypt(share, x, &nonces()).unwrap())
+            .collect();
+        for (i, share) in decrypted.iter().enumerate() {
+            assert_eq!(
+                pvss.verify_decryption(&dealing.shares()[i], share, &public_keys[i]),
+                Ok(())
+            );
+        }
+        let expected = pvss.secret_element(&secret);
+        assert_eq!(pvss.recover(&decrypted[..3]).unwrap(), expected);
+        assert_eq!(pvss.recover(&decrypted[2..]).unwrap(), expected);
+    }
+
+    #[test]
+    fn tampered_test() {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        let pvss = Pvss::new(2, 3, group).unwrap();
+        let secret_keys: Vec<BigInt> = (0..3).map(|i| BigInt::from(5 + i)).collect();
+        let public_keys: Vec<BigInt> = secret_keys.iter().map(|x| pvss.public_key(x)).collect();
+        let mut dealing = pvss
+            .deal_with(BigInt::from(9), &public_keys, counter(3))
+            .unwrap();
+
+        let decrypted = pvss
+            .decrypt(&dealing.shares[0], &secret_keys[0], &BigInt::from(11))
+            .unwrap();
+        assert_eq!(
+            pvss.verify_decryption(&dealing.shares[0], &decrypted, &public_keys[1]),
+            Err(TinySsError::InvalidShare { index: 1 })
+        );
+
+        let group = pvss.group().clone();
+        dealing.shares[1].value = group.mul(&dealing.shares[1].value, &BigInt::from(4));
+        assert_eq!(
+            pvss.verify_dealing(&dealing, &public_keys),
+            Err(TinySsError::InvalidShare { index: 2 })
+        );
+    }
+}
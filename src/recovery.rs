@@ -0,0 +1,281 @@
+// Recomputation of a lost share by `t` helpers without reconstructing the
+// secret.
+//
+// Helper `i` holds the contribution `l_i(r) * s_i` to the lost share at `r`,
+// with `l_i` its Lagrange basis polynomial over the helpers. It blinds the
+// contribution by splitting it into random additive pieces, one per helper
+// (round 1). Every helper adds up the pieces it received and sends the sum to
+// the holder of the lost share (round 2), who adds the sums (round 3). Each
+// sum is uniformly random on its own, so nobody learns another holder's
+// share.
+
+use crate::{field::Field, poly, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A blinded value in transit between the participants of a recovery.
+#[derive(Clone, PartialEq, Eq)]
+pub struct RecoveryMessage<V> {
+    sender: usize,
+    receiver: usize,
+    value: V,
+}
+
+impl<V: fmt::Debug> fmt::Debug for RecoveryMessage<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RecoveryMessage")
+            .field("sender", &self.sender)
+            .field("receiver", &self.receiver)
+            .field("value", crate::debug::secret(&self.value))
+            .finish()
+    }
+}
+
+impl<V> RecoveryMessage<V> {
+    pub fn new(sender: usize, receiver: usize, value: V) -> Self {
+        RecoveryMessage {
+            sender,
+            receiver,
+            value,
+        }
+    }
+
+    pub fn sender(&self) -> usize {
+        self.sender
+    }
+
+    pub fn receiver(&self) -> usize {
+        self.receiver
+    }
+
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+}
+
+/// One run of the recovery of the share at `lost` by a fixed set of
+/// helpers.
+#[derive(Clone, Debug)]
+pub struct ShareRecovery<F: Field> {
+    scheme: SecretShare<F>,
+    /// indices of the helping holders
+    helpers: Vec<usize>,
+    /// index of the lost share
+    lost: usize,
+}
+
+impl<F: Field + Clone> SecretShare<F> {
+    /// Sets up the recovery of the share at `lost` by the holders at
+    /// `helpers`, at least `t` of them.
+    pub fn share_recovery(
+        &self,
+        helpers: &[usize],
+        lost: usize,
+    ) -> Result<ShareRecovery<F>, TinySsError> {
+        for (i, &helper) in helpers.iter().enumerate() {
+            if helpers[..i].contains(&helper) {
+                return Err(TinySsError::ConflictingShares { index: helper });
+            }
+        }
+        if helpers.len() < self.t() {
+            return Err(TinySsError::NotEnoughShares {
+                required: self.t(),
+                got: helpers.len(),
+            });
+        }
+        let in_range = |i: usize| i != 0 && i <= self.n();
+        if !in_range(lost) || !helpers.iter().all(|&h| in_range(h) && h != lost) {
+            return Err(TinySsError::InvalidIndex);
+        }
+        Ok(ShareRecovery {
+            scheme: self.clone(),
+            helpers: helpers.to_vec(),
+            lost,
+        })
+    }
+}
+
+impl<F: Field + Clone> ShareRecovery<F> {
+    pub fn helpers(&self) -> &[usize] {
+        &self.helpers
+    }
+
+    pub fn lost(&self) -> usize {
+        self.lost
+    }
+
+    /// Round 1: splits a helper's contribution into one blinded piece for
+    /// every helper, itself included.
+    #[cfg(feature = "rand")]
+    pub fn blind(
+        &self,
+        share: &Share<F::Elem>,
+    ) -> Result<Vec<RecoveryMessage<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let field = self.scheme.field();
+        self.blind_with(share, || field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Round 1, drawing the blinding from `sample`, which must return
+    /// uniformly random field elements.
+    pub fn blind_with<S>(
+        &self,
+        share: &Share<F::Elem>,
+        mut sample: S,
+    ) -> Result<Vec<RecoveryMessage<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let position = self.position(share.index())?;
+        let field = self.scheme.field();
+        let xs: Vec<F::Elem> = self
+            .helpers
+            .iter()
+            .map(|&h| field.elem_from_index(h))
+            .collect();
+        let lambdas = poly::lagrange_coefficients(field, &xs, &field.elem_from_index(self.lost))?;
+        let contribution = field.mul(&lambdas[position], share.value());
+
+        // random pieces for all but the last helper, which gets the rest
+        let mut rest = contribution;
+        let mut messages: Vec<RecoveryMessage<F::Elem>> = Vec::with_capacity(self.helpers.len());
+        for &receiver in &self.helpers[..self.helpers.len() - 1] {
+            let piece = sample();
+            rest = field.sub(&rest, &piece);
+            messages.push(RecoveryMessage::new(share.index(), receiver, piece));
+        }
+        let last = self.helpers[self.helpers.len() - 1];
+        messages.push(RecoveryMessage::new(share.index(), last, rest));
+        Ok(messages)
+    }
+
+    /// Round 2: adds up the pieces `helper` received from every helper into
+    /// its message for the holder of the lost share.
+    pub fn aggregate(
+        &self,
+        helper: usize,
+        pieces: &[RecoveryMessage<F::Elem>],
+    ) -> Result<RecoveryMessage<F::Elem>, TinySsError> {
+        self.position(helper)?;
+        if pieces.iter().any(|p| p.receiver != helper) {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let sum = self.sum_from_helpers(pieces)?;
+        Ok(RecoveryMessage::new(helper, self.lost, sum))
+    }
+
+    /// Round 3: the holder of the lost share adds up the sums of all
+    /// helpers.
+    pub fn finish(&self, sums: &[RecoveryMessage<F::Elem>]) -> Result<Share<F::Elem>, TinySsError> {
+        if sums.iter().any(|s| s.receiver != self.lost) {
+            return Err(TinySsError::InvalidIndex);
+        }
+        Ok(Share::new(self.lost, self.sum_from_helpers(sums)?))
+    }
+
+    // Adds one message from each helper
+    fn sum_from_helpers(
+        &self,
+        messages: &[RecoveryMessage<F::Elem>],
+    ) -> Result<F::Elem, TinySsError> {
+        let field = self.scheme.field();
+        let mut sum = field.zero();
+        for &helper in &self.helpers {
+            let mut from = messages.iter().filter(|m| m.sender == helper);
+            let message = from.next().ok_or(TinySsError::NotEnoughShares {
+                required: self.helpers.len(),
+                got: messages.len(),
+            })?;
+            if from.next().is_some() {
+                return Err(TinySsError::ConflictingShares { index: helper });
+            }
+            sum = field.add(&sum, &message.value);
+        }
+        if messages.len() != self.helpers.len() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        Ok(sum)
+    }
+
+    // Position of `index` among the helpers
+    fn position(&self, index: usize) -> Result<usize, TinySsError> {
+        self.helpers
+            .iter()
+            .position(|&h| h == index)
+            .ok_or(TinySsError::InvalidIndex)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn share_recovery_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        let shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+        let recovery = ss.share_recovery(&[1, 3, 5], 2).unwrap();
+
+        let mut next = 11;
+        let pieces: Vec<RecoveryMessage<BigInt>> = [0, 2, 4]
+            .iter()
+            .flat_map(|&i| {
+                recovery
+                    .blind_with(&shares[i], || {
+                        next += 301;
+                        BigInt::from(next)
+                    })
+                    .unwrap()
+            })
+            .collect();
+        let sums: Vec<RecoveryMessage<BigInt>> = recovery
+            .helpers()
+            .iter()
+            .map(|&h| {
+                let received: Vec<_> = pieces
+                    .iter()
+                    .filter(|p| p.receiver() == h)
+                    .cloned()
+                    .collect();
+                recovery.aggregate(h, &received).unwrap()
+            })
+            .collect();
+        // no single sum equals the lost share
+        assert!(sums.iter().all(|s| s.value() != shares[1].value()));
+        #[cfg(not(feature = "danger_debug"))]
+        assert!(alloc::format!("{:?}", sums[0]).contains("[REDACTED]"));
+        assert_eq!(recovery.finish(&sums).unwrap(), shares[1]);
+
+        assert_eq!(
+            recovery.finish(&sums[1..]),
+            Err(TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            })
+        );
+    }
+
+    #[test]
+    fn setup_error_test() {
+        let ss = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        assert_eq!(
+            ss.share_recovery(&[1, 3], 2).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+        assert_eq!(
+            ss.share_recovery(&[1, 2, 3], 2).unwrap_err(),
+            TinySsError::InvalidIndex
+        );
+        assert_eq!(
+            ss.share_recovery(&[1, 3, 3], 2).unwrap_err(),
+            TinySsError::ConflictingShares { index: 3 }
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! Replicated (CNF) sharing for a handful of holders.
+//!
+//! The secret is the sum of one random part per set `A` of `t - 1` holders,
+//! and every holder outside `A` receives the part of `A`. So any `t - 1`
+//! holders miss the part of their own set, while any `t` together know all
+//! of them. Holders can multiply replicated shares locally, at the price of
+//! `C(n, t - 1)` parts per share, which limits the scheme to `n <= 5`.
+//!
+//! A replicated share converts to a Shamir share of the same secret without
+//! interaction, weighting every part with the polynomial of degree `t - 1`
+//! that is one at zero and vanishes on the part's set. For the way back,
+//! `t` Shamir holders deal their additive shares with
+//! [`ReplicatedSharing::from_shamir_with`] and every holder adds up what it
+//! received with [`ReplicatedSharing::combine`].
+
+use crate::{
+    field::{Field, PrimeField},
+    SecretShare, Share, TinySsError,
+};
+use alloc::{vec, vec::Vec};
+use core::fmt;
+
+/// largest number of holders supported
+pub const MAX_HOLDERS: usize = 5;
+
+/// The parts of a replicated sharing known to one holder.
+#[derive(Clone, PartialEq, Eq)]
+pub struct ReplicatedShare<V = num_bigint::BigInt> {
+    index: usize,
+    /// number of the set and its part, for every set the holder is not in
+    parts: Vec<(usize, V)>,
+}
+
+impl<V: fmt::Debug> fmt::Debug for ReplicatedShare<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts: Vec<_> = self
+            .parts
+            .iter()
+            .map(|(set, part)| (set, crate::debug::secret(part)))
+            .collect();
+        f.debug_struct("ReplicatedShare")
+            .field("index", &self.index)
+            .field("parts", &parts)
+            .finish()
+    }
+}
+
+impl<V> ReplicatedShare<V> {
+    pub fn new(index: usize, parts: Vec<(usize, V)>) -> Self {
+        ReplicatedShare { index, parts }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn parts(&self) -> &[(usize, V)] {
+        &self.parts
+    }
+}
+
+/// A `t`-of-`n` replicated scheme over the field `F`.
+#[derive(Clone, Debug)]
+pub struct ReplicatedSharing<F: Field = PrimeField> {
+    scheme: SecretShare<F>,
+    /// all sets of `t - 1` holders, in lexicographic order
+    sets: Vec<Vec<usize>>,
+}
+
+impl<F: Field> ReplicatedSharing<F> {
+    /// Creates a scheme with `0 < t < n <= MAX_HOLDERS`.
+    pub fn new(t: usize, n: usize, field: F) -> Result<Self, TinySsError> {
+        if n > MAX_HOLDERS {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        let scheme = SecretShare::with_field(t, n, field)?;
+        Ok(ReplicatedSharing {
+            sets: subsets(n, t - 1),
+            scheme,
+        })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.scheme.t()
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.scheme.n()
+    }
+
+    /// The Shamir scheme shares convert to.
+    pub fn shamir(&self) -> &SecretShare<F> {
+        &self.scheme
+    }
+
+    /// The set of holders missing part `set`.
+    pub fn set(&self, set: usize) -> Option<&[usize]> {
+        self.sets.get(set).map(Vec::as_slice)
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: F::Elem) -> Vec<ReplicatedShare<F::Elem>> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let field = self.scheme.field();
+        self.split_with(secret, || field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Splits `secret` drawing all parts but the last from `sample`, which
+    /// must return uniformly random field elements.
+    pub fn split_with<S>(&self, secret: F::Elem, mut sample: S) -> Vec<ReplicatedShare<F::Elem>>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let field = self.scheme.field();
+        let mut rest = secret;
+        let mut parts: Vec<F::Elem> = (1..self.sets.len())
+            .map(|_| {
+                let part = sample();
+                rest = field.sub(&rest, &part);
+                part
+            })
+            .collect();
+        parts.push(rest);
+        (1..=self.n())
+            .map(|index| {
+                let known = self
+                    .sets
+                    .iter()
+                    .zip(&parts)
+                    .enumerate()
+                    .filter(|(_, (set, _))| !set.contains(&index))
+                    .map(|(i, (_, part))| (i, part.clone()))
+                    .collect();
+                ReplicatedShare::new(index, known)
+            })
+            .collect()
+    }
+
+    /// Recovers the secret from the shares of at least `t` holders.
+    pub fn recover(&self, shares: &[ReplicatedShare<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        let field = self.scheme.field();
+        let mut parts: Vec<Option<&F::Elem>> = vec![None; self.sets.len()];
+        for share in shares {
+            self.check(share)?;
+            for (set, value) in &share.parts {
+                match parts[*set] {
+                    Some(known) if known != value => {
+                        return Err(TinySsError::ConflictingShares { index: share.index })
+                    }
+                    _ => parts[*set] = Some(value),
+                }
+            }
+        }
+        parts
+            .iter()
+            .try_fold(field.zero(), |sum, part| Some(field.add(&sum, (*part)?)))
+            .ok_or_else(|| {
+                let mut indices: Vec<usize> = shares.iter().map(|s| s.index).collect();
+                indices.sort_unstable();
+                indices.dedup();
+                TinySsError::NotEnoughShares {
+                    required: self.t(),
+                    got: indices.len(),
+                }
+            })
+    }
+
+    /// Converts a replicated share into the Shamir share of the same holder.
+    pub fn to_shamir(
+        &self,
+        share: &ReplicatedShare<F::Elem>,
+    ) -> Result<Share<F::Elem>, TinySsError> {
+        self.check(share)?;
+        let field = self.scheme.field();
+        let x = field.elem_from_index(share.index);
+        let mut value = field.zero();
+        for (set, part) in &share.parts {
+            // f(y) = prod (y - a) / (0 - a) over the holders a of the set
+            let weight = self.sets[*set].iter().try_fold(field.one(), |w, &a| {
+                let a = field.elem_from_index(a);
+                let denominator = field.inv(&field.sub(&field.zero(), &a))?;
+                Some(field.mul(&w, &field.mul(&field.sub(&x, &a), &denominator)))
+            });
+            let weight = weight.ok_or(TinySsError::NotInvertible)?;
+            value = field.add(&value, &field.mul(&weight, part));
+        }
+        Ok(Share::new(share.index, value))
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn from_shamir(
+        &self,
+        share: &Share<F::Elem>,
+        parties: &[usize],
+    ) -> Result<Vec<ReplicatedShare<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        let field = self.scheme.field();
+        self.from_shamir_with(share, parties, || {
+            field.random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Deals the additive share of one of the Shamir holders at `parties`
+    /// as a replicated sharing, one share for every holder, drawing the
+    /// parts from `sample`.
+    pub fn from_shamir_with<S>(
+        &self,
+        share: &Share<F::Elem>,
+        parties: &[usize],
+        sample: S,
+    ) -> Result<Vec<ReplicatedShare<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let additive = self.scheme.to_additive(share, parties)?;
+        Ok(self.split_with(additive.value().clone(), sample))
+    }
+
+    /// Adds up the replicated shares one holder received from every Shamir
+    /// holder taking part in a conversion.
+    pub fn combine(
+        &self,
+        shares: &[ReplicatedShare<F::Elem>],
+    ) -> Result<ReplicatedShare<F::Elem>, TinySsError> {
+        let first = shares.first().ok_or(TinySsError::NotEnoughShares {
+            required: self.t(),
+            got: 0,
+        })?;
+        let field = self.scheme.field();
+        let mut sum = first.clone();
+        for share in &shares[1..] {
+            self.check(share)?;
+            if share.index != first.index {
+                return Err(TinySsError::ConflictingShares { index: first.index });
+            }
+            for ((_, total), (_, part)) in sum.parts.iter_mut().zip(&share.parts) {
+                *total = field.add(total, part);
+            }
+        }
+        self.check(&sum)?;
+        Ok(sum)
+    }
+
+    // The parts of a share must be exactly those of all sets without its
+    // holder, in order
+    fn check(&self, share: &ReplicatedShare<F::Elem>) -> Result<(), TinySsError> {
+        if share.index == 0 || share.index > self.n() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let mut expected = self
+            .sets
+            .iter()
+            .enumerate()
+            .filter(|(_, set)| !set.contains(&share.index))
+            .map(|(i, _)| i);
+        let matches = share
+            .parts
+            .iter()
+            .all(|(set, _)| expected.next() == Some(*set));
+        if !matches || expected.next().is_some() {
+            return Err(TinySsError::InvalidShare { index: share.index });
+        }
+        Ok(())
+    }
+}
+
+// All subsets of 1..=n with k elements, in lexicographic order
+pub(crate) fn subsets(n: usize, k: usize) -> Vec<Vec<usize>> {
+    if k == 0 {
+        return vec![Vec::new()];
+    }
+    let mut sets = Vec::new();
+    for last in k..=n {
+        for mut set in subsets(last - 1, k - 1) {
+            set.push(last);
+            sets.push(set);
+        }
+    }
+    sets.sort();
+    sets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    fn scheme() -> ReplicatedSharing {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        ReplicatedSharing::new(3, 4, field).unwrap()
+    }
+
+    #[test]
+    fn replicated_test() {
+        let replicated = scheme();
+        assert_eq!(replicated.set(0), Some(&[1, 2][..]));
+        assert_eq!(replicated.set(5), Some(&[3, 4][..]));
+        assert_eq!(replicated.set(6), None);
+
+        let mut next = 0;
+        let shares = replicated.split_with(BigInt::from(1234), || {
+            next += 500;
+            BigInt::from(next)
+        });
+        // holder 1 is outside {2, 3}, {2, 4} and {3, 4}
+        assert_eq!(shares[0].parts().len(), 3);
+        #[cfg(not(feature = "danger_debug"))]
+        assert!(alloc::format!("{:?}", shares[0]).contains("[REDACTED]"));
+        assert_eq!(
+            replicated.recover(&shares[1..]).unwrap(),
+            BigInt::from(1234)
+        );
+        assert_eq!(
+            replicated.recover(&shares[2..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+        assert!(
+            ReplicatedSharing::new(2, 6, PrimeField::new(BigInt::from(1613)).unwrap()).is_err()
+        );
+    }
+
+    #[test]
+    fn shamir_conversion_test() {
+        let replicated = scheme();
+        let secret = BigInt::from(1234);
+        let mut next = 0;
+        let shares = replicated.split_with(secret.clone(), || {
+            next += 500;
+            BigInt::from(next)
+        });
+        let shamir: Vec<Share> = shares
+            .iter()
+            .map(|s| replicated.to_shamir(s).unwrap())
+            .collect();
+        let ss = replicated.shamir();
+        assert_eq!(ss.verify_consistent(&shamir), Ok(()));
+        assert_eq!(ss.recover(&shamir[1..]).unwrap(), secret);
+
+        // Shamir holders 1, 2 and 4 convert back
+        let parties = [1, 2, 4];
+        let dealt: Vec<Vec<ReplicatedShare>> = parties
+            .iter()
+            .map(|&p| {
+                replicated
+                    .from_shamir_with(&shamir[p - 1], &parties, || {
+                        next += 77;
+                        BigInt::from(next)
+                    })
+                    .unwrap()
+            })
+            .collect();
+        let fresh: Vec<ReplicatedShare> = (0..replicated.n())
+            .map(|j| {
+                let received: Vec<ReplicatedShare> = dealt.iter().map(|d| d[j].clone()).collect();
+                replicated.combine(&received).unwrap()
+            })
+            .collect();
+        assert_ne!(fresh, shares);
+        assert_eq!(replicated.recover(&fresh[..3]).unwrap(), secret);
+
+        let forged = ReplicatedShare::new(1, shares[1].parts().to_vec());
+        assert_eq!(
+            replicated.to_shamir(&forged).unwrap_err(),
+            TinySsError::InvalidShare { index: 1 }
+        );
+    }
+}
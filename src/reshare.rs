@@ -0,0 +1,197 @@
+// Redistribution of a sharing to a new threshold and set of holders, after
+// Desmedt and Jajodia.
+//
+// Each of at least `t` old holders splits its own share with the new
+// scheme and sends one subshare to every new holder. A new holder weights
+// the subshares by the Lagrange coefficients of their senders and adds them
+// up, which yields a share of the same secret on a fresh polynomial. The
+// secret is never assembled anywhere.
+
+use crate::{field::Field, poly, SecretShare, Share, TinySsError};
+use alloc::vec::Vec;
+
+/// A piece of an old holder's share destined for one new holder.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Subshare<V> {
+    /// index of the old share that was split
+    sender: usize,
+    /// the piece, indexed by its new holder
+    share: Share<V>,
+}
+
+impl<V> Subshare<V> {
+    pub fn new(sender: usize, share: Share<V>) -> Self {
+        Subshare { sender, share }
+    }
+
+    pub fn sender(&self) -> usize {
+        self.sender
+    }
+
+    /// index of the new holder
+    pub fn receiver(&self) -> usize {
+        self.share.index()
+    }
+
+    pub fn value(&self) -> &V {
+        self.share.value()
+    }
+}
+
+impl<F: Field> SecretShare<F> {
+    /// Splits a share of this scheme into one subshare for each holder of
+    /// `new`.
+    #[cfg(feature = "rand")]
+    pub fn reshare(
+        &self,
+        share: &Share<F::Elem>,
+        new: &SecretShare<F>,
+    ) -> Result<Vec<Subshare<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.reshare_with(share, new, || {
+            new.field().random(&mut |buf| rng.fill_bytes(buf))
+        })
+    }
+
+    /// Splits a share of this scheme into one subshare for each holder of
+    /// `new`, drawing the coefficients from `sample`, which must return
+    /// uniformly random field elements.
+    pub fn reshare_with<S>(
+        &self,
+        share: &Share<F::Elem>,
+        new: &SecretShare<F>,
+        sample: S,
+    ) -> Result<Vec<Subshare<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        if share.index() == 0 || share.index() > self.n() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        Ok(new
+            .split_with(share.value().clone(), sample)?
+            .into_iter()
+            .map(|piece| Subshare::new(share.index(), piece))
+            .collect())
+    }
+
+    /// Combines the subshares one new holder received into its share of
+    /// `new`.
+    ///
+    /// All new holders must combine subshares from the same set of senders,
+    /// which needs at least `t` old holders; otherwise their shares do not
+    /// lie on a common polynomial.
+    pub fn combine_subshares(
+        &self,
+        new: &SecretShare<F>,
+        subshares: &[Subshare<F::Elem>],
+    ) -> Result<Share<F::Elem>, TinySsError> {
+        let receiver = match subshares.first() {
+            Some(subshare) => subshare.receiver(),
+            None => {
+                return Err(TinySsError::NotEnoughShares {
+                    required: self.t(),
+                    got: 0,
+                })
+            }
+        };
+        if receiver == 0 || receiver > new.n() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        if subshares.iter().any(|s| s.receiver() != receiver) {
+            return Err(TinySsError::ConflictingShares { index: receiver });
+        }
+        if subshares
+            .iter()
+            .any(|s| s.sender == 0 || s.sender > self.n())
+        {
+            return Err(TinySsError::InvalidIndex);
+        }
+
+        // keyed by sender, so that the usual deduplication applies
+        let by_sender: Vec<Share<F::Elem>> = subshares
+            .iter()
+            .map(|s| Share::new(s.sender, s.value().clone()))
+            .collect();
+        let field = self.field();
+        let unique = poly::distinct_field_shares(field, &by_sender, self.t())?;
+        let xs: Vec<F::Elem> = unique
+            .iter()
+            .map(|s| field.elem_from_index(s.index()))
+            .collect();
+        let lambdas = poly::lagrange_coefficients(field, &xs, &field.zero())?;
+        let value = unique
+            .iter()
+            .zip(&lambdas)
+            .fold(field.zero(), |sum, (s, lambda)| {
+                field.add(&sum, &field.mul(lambda, s.value()))
+            });
+        Ok(Share::new(receiver, value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn reshare_test() {
+        let p = BigInt::from(1613);
+        let old = SecretShare::new(3, 5, p.clone()).unwrap();
+        let new = SecretShare::new(2, 4, p).unwrap();
+        let secret = BigInt::from(1234);
+        let shares = old
+            .split_with_coefficients(secret.clone(), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+
+        // old holders 1, 3 and 5 take part
+        let mut next = 7;
+        let subshares: Vec<Vec<Subshare<BigInt>>> = [0, 2, 4]
+            .iter()
+            .map(|&i| {
+                old.reshare_with(&shares[i], &new, || {
+                    next += 101;
+                    BigInt::from(next)
+                })
+                .unwrap()
+            })
+            .collect();
+        let new_shares: Vec<Share> = (0..new.n())
+            .map(|j| {
+                let received: Vec<Subshare<BigInt>> =
+                    subshares.iter().map(|s| s[j].clone()).collect();
+                old.combine_subshares(&new, &received).unwrap()
+            })
+            .collect();
+        assert_eq!(new.verify_consistent(&new_shares), Ok(()));
+        assert_eq!(new.recover(&new_shares[2..]).unwrap(), secret);
+    }
+
+    #[test]
+    fn combine_error_test() {
+        let p = BigInt::from(1613);
+        let old = SecretShare::new(2, 3, p.clone()).unwrap();
+        let new = SecretShare::new(2, 3, p).unwrap();
+        let subshares = [
+            Subshare::new(1, Share::new(1, BigInt::from(5))),
+            Subshare::new(2, Share::new(2, BigInt::from(6))),
+        ];
+        assert_eq!(
+            old.combine_subshares(&new, &subshares[..1]),
+            Err(TinySsError::NotEnoughShares {
+                required: 2,
+                got: 1
+            })
+        );
+        assert_eq!(
+            old.combine_subshares(&new, &subshares),
+            Err(TinySsError::ConflictingShares { index: 1 })
+        );
+        assert_eq!(
+            old.reshare_with(&Share::new(4, BigInt::from(1)), &new, || BigInt::from(1)),
+            Err(TinySsError::InvalidIndex)
+        );
+    }
+}
@@ -0,0 +1,240 @@
+// Robust reconstruction after Berlekamp and Welch, and by majority vote
+// over subsets of the shares.
+//
+// With `m` distinct shares of which at most `e = (m - t) / 2` are wrong, the
+// monic error locator `E` of degree `e` and `Q = f * E` satisfy
+// `Q(x_i) = y_i * E(x_i)` at every share, which is linear in the
+// coefficients of `E` and `Q`. Every solution has `Q / E = f`, so a single
+// elimination recovers the polynomial and with it the bad shares.
+
+use crate::{field::Field, poly, replicated::subsets, SecretShare, Share, TinySsError};
+use alloc::{vec, vec::Vec};
+use core::cmp::Reverse;
+
+impl<F: Field> SecretShare<F> {
+    /// Recovers the secret from `m >= t` shares of which up to `(m - t) / 2`
+    /// may be corrupted, returning it with the indices of the corrupted
+    /// shares.
+    ///
+    /// Fails with `TooManyErrors` if the shares are further from every
+    /// polynomial of degree `t - 1` than the redundancy can correct.
+    pub fn recover_robust(
+        &self,
+        shares: &[Share<F::Elem>],
+    ) -> Result<(F::Elem, Vec<usize>), TinySsError> {
+        let field = self.field();
+        let t = self.t();
+        let unique = poly::distinct_field_shares(field, shares, t)?;
+        let e = (unique.len() - t) / 2;
+        let points: Vec<(F::Elem, F::Elem)> = unique
+            .iter()
+            .map(|s| (field.elem_from_index(s.index()), s.value().clone()))
+            .collect();
+        // unknowns are the t + e coefficients of Q, then the lower e of E
+        let (matrix, rhs) = points
+            .iter()
+            .map(|(x, y)| {
+                let mut row = Vec::with_capacity(t + 2 * e);
+                let mut power = field.one();
+                for _ in 0..t + e {
+                    row.push(power.clone());
+                    power = field.mul(&power, x);
+                }
+                let mut power = field.one();
+                for _ in 0..e {
+                    row.push(field.sub(&field.zero(), &field.mul(y, &power)));
+                    power = field.mul(&power, x);
+                }
+                (row, field.mul(y, &power))
+            })
+            .unzip();
+        let solution = solve_any(field, matrix, rhs).ok_or(TinySsError::TooManyErrors)?;
+        let (q, locator) = solution.split_at(t + e);
+        let mut locator = locator.to_vec();
+        locator.push(field.one());
+        let f = divide(field, q, &locator).ok_or(TinySsError::TooManyErrors)?;
+        let corrupted: Vec<usize> = unique
+            .iter()
+            .zip(&points)
+            .filter(|(_, (x, y))| field.sub(&poly::evaluate(field, &f, x), y) != field.zero())
+            .map(|(s, _)| s.index())
+            .collect();
+        if corrupted.len() > e {
+            return Err(TinySsError::TooManyErrors);
+        }
+        Ok((f[0].clone(), corrupted))
+    }
+
+    /// Recovers the secret by interpolating every `t`-subset of the shares
+    /// and taking the most frequent result, returning it with the indices of
+    /// the shares off the winning polynomial.
+    ///
+    /// This tries all `m choose t` subsets of the `m` distinct shares and is
+    /// meant for a small surplus over `t`; with `t + 1` shares a single bad
+    /// one is detected but cannot be told apart, which fails with
+    /// `TooManyErrors` like any other tie.
+    pub fn recover_identify(
+        &self,
+        shares: &[Share<F::Elem>],
+    ) -> Result<(F::Elem, Vec<usize>), TinySsError> {
+        let unique = poly::distinct_field_shares(self.field(), shares, self.t())?;
+        // distinct secrets with their vote count and a subset voting for them
+        let mut votes: Vec<(F::Elem, usize, Vec<usize>)> = Vec::new();
+        for subset in subsets(unique.len(), self.t()) {
+            let (xs, ys) = subset
+                .iter()
+                .map(|&i| (unique[i - 1].index(), unique[i - 1].value().clone()))
+                .unzip();
+            let secret = self.lagrange_interpolation(self.field().zero(), xs, ys)?;
+            match votes.iter_mut().find(|(s, _, _)| *s == secret) {
+                Some((_, count, _)) => *count += 1,
+                None => votes.push((secret, 1, subset)),
+            }
+        }
+        votes.sort_by_key(|(_, count, _)| Reverse(*count));
+        if votes.len() > 1 && votes[0].1 == votes[1].1 {
+            return Err(TinySsError::TooManyErrors);
+        }
+        let (secret, _, subset) = votes.swap_remove(0);
+        let (xs, ys): (Vec<usize>, Vec<F::Elem>) = subset
+            .iter()
+            .map(|&i| (unique[i - 1].index(), unique[i - 1].value().clone()))
+            .unzip();
+        let mut faulty = Vec::new();
+        for share in &unique {
+            let x = self.field().elem_from_index(share.index());
+            let expected = self.lagrange_interpolation(x, xs.clone(), ys.clone())?;
+            if self.field().sub(&expected, share.value()) != self.field().zero() {
+                faulty.push(share.index());
+            }
+        }
+        Ok((secret, faulty))
+    }
+}
+
+// Gauss-Jordan elimination for a system that may be overdetermined or rank
+// deficient, setting free unknowns to zero; `None` if it is inconsistent
+fn solve_any<F: Field>(
+    field: &F,
+    mut matrix: Vec<Vec<F::Elem>>,
+    mut rhs: Vec<F::Elem>,
+) -> Option<Vec<F::Elem>> {
+    let cols = matrix.first().map_or(0, Vec::len);
+    let mut pivots = Vec::new();
+    for col in 0..cols {
+        let row = pivots.len();
+        let Some(pivot) = (row..rhs.len()).find(|&r| matrix[r][col] != field.zero()) else {
+            continue;
+        };
+        matrix.swap(row, pivot);
+        rhs.swap(row, pivot);
+        let inverse = field.inv(&matrix[row][col])?;
+        for entry in matrix[row].iter_mut() {
+            *entry = field.mul(entry, &inverse);
+        }
+        rhs[row] = field.mul(&rhs[row], &inverse);
+        let (pivot_row, pivot_rhs) = (matrix[row].clone(), rhs[row].clone());
+        for (r, (other, value)) in matrix.iter_mut().zip(rhs.iter_mut()).enumerate() {
+            if r == row || other[col] == field.zero() {
+                continue;
+            }
+            let factor = other[col].clone();
+            for (entry, p) in other.iter_mut().zip(&pivot_row) {
+                *entry = field.sub(entry, &field.mul(&factor, p));
+            }
+            *value = field.sub(value, &field.mul(&factor, &pivot_rhs));
+        }
+        pivots.push(col);
+    }
+    if rhs[pivots.len()..].iter().any(|v| *v != field.zero()) {
+        return None;
+    }
+    let mut solution = vec![field.zero(); cols];
+    for (row, col) in pivots.into_iter().enumerate() {
+        solution[col] = rhs[row].clone();
+    }
+    Some(solution)
+}
+
+// Long division by a monic polynomial, `None` if it leaves a remainder
+fn divide<F: Field>(field: &F, dividend: &[F::Elem], divisor: &[F::Elem]) -> Option<Vec<F::Elem>> {
+    let degree = divisor.len() - 1;
+    let mut remainder = dividend.to_vec();
+    let mut quotient = vec![field.zero(); dividend.len() - degree];
+    for i in (0..quotient.len()).rev() {
+        let coefficient = remainder[i + degree].clone();
+        for (r, d) in remainder[i..].iter_mut().zip(divisor) {
+            *r = field.sub(r, &field.mul(&coefficient, d));
+        }
+        quotient[i] = coefficient;
+    }
+    remainder[..degree]
+        .iter()
+        .all(|r| *r == field.zero())
+        .then_some(quotient)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn recover_robust_test() {
+        let ss = SecretShare::new(3, 7, BigInt::from(1613)).unwrap();
+        let mut shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+        assert_eq!(
+            ss.recover_robust(&shares).unwrap(),
+            (BigInt::from(1234), vec![])
+        );
+        shares[1] = Share::new(2, BigInt::from(1));
+        shares[4] = Share::new(5, BigInt::from(1000));
+        assert_ne!(ss.recover(&shares).unwrap(), BigInt::from(1234));
+        assert_eq!(
+            ss.recover_robust(&shares).unwrap(),
+            (BigInt::from(1234), vec![2, 5])
+        );
+        // five shares correct a single error
+        assert_eq!(
+            ss.recover_robust(&shares[1..6]),
+            Err(TinySsError::TooManyErrors)
+        );
+        assert_eq!(
+            ss.recover_robust(&shares[2..]).unwrap(),
+            (BigInt::from(1234), vec![5])
+        );
+    }
+
+    #[test]
+    fn recover_robust_small_test() {
+        let ss = SecretShare::small(2, 5, 65521).unwrap();
+        let mut shares = ss.split_with_coefficients(42, &[7]).unwrap();
+        shares[0] = Share::new(1, 0);
+        assert_eq!(ss.recover_robust(&shares).unwrap(), (42, vec![1]));
+        // without redundancy nothing can be detected
+        assert!(ss.recover_robust(&shares[..2]).unwrap().1.is_empty());
+    }
+
+    #[test]
+    fn recover_identify_test() {
+        let ss = SecretShare::new(3, 6, BigInt::from(1613)).unwrap();
+        let mut shares = ss
+            .split_with_coefficients(BigInt::from(1234), &[BigInt::from(166), BigInt::from(94)])
+            .unwrap();
+        shares[2] = Share::new(3, BigInt::from(7));
+        assert_eq!(
+            ss.recover_identify(&shares[..5]).unwrap(),
+            (BigInt::from(1234), vec![3])
+        );
+        assert_eq!(
+            ss.recover_identify(&shares[..4]),
+            Err(TinySsError::TooManyErrors)
+        );
+        assert_eq!(
+            ss.recover_identify(&shares[3..]).unwrap(),
+            (BigInt::from(1234), vec![])
+        );
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{
+    blakley::{BlakleySharing, Hyperplane},
+    crt::{AsmuthBloom, Mignotte},
+    field::Field,
+    gf256::{ByteShare, Gf256Sharing},
+    SecretShare, Share, TinySsError,
+};
+use alloc::vec::Vec;
+use num_bigint::BigInt;
+
+/// A `t`-of-`n` threshold scheme, so that callers can swap the polynomial
+/// schemes for the CRT-based ones.
+pub trait ThresholdScheme {
+    type Secret;
+    type Share;
+
+    /// shares needed for recovery
+    fn t(&self) -> usize;
+
+    /// total number of shares
+    fn n(&self) -> usize;
+
+    /// Splits `secret` into `n` shares drawing any randomness from
+    /// `fill_random`, which must fill the buffer with uniformly random
+    /// bytes.
+    fn share(
+        &self,
+        secret: Self::Secret,
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<Vec<Self::Share>, TinySsError>;
+
+    /// Recovers the secret from at least `t` shares.
+    fn reconstruct(&self, shares: &[Self::Share]) -> Result<Self::Secret, TinySsError>;
+}
+
+impl<F: Field> ThresholdScheme for SecretShare<F> {
+    type Secret = F::Elem;
+    type Share = Share<F::Elem>;
+
+    fn t(&self) -> usize {
+        self.t()
+    }
+
+    fn n(&self) -> usize {
+        self.n()
+    }
+
+    fn share(
+        &self,
+        secret: F::Elem,
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<Vec<Share<F::Elem>>, TinySsError> {
+        self.split_with(secret, || self.field().random(fill_random))
+    }
+
+    fn reconstruct(&self, shares: &[Share<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        self.recover(shares)
+    }
+}
+
+impl<F: Field> ThresholdScheme for BlakleySharing<F> {
+    type Secret = F::Elem;
+    type Share = Share<Hyperplane<F::Elem>>;
+
+    fn t(&self) -> usize {
+        self.t()
+    }
+
+    fn n(&self) -> usize {
+        self.n()
+    }
+
+    fn share(
+        &self,
+        secret: F::Elem,
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<Vec<Self::Share>, TinySsError> {
+        Ok(self.split_with(secret, || self.field().random(fill_random)))
+    }
+
+    fn reconstruct(&self, shares: &[Self::Share]) -> Result<F::Elem, TinySsError> {
+        self.recover(shares)
+    }
+}
+
+impl ThresholdScheme for Gf256Sharing {
+    type Secret = Vec<u8>;
+    type Share = ByteShare;
+
+    fn t(&self) -> usize {
+        self.t()
+    }
+
+    fn n(&self) -> usize {
+        self.n()
+    }
+
+    fn share(
+        &self,
+        secret: Vec<u8>,
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<Vec<ByteShare>, TinySsError> {
+        Ok(self.split_with(&secret, fill_random))
+    }
+
+    fn reconstruct(&self, shares: &[ByteShare]) -> Result<Vec<u8>, TinySsError> {
+        self.recover(shares)
+    }
+}
+
+impl ThresholdScheme for Mignotte {
+    type Secret = BigInt;
+    type Share = Share;
+
+    fn t(&self) -> usize {
+        self.t()
+    }
+
+    fn n(&self) -> usize {
+        self.n()
+    }
+
+    // deterministic, the randomness is not needed
+    fn share(
+        &self,
+        secret: BigInt,
+        _fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<Vec<Share>, TinySsError> {
+        self.split(&secret)
+    }
+
+    fn reconstruct(&self, shares: &[Share]) -> Result<BigInt, TinySsError> {
+        self.recover(shares)
+    }
+}
+
+impl ThresholdScheme for AsmuthBloom {
+    type Secret = BigInt;
+    type Share = Share;
+
+    fn t(&self) -> usize {
+        self.t()
+    }
+
+    fn n(&self) -> usize {
+        self.n()
+    }
+
+    fn share(
+        &self,
+        secret: BigInt,
+        fill_random: &mut dyn FnMut(&mut [u8]),
+    ) -> Result<Vec<Share>, TinySsError> {
+        self.split_with(&secret, fill_random)
+    }
+
+    fn reconstruct(&self, shares: &[Share]) -> Result<BigInt, TinySsError> {
+        self.recover(shares)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field::PrimeField;
+    use crate::test_util::counter;
+
+    // Round trip through any scheme, using the last `t` shares
+    fn round_trip<S: ThresholdScheme>(scheme: &S, secret: S::Secret) -> S::Secret
+    where
+        S::Share: Clone,
+    {
+        let mut fill = counter(89);
+        let shares = scheme.share(secret, &mut fill).unwrap();
+        assert_eq!(shares.len(), scheme.n());
+        scheme
+            .reconstruct(&shares[scheme.n() - scheme.t()..])
+            .unwrap()
+    }
+
+    #[test]
+    fn threshold_scheme_test() {
+        let shamir = SecretShare::new(3, 5, BigInt::from(1613)).unwrap();
+        assert_eq!(round_trip(&shamir, BigInt::from(1234)), BigInt::from(1234));
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        let blakley = BlakleySharing::new(3, 5, field).unwrap();
+        assert_eq!(round_trip(&blakley, BigInt::from(1234)), BigInt::from(1234));
+        let gf = Gf256Sharing::new(3, 5).unwrap();
+        assert_eq!(round_trip(&gf, b"ss".to_vec()), b"ss");
+        let mignotte = Mignotte::generate(3, 5, 32).unwrap();
+        let secret: BigInt = mignotte.beta() + 1;
+        assert_eq!(round_trip(&mignotte, secret.clone()), secret);
+        let asmuth_bloom = AsmuthBloom::generate(3, 5, 64).unwrap();
+        assert_eq!(
+            round_trip(&asmuth_bloom, BigInt::from(1234)),
+            BigInt::from(1234)
+        );
+    }
+}
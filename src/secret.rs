@@ -0,0 +1,181 @@
+//! Wrappers that keep secrets out of logs.
+//!
+//! `SecretScalar` and `SecretBytes` print as `[REDACTED]` and hand out
+//! their contents only through `expose_secret`, so that every place a secret
+//! is read stands out in review. The `*_secret` variants of `split` and
+//! `recover` take and return them. With the `zeroize` feature, `SecretBytes`
+//! is `ZeroizeOnDrop`, and a `SecretScalar` implements `Zeroize` and can be
+//! kept in a `Zeroizing`.
+
+use crate::{
+    field::Field,
+    gf256::{ByteShare, Gf256Sharing},
+    SecretShare, Share, TinySsError,
+};
+use alloc::vec::Vec;
+use core::fmt;
+
+/// A secret field element.
+#[derive(Clone)]
+pub struct SecretScalar<V = num_bigint::BigInt>(V);
+
+impl<V> SecretScalar<V> {
+    pub fn new(value: V) -> Self {
+        SecretScalar(value)
+    }
+
+    pub fn expose_secret(&self) -> &V {
+        &self.0
+    }
+}
+
+impl<V> fmt::Debug for SecretScalar<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretScalar([REDACTED])")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+crate::zeroize::impl_zeroize!(SecretScalar, 0);
+
+/// A secret byte string.
+#[derive(Clone)]
+pub struct SecretBytes(Vec<u8>);
+
+impl SecretBytes {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        SecretBytes(bytes)
+    }
+
+    pub fn expose_secret(&self) -> &[u8] {
+        &self.0
+    }
+}
+
+impl From<Vec<u8>> for SecretBytes {
+    fn from(bytes: Vec<u8>) -> Self {
+        SecretBytes::new(bytes)
+    }
+}
+
+impl fmt::Debug for SecretBytes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SecretBytes([REDACTED])")
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl crate::zeroize::Zeroize for SecretBytes {
+    fn zeroize(&mut self) {
+        self.0.zeroize();
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl Drop for SecretBytes {
+    fn drop(&mut self) {
+        crate::zeroize::Zeroize::zeroize(self);
+    }
+}
+
+#[cfg(feature = "zeroize")]
+impl crate::zeroize::ZeroizeOnDrop for SecretBytes {}
+
+impl<F: Field> SecretShare<F> {
+    #[cfg(feature = "rand")]
+    pub fn split_secret(
+        &self,
+        secret: &SecretScalar<F::Elem>,
+    ) -> Result<Vec<Share<F::Elem>>, TinySsError> {
+        self.split(secret.0.clone())
+    }
+
+    /// `split_with` for a wrapped secret.
+    pub fn split_secret_with<S>(
+        &self,
+        secret: &SecretScalar<F::Elem>,
+        sample: S,
+    ) -> Result<Vec<Share<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        self.split_with(secret.0.clone(), sample)
+    }
+
+    /// `recover` into a wrapped secret.
+    pub fn recover_secret(
+        &self,
+        shares: &[Share<F::Elem>],
+    ) -> Result<SecretScalar<F::Elem>, TinySsError> {
+        self.recover(shares).map(SecretScalar)
+    }
+}
+
+impl Gf256Sharing {
+    #[cfg(feature = "rand")]
+    pub fn split_secret(&self, secret: &SecretBytes) -> Vec<ByteShare> {
+        self.split(&secret.0)
+    }
+
+    /// `split_with` for a wrapped secret.
+    pub fn split_secret_with<R>(&self, secret: &SecretBytes, fill_random: R) -> Vec<ByteShare>
+    where
+        R: FnMut(&mut [u8]),
+    {
+        self.split_with(&secret.0, fill_random)
+    }
+
+    /// `recover` into a wrapped secret.
+    pub fn recover_secret(&self, shares: &[ByteShare]) -> Result<SecretBytes, TinySsError> {
+        self.recover(shares).map(SecretBytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+    use alloc::format;
+    use num_bigint::BigInt;
+
+    #[test]
+    fn secret_scalar_test() {
+        let ss = SecretShare::new(2, 3, BigInt::from(1613)).unwrap();
+        let secret = SecretScalar::new(BigInt::from(1000));
+        assert_eq!(format!("{:?}", secret), "SecretScalar([REDACTED])");
+        let mut next = 0;
+        let shares = ss
+            .split_secret_with(&secret, || {
+                next += 7;
+                BigInt::from(next)
+            })
+            .unwrap();
+        let recovered = ss.recover_secret(&shares[1..]).unwrap();
+        assert_eq!(recovered.expose_secret(), secret.expose_secret());
+    }
+
+    #[test]
+    fn secret_bytes_test() {
+        let sharing = Gf256Sharing::new(3, 5).unwrap();
+        let secret = SecretBytes::from(b"hunter2".to_vec());
+        assert_eq!(format!("{:?}", secret), "SecretBytes([REDACTED])");
+        let shares = sharing.split_secret_with(&secret, counter(41));
+        let recovered = sharing.recover_secret(&shares[2..]).unwrap();
+        assert_eq!(recovered.expose_secret(), b"hunter2");
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize_test() {
+        use crate::zeroize::{Zeroize, Zeroizing};
+
+        let mut secret = SecretScalar::new(BigInt::from(1000));
+        secret.zeroize();
+        assert_eq!(secret.expose_secret(), &BigInt::from(0));
+        let secret = Zeroizing::new(SecretScalar::new(0x42u8));
+        assert_eq!(secret.expose_secret(), &0x42);
+        let mut bytes = SecretBytes::from(b"hunter2".to_vec());
+        bytes.zeroize();
+        assert!(bytes.expose_secret().is_empty());
+    }
+}
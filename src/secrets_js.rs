@@ -0,0 +1,475 @@
+//! Shares of the [secrets.js](https://github.com/grempe/secrets.js)
+//! JavaScript library, so that shares dealt in a browser recover here and
+//! the other way round.
+//!
+//! secrets.js works in GF(2^bits) for `bits` from 3 to 20, 8 by default,
+//! modulo a fixed primitive polynomial per size. It prefixes the secret's
+//! bits with a 1, so that leading zeros survive, pads them with zeros on
+//! the left to a multiple of 128 bits, and cuts them into field elements
+//! from the right, sharing each with its own polynomial at x = 1, 2, ... A
+//! share is printed as `bits` in one base-36 digit, x in as many hex digits
+//! as 2^bits - 1 has, and the y-coordinates as one hex string, most
+//! significant element first. secrets.js takes secrets as hex strings; the
+//! ones here are bytes, which is what its `str2hex` and random keys come to
+//! as long as the hex has an even number of digits.
+
+use crate::{field::Field, poly, DecodeError, Share, TinySsError};
+use alloc::{string::String, vec, vec::Vec};
+use core::{fmt, str::FromStr};
+
+const MIN_BITS: u32 = 3;
+const MAX_BITS: u32 = 20;
+
+// The low terms of the primitive polynomial secrets.js reduces by, for
+// every field size from 0 to `MAX_BITS` bits.
+const PRIMITIVE: [u32; MAX_BITS as usize + 1] = [
+    0, 0, 1, 3, 3, 5, 3, 3, 29, 17, 9, 5, 83, 27, 43, 3, 45, 9, 39, 39, 9,
+];
+
+/// GF(2^bits) modulo x^bits plus `PRIMITIVE[bits]`, with elements as the
+/// integers below 2^bits.
+#[derive(Clone, Copy, Debug)]
+struct SecretsField {
+    bits: u32,
+}
+
+impl SecretsField {
+    fn order(&self) -> u32 {
+        1 << self.bits
+    }
+}
+
+impl Field for SecretsField {
+    type Elem = u32;
+
+    fn zero(&self) -> u32 {
+        0
+    }
+
+    fn one(&self) -> u32 {
+        1
+    }
+
+    fn add(&self, a: &u32, b: &u32) -> u32 {
+        a ^ b
+    }
+
+    fn sub(&self, a: &u32, b: &u32) -> u32 {
+        a ^ b
+    }
+
+    fn mul(&self, a: &u32, b: &u32) -> u32 {
+        let (mut a, mut b) = (*a, *b);
+        let mut product = 0;
+        while b != 0 {
+            if b & 1 != 0 {
+                product ^= a;
+            }
+            b >>= 1;
+            a <<= 1;
+            if a & self.order() != 0 {
+                a ^= self.order() | PRIMITIVE[self.bits as usize];
+            }
+        }
+        product
+    }
+
+    fn inv(&self, a: &u32) -> Option<u32> {
+        if *a == 0 {
+            return None;
+        }
+        // a^(2^bits - 2)
+        let mut result = 1;
+        let mut base = *a;
+        let mut e = self.order() - 2;
+        while e != 0 {
+            if e & 1 != 0 {
+                result = self.mul(&result, &base);
+            }
+            base = self.mul(&base, &base);
+            e >>= 1;
+        }
+        Some(result)
+    }
+
+    fn elem_from_index(&self, index: usize) -> u32 {
+        index as u32
+    }
+
+    fn has_indices(&self, n: usize) -> bool {
+        n < self.order() as usize
+    }
+
+    fn random(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> u32 {
+        let mut bytes = [0u8; 4];
+        fill_random(&mut bytes);
+        u32::from_le_bytes(bytes) & (self.order() - 1)
+    }
+
+    fn encode(&self, a: &u32) -> Vec<u8> {
+        a.to_be_bytes().to_vec()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<u32> {
+        let a = u32::from_be_bytes(bytes.try_into().ok()?);
+        (a < self.order()).then_some(a)
+    }
+}
+
+/// A share as secrets.js prints it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SecretsJsShare {
+    /// size of the field elements
+    bits: u32,
+    /// x-coordinate
+    id: usize,
+    /// the y-coordinates in lowercase hex
+    data: String,
+}
+
+impl SecretsJsShare {
+    /// size of the field elements
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    // The y-coordinates, least significant first, as secrets.js cuts the
+    // bits of `data` from the right.
+    fn elements(&self) -> Vec<u32> {
+        hex_bits(&self.data)
+            .rchunks(self.bits as usize)
+            .map(from_bits)
+            .collect()
+    }
+}
+
+impl fmt::Display for SecretsJsShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let digit = char::from_digit(self.bits, 36).expect("bits below 36");
+        write!(f, "{}", digit.to_ascii_uppercase())?;
+        write!(f, "{:0width$x}", self.id, width = id_width(self.bits))?;
+        f.write_str(&self.data)
+    }
+}
+
+/// Parses `bits || id || data` as secrets.js's `extractShareComponents`
+/// does, with the hex in either case.
+impl FromStr for SecretsJsShare {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let bits = s
+            .chars()
+            .next()
+            .and_then(|digit| digit.to_digit(36))
+            .ok_or(DecodeError::InvalidEncoding)?;
+        if !(MIN_BITS..=MAX_BITS).contains(&bits) {
+            return Err(DecodeError::InvalidValue);
+        }
+        let width = id_width(bits);
+        if s.len() <= 1 + width {
+            return Err(DecodeError::Truncated);
+        }
+        if !s.bytes().skip(1).all(|b| b.is_ascii_hexdigit()) {
+            return Err(DecodeError::InvalidEncoding);
+        }
+        let id = usize::from_str_radix(&s[1..1 + width], 16).expect("hex digits");
+        if id == 0 || id >= 1 << bits {
+            return Err(DecodeError::InvalidValue);
+        }
+        Ok(SecretsJsShare {
+            bits,
+            id,
+            data: s[1 + width..].to_ascii_lowercase(),
+        })
+    }
+}
+
+// Hex digits of the largest id, 2^bits - 1.
+fn id_width(bits: u32) -> usize {
+    bits.div_ceil(4) as usize
+}
+
+/// A `t`-of-`n` scheme dealing as `secrets.share(secret, n, t)` does.
+#[derive(Clone, Debug)]
+pub struct SecretsJsSharing {
+    /// threshold
+    t: usize,
+    /// total number of shares
+    n: usize,
+    field: SecretsField,
+    /// bits the marked secret is padded to a multiple of
+    padding: usize,
+}
+
+impl SecretsJsSharing {
+    /// Creates a `t`-of-`n` scheme with `2 <= t <= n` over GF(2^8), as
+    /// secrets.js is set up by default.
+    pub fn new(t: usize, n: usize) -> Result<Self, TinySsError> {
+        SecretsJsSharing::with_bits(t, n, 8)
+    }
+
+    /// Creates a `t`-of-`n` scheme over GF(2^bits), as after
+    /// `secrets.init(bits)`. Fails with `InvalidModulus` for `bits` outside
+    /// 3 to 20, and for more shares than the field has nonzero elements.
+    pub fn with_bits(t: usize, n: usize, bits: u32) -> Result<Self, TinySsError> {
+        if t < 2 || t > n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if !(MIN_BITS..=MAX_BITS).contains(&bits) {
+            return Err(TinySsError::InvalidModulus);
+        }
+        let field = SecretsField { bits };
+        if !field.has_indices(n) {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(SecretsJsSharing {
+            t,
+            n,
+            field,
+            padding: 128,
+        })
+    }
+
+    /// Pads the marked secret to a multiple of `padding` bits instead of
+    /// 128, as the `padLength` argument of `secrets.share` does; 0 and 1
+    /// leave it unpadded. secrets.js allows at most 1024.
+    pub fn with_padding(mut self, padding: usize) -> Result<Self, TinySsError> {
+        if padding > 1024 {
+            return Err(TinySsError::InvalidModulus);
+        }
+        self.padding = padding;
+        Ok(self)
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: &[u8]) -> Vec<SecretsJsShare> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits `secret` with coefficients drawn from `fill_random`, which must
+    /// fill the buffer with uniformly random bytes; every coefficient comes
+    /// from 4 bytes, little-endian and cut to `bits` bits.
+    pub fn split_with<F>(&self, secret: &[u8], mut fill_random: F) -> Vec<SecretsJsShare>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        let mut bits = vec![true];
+        bits.extend(
+            secret
+                .iter()
+                .flat_map(|byte| (0..8).rev().map(move |i| byte >> i & 1 == 1)),
+        );
+        if self.padding > 1 && !bits.len().is_multiple_of(self.padding) {
+            let missing = self.padding - bits.len() % self.padding;
+            bits.splice(0..0, core::iter::repeat_n(false, missing));
+        }
+        let width = self.field.bits as usize;
+        // the secret elements, least significant first
+        let polynomials: Vec<Vec<u32>> = bits
+            .rchunks(width)
+            .map(|chunk| {
+                let mut polynomial = vec![from_bits(chunk)];
+                polynomial.extend((1..self.t).map(|_| self.field.random(&mut fill_random)));
+                polynomial
+            })
+            .collect();
+        (1..=self.n)
+            .map(|x| {
+                let x_elem = self.field.elem_from_index(x);
+                let ys: Vec<u32> = polynomials
+                    .iter()
+                    .map(|polynomial| poly::evaluate(&self.field, polynomial, &x_elem))
+                    .collect();
+                SecretsJsShare {
+                    bits: self.field.bits,
+                    id: x,
+                    data: to_hex(&element_bits(&ys, width)),
+                }
+            })
+            .collect()
+    }
+}
+
+// The bits of `elements`, least significant first, in `width` bits each.
+fn element_bits(elements: &[u32], width: usize) -> Vec<bool> {
+    elements
+        .iter()
+        .rev()
+        .flat_map(|y| (0..width).rev().map(move |i| y >> i & 1 == 1))
+        .collect()
+}
+
+fn from_bits(bits: &[bool]) -> u32 {
+    bits.iter().fold(0, |acc, &bit| acc << 1 | bit as u32)
+}
+
+fn hex_bits(hex: &str) -> Vec<bool> {
+    hex.chars()
+        .flat_map(|digit| {
+            let nibble = digit.to_digit(16).expect("hex digit");
+            (0..4).rev().map(move |i| nibble >> i & 1 == 1)
+        })
+        .collect()
+}
+
+// `bits` in lowercase hex, padded with zeros on the left to whole digits.
+fn to_hex(bits: &[bool]) -> String {
+    let mut padded = vec![false; (4 - bits.len() % 4) % 4];
+    padded.extend_from_slice(bits);
+    padded
+        .chunks(4)
+        .map(|nibble| char::from_digit(from_bits(nibble), 16).expect("nibble"))
+        .collect()
+}
+
+/// Recovers the secret from `shares` as `secrets.combine` does, by
+/// interpolating through all of them, which must be at least two and agree
+/// on `bits`; too few for the threshold the secret was split with recover
+/// an unrelated one.
+///
+/// Shares repeating an id are ignored as long as they carry the same data.
+/// Fails with `SecretOutOfRange` if the recovered bits lack the marker or
+/// do not come to whole bytes after it.
+pub fn combine(shares: &[SecretsJsShare]) -> Result<Vec<u8>, TinySsError> {
+    let bits = shares.first().map_or(8, |share| share.bits);
+    if shares.iter().any(|share| share.bits != bits) {
+        return Err(TinySsError::MixedDealings);
+    }
+    let field = SecretsField { bits };
+    let points: Vec<Share<Vec<u32>>> = shares
+        .iter()
+        .map(|share| Share::new(share.id, share.elements()))
+        .collect();
+    if points
+        .iter()
+        .any(|p| p.value().len() != points[0].value().len())
+    {
+        return Err(TinySsError::LengthMismatch);
+    }
+    let unique = poly::distinct_shares(&points, 2)?;
+    let xs: Vec<u32> = unique
+        .iter()
+        .map(|share| field.elem_from_index(share.index()))
+        .collect();
+    let weights = poly::lagrange_coefficients(&field, &xs, &0)?;
+    let elements: Vec<u32> = (0..points[0].value().len())
+        .map(|i| {
+            let ys: Vec<u32> = unique.iter().map(|share| share.value()[i]).collect();
+            field.dot(&weights, &ys)
+        })
+        .collect();
+    let recovered = element_bits(&elements, bits as usize);
+    let marker = recovered
+        .iter()
+        .position(|&bit| bit)
+        .ok_or(TinySsError::SecretOutOfRange)?;
+    let secret = &recovered[marker + 1..];
+    if !secret.len().is_multiple_of(8) {
+        return Err(TinySsError::SecretOutOfRange);
+    }
+    Ok(secret.chunks(8).map(|byte| from_bits(byte) as u8).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn field_test() {
+        // 2 generates every field, so each polynomial is primitive
+        for bits in MIN_BITS..=MAX_BITS {
+            let field = SecretsField { bits };
+            let mut x = 2;
+            let mut order = 1;
+            while x != 1 {
+                x = field.mul(&x, &2);
+                order += 1;
+            }
+            assert_eq!(order, field.order() - 1, "{} bits", bits);
+        }
+        let field = SecretsField { bits: 8 };
+        assert_eq!(field.mul(&0x80, &2), 0x1d);
+        assert_eq!(field.mul(&0x53, &field.inv(&0x53).unwrap()), 1);
+    }
+
+    #[test]
+    fn split_with_test() {
+        let ss = SecretsJsSharing::new(2, 3)
+            .unwrap()
+            .with_padding(0)
+            .unwrap();
+        let shares = ss.split_with(&[0xab], |buf| buf.fill(7));
+        // the marked secret 1 1010_1011 as the elements 1 and 0xab
+        let field = SecretsField { bits: 8 };
+        let y = |x: u32| field.mul(&7, &x);
+        let expected = alloc::format!("801{:02x}{:02x}", 1 ^ y(1), 0xab ^ y(1));
+        assert_eq!(shares[0].to_string(), expected);
+        assert_eq!(combine(&shares[1..]).unwrap(), [0xab]);
+
+        // padded to 128 bits by default, 16 elements of 8 bits
+        let secret = *b"\x00\x00leading zeros";
+        let ss = SecretsJsSharing::new(3, 5).unwrap();
+        let shares = ss.split_with(&secret, |buf| buf.fill(0x5c));
+        assert_eq!(shares[4].to_string().len(), 3 + 32);
+        assert_eq!(combine(&shares[..3]).unwrap(), secret);
+        assert_eq!(combine(&shares).unwrap(), secret);
+
+        let ss = SecretsJsSharing::with_bits(3, 7, 3).unwrap();
+        let shares = ss.split_with(b"odd", |buf| buf.fill(0xa5));
+        assert_eq!(shares[6].to_string()[..2], *"37");
+        assert_eq!(combine(&shares[4..]).unwrap(), b"odd");
+        assert!(SecretsJsSharing::with_bits(3, 8, 3).is_err());
+        assert!(SecretsJsSharing::with_bits(2, 3, 21).is_err());
+    }
+
+    #[test]
+    fn parse_test() {
+        let share: SecretsJsShare = "801ABCD".parse().unwrap();
+        assert_eq!((share.bits(), share.id()), (8, 1));
+        assert_eq!(share.to_string(), "801abcd");
+        let share: SecretsJsShare = "K000011f".parse().unwrap();
+        assert_eq!((share.bits(), share.id()), (20, 1));
+        assert_eq!(share.elements(), [0x1f]);
+
+        assert_eq!("801".parse::<SecretsJsShare>(), Err(DecodeError::Truncated));
+        assert_eq!(
+            "800ab".parse::<SecretsJsShare>(),
+            Err(DecodeError::InvalidValue)
+        );
+        assert_eq!(
+            "201ab".parse::<SecretsJsShare>(),
+            Err(DecodeError::InvalidValue)
+        );
+        assert_eq!(
+            "801xy".parse::<SecretsJsShare>(),
+            Err(DecodeError::InvalidEncoding)
+        );
+        assert_eq!(
+            "-01ab".parse::<SecretsJsShare>(),
+            Err(DecodeError::InvalidEncoding)
+        );
+
+        let other: SecretsJsShare = "901abc".parse().unwrap();
+        let share: SecretsJsShare = "801abcd".parse().unwrap();
+        assert_eq!(combine(&[share, other]), Err(TinySsError::MixedDealings));
+    }
+}
@@ -0,0 +1,43 @@
+// Serializes `BigInt` values as lowercase hexadecimal strings, which stay
+// stable across num-bigint releases and are readable in JSON.
+
+use alloc::string::String;
+use num_bigint::BigInt;
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+pub fn serialize<S: Serializer>(value: &BigInt, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.serialize_str(&value.to_str_radix(16))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<BigInt, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    BigInt::parse_bytes(hex.as_bytes(), 16).ok_or_else(|| D::Error::custom("invalid hex integer"))
+}
+
+// The same encoding for every element of a `Vec<BigInt>`
+#[cfg(feature = "vss")]
+pub mod vec {
+    use alloc::{string::String, vec::Vec};
+    use num_bigint::BigInt;
+    use serde::{de::Error, ser::SerializeSeq, Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(values: &[BigInt], serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(values.len()))?;
+        for value in values {
+            seq.serialize_element(&value.to_str_radix(16))?;
+        }
+        seq.end()
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Vec<BigInt>, D::Error> {
+        Vec::<String>::deserialize(deserializer)?
+            .iter()
+            .map(|hex| {
+                BigInt::parse_bytes(hex.as_bytes(), 16)
+                    .ok_or_else(|| D::Error::custom("invalid hex integer"))
+            })
+            .collect()
+    }
+}
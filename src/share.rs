@@ -0,0 +1,322 @@
+use crate::{codec, field::Field, DecodeError, SecretShare};
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+/// magic bytes opening every binary share
+const MAGIC: [u8; 3] = *b"TSS";
+/// current binary format version
+const VERSION: u8 = 1;
+
+/// A single share: the evaluation of the dealing polynomial at `index`.
+///
+/// `V` is the element type of the field the dealing was made in, a `BigInt`
+/// for the default prime field.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Share<V = BigInt> {
+    /// x-coordinate
+    index: usize,
+    /// y-coordinate
+    value: V,
+}
+
+impl<V: fmt::Debug> fmt::Debug for Share<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Share")
+            .field("index", &self.index)
+            .field("value", crate::debug::secret(&self.value))
+            .finish()
+    }
+}
+
+impl<V> Share<V> {
+    pub fn new(index: usize, value: V) -> Self {
+        Share { index, value }
+    }
+
+    /// x-coordinate
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// y-coordinate
+    pub fn value(&self) -> &V {
+        &self.value
+    }
+}
+
+// the index is public, only the value is wiped
+#[cfg(feature = "zeroize")]
+crate::zeroize::impl_zeroize!(Share, value);
+
+impl Share {
+    /// Encodes the share as `magic || version || index || len || value`,
+    /// where the index is a big-endian `u64`, `len` a big-endian `u32` and
+    /// the value the big-endian magnitude of the share value.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let value = if self.value.is_zero() {
+            Vec::new()
+        } else {
+            self.value.magnitude().to_bytes_be()
+        };
+        encode(self.index, &value)
+    }
+
+    /// Parses a share written by `to_bytes`, rejecting anything but exactly
+    /// one well-formed share.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, DecodeError> {
+        let (index, value) = decode(bytes)?;
+        Ok(Share::new(index, BigInt::from_bytes_be(Sign::Plus, value)))
+    }
+
+    /// Hex rendering of `to_bytes`.
+    pub fn to_hex(&self) -> String {
+        codec::to_hex(&self.to_bytes())
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, DecodeError> {
+        Share::from_bytes(&codec::from_hex(hex)?)
+    }
+
+    /// Padded standard base64 rendering of `to_bytes`.
+    pub fn to_base64(&self) -> String {
+        codec::to_base64(&self.to_bytes())
+    }
+
+    pub fn from_base64(encoded: &str) -> Result<Self, DecodeError> {
+        Share::from_bytes(&codec::from_base64(encoded)?)
+    }
+
+    /// Renders `to_bytes` as words from the BIP-0039 English wordlist, with
+    /// a 21-bit checksum to catch transcription errors.
+    #[cfg(feature = "mnemonic")]
+    pub fn to_mnemonic(&self) -> String {
+        crate::mnemonic::encode(&self.to_bytes())
+    }
+
+    #[cfg(feature = "mnemonic")]
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self, DecodeError> {
+        Share::from_bytes(&crate::mnemonic::decode(mnemonic)?)
+    }
+}
+
+/// Formats the share as hex, see `Share::to_hex`.
+impl fmt::Display for Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_hex())
+    }
+}
+
+impl FromStr for Share {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Share::from_hex(s.trim())
+    }
+}
+
+impl<F: Field> SecretShare<F> {
+    /// Encodes `share` in the layout of `Share::to_bytes`, but with the value
+    /// as `Field::encode` writes it, padded to the width of the field, so
+    /// that every share of a dealing has the same length whatever its value.
+    pub fn encode_share(&self, share: &Share<F::Elem>) -> Vec<u8> {
+        encode(share.index, &self.field().encode(&share.value))
+    }
+
+    /// Parses a share written by `encode_share`, rejecting values that are
+    /// not of the width of the field or lie outside it.
+    pub fn decode_share(&self, bytes: &[u8]) -> Result<Share<F::Elem>, DecodeError> {
+        let (index, value) = decode(bytes)?;
+        let value = self
+            .field()
+            .decode(value)
+            .ok_or(DecodeError::InvalidValue)?;
+        Ok(Share::new(index, value))
+    }
+}
+
+// `magic || version || index || len || value`
+fn encode(index: usize, value: &[u8]) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(MAGIC.len() + 1 + 8 + 4 + value.len());
+    bytes.extend_from_slice(&MAGIC);
+    bytes.push(VERSION);
+    bytes.extend_from_slice(&(index as u64).to_be_bytes());
+    bytes.extend_from_slice(&(value.len() as u32).to_be_bytes());
+    bytes.extend_from_slice(value);
+    bytes
+}
+
+// The index and value bytes of exactly one encoded share
+fn decode(bytes: &[u8]) -> Result<(usize, &[u8]), DecodeError> {
+    let rest = bytes.strip_prefix(&MAGIC).ok_or(DecodeError::BadMagic)?;
+    let (version, rest) = codec::take(rest, 1)?;
+    if version[0] != VERSION {
+        return Err(DecodeError::UnsupportedVersion(version[0]));
+    }
+    let (index, rest) = codec::take_u64(rest)?;
+    let (len, rest) = codec::take_u32(rest)?;
+    let (value, rest) = codec::take(rest, len)?;
+    if !rest.is_empty() {
+        return Err(DecodeError::TrailingBytes);
+    }
+    Ok((index, value))
+}
+
+impl<V> From<(usize, V)> for Share<V> {
+    fn from((index, value): (usize, V)) -> Self {
+        Share::new(index, value)
+    }
+}
+
+impl<V> From<Share<V>> for (usize, V) {
+    fn from(share: Share<V>) -> Self {
+        (share.index, share.value)
+    }
+}
+
+// Wire form of `Share`, with the value as a hex string
+#[cfg(feature = "serde")]
+#[derive(Serialize, Deserialize)]
+struct RawShare {
+    index: usize,
+    #[serde(with = "crate::serde_bigint")]
+    value: BigInt,
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Share {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawShare {
+            index: self.index,
+            value: self.value.clone(),
+        }
+        .serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Share {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawShare::deserialize(deserializer)?;
+        Ok(Share::new(raw.index, raw.value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+
+    #[test]
+    fn tuple_conversion_test() {
+        let share = Share::from((3, BigInt::from(42)));
+        assert_eq!(share.index(), 3);
+        assert_eq!(share.value(), &BigInt::from(42));
+        assert_eq!(<(usize, BigInt)>::from(share), (3, BigInt::from(42)));
+    }
+
+    #[test]
+    fn bytes_test() {
+        let share = Share::new(3, BigInt::from(1613));
+        let bytes = share.to_bytes();
+        assert_eq!(
+            bytes,
+            [b'T', b'S', b'S', 1, 0, 0, 0, 0, 0, 0, 0, 3, 0, 0, 0, 2, 0x06, 0x4d]
+        );
+        assert_eq!(Share::from_bytes(&bytes).unwrap(), share);
+
+        let zero = Share::new(1, BigInt::from(0));
+        assert_eq!(Share::from_bytes(&zero.to_bytes()).unwrap(), zero);
+
+        assert_eq!(Share::from_bytes(b"XSS"), Err(DecodeError::BadMagic));
+        assert_eq!(
+            Share::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(DecodeError::Truncated)
+        );
+        assert_eq!(Share::from_bytes(&bytes[..4]), Err(DecodeError::Truncated));
+        let mut future = bytes.clone();
+        future[3] = 2;
+        assert_eq!(
+            Share::from_bytes(&future),
+            Err(DecodeError::UnsupportedVersion(2))
+        );
+        let mut trailing = bytes;
+        trailing.push(0);
+        assert_eq!(
+            Share::from_bytes(&trailing),
+            Err(DecodeError::TrailingBytes)
+        );
+    }
+
+    #[test]
+    fn encode_share_test() {
+        let ss = SecretShare::new(2, 3, BigInt::from(1613)).unwrap();
+        let shares = ss
+            .split_with_coefficients(BigInt::from(7), &[BigInt::from(1)])
+            .unwrap();
+        let encoded: Vec<Vec<u8>> = shares.iter().map(|s| ss.encode_share(s)).collect();
+        // the values 8, 9 and 10 take a single byte in `to_bytes`
+        assert_eq!(
+            encoded[0],
+            [b'T', b'S', b'S', 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 2, 0, 8]
+        );
+        assert!(encoded.iter().all(|e| e.len() == encoded[0].len()));
+        for (bytes, share) in encoded.iter().zip(&shares) {
+            assert_eq!(ss.decode_share(bytes).unwrap(), *share);
+            assert_eq!(Share::from_bytes(bytes).unwrap(), *share);
+        }
+        assert_eq!(
+            ss.decode_share(&shares[0].to_bytes()),
+            Err(DecodeError::InvalidValue)
+        );
+        let outside = Share::new(1, BigInt::from(1613)).to_bytes();
+        assert_eq!(ss.decode_share(&outside), Err(DecodeError::InvalidValue));
+
+        let small = SecretShare::small(2, 3, 1613).unwrap();
+        let share = Share::new(2, 9u64);
+        assert_eq!(
+            small.decode_share(&small.encode_share(&share)).unwrap(),
+            share
+        );
+    }
+
+    #[test]
+    fn text_test() {
+        let share = Share::new(3, BigInt::from(1613));
+        let hex = share.to_string();
+        assert_eq!(hex, "54535301000000000000000300000002064d");
+        assert_eq!(hex.parse::<Share>().unwrap(), share);
+        assert_eq!(Share::from_hex(&share.to_hex()).unwrap(), share);
+
+        let base64 = share.to_base64();
+        assert_eq!(base64, "VFNTAQAAAAAAAAADAAAAAgZN");
+        assert_eq!(Share::from_base64(&base64).unwrap(), share);
+
+        assert_eq!(
+            "5453533".parse::<Share>(),
+            Err(DecodeError::InvalidEncoding)
+        );
+    }
+
+    #[cfg(feature = "mnemonic")]
+    #[test]
+    fn mnemonic_test() {
+        let share = Share::new(3, BigInt::from(1613));
+        let mnemonic = share.to_mnemonic();
+        assert_eq!(mnemonic.split(' ').count(), 2 + 14);
+        assert_eq!(Share::from_mnemonic(&mnemonic).unwrap(), share);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_test() {
+        let share = Share::new(3, BigInt::from(1613));
+        let json = serde_json::to_string(&share).unwrap();
+        assert_eq!(json, r#"{"index":3,"value":"64d"}"#);
+        assert_eq!(serde_json::from_str::<Share>(&json).unwrap(), share);
+        assert!(serde_json::from_str::<Share>(r#"{"index":3,"value":"xyz"}"#).is_err());
+    }
+}
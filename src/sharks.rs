@@ -0,0 +1,307 @@
+//! Shares in the layout of the [`sharks`](https://crates.io/crates/sharks)
+//! crate, so that shares it dealt can be recovered here and the other way
+//! round.
+//!
+//! `sharks` shares byte strings byte by byte like `Gf256Sharing`, but
+//! modulo x^8 + x^4 + x^3 + x^2 + 1 rather than the AES polynomial, so its
+//! shares only recover under `SharksSharing`. It serializes a share as the
+//! x-coordinate byte followed by the y bytes, deals at x = 1, 2, ... and
+//! draws every coefficient of degree 1 and up from the nonzero bytes.
+
+use crate::{field::Field, gf256::ByteShare, poly, DecodeError, Share, TinySsError};
+use alloc::{vec, vec::Vec};
+
+const fn tables() -> ([u8; 255], [u8; 256]) {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut x: u16 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = x as u8;
+        log[x as usize] = i as u8;
+        // multiply by the generator 2 = x
+        x <<= 1;
+        if x & 0x100 != 0 {
+            x ^= 0x11d;
+        }
+        i += 1;
+    }
+    (exp, log)
+}
+
+const EXP: [u8; 255] = tables().0;
+const LOG: [u8; 256] = tables().1;
+
+/// GF(2^8) modulo x^8 + x^4 + x^3 + x^2 + 1, with elements as bytes.
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct SharksField;
+
+impl Field for SharksField {
+    type Elem = u8;
+
+    fn zero(&self) -> u8 {
+        0
+    }
+
+    fn one(&self) -> u8 {
+        1
+    }
+
+    fn add(&self, a: &u8, b: &u8) -> u8 {
+        a ^ b
+    }
+
+    fn sub(&self, a: &u8, b: &u8) -> u8 {
+        a ^ b
+    }
+
+    fn mul(&self, a: &u8, b: &u8) -> u8 {
+        if *a == 0 || *b == 0 {
+            return 0;
+        }
+        EXP[(LOG[*a as usize] as usize + LOG[*b as usize] as usize) % 255]
+    }
+
+    fn inv(&self, a: &u8) -> Option<u8> {
+        if *a == 0 {
+            None
+        } else {
+            Some(EXP[(255 - LOG[*a as usize] as usize) % 255])
+        }
+    }
+
+    fn elem_from_index(&self, index: usize) -> u8 {
+        index as u8
+    }
+
+    fn has_indices(&self, n: usize) -> bool {
+        n <= 255
+    }
+
+    fn random(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> u8 {
+        let mut byte = [0u8];
+        fill_random(&mut byte);
+        byte[0]
+    }
+
+    fn encode(&self, a: &u8) -> Vec<u8> {
+        vec![*a]
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<u8> {
+        match bytes {
+            [byte] => Some(*byte),
+            _ => None,
+        }
+    }
+}
+
+/// The `sharks` serialization of `share`: its index in one byte, then its
+/// value. Fails with `InvalidIndex` for an index outside `1..=255`.
+pub fn to_bytes(share: &ByteShare) -> Result<Vec<u8>, TinySsError> {
+    let x = u8::try_from(share.index()).map_err(|_| TinySsError::InvalidIndex)?;
+    if x == 0 {
+        return Err(TinySsError::InvalidIndex);
+    }
+    let mut bytes = Vec::with_capacity(share.value().len() + 1);
+    bytes.push(x);
+    bytes.extend_from_slice(share.value());
+    Ok(bytes)
+}
+
+/// Parses the `sharks` serialization, which is at least 2 bytes long,
+/// rejecting the zero x-coordinate.
+pub fn from_bytes(bytes: &[u8]) -> Result<ByteShare, DecodeError> {
+    match bytes {
+        [] | [_] => Err(DecodeError::Truncated),
+        [0, ..] => Err(DecodeError::InvalidValue),
+        [x, value @ ..] => Ok(Share::new((*x).into(), value.to_vec())),
+    }
+}
+
+/// A `t`-of-`n` scheme dealing and recovering as `Sharks(t)` does.
+#[derive(Clone, Debug)]
+pub struct SharksSharing {
+    /// threshold
+    t: usize,
+    /// total number of shares
+    n: usize,
+}
+
+impl SharksSharing {
+    /// Creates a `t`-of-`n` scheme with `0 < t <= n <= 255`; `sharks` lets
+    /// a dealing hand out as many shares as the threshold.
+    pub fn new(t: usize, n: usize) -> Result<Self, TinySsError> {
+        if t == 0 || t > n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if !SharksField.has_indices(n) {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(SharksSharing { t, n })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: &[u8]) -> Vec<ByteShare> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits `secret` with coefficients drawn from `fill_random`, which must
+    /// fill the buffer with uniformly random bytes; zero bytes are drawn
+    /// again, as `sharks` only uses nonzero coefficients. The coefficients
+    /// are drawn byte by byte from the top degree down, in the order
+    /// `sharks` draws them.
+    pub fn split_with<F>(&self, secret: &[u8], mut fill_random: F) -> Vec<ByteShare>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        let degree = self.t - 1;
+        let mut coefficients = vec![0u8; degree * secret.len()];
+        fill_random(&mut coefficients);
+        for coefficient in &mut coefficients {
+            while *coefficient == 0 {
+                *coefficient = SharksField.random(&mut fill_random);
+            }
+        }
+        // coefficient degree - j of byte i at i * degree + j, as `sharks`
+        // draws the coefficients of each byte from the top degree down
+        let polynomials: Vec<Vec<u8>> = secret
+            .iter()
+            .enumerate()
+            .map(|(i, &byte)| {
+                let mut polynomial = Vec::from([byte]);
+                polynomial.extend(coefficients[i * degree..(i + 1) * degree].iter().rev());
+                polynomial
+            })
+            .collect();
+        (1..=self.n)
+            .map(|x| {
+                let x_elem = SharksField.elem_from_index(x);
+                let value = polynomials
+                    .iter()
+                    .map(|polynomial| poly::evaluate(&SharksField, polynomial, &x_elem))
+                    .collect();
+                Share::new(x, value)
+            })
+            .collect()
+    }
+
+    /// Recovers the secret from at least `t` shares of equal length.
+    ///
+    /// Shares repeating an index are ignored as long as they carry the same
+    /// value; the first `t` distinct shares are used for interpolation.
+    pub fn recover(&self, shares: &[ByteShare]) -> Result<Vec<u8>, TinySsError> {
+        for share in shares {
+            if share.index() == 0 || !SharksField.has_indices(share.index()) {
+                return Err(TinySsError::InvalidIndex);
+            }
+            if share.value().len() != shares[0].value().len() {
+                return Err(TinySsError::LengthMismatch);
+            }
+        }
+        let unique = poly::distinct_shares(shares, self.t)?;
+        let xs: Vec<u8> = unique[..self.t]
+            .iter()
+            .map(|share| SharksField.elem_from_index(share.index()))
+            .collect();
+        let weights = poly::lagrange_coefficients(&SharksField, &xs, &0)?;
+        let len = shares.first().map_or(0, |share| share.value().len());
+        Ok((0..len)
+            .map(|i| {
+                let ys: Vec<u8> = unique[..self.t]
+                    .iter()
+                    .map(|share| share.value()[i])
+                    .collect();
+                SharksField.dot(&weights, &ys)
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn field_test() {
+        // the tables of the Reed-Solomon field
+        assert_eq!(EXP[8], 0x1d);
+        assert_eq!(LOG[3], 0x19);
+        assert_eq!(SharksField.mul(&0x80, &2), 0x1d);
+        for a in 1..=255u8 {
+            assert_eq!(SharksField.mul(&a, &SharksField.inv(&a).unwrap()), 1);
+        }
+    }
+
+    #[test]
+    fn split_with_test() {
+        let ss = SharksSharing::new(2, 3).unwrap();
+        // the zero is drawn again, as the second buffer
+        let mut draws = [&[0, 9][..], &[5]].into_iter();
+        let shares = ss.split_with(b"ss", |buf| buf.copy_from_slice(draws.next().unwrap()));
+        assert_eq!(shares[0], Share::new(1, vec![b's' ^ 5, b's' ^ 9]));
+        let two = |c: u8| SharksField.mul(&c, &2);
+        assert_eq!(shares[1], Share::new(2, vec![b's' ^ two(5), b's' ^ two(9)]));
+        assert_eq!(ss.recover(&shares[1..]).unwrap(), b"ss");
+
+        let bytes = to_bytes(&shares[1]).unwrap();
+        assert_eq!(bytes, [2, b's' ^ two(5), b's' ^ two(9)]);
+        assert_eq!(from_bytes(&bytes).unwrap(), shares[1]);
+        assert_eq!(from_bytes(&[2]), Err(DecodeError::Truncated));
+        assert_eq!(from_bytes(&[0, 1]), Err(DecodeError::InvalidValue));
+    }
+
+    // `Sharks(3).dealer_rng(b"tiny-ss", &mut rng).take(5)` of sharks 0.5,
+    // serialized, with `rng.next_u32()` returning `WORD`, `2 * WORD`, ...
+    const WORD: u32 = 0x9e37_79b9;
+    const DEALT: [[u8; 8]; 5] = [
+        [1, 215, 202, 204, 220, 147, 212, 221],
+        [2, 76, 212, 69, 203, 11, 178, 4],
+        [3, 239, 119, 231, 110, 181, 21, 170],
+        [4, 149, 177, 237, 179, 96, 158, 69],
+        [5, 54, 18, 79, 22, 222, 57, 235],
+    ];
+
+    #[test]
+    fn sharks_test() {
+        let ss = SharksSharing::new(3, 5).unwrap();
+        let shares: Vec<ByteShare> = DEALT
+            .iter()
+            .map(|bytes| from_bytes(bytes).unwrap())
+            .collect();
+        assert_eq!(ss.recover(&shares[2..]).unwrap(), b"tiny-ss");
+        assert_eq!(
+            ss.recover(&[shares[4].clone(), shares[0].clone(), shares[2].clone()])
+                .unwrap(),
+            b"tiny-ss"
+        );
+
+        // the same words through `Uniform::new_inclusive(1, 255)`, which
+        // takes the high word of 255 * w; none of them is rejected
+        let mut word = 0u32;
+        let fill = |buf: &mut [u8]| {
+            for byte in buf {
+                word = word.wrapping_add(WORD);
+                *byte = ((u64::from(word) * 255) >> 32) as u8 + 1;
+            }
+        };
+        let dealt: Vec<Vec<u8>> = ss
+            .split_with(b"tiny-ss", fill)
+            .iter()
+            .map(|share| to_bytes(share).unwrap())
+            .collect();
+        assert_eq!(dealt, DEALT);
+    }
+}
@@ -0,0 +1,240 @@
+//! Threshold Schnorr signatures.
+//!
+//! The signing key `x` with public key `Y = g^x` is Shamir shared modulo q,
+//! by a dealer or by `dkg`, and so is a one-time nonce `k` with public
+//! `R = g^k`. Each signer publishes the partial signature
+//! `z_i = k_i + c x_i`, where `c` is the challenge over `R`, `Y` and the
+//! message, and any `t` partial signatures combine, weighted with their
+//! Lagrange coefficients at 0, into `z = k + c x`. The signature `(R, z)`
+//! verifies as an ordinary Schnorr signature, `g^z = R Y^c`.
+//!
+//! A nonce sharing must never sign two messages, as two partial signatures
+//! under one nonce reveal the key share.
+
+use crate::{
+    field::Field,
+    group::SchnorrGroup,
+    poly,
+    transcript::{Sha256Transcript, Transcript},
+    SecretShare, Share, TinySsError,
+};
+use alloc::vec::Vec;
+use num_bigint::BigInt;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// A Schnorr signature `(R, z)`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct Signature {
+    /// nonce commitment
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    r: BigInt,
+    /// response
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    z: BigInt,
+}
+
+impl Signature {
+    pub fn new(r: BigInt, z: BigInt) -> Self {
+        Signature { r, z }
+    }
+
+    /// nonce commitment
+    pub fn r(&self) -> &BigInt {
+        &self.r
+    }
+
+    /// response
+    pub fn z(&self) -> &BigInt {
+        &self.z
+    }
+
+    /// Whether this is a signature on `message` under `public_key`.
+    pub fn verify(&self, group: &SchnorrGroup, public_key: &BigInt, message: &[u8]) -> bool {
+        if !group.is_element(&self.r) || !group.is_element(public_key) {
+            return false;
+        }
+        let c = challenge(group, &self.r, public_key, message);
+        group.exp(group.g(), &self.z) == group.mul(&self.r, &group.exp(public_key, &c))
+    }
+}
+
+/// One signer's contribution `z_i = k_i + c x_i`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct PartialSignature {
+    index: usize,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_bigint"))]
+    z: BigInt,
+}
+
+impl PartialSignature {
+    pub fn new(index: usize, z: BigInt) -> Self {
+        PartialSignature { index, z }
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    pub fn z(&self) -> &BigInt {
+        &self.z
+    }
+}
+
+/// Signing with a `t`-of-`n` sharing of a Schnorr key.
+#[derive(Clone, Debug)]
+pub struct ThresholdSchnorr {
+    group: SchnorrGroup,
+    /// sharing over the exponents, modulo q
+    sharing: SecretShare,
+}
+
+impl ThresholdSchnorr {
+    pub fn new(group: SchnorrGroup, t: usize, n: usize) -> Result<Self, TinySsError> {
+        let sharing = SecretShare::new(t, n, group.q().clone())?;
+        Ok(ThresholdSchnorr { group, sharing })
+    }
+
+    pub fn group(&self) -> &SchnorrGroup {
+        &self.group
+    }
+
+    /// The sharing of the exponents.
+    pub fn shamir(&self) -> &SecretShare {
+        &self.sharing
+    }
+
+    /// A signer's partial signature on `message` from its shares of the key
+    /// and of the nonce, given the public key and the nonce commitment `R`.
+    pub fn sign_partial(
+        &self,
+        key_share: &Share,
+        nonce_share: &Share,
+        (public_key, r): (&BigInt, &BigInt),
+        message: &[u8],
+    ) -> Result<PartialSignature, TinySsError> {
+        if key_share.index() != nonce_share.index() {
+            return Err(TinySsError::IndexMismatch);
+        }
+        if key_share.index() == 0 || key_share.index() > self.sharing.n() {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let scalars = self.group.scalars();
+        let c = challenge(&self.group, r, public_key, message);
+        let z = scalars.add(nonce_share.value(), &scalars.mul(&c, key_share.value()));
+        Ok(PartialSignature::new(key_share.index(), z))
+    }
+
+    /// Checks a partial signature against the signer's public shares
+    /// `g^{x_i}` and `g^{k_i}`.
+    pub fn verify_partial(
+        &self,
+        partial: &PartialSignature,
+        (public_share, nonce_share): (&BigInt, &BigInt),
+        (public_key, r): (&BigInt, &BigInt),
+        message: &[u8],
+    ) -> bool {
+        let group = &self.group;
+        let c = challenge(group, r, public_key, message);
+        group.exp(group.g(), &partial.z) == group.mul(nonce_share, &group.exp(public_share, &c))
+    }
+
+    /// Combines at least `t` partial signatures into a signature with nonce
+    /// commitment `r`.
+    pub fn combine(
+        &self,
+        partials: &[PartialSignature],
+        r: &BigInt,
+    ) -> Result<Signature, TinySsError> {
+        if partials
+            .iter()
+            .any(|p| p.index == 0 || p.index > self.sharing.n())
+        {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let shares: Vec<Share> = partials
+            .iter()
+            .map(|p| Share::new(p.index, p.z.clone()))
+            .collect();
+        let unique = poly::distinct_shares(&shares, self.sharing.t())?;
+        let scalars = self.group.scalars();
+        let xs: Vec<BigInt> = unique
+            .iter()
+            .map(|s| scalars.elem_from_index(s.index()))
+            .collect();
+        let weights = poly::lagrange_coefficients(scalars, &xs, &scalars.zero())?;
+        let z = unique
+            .iter()
+            .zip(&weights)
+            .fold(scalars.zero(), |sum, (s, w)| {
+                scalars.add(&sum, &scalars.mul(w, s.value()))
+            });
+        Ok(Signature::new(r.clone(), z))
+    }
+}
+
+// Challenge over the nonce commitment, the public key and the message
+fn challenge(group: &SchnorrGroup, r: &BigInt, public_key: &BigInt, message: &[u8]) -> BigInt {
+    let mut transcript = Sha256Transcript::new(b"tiny-ss schnorr signature");
+    group.append_element(&mut transcript, b"R", r);
+    group.append_element(&mut transcript, b"Y", public_key);
+    transcript.append_message(b"message", message);
+    group.challenge_scalar(&mut transcript, b"c")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn threshold_schnorr_test() {
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        let signing = ThresholdSchnorr::new(group.clone(), 2, 3).unwrap();
+        let (x, k) = (BigInt::from(777), BigInt::from(95));
+        let keys = signing
+            .shamir()
+            .split_with_coefficients(x.clone(), &[BigInt::from(13)])
+            .unwrap();
+        let nonces = signing
+            .shamir()
+            .split_with_coefficients(k.clone(), &[BigInt::from(400)])
+            .unwrap();
+        let y = group.exp(group.g(), &x);
+        let r = group.exp(group.g(), &k);
+
+        let partials: Vec<PartialSignature> = keys
+            .iter()
+            .zip(&nonces)
+            .map(|(key, nonce)| {
+                signing
+                    .sign_partial(key, nonce, (&y, &r), b"hello")
+                    .unwrap()
+            })
+            .collect();
+        for ((partial, key), nonce) in partials.iter().zip(&keys).zip(&nonces) {
+            let public = (
+                &group.exp(group.g(), key.value()),
+                &group.exp(group.g(), nonce.value()),
+            );
+            assert!(signing.verify_partial(partial, public, (&y, &r), b"hello"));
+        }
+        let signature = signing.combine(&partials[1..], &r).unwrap();
+        assert_eq!(signing.combine(&partials[..2], &r).unwrap(), signature);
+        assert!(signature.verify(&group, &y, b"hello"));
+        assert!(!signature.verify(&group, &y, b"hullo"));
+
+        assert_eq!(
+            signing.combine(&partials[..1], &r),
+            Err(TinySsError::NotEnoughShares {
+                required: 2,
+                got: 1
+            })
+        );
+        assert_eq!(
+            signing.sign_partial(&keys[0], &nonces[1], (&y, &r), b"hello"),
+            Err(TinySsError::IndexMismatch)
+        );
+    }
+}
@@ -0,0 +1,670 @@
+//! [SLIP-0039](https://github.com/satoshilabs/slips/blob/master/slip-0039.md)
+//! mnemonic shares, interoperable with Trezor-style wallets.
+//!
+//! The master secret is encrypted with the passphrase, split into group
+//! shares, and every group share is split again into member shares. Splitting
+//! happens over GF(256) and each member share is rendered as a list of words.
+
+mod wordlist;
+
+use crate::gf256;
+use alloc::{string::String, vec, vec::Vec};
+use core::fmt;
+use hmac::{Hmac, Mac};
+use num_bigint::BigUint;
+use num_traits::{ToPrimitive, Zero};
+use sha2::Sha256;
+use wordlist::WORDLIST;
+
+/// bits encoded by a single word
+const RADIX_BITS: usize = 10;
+/// words taken by the identifier, flags and group/member parameters
+const HEADER_WORDS: usize = 4;
+/// words taken by the RS1024 checksum
+const CHECKSUM_WORDS: usize = 3;
+/// shortest master secret in bytes
+const MIN_SECRET_BYTES: usize = 16;
+/// shortest valid mnemonic
+const MIN_MNEMONIC_WORDS: usize =
+    HEADER_WORDS + CHECKSUM_WORDS + (MIN_SECRET_BYTES * 8).div_ceil(RADIX_BITS);
+/// x-coordinate of the shared secret
+const SECRET_INDEX: u8 = 255;
+/// x-coordinate of the digest share
+const DIGEST_INDEX: u8 = 254;
+/// bytes of HMAC output kept in the digest share
+const DIGEST_BYTES: usize = 4;
+/// upper bound on groups and on members per group
+const MAX_SHARE_COUNT: u8 = 16;
+/// PBKDF2 iterations of all Feistel rounds with exponent 0
+const BASE_ITERATIONS: u32 = 10000;
+/// Feistel rounds used to encrypt the master secret
+const ROUNDS: u8 = 4;
+
+/// Errors produced while generating or combining SLIP-0039 mnemonics.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Slip39Error {
+    /// a word is not in the SLIP-0039 wordlist
+    InvalidWord,
+    /// the mnemonic has too few words or an impossible share length
+    InvalidLength,
+    /// the padding bits of the share value are not zero
+    InvalidPadding,
+    /// the RS1024 checksum does not match
+    InvalidChecksum,
+    /// the master secret is shorter than 16 bytes or of odd length
+    InvalidSecretLength,
+    /// thresholds, counts or the iteration exponent are out of range
+    InvalidParameters,
+    /// shares belong to different dealings or disagree on parameters
+    MismatchedShares,
+    /// two different shares carry the same member index
+    DuplicateMemberIndex,
+    /// the number of groups or of members in a group is not the threshold
+    WrongShareCount,
+    /// the recovered secret does not match its digest
+    InvalidDigest,
+}
+
+impl fmt::Display for Slip39Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Slip39Error::InvalidWord => write!(f, "word not in the SLIP-0039 wordlist"),
+            Slip39Error::InvalidLength => write!(f, "invalid mnemonic length"),
+            Slip39Error::InvalidPadding => write!(f, "invalid mnemonic padding"),
+            Slip39Error::InvalidChecksum => write!(f, "invalid mnemonic checksum"),
+            Slip39Error::InvalidSecretLength => write!(f, "invalid master secret length"),
+            Slip39Error::InvalidParameters => write!(f, "invalid sharing parameters"),
+            Slip39Error::MismatchedShares => write!(f, "mnemonics belong to different dealings"),
+            Slip39Error::DuplicateMemberIndex => write!(f, "duplicate member index"),
+            Slip39Error::WrongShareCount => write!(f, "wrong number of groups or members"),
+            Slip39Error::InvalidDigest => write!(f, "invalid digest of the shared secret"),
+        }
+    }
+}
+
+/// A single decoded SLIP-0039 member share.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Slip39Share {
+    /// random 15-bit identifier of the dealing
+    identifier: u16,
+    /// whether the identifier is left out of the encryption salt
+    extendable: bool,
+    /// PBKDF2 cost, `2500 << e` iterations per round
+    iteration_exponent: u8,
+    /// x-coordinate of the group share
+    group_index: u8,
+    /// groups required to recover the secret
+    group_threshold: u8,
+    /// total number of groups
+    group_count: u8,
+    /// x-coordinate of this share within its group
+    member_index: u8,
+    /// members of this group required to recover the group share
+    member_threshold: u8,
+    /// y-coordinates, one per byte of the encrypted master secret
+    value: Vec<u8>,
+}
+
+impl fmt::Debug for Slip39Share {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Slip39Share")
+            .field("identifier", &self.identifier)
+            .field("extendable", &self.extendable)
+            .field("iteration_exponent", &self.iteration_exponent)
+            .field("group_index", &self.group_index)
+            .field("group_threshold", &self.group_threshold)
+            .field("group_count", &self.group_count)
+            .field("member_index", &self.member_index)
+            .field("member_threshold", &self.member_threshold)
+            .field("value", crate::debug::secret(&self.value))
+            .finish()
+    }
+}
+
+impl Slip39Share {
+    pub fn identifier(&self) -> u16 {
+        self.identifier
+    }
+
+    pub fn extendable(&self) -> bool {
+        self.extendable
+    }
+
+    pub fn iteration_exponent(&self) -> u8 {
+        self.iteration_exponent
+    }
+
+    pub fn group_index(&self) -> u8 {
+        self.group_index
+    }
+
+    pub fn group_threshold(&self) -> u8 {
+        self.group_threshold
+    }
+
+    pub fn group_count(&self) -> u8 {
+        self.group_count
+    }
+
+    pub fn member_index(&self) -> u8 {
+        self.member_index
+    }
+
+    pub fn member_threshold(&self) -> u8 {
+        self.member_threshold
+    }
+
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Parses a space separated mnemonic, verifying its checksum and padding.
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self, Slip39Error> {
+        let words = mnemonic
+            .split_whitespace()
+            .map(word_index)
+            .collect::<Result<Vec<u16>, _>>()?;
+        if words.len() < MIN_MNEMONIC_WORDS {
+            return Err(Slip39Error::InvalidLength);
+        }
+        let value_words = words.len() - HEADER_WORDS - CHECKSUM_WORDS;
+        let padding = (RADIX_BITS * value_words) % 16;
+        if padding > 8 {
+            return Err(Slip39Error::InvalidLength);
+        }
+
+        let extendable = words[1] >> 4 & 1 == 1;
+        if rs1024_polymod(customization(extendable), words.iter().copied()) != 1 {
+            return Err(Slip39Error::InvalidChecksum);
+        }
+
+        let identifier = words[0] << 5 | words[1] >> 5;
+        let iteration_exponent = (words[1] & 0xf) as u8;
+        let group_index = (words[2] >> 6) as u8;
+        let group_threshold = (words[2] >> 2 & 0xf) as u8 + 1;
+        let group_count = ((words[2] & 3) << 2 | words[3] >> 8) as u8 + 1;
+        let member_index = (words[3] >> 4 & 0xf) as u8;
+        let member_threshold = (words[3] & 0xf) as u8 + 1;
+        if group_threshold > group_count {
+            return Err(Slip39Error::InvalidParameters);
+        }
+
+        let len = (RADIX_BITS * value_words - padding) / 8;
+        let value = words[HEADER_WORDS..words.len() - CHECKSUM_WORDS]
+            .iter()
+            .fold(BigUint::zero(), |acc, &w| (acc << RADIX_BITS) + w);
+        if value.bits() > (len * 8) as u64 {
+            return Err(Slip39Error::InvalidPadding);
+        }
+        let bytes = value.to_bytes_be();
+        let mut padded = vec![0u8; len];
+        if !value.is_zero() {
+            padded[len - bytes.len()..].copy_from_slice(&bytes);
+        }
+
+        Ok(Slip39Share {
+            identifier,
+            extendable,
+            iteration_exponent,
+            group_index,
+            group_threshold,
+            group_count,
+            member_index,
+            member_threshold,
+            value: padded,
+        })
+    }
+
+    /// Renders the share as space separated words.
+    pub fn to_mnemonic(&self) -> String {
+        let mut words: Vec<u16> = vec![
+            self.identifier >> 5,
+            (self.identifier & 0x1f) << 5
+                | (self.extendable as u16) << 4
+                | self.iteration_exponent as u16,
+            (self.group_index as u16) << 6
+                | ((self.group_threshold - 1) as u16) << 2
+                | ((self.group_count - 1) as u16) >> 2,
+            ((self.group_count - 1) as u16 & 3) << 8
+                | (self.member_index as u16) << 4
+                | (self.member_threshold - 1) as u16,
+        ];
+        let value_words = (self.value.len() * 8).div_ceil(RADIX_BITS);
+        let mut value = BigUint::from_bytes_be(&self.value);
+        let start = words.len();
+        for _ in 0..value_words {
+            words.push((&value & BigUint::from(0x3ffu16)).to_u16().unwrap());
+            value >>= RADIX_BITS;
+        }
+        words[start..].reverse();
+
+        let checksum = rs1024_polymod(
+            customization(self.extendable),
+            words.iter().copied().chain([0; CHECKSUM_WORDS]),
+        ) ^ 1;
+        for i in (0..CHECKSUM_WORDS).rev() {
+            words.push((checksum >> (RADIX_BITS * i) & 0x3ff) as u16);
+        }
+
+        let mut mnemonic = String::new();
+        for (i, &w) in words.iter().enumerate() {
+            if i > 0 {
+                mnemonic.push(' ');
+            }
+            mnemonic.push_str(WORDLIST[w as usize]);
+        }
+        mnemonic
+    }
+}
+
+/// Splits `master_secret` into mnemonics using the thread-local RNG.
+///
+/// `groups` lists `(member_threshold, member_count)` per group; the result
+/// holds one list of mnemonics per group.
+#[cfg(feature = "rand")]
+pub fn generate_mnemonics(
+    group_threshold: u8,
+    groups: &[(u8, u8)],
+    master_secret: &[u8],
+    passphrase: &[u8],
+    extendable: bool,
+    iteration_exponent: u8,
+) -> Result<Vec<Vec<String>>, Slip39Error> {
+    use rand::RngCore;
+    let mut rng = rand::thread_rng();
+    generate_mnemonics_with(
+        group_threshold,
+        groups,
+        master_secret,
+        passphrase,
+        extendable,
+        iteration_exponent,
+        |buf| rng.fill_bytes(buf),
+    )
+}
+
+/// Same as `generate_mnemonics`, with randomness drawn from `fill_random`,
+/// which must fill the buffer with uniformly random bytes.
+pub fn generate_mnemonics_with<F>(
+    group_threshold: u8,
+    groups: &[(u8, u8)],
+    master_secret: &[u8],
+    passphrase: &[u8],
+    extendable: bool,
+    iteration_exponent: u8,
+    mut fill_random: F,
+) -> Result<Vec<Vec<String>>, Slip39Error>
+where
+    F: FnMut(&mut [u8]),
+{
+    if master_secret.len() < MIN_SECRET_BYTES || !master_secret.len().is_multiple_of(2) {
+        return Err(Slip39Error::InvalidSecretLength);
+    }
+    let group_count = groups.len();
+    if group_threshold == 0
+        || group_threshold as usize > group_count
+        || group_count > MAX_SHARE_COUNT as usize
+        || iteration_exponent > 0xf
+    {
+        return Err(Slip39Error::InvalidParameters);
+    }
+    for &(threshold, count) in groups {
+        // a 1-of-n group would just be n copies of the same share
+        if threshold == 0 || threshold > count || count > MAX_SHARE_COUNT {
+            return Err(Slip39Error::InvalidParameters);
+        }
+        if threshold == 1 && count > 1 {
+            return Err(Slip39Error::InvalidParameters);
+        }
+    }
+
+    let mut id = [0u8; 2];
+    fill_random(&mut id);
+    let identifier = u16::from_be_bytes(id) & 0x7fff;
+    let encrypted = feistel(
+        master_secret,
+        passphrase,
+        identifier,
+        extendable,
+        iteration_exponent,
+        false,
+    );
+
+    let group_shares = split_secret(
+        group_threshold,
+        group_count as u8,
+        &encrypted,
+        &mut fill_random,
+    );
+    let mut mnemonics = Vec::with_capacity(group_count);
+    for (group_index, (&(threshold, count), group_share)) in
+        groups.iter().zip(group_shares).enumerate()
+    {
+        let members = split_secret(threshold, count, &group_share, &mut fill_random);
+        mnemonics.push(
+            members
+                .into_iter()
+                .enumerate()
+                .map(|(member_index, value)| {
+                    Slip39Share {
+                        identifier,
+                        extendable,
+                        iteration_exponent,
+                        group_index: group_index as u8,
+                        group_threshold,
+                        group_count: group_count as u8,
+                        member_index: member_index as u8,
+                        member_threshold: threshold,
+                        value,
+                    }
+                    .to_mnemonic()
+                })
+                .collect(),
+        );
+    }
+    Ok(mnemonics)
+}
+
+/// Recovers the master secret from exactly `group_threshold` groups, each with
+/// exactly its member threshold of mnemonics.
+pub fn combine_mnemonics<S: AsRef<str>>(
+    mnemonics: &[S],
+    passphrase: &[u8],
+) -> Result<Vec<u8>, Slip39Error> {
+    let shares = mnemonics
+        .iter()
+        .map(|m| Slip39Share::from_mnemonic(m.as_ref()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let first = shares.first().ok_or(Slip39Error::WrongShareCount)?;
+    if shares.iter().any(|s| {
+        s.identifier != first.identifier
+            || s.extendable != first.extendable
+            || s.iteration_exponent != first.iteration_exponent
+            || s.group_threshold != first.group_threshold
+            || s.group_count != first.group_count
+            || s.value.len() != first.value.len()
+    }) {
+        return Err(Slip39Error::MismatchedShares);
+    }
+
+    // group index -> distinct member shares
+    let mut groups: Vec<(u8, Vec<&Slip39Share>)> = Vec::new();
+    for share in &shares {
+        let members = match groups.iter_mut().find(|(gi, _)| *gi == share.group_index) {
+            Some((_, members)) => members,
+            None => {
+                groups.push((share.group_index, Vec::new()));
+                &mut groups.last_mut().unwrap().1
+            }
+        };
+        match members
+            .iter()
+            .find(|m| m.member_index == share.member_index)
+        {
+            Some(m) if m.value != share.value => return Err(Slip39Error::DuplicateMemberIndex),
+            Some(_) => {}
+            None => {
+                if members
+                    .iter()
+                    .any(|m| m.member_threshold != share.member_threshold)
+                {
+                    return Err(Slip39Error::MismatchedShares);
+                }
+                members.push(share);
+            }
+        }
+    }
+    if groups.len() != first.group_threshold as usize {
+        return Err(Slip39Error::WrongShareCount);
+    }
+
+    let mut group_shares: Vec<(u8, Vec<u8>)> = Vec::with_capacity(groups.len());
+    for (group_index, members) in &groups {
+        if members.len() != members[0].member_threshold as usize {
+            return Err(Slip39Error::WrongShareCount);
+        }
+        let points: Vec<(u8, &[u8])> = members
+            .iter()
+            .map(|m| (m.member_index, m.value.as_slice()))
+            .collect();
+        group_shares.push((*group_index, recover_secret(&points)?));
+    }
+    let points: Vec<(u8, &[u8])> = group_shares
+        .iter()
+        .map(|(gi, value)| (*gi, value.as_slice()))
+        .collect();
+    let encrypted = recover_secret(&points)?;
+    Ok(feistel(
+        &encrypted,
+        passphrase,
+        first.identifier,
+        first.extendable,
+        first.iteration_exponent,
+        true,
+    ))
+}
+
+// Splits `secret` over GF(256); beyond the threshold-1 case, the secret sits
+// at x = 255 and a digest of it at x = 254
+fn split_secret<F>(threshold: u8, count: u8, secret: &[u8], fill_random: &mut F) -> Vec<Vec<u8>>
+where
+    F: FnMut(&mut [u8]),
+{
+    if threshold == 1 {
+        return (0..count).map(|_| secret.to_vec()).collect();
+    }
+    let random_count = threshold as usize - 2;
+    let mut shares: Vec<Vec<u8>> = (0..random_count)
+        .map(|_| {
+            let mut value = vec![0u8; secret.len()];
+            fill_random(&mut value);
+            value
+        })
+        .collect();
+
+    let mut digest = vec![0u8; secret.len()];
+    fill_random(&mut digest[DIGEST_BYTES..]);
+    let tag = hmac_sha256(&digest[DIGEST_BYTES..], secret);
+    digest[..DIGEST_BYTES].copy_from_slice(&tag[..DIGEST_BYTES]);
+
+    let mut points: Vec<(u8, &[u8])> = shares
+        .iter()
+        .enumerate()
+        .map(|(i, value)| (i as u8, value.as_slice()))
+        .collect();
+    points.push((DIGEST_INDEX, &digest));
+    points.push((SECRET_INDEX, secret));
+    let derived: Vec<Vec<u8>> = (random_count as u8..count)
+        .map(|x| gf256::interpolate(&points, x))
+        .collect();
+    shares.extend(derived);
+    shares
+}
+
+// Inverse of `split_secret`, checking the digest share
+fn recover_secret(points: &[(u8, &[u8])]) -> Result<Vec<u8>, Slip39Error> {
+    if points.len() == 1 {
+        return Ok(points[0].1.to_vec());
+    }
+    let secret = gf256::interpolate(points, SECRET_INDEX);
+    let digest = gf256::interpolate(points, DIGEST_INDEX);
+    let tag = hmac_sha256(&digest[DIGEST_BYTES..], &secret);
+    if tag[..DIGEST_BYTES] != digest[..DIGEST_BYTES] {
+        return Err(Slip39Error::InvalidDigest);
+    }
+    Ok(secret)
+}
+
+// Four-round Feistel cipher keyed by PBKDF2-HMAC-SHA256 of the passphrase
+fn feistel(
+    input: &[u8],
+    passphrase: &[u8],
+    identifier: u16,
+    extendable: bool,
+    iteration_exponent: u8,
+    decrypt: bool,
+) -> Vec<u8> {
+    let (l, r) = input.split_at(input.len() / 2);
+    let (mut l, mut r) = (l.to_vec(), r.to_vec());
+    let mut salt = Vec::new();
+    if !extendable {
+        salt.extend_from_slice(customization(false));
+        salt.extend_from_slice(&identifier.to_be_bytes());
+    }
+    let iterations = (BASE_ITERATIONS / ROUNDS as u32) << iteration_exponent;
+    for step in 0..ROUNDS {
+        let round = if decrypt { ROUNDS - 1 - step } else { step };
+        let mut password = vec![round];
+        password.extend_from_slice(passphrase);
+        let mut round_salt = salt.clone();
+        round_salt.extend_from_slice(&r);
+        let mut f = vec![0u8; r.len()];
+        pbkdf2::pbkdf2_hmac::<Sha256>(&password, &round_salt, iterations, &mut f);
+        for (x, y) in l.iter_mut().zip(f) {
+            *x ^= y;
+        }
+        core::mem::swap(&mut l, &mut r);
+    }
+    r.extend_from_slice(&l);
+    r
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> [u8; 32] {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+fn customization(extendable: bool) -> &'static [u8] {
+    if extendable {
+        b"shamir_extendable"
+    } else {
+        b"shamir"
+    }
+}
+
+fn word_index(word: &str) -> Result<u16, Slip39Error> {
+    WORDLIST
+        .binary_search(&word)
+        .map(|i| i as u16)
+        .map_err(|_| Slip39Error::InvalidWord)
+}
+
+// RS1024 checksum over GF(1024), seeded with the customization string
+fn rs1024_polymod<I: IntoIterator<Item = u16>>(customization: &[u8], values: I) -> u32 {
+    const GEN: [u32; 10] = [
+        0xe0e040, 0x1c1c080, 0x3838100, 0x7070200, 0xe0e0009, 0x1c0c2412, 0x38086c24, 0x3090fc48,
+        0x21b1f890, 0x3f3f120,
+    ];
+    let mut chk = 1u32;
+    let values = customization.iter().map(|&c| c as u16).chain(values);
+    for v in values {
+        let b = chk >> 20;
+        chk = (chk & 0xfffff) << 10 ^ v as u32;
+        for (i, g) in GEN.iter().enumerate() {
+            if b >> i & 1 == 1 {
+                chk ^= g;
+            }
+        }
+    }
+    chk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // counter-based byte source so tests are deterministic
+    fn counter() -> impl FnMut(&mut [u8]) {
+        let mut next = 0u8;
+        move |buf: &mut [u8]| {
+            for b in buf {
+                *b = next;
+                next = next.wrapping_mul(31).wrapping_add(7);
+            }
+        }
+    }
+
+    #[test]
+    fn reference_vectors_test() {
+        // https://github.com/trezor/python-shamir-mnemonic/blob/master/vectors.json
+        let vectors: serde_json::Value =
+            serde_json::from_str(include_str!("vectors.json")).unwrap();
+        for vector in vectors.as_array().unwrap() {
+            let description = vector[0].as_str().unwrap();
+            let mnemonics: Vec<&str> = vector[1]
+                .as_array()
+                .unwrap()
+                .iter()
+                .map(|m| m.as_str().unwrap())
+                .collect();
+            let expected = vector[2].as_str().unwrap();
+            match combine_mnemonics(&mnemonics, b"TREZOR") {
+                Ok(secret) => {
+                    assert_eq!(crate::codec::to_hex(&secret), expected, "{}", description)
+                }
+                Err(_) => assert!(expected.is_empty(), "{}", description),
+            }
+        }
+    }
+
+    #[test]
+    fn invalid_mnemonic_test() {
+        let valid = "duckling enlarge academic academic agency result length solution fridge kidney coal piece deal husband erode duke ajar critical decision keyboard";
+        let share = Slip39Share::from_mnemonic(valid).unwrap();
+        assert_eq!(share.to_mnemonic(), valid);
+        #[cfg(not(feature = "danger_debug"))]
+        assert!(alloc::format!("{:?}", share).contains("[REDACTED]"));
+        assert_eq!(
+            Slip39Share::from_mnemonic(&valid.replace("keyboard", "kidney")),
+            Err(Slip39Error::InvalidChecksum)
+        );
+        assert_eq!(
+            Slip39Share::from_mnemonic("duckling enlarge academic academic email result length solution fridge kidney coal piece deal husband erode duke ajar music cargo fitness"),
+            Err(Slip39Error::InvalidPadding)
+        );
+        assert_eq!(
+            Slip39Share::from_mnemonic(&valid.replace("keyboard", "keyboards")),
+            Err(Slip39Error::InvalidWord)
+        );
+        assert_eq!(
+            Slip39Share::from_mnemonic("duckling enlarge academic"),
+            Err(Slip39Error::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn generate_and_combine_test() {
+        let secret = b"ABCDEFGHIJKLMNOP";
+        for extendable in [false, true] {
+            let groups = generate_mnemonics_with(
+                2,
+                &[(1, 1), (2, 3), (3, 5)],
+                secret,
+                b"TREZOR",
+                extendable,
+                0,
+                counter(),
+            )
+            .unwrap();
+            assert_eq!(groups[2].len(), 5);
+            let quorum = [&groups[0][0], &groups[2][4], &groups[2][0], &groups[2][2]];
+            assert_eq!(combine_mnemonics(&quorum, b"TREZOR").unwrap(), secret);
+            // a wrong passphrase yields a different secret rather than an error
+            assert_ne!(combine_mnemonics(&quorum, b"").unwrap(), secret);
+            assert_eq!(
+                combine_mnemonics(&quorum[..3], b"TREZOR"),
+                Err(Slip39Error::WrongShareCount)
+            );
+        }
+
+        assert_eq!(
+            generate_mnemonics_with(1, &[(1, 2)], secret, b"", false, 0, counter()),
+            Err(Slip39Error::InvalidParameters)
+        );
+        assert_eq!(
+            generate_mnemonics_with(1, &[(1, 1)], &secret[..15], b"", false, 0, counter()),
+            Err(Slip39Error::InvalidSecretLength)
+        );
+    }
+}
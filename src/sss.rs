@@ -0,0 +1,335 @@
+//! Shares of the [`sss`](https://github.com/dsprenkels/sss) C library, so
+//! that secrets dealt by applications built on it recover here and the
+//! other way round.
+//!
+//! `sss` shares messages of exactly `DATA_LEN` bytes. It draws a random
+//! 32-byte key, encrypts the message under it with NaCl's
+//! `crypto_secretbox` (XSalsa20-Poly1305) and a zero nonce, and splits only
+//! the key. The key is split byte by byte over GF(256) modulo the AES
+//! polynomial into keyshares of the x-coordinate byte followed by the 32 y
+//! bytes, dealt at x = 1, 2, ...; its "hazmat" interface hands these out
+//! directly. A share is a keyshare followed by the authenticated ciphertext,
+//! tag first, which is the same in every share of a dealing.
+
+use crate::{gf256, gf256::ByteShare, poly, Share, TinySsError};
+use alloc::{vec, vec::Vec};
+use crypto_secretbox::{
+    aead::{AeadInPlace, KeyInit},
+    Nonce, Tag, XSalsa20Poly1305,
+};
+
+/// Length of the messages `sss` shares.
+pub const DATA_LEN: usize = 64;
+/// Length of the key split into keyshares.
+pub const KEY_LEN: usize = 32;
+/// Length of a keyshare: the x-coordinate, then one y byte per key byte.
+pub const KEYSHARE_LEN: usize = KEY_LEN + 1;
+/// Length of the ciphertext carried by every share, tag included.
+pub const CIPHERTEXT_LEN: usize = TAG_LEN + DATA_LEN;
+/// Length of a share: a keyshare, then the ciphertext.
+pub const SHARE_LEN: usize = KEYSHARE_LEN + CIPHERTEXT_LEN;
+
+const TAG_LEN: usize = 16;
+
+/// A keyshare of the hazmat interface, `sss_Keyshare` in C.
+pub type Keyshare = [u8; KEYSHARE_LEN];
+/// A share of a message, `sss_Share` in C.
+pub type SssShare = [u8; SHARE_LEN];
+
+/// A `t`-of-`n` scheme dealing and combining as `sss_create_shares` and
+/// `sss_combine_shares` do.
+#[derive(Clone, Debug)]
+pub struct SssSharing {
+    /// threshold
+    t: usize,
+    /// total number of shares
+    n: usize,
+}
+
+impl SssSharing {
+    /// Creates a `t`-of-`n` scheme with `0 < t <= n <= 255`, the parameters
+    /// `sss` accepts.
+    pub fn new(t: usize, n: usize) -> Result<Self, TinySsError> {
+        if t == 0 || t > n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if n > 255 {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(SssSharing { t, n })
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, data: &[u8; DATA_LEN]) -> Vec<SssShare> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(data, |buf| rng.fill_bytes(buf))
+    }
+
+    /// Encrypts `data` under a key drawn from `fill_random` and splits the
+    /// key with coefficients drawn from it as well.
+    pub fn split_with<F>(&self, data: &[u8; DATA_LEN], mut fill_random: F) -> Vec<SssShare>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        let mut key = [0u8; KEY_LEN];
+        fill_random(&mut key);
+        let cipher = XSalsa20Poly1305::new(&key.into());
+        let mut ciphertext = [0u8; CIPHERTEXT_LEN];
+        ciphertext[TAG_LEN..].copy_from_slice(data);
+        let tag = cipher
+            .encrypt_in_place_detached(&Nonce::default(), &[], &mut ciphertext[TAG_LEN..])
+            .expect("no associated data");
+        ciphertext[..TAG_LEN].copy_from_slice(&tag);
+        self.split_key_with(&key, fill_random)
+            .into_iter()
+            .map(|keyshare| {
+                let mut share = [0u8; SHARE_LEN];
+                share[..KEYSHARE_LEN].copy_from_slice(&keyshare);
+                share[KEYSHARE_LEN..].copy_from_slice(&ciphertext);
+                share
+            })
+            .collect()
+    }
+
+    /// Recovers the message from the keyshares of at least `t` shares and
+    /// the first of their ciphertexts that decrypts under the recovered key,
+    /// as `sss_combine_shares` does. Fails with `IntegrityCheckFailed` if
+    /// none does, which is also how too few or mixed shares show.
+    pub fn recover(&self, shares: &[SssShare]) -> Result<[u8; DATA_LEN], TinySsError> {
+        let keyshares: Vec<Keyshare> = shares
+            .iter()
+            .map(|share| share[..KEYSHARE_LEN].try_into().expect("keyshare length"))
+            .collect();
+        let key = self.recover_key(&keyshares)?;
+        let cipher = XSalsa20Poly1305::new(&key.into());
+        shares
+            .iter()
+            .find_map(|share| {
+                let (tag, ciphertext) = share[KEYSHARE_LEN..].split_at(TAG_LEN);
+                let tag: [u8; TAG_LEN] = tag.try_into().expect("tag length");
+                let mut data = [0u8; DATA_LEN];
+                data.copy_from_slice(ciphertext);
+                cipher
+                    .decrypt_in_place_detached(&Nonce::default(), &[], &mut data, &Tag::from(tag))
+                    .ok()
+                    .map(|()| data)
+            })
+            .ok_or(TinySsError::IntegrityCheckFailed)
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split_key(&self, key: &[u8; KEY_LEN]) -> Vec<Keyshare> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_key_with(key, |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits `key` into keyshares as `sss_create_keyshares` does, with
+    /// coefficients drawn from `fill_random`.
+    pub fn split_key_with<F>(&self, key: &[u8; KEY_LEN], mut fill_random: F) -> Vec<Keyshare>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        // coefficient j + 1 of every key byte at rows[j]
+        let mut rows = vec![[0u8; KEY_LEN]; self.t - 1];
+        for row in &mut rows {
+            fill_random(row);
+        }
+        (1..=self.n as u8)
+            .map(|x| {
+                let mut keyshare = [0u8; KEYSHARE_LEN];
+                keyshare[0] = x;
+                for row in rows.iter().rev().chain([key]) {
+                    for (y, c) in keyshare[1..].iter_mut().zip(row) {
+                        *y = gf256::mul(*y, x) ^ c;
+                    }
+                }
+                keyshare
+            })
+            .collect()
+    }
+
+    /// Recovers the key from at least `t` keyshares, as
+    /// `sss_combine_keyshares` does; too few of them recover an unrelated
+    /// key.
+    ///
+    /// Keyshares repeating an x-coordinate are ignored as long as they carry
+    /// the same value; the first `t` distinct ones are used for
+    /// interpolation.
+    pub fn recover_key(&self, keyshares: &[Keyshare]) -> Result<[u8; KEY_LEN], TinySsError> {
+        let shares: Vec<ByteShare> = keyshares
+            .iter()
+            .map(|keyshare| Share::new(keyshare[0].into(), keyshare[1..].to_vec()))
+            .collect();
+        if shares.iter().any(|share| share.index() == 0) {
+            return Err(TinySsError::InvalidIndex);
+        }
+        let unique = poly::distinct_shares(&shares, self.t)?;
+        let points: Vec<(u8, &[u8])> = unique[..self.t]
+            .iter()
+            .map(|share| (share.index() as u8, share.value().as_slice()))
+            .collect();
+        let mut key = [0u8; KEY_LEN];
+        key.copy_from_slice(&gf256::interpolate(&points, 0));
+        Ok(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_util::counter;
+    use crypto_secretbox::aead::Aead;
+
+    #[test]
+    fn keyshare_test() {
+        let ss = SssSharing::new(2, 3).unwrap();
+        let key = [0x5a; KEY_LEN];
+        let keyshares = ss.split_key_with(&key, |buf| buf.fill(7));
+        // y = key + 7x in every byte, x first
+        assert_eq!(keyshares[2][0], 3);
+        assert!(keyshares[2][1..]
+            .iter()
+            .all(|&y| y == 0x5a ^ gf256::mul(7, 3)));
+        assert_eq!(ss.recover_key(&keyshares[1..]).unwrap(), key);
+        assert_eq!(
+            ss.recover_key(&[keyshares[0], keyshares[0]]),
+            Err(TinySsError::NotEnoughShares {
+                required: 2,
+                got: 1
+            })
+        );
+        let mut zero = keyshares[0];
+        zero[0] = 0;
+        assert_eq!(
+            ss.recover_key(&[zero, keyshares[1]]),
+            Err(TinySsError::InvalidIndex)
+        );
+        assert!(SssSharing::new(3, 3).is_ok());
+        assert!(SssSharing::new(3, 2).is_err());
+        assert!(SssSharing::new(3, 256).is_err());
+    }
+
+    // the test vector of `shamirsecretsharing`, the Rust port of the
+    // library by its author: 64 bytes of 42 dealt 4-of-5
+    const VECTOR: [&str; 5] = [
+        "01e871ea0dcb8cd9129e4c8d30f506d1ff9466c208651ee3e6d51626f0c9f0a940",
+        "028054e5b9379078fbaf888d232ddd6575d46101d8e56175f7f7ca251f2570206f",
+        "03386d812f3d41a11b9eaad7136fcbd6d015d3c64c04a31bcf1ac03cd5988fbd6e",
+        "043d07957875a2916042d4090cb4eceee6efcf7e9b9bc89a534573d8eab6db6fec",
+        "0570f3ea28ef05eee69c9e608bb537b1111197ebee2a9de84b61ee89732bc977f8",
+    ];
+    const VECTOR_CIPHERTEXT: &str = "92eeed45ce71f8bbc97d517415a7bb268b69bf6452390ac6c9897933d8cfbb9ed60bd761362d0b4ca9c7fb684604b83a65b0a53f8f892a9487390b1f7f33d27599361c2f7256d9aca28e8fe635514131";
+
+    #[test]
+    fn known_answer_test() {
+        let shares: Vec<SssShare> = VECTOR
+            .iter()
+            .map(|keyshare| {
+                let hex = alloc::format!("{}{}", keyshare, VECTOR_CIPHERTEXT);
+                crate::codec::from_hex(&hex).unwrap().try_into().unwrap()
+            })
+            .collect();
+        let ss = SssSharing::new(4, 5).unwrap();
+        assert_eq!(ss.recover(&shares).unwrap(), [42; DATA_LEN]);
+        assert_eq!(ss.recover(&shares[1..]).unwrap(), [42; DATA_LEN]);
+        assert_eq!(
+            ss.recover(&[shares[4], shares[0], shares[2], shares[1]])
+                .unwrap(),
+            [42; DATA_LEN]
+        );
+        // three shares recover a key nothing decrypts under
+        let ss = SssSharing::new(3, 5).unwrap();
+        assert_eq!(
+            ss.recover(&shares[..3]),
+            Err(TinySsError::IntegrityCheckFailed)
+        );
+    }
+
+    #[test]
+    fn interop_test() {
+        let mut data = [0u8; DATA_LEN];
+        data[..11].copy_from_slice(b"interchange");
+        let ss = SssSharing::new(3, 5).unwrap();
+
+        // dealt here, combined by `shamirsecretsharing`
+        let shares: Vec<Vec<u8>> = ss
+            .split_with(&data, counter(1))
+            .iter()
+            .map(|share| share.to_vec())
+            .collect();
+        let combined = shamirsecretsharing::combine_shares(&shares[2..]).unwrap();
+        assert_eq!(combined.as_deref(), Some(&data[..]));
+        let keyshares: Vec<Vec<u8>> = shares
+            .iter()
+            .map(|share| share[..KEYSHARE_LEN].to_vec())
+            .collect();
+        let mut key = [0u8; KEY_LEN];
+        counter(1)(&mut key);
+        assert_eq!(
+            shamirsecretsharing::hazmat::combine_keyshares(&keyshares[..3]).unwrap(),
+            key
+        );
+
+        // dealt by `shamirsecretsharing`, combined here
+        let shares: Vec<SssShare> = shamirsecretsharing::create_shares(&data, 5, 3)
+            .unwrap()
+            .into_iter()
+            .map(|share| share.try_into().unwrap())
+            .collect();
+        assert_eq!(ss.recover(&shares[..3]).unwrap(), data);
+        let keyshares: Vec<Keyshare> = shamirsecretsharing::hazmat::create_keyshares(&key, 5, 3)
+            .unwrap()
+            .into_iter()
+            .map(|keyshare| keyshare.try_into().unwrap())
+            .collect();
+        assert_eq!(ss.recover_key(&keyshares[1..4]).unwrap(), key);
+    }
+
+    #[test]
+    fn roundtrip_test() {
+        let ss = SssSharing::new(3, 5).unwrap();
+        let mut data = [0u8; DATA_LEN];
+        data[..14].copy_from_slice(b"sss compatible");
+        let shares = ss.split_with(&data, counter(1));
+        assert_eq!(shares.len(), 5);
+        // the key comes first from the randomness, then the coefficients
+        let mut key = [0u8; KEY_LEN];
+        counter(1)(&mut key);
+        let keyshares: Vec<Keyshare> = shares
+            .iter()
+            .map(|share| share[..KEYSHARE_LEN].try_into().unwrap())
+            .collect();
+        assert_eq!(ss.recover_key(&keyshares[..3]).unwrap(), key);
+        // NaCl's layout of the box, tag first
+        let cipher = XSalsa20Poly1305::new(&key.into());
+        let opened = cipher.decrypt(&Nonce::default(), &shares[0][KEYSHARE_LEN..]);
+        assert_eq!(opened.unwrap(), data);
+        assert!(shares
+            .iter()
+            .all(|s| s[KEYSHARE_LEN..] == shares[0][KEYSHARE_LEN..]));
+        assert_eq!(ss.recover(&shares[2..]).unwrap(), data);
+
+        // a corrupted ciphertext is skipped for the next one
+        let mut tampered = shares[2..].to_vec();
+        tampered[0][SHARE_LEN - 1] ^= 1;
+        assert_eq!(ss.recover(&tampered).unwrap(), data);
+        // a corrupted keyshare recovers a key nothing decrypts under
+        tampered[1][1] ^= 1;
+        assert_eq!(
+            ss.recover(&tampered),
+            Err(TinySsError::IntegrityCheckFailed)
+        );
+    }
+}
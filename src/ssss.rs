@@ -0,0 +1,608 @@
+//! Shares of the [`ssss`](http://point-at-infinity.org/ssss/) command-line
+//! tool, so that the output of `ssss-split` can be combined here and shares
+//! dealt here can be combined by `ssss-combine`.
+//!
+//! At a security level of `m` bits, a multiple of 8 up to 1024, `ssss`
+//! computes in GF(2^m) modulo the pentanomial of degree `m` in its table.
+//! The secret is read as a big-endian integer of `m / 8` bytes and, from
+//! 64 bits on, first passed through a diffusion layer: 40 rounds per byte
+//! of XTEA with an all-zero key, over 8-byte windows that wrap around the
+//! secret, so that any one bit of it depends on all others. It becomes the
+//! constant term of the polynomial `x^t + c_{t-1} x^{t-1} + ... + c_0`,
+//! whose leading coefficient is 1 rather than random, and the share at `x`
+//! is printed as `[token-]x-y`, with `x` in decimal padded to the digits of
+//! `n` and `y` in `m / 4` lowercase hex digits.
+
+use crate::{codec, field::Field, poly, DecodeError, Share, TinySsError};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{fmt, str::FromStr};
+
+/// highest security level in bits
+const MAX_DEGREE: usize = 1024;
+/// 64-bit words of an element, and one more for the modulus
+const WORDS: usize = MAX_DEGREE / 64 + 1;
+/// lowest security level in bits with the diffusion layer
+const DIFFUSION_DEGREE: usize = 64;
+
+/// The exponents `a > b > c` of `x^m + x^a + x^b + x^c + 1` for `m = 8`,
+/// 16, ..., 1024, three by three, as `ssss` lists them.
+const IRREDUCIBLE: [u8; 3 * MAX_DEGREE / 8] = [
+    4, 3, 1, 5, 3, 1, 4, 3, 1, 7, 3, 2, 5, 4, 3, 5, 3, 2, 7, 4, 2, 4, 3, 1, 10, 9, 3, 9, 4, 2, 7,
+    6, 2, 10, 9, 6, 4, 3, 1, 5, 4, 3, 4, 3, 1, 7, 2, 1, 5, 3, 2, 7, 4, 2, 6, 3, 2, 5, 3, 2, 15, 3,
+    2, 11, 3, 2, 9, 8, 7, 7, 2, 1, 5, 3, 2, 9, 3, 1, 7, 3, 1, 9, 8, 3, 9, 4, 2, 8, 5, 3, 15, 14,
+    10, 10, 5, 2, 9, 6, 2, 9, 3, 2, 9, 5, 2, 11, 10, 1, 7, 3, 2, 11, 2, 1, 9, 7, 4, 4, 3, 1, 8, 3,
+    1, 7, 4, 1, 7, 2, 1, 13, 11, 6, 5, 3, 2, 7, 3, 2, 8, 7, 5, 12, 3, 2, 13, 10, 6, 5, 3, 2, 5, 3,
+    2, 9, 5, 2, 9, 7, 2, 13, 4, 3, 4, 3, 1, 11, 6, 4, 18, 9, 6, 19, 18, 13, 11, 3, 2, 15, 9, 6, 4,
+    3, 1, 16, 5, 2, 15, 14, 6, 8, 5, 2, 15, 11, 2, 11, 6, 2, 7, 5, 3, 8, 3, 1, 19, 16, 9, 11, 9, 6,
+    15, 7, 6, 13, 4, 3, 14, 13, 3, 13, 6, 3, 9, 5, 2, 19, 13, 6, 19, 10, 3, 11, 6, 5, 9, 2, 1, 14,
+    3, 2, 13, 3, 1, 7, 5, 4, 11, 9, 8, 11, 6, 5, 23, 16, 9, 19, 14, 6, 23, 10, 2, 8, 3, 2, 5, 4, 3,
+    9, 6, 4, 4, 3, 2, 13, 8, 6, 13, 11, 1, 13, 10, 3, 11, 6, 5, 19, 17, 4, 15, 14, 7, 13, 9, 6, 9,
+    7, 3, 9, 7, 1, 14, 3, 2, 11, 8, 2, 11, 6, 4, 13, 5, 2, 11, 5, 1, 11, 4, 1, 19, 10, 3, 21, 10,
+    6, 13, 3, 1, 15, 7, 5, 19, 18, 10, 7, 5, 3, 12, 7, 2, 7, 5, 1, 14, 9, 6, 10, 3, 2, 15, 13, 12,
+    12, 11, 9, 16, 9, 7, 12, 9, 3, 9, 5, 2, 17, 10, 6, 24, 9, 3, 17, 15, 13, 5, 4, 3, 19, 17, 8,
+    15, 6, 3, 19, 6, 1,
+];
+
+/// A polynomial over GF(2), bit `i` of word `i / 64` holding the
+/// coefficient of `x^i`.
+type Poly = [u64; WORDS];
+
+fn bit(a: &Poly, i: usize) -> bool {
+    a[i / 64] >> (i % 64) & 1 == 1
+}
+
+fn set_bit(a: &mut Poly, i: usize) {
+    a[i / 64] |= 1 << (i % 64);
+}
+
+// The degree of a nonzero `a`
+fn degree(a: &Poly) -> Option<usize> {
+    let word = a.iter().rposition(|&w| w != 0)?;
+    Some(64 * word + 63 - a[word].leading_zeros() as usize)
+}
+
+// a ^= b * x^shift
+fn add_shifted(a: &mut Poly, b: &Poly, shift: usize) {
+    let (words, bits) = (shift / 64, shift % 64);
+    for i in (words..WORDS).rev() {
+        let mut word = b[i - words] << bits;
+        if bits > 0 && i > words {
+            word |= b[i - words - 1] >> (64 - bits);
+        }
+        a[i] ^= word;
+    }
+}
+
+/// GF(2^m) modulo the pentanomial `ssss` picks for `m`.
+#[derive(Clone, Debug)]
+struct BinaryField {
+    /// degree of the extension
+    m: usize,
+    /// the irreducible polynomial of degree m
+    modulus: Poly,
+}
+
+impl BinaryField {
+    fn new(m: usize) -> Self {
+        let mut modulus = [0; WORDS];
+        for &exponent in &IRREDUCIBLE[3 * (m / 8 - 1)..3 * m / 8] {
+            set_bit(&mut modulus, exponent.into());
+        }
+        set_bit(&mut modulus, m);
+        set_bit(&mut modulus, 0);
+        BinaryField { m, modulus }
+    }
+
+    fn bytes(&self) -> usize {
+        self.m / 8
+    }
+}
+
+impl Field for BinaryField {
+    type Elem = Poly;
+
+    fn zero(&self) -> Poly {
+        [0; WORDS]
+    }
+
+    fn one(&self) -> Poly {
+        let mut one = [0; WORDS];
+        one[0] = 1;
+        one
+    }
+
+    fn add(&self, a: &Poly, b: &Poly) -> Poly {
+        let mut sum = *a;
+        add_shifted(&mut sum, b, 0);
+        sum
+    }
+
+    fn sub(&self, a: &Poly, b: &Poly) -> Poly {
+        self.add(a, b)
+    }
+
+    // Shift and add over the bits of b, reducing a * x^i as it goes
+    fn mul(&self, a: &Poly, b: &Poly) -> Poly {
+        let (mut product, mut shifted) = ([0; WORDS], *a);
+        for i in 0..self.m {
+            if bit(b, i) {
+                add_shifted(&mut product, &shifted, 0);
+            }
+            for j in (1..WORDS).rev() {
+                shifted[j] = shifted[j] << 1 | shifted[j - 1] >> 63;
+            }
+            shifted[0] <<= 1;
+            if bit(&shifted, self.m) {
+                add_shifted(&mut shifted, &self.modulus, 0);
+            }
+        }
+        product
+    }
+
+    // The extended Euclidean algorithm, keeping u = g1 * a and v = g2 * a
+    // modulo the modulus until u is 1
+    fn inv(&self, a: &Poly) -> Option<Poly> {
+        let (mut u, mut v) = (*a, self.modulus);
+        let (mut g1, mut g2) = (self.one(), self.zero());
+        loop {
+            let du = degree(&u)?;
+            if du == 0 {
+                return Some(g1);
+            }
+            let dv = degree(&v).expect("the modulus is nonzero");
+            if du < dv {
+                core::mem::swap(&mut u, &mut v);
+                core::mem::swap(&mut g1, &mut g2);
+                continue;
+            }
+            add_shifted(&mut u, &v, du - dv);
+            add_shifted(&mut g1, &g2, du - dv);
+        }
+    }
+
+    fn elem_from_index(&self, index: usize) -> Poly {
+        let mut x = [0; WORDS];
+        x[0] = index as u64;
+        x
+    }
+
+    fn has_indices(&self, n: usize) -> bool {
+        self.m >= 64 || (n as u64) < 1 << self.m
+    }
+
+    fn random(&self, fill_random: &mut dyn FnMut(&mut [u8])) -> Poly {
+        let mut bytes = vec![0u8; self.bytes()];
+        fill_random(&mut bytes);
+        self.decode(&bytes).expect("m / 8 bytes")
+    }
+
+    // big-endian in m / 8 bytes
+    fn encode(&self, a: &Poly) -> Vec<u8> {
+        (0..self.bytes())
+            .rev()
+            .map(|i| (a[i / 8] >> (8 * (i % 8))) as u8)
+            .collect()
+    }
+
+    fn decode(&self, bytes: &[u8]) -> Option<Poly> {
+        if bytes.len() != self.bytes() {
+            return None;
+        }
+        let mut a = [0; WORDS];
+        for (i, byte) in bytes.iter().rev().enumerate() {
+            a[i / 8] |= u64::from(*byte) << (8 * (i % 8));
+        }
+        Some(a)
+    }
+}
+
+// XTEA with an all-zero key, so that the round keys are the sums alone
+fn encipher(v: &mut [u32; 2]) {
+    let mut sum = 0u32;
+    for _ in 0..32 {
+        v[0] = v[0].wrapping_add(((v[1] << 4 ^ v[1] >> 5).wrapping_add(v[1])) ^ sum);
+        sum = sum.wrapping_add(0x9e37_79b9);
+        v[1] = v[1].wrapping_add(((v[0] << 4 ^ v[0] >> 5).wrapping_add(v[0])) ^ sum);
+    }
+}
+
+fn decipher(v: &mut [u32; 2]) {
+    let mut sum = 0xc6ef_3720u32;
+    for _ in 0..32 {
+        v[1] = v[1].wrapping_sub(((v[0] << 4 ^ v[0] >> 5).wrapping_add(v[0])) ^ sum);
+        sum = sum.wrapping_sub(0x9e37_79b9);
+        v[0] = v[0].wrapping_sub(((v[1] << 4 ^ v[1] >> 5).wrapping_add(v[1])) ^ sum);
+    }
+}
+
+// Runs `cipher` over the 8 bytes of `data` from `start` on, wrapping around
+fn slice(data: &mut [u8], start: usize, cipher: fn(&mut [u32; 2])) {
+    let len = data.len();
+    let mut v = [0u32; 2];
+    for (i, word) in v.iter_mut().enumerate() {
+        *word = (0..4).fold(0, |w, j| {
+            w << 8 | u32::from(data[(start + 4 * i + j) % len])
+        });
+    }
+    cipher(&mut v);
+    for (i, word) in v.iter().enumerate() {
+        for j in 0..4 {
+            data[(start + 4 * i + j) % len] = (word >> (24 - 8 * j)) as u8;
+        }
+    }
+}
+
+// `ssss` lays the integer out in 16-bit words, the least significant
+// first, each big-endian, with the top byte at its even position when the
+// length is odd. Position `i` there holds big-endian byte `len - 1 -
+// paired(i, len)`.
+fn paired(i: usize, len: usize) -> usize {
+    if i ^ 1 < len {
+        i ^ 1
+    } else {
+        i
+    }
+}
+
+fn word_order(bytes: &[u8]) -> Vec<u8> {
+    let len = bytes.len();
+    (0..len).map(|i| bytes[len - 1 - paired(i, len)]).collect()
+}
+
+fn big_endian(words: &[u8]) -> Vec<u8> {
+    let len = words.len();
+    (0..len).map(|k| words[paired(len - 1 - k, len)]).collect()
+}
+
+// The diffusion layer on the big-endian bytes of the secret
+fn diffuse(secret: &[u8], forward: bool) -> Vec<u8> {
+    let mut data = word_order(secret);
+    let steps = (0..40 * data.len()).step_by(2);
+    if forward {
+        for start in steps {
+            slice(&mut data, start, encipher);
+        }
+    } else {
+        for start in steps.rev() {
+            slice(&mut data, start, decipher);
+        }
+    }
+    big_endian(&data)
+}
+
+/// A share as `ssss` prints it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct SsssShare {
+    /// optional label ahead of the index, without dashes
+    token: Option<String>,
+    /// x-coordinate
+    index: usize,
+    /// decimal digits the index is padded to
+    width: usize,
+    /// y-coordinate, big-endian in m / 8 bytes
+    value: Vec<u8>,
+}
+
+impl fmt::Debug for SsssShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SsssShare")
+            .field("token", &self.token)
+            .field("index", &self.index)
+            .field("width", &self.width)
+            .field("value", crate::debug::secret(&self.value))
+            .finish()
+    }
+}
+
+impl SsssShare {
+    pub fn token(&self) -> Option<&str> {
+        self.token.as_deref()
+    }
+
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// y-coordinate, big-endian in `m / 8` bytes
+    pub fn value(&self) -> &[u8] {
+        &self.value
+    }
+
+    /// Labels the share with `token` as `ssss-split -t` does; `None` if the
+    /// token is empty or contains a dash.
+    pub fn with_token(mut self, token: &str) -> Option<Self> {
+        if token.is_empty() || token.contains('-') {
+            return None;
+        }
+        self.token = Some(token.to_string());
+        Some(self)
+    }
+}
+
+impl fmt::Display for SsssShare {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(token) = &self.token {
+            write!(f, "{}-", token)?;
+        }
+        write!(f, "{:0width$}-", self.index, width = self.width)?;
+        f.write_str(&codec::to_hex(&self.value))
+    }
+}
+
+/// Parses `[token-]index-value` as `ssss-combine` reads it, the value in
+/// either case of hex and its length fixing the security level.
+impl FromStr for SsssShare {
+    type Err = DecodeError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let fields: Vec<&str> = s.trim().split('-').collect();
+        let (token, index, value) = match fields[..] {
+            [index, value] => (None, index, value),
+            [token, index, value] => (Some(token.to_string()), index, value),
+            _ => return Err(DecodeError::InvalidEncoding),
+        };
+        if index.is_empty() || !index.bytes().all(|b| b.is_ascii_digit()) {
+            return Err(DecodeError::InvalidEncoding);
+        }
+        let width = index.len();
+        let index = index.parse().map_err(|_| DecodeError::IndexOverflow)?;
+        let value = codec::from_hex(value)?;
+        if value.is_empty() || value.len() > MAX_DEGREE / 8 {
+            return Err(DecodeError::InvalidValue);
+        }
+        Ok(SsssShare {
+            token,
+            index,
+            width,
+            value,
+        })
+    }
+}
+
+/// A `t`-of-`n` scheme as `ssss-split -t t -n n -s m` deals it.
+#[derive(Clone, Debug)]
+pub struct SsssSharing {
+    /// threshold
+    t: usize,
+    /// total number of shares
+    n: usize,
+    field: BinaryField,
+    /// whether the diffusion layer is on, as without `-D`
+    diffusion: bool,
+}
+
+impl SsssSharing {
+    /// Creates a `t`-of-`n` scheme with `2 <= t <= n` at a security level of
+    /// `bytes` bytes, at most 128, with the diffusion layer on. Fails with
+    /// `InvalidModulus` for other levels and for more shares than the field
+    /// has nonzero elements.
+    pub fn new(t: usize, n: usize, bytes: usize) -> Result<Self, TinySsError> {
+        if t < 2 || t > n {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if bytes == 0 || bytes > MAX_DEGREE / 8 {
+            return Err(TinySsError::InvalidModulus);
+        }
+        let field = BinaryField::new(8 * bytes);
+        if !field.has_indices(n) {
+            return Err(TinySsError::InvalidModulus);
+        }
+        Ok(SsssSharing {
+            t,
+            n,
+            field,
+            diffusion: true,
+        })
+    }
+
+    /// Turns the diffusion layer off, as `ssss-split -D` and
+    /// `ssss-combine -D` do. Below 64 bits it is off either way.
+    pub fn without_diffusion(mut self) -> Self {
+        self.diffusion = false;
+        self
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    /// security level in bytes
+    pub fn bytes(&self) -> usize {
+        self.field.bytes()
+    }
+
+    fn diffuses(&self) -> bool {
+        self.diffusion && self.field.m >= DIFFUSION_DEGREE
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: &[u8]) -> Result<Vec<SsssShare>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits `secret`, a big-endian integer of at most `bytes` bytes, with
+    /// the coefficients `c_1` to `c_{t-1}` read big-endian from bytes written
+    /// by `fill_random`.
+    pub fn split_with<F>(
+        &self,
+        secret: &[u8],
+        mut fill_random: F,
+    ) -> Result<Vec<SsssShare>, TinySsError>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        let bytes = self.bytes();
+        if secret.len() > bytes {
+            return Err(TinySsError::SecretOutOfRange);
+        }
+        let mut padded = vec![0u8; bytes - secret.len()];
+        padded.extend_from_slice(secret);
+        if self.diffuses() {
+            padded = diffuse(&padded, true);
+        }
+        let field = &self.field;
+        let mut polynomial = Vec::from([field.decode(&padded).expect("m / 8 bytes")]);
+        polynomial.extend((1..self.t).map(|_| field.random(&mut fill_random)));
+        polynomial.push(field.one());
+        let width = self.n.to_string().len();
+        Ok((1..=self.n)
+            .map(|x| SsssShare {
+                token: None,
+                index: x,
+                width,
+                value: field.encode(&poly::evaluate(
+                    field,
+                    &polynomial,
+                    &field.elem_from_index(x),
+                )),
+            })
+            .collect())
+    }
+
+    /// Recovers the secret, big-endian in `bytes` bytes, from at least `t`
+    /// shares; `ssss-combine` prints it with the leading zero bytes left
+    /// out, as text or with `-x` as hex.
+    ///
+    /// Shares repeating an index are ignored as long as they carry the same
+    /// value; the first `t` distinct shares are used for interpolation.
+    pub fn recover(&self, shares: &[SsssShare]) -> Result<Vec<u8>, TinySsError> {
+        let field = &self.field;
+        let mut points = Vec::with_capacity(shares.len());
+        for share in shares {
+            if share.index == 0 || !field.has_indices(share.index) {
+                return Err(TinySsError::InvalidIndex);
+            }
+            let value = field
+                .decode(&share.value)
+                .ok_or(TinySsError::LengthMismatch)?;
+            points.push(Share::new(share.index, value));
+        }
+        let unique = poly::distinct_shares(&points, self.t)?;
+        // the shares of c_0 + ... + c_{t-1} x^{t-1}, without the x^t term
+        let (xs, ys): (Vec<Poly>, Vec<Poly>) = unique[..self.t]
+            .iter()
+            .map(|share| {
+                let x = field.elem_from_index(share.index());
+                let power = (0..self.t).fold(field.one(), |power, _| field.mul(&power, &x));
+                (x, field.add(share.value(), &power))
+            })
+            .unzip();
+        let weights = poly::lagrange_coefficients(field, &xs, &field.zero())?;
+        let secret = field.encode(&field.dot(&weights, &ys));
+        Ok(if self.diffuses() {
+            diffuse(&secret, false)
+        } else {
+            secret
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf256;
+    use alloc::format;
+
+    #[test]
+    fn field_test() {
+        // at 8 bits the field is that of AES
+        let field = BinaryField::new(8);
+        let elem = |byte: u8| field.decode(&[byte]).unwrap();
+        assert_eq!(field.mul(&elem(0x57), &elem(0x83)), elem(0xc1));
+        for m in [64, 72, 1024] {
+            let field = BinaryField::new(m);
+            let a = field.decode(&[0xa5; 128][..m / 8]).unwrap();
+            assert_eq!(field.mul(&a, &field.inv(&a).unwrap()), field.one());
+        }
+        assert_eq!(field.inv(&field.zero()), None);
+    }
+
+    #[test]
+    fn diffusion_test() {
+        for len in [8, 9, 16, 33] {
+            let secret: Vec<u8> = (0..len as u8).collect();
+            let diffused = diffuse(&secret, true);
+            assert_ne!(diffused, secret);
+            assert_eq!(diffuse(&diffused, false), secret);
+        }
+        assert_eq!(word_order(&[1, 2, 3]), [2, 3, 1]);
+        assert_eq!(big_endian(&[2, 3, 1]), [1, 2, 3]);
+    }
+
+    #[test]
+    fn split_with_test() {
+        // y = x^2 + 5x + 's' at 8 bits, in the field of AES
+        let ss = SsssSharing::new(2, 12, 1).unwrap();
+        let shares = ss.split_with(b"s", |buf| buf.fill(5)).unwrap();
+        let y = |x: u8| gf256::mul(x, x) ^ gf256::mul(5, x) ^ b's';
+        assert_eq!(shares[1].value(), [y(2)]);
+        assert_eq!(shares[1].to_string(), format!("02-{:02x}", y(2)));
+        assert_eq!(ss.recover(&shares[10..]).unwrap(), b"s");
+
+        let ss = SsssSharing::new(3, 5, 24).unwrap();
+        let secret = b"correct horse battery";
+        let shares = ss.split_with(secret, |buf| buf.fill(0x3c)).unwrap();
+        let recovered = ss.recover(&shares[2..]).unwrap();
+        assert_eq!(recovered[3..], secret[..]);
+        let plain = ss.clone().without_diffusion();
+        assert_ne!(plain.recover(&shares[2..]).unwrap(), recovered);
+        assert_eq!(
+            ss.split_with(&[0; 25], |_| {}),
+            Err(TinySsError::SecretOutOfRange)
+        );
+    }
+
+    #[test]
+    fn known_answer_test() {
+        // the 3-of-5 example of the ssss-split(1) man page, at the 184-bit
+        // level ssss picks for a 23-character secret
+        let lines = [
+            "1-1c41ef496eccfbeba439714085df8437236298da8dd824",
+            "2-fbc74a03a50e14ab406c225afb5f45c40ae11976d2b665",
+            "3-fa1c3a9c6df8af0779c36de6c33f6e36e989d0e0b91309",
+            "4-468de7d6eb36674c9cf008c8e8fc8c566537ad6301eb9e",
+        ];
+        let shares: Vec<SsssShare> = lines.iter().map(|line| line.parse().unwrap()).collect();
+        for (share, line) in shares.iter().zip(lines) {
+            assert_eq!(share.to_string(), line);
+        }
+        let ss = SsssSharing::new(3, 5, 23).unwrap();
+        for subset in [[0, 1, 2], [3, 1, 0], [0, 2, 3], [1, 2, 3]] {
+            let subset: Vec<SsssShare> = subset.iter().map(|&i| shares[i].clone()).collect();
+            assert_eq!(ss.recover(&subset).unwrap(), b"my secret root password");
+        }
+        let plain = ss.without_diffusion();
+        assert_ne!(
+            plain.recover(&shares[..3]).unwrap(),
+            b"my secret root password"
+        );
+    }
+
+    #[test]
+    fn encoding_test() {
+        let share: SsssShare = "token-03-00ff1a".parse().unwrap();
+        assert_eq!(share.token(), Some("token"));
+        assert_eq!((share.index(), share.value()), (3, &[0, 0xff, 0x1a][..]));
+        assert_eq!(share.to_string(), "token-03-00ff1a");
+        #[cfg(not(feature = "danger_debug"))]
+        assert!(format!("{:?}", share).contains("[REDACTED]"));
+        let share: SsssShare = "3-00FF1A".parse().unwrap();
+        assert_eq!(share.to_string(), "3-00ff1a");
+        assert_eq!(share.with_token("a-b"), None);
+        for bad in ["", "3", "3-0g", "3-abc", "a-b-3-00", "-00", "x3-00"] {
+            assert!(bad.parse::<SsssShare>().is_err(), "{}", bad);
+        }
+    }
+}
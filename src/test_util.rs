@@ -0,0 +1,13 @@
+//! Fixtures shared by the tests of several modules.
+
+/// A deterministic stand-in for a random byte source, for the `_with`
+/// variants: every byte is `step` more than the last, starting at `step`.
+pub(crate) fn counter(step: u8) -> impl FnMut(&mut [u8]) {
+    let mut next = 0u8;
+    move |buf| {
+        for byte in buf {
+            next = next.wrapping_add(step);
+            *byte = next;
+        }
+    }
+}
@@ -0,0 +1,133 @@
+//! Fiat-Shamir transcripts for the zero-knowledge proofs.
+//!
+//! A proof appends its statement and commitments to a `Transcript` under
+//! labels and draws its challenge from it, so the challenge is bound to
+//! everything appended before, including whatever protocol context the
+//! caller put there first. Prover and verifier must start from transcripts
+//! in the same state. The trait mirrors the interface of the `merlin` crate,
+//! and with the `merlin` feature a `merlin::Transcript` implements it, so
+//! that one can stand in for the built-in `Sha256Transcript`.
+
+use sha2::{Digest, Sha256};
+
+/// A running Fiat-Shamir transcript.
+pub trait Transcript {
+    /// Absorbs `message` under `label`.
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]);
+
+    /// Fills `dest` with a challenge bound to everything absorbed so far,
+    /// which is absorbed in turn.
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]);
+}
+
+/// Transcript hashing length-prefixed labels and messages with SHA-256.
+#[derive(Clone, Debug)]
+pub struct Sha256Transcript {
+    hasher: Sha256,
+}
+
+impl Sha256Transcript {
+    /// Starts a transcript for the protocol named `label`.
+    pub fn new(label: &'static [u8]) -> Self {
+        let mut transcript = Sha256Transcript {
+            hasher: Sha256::new(),
+        };
+        transcript.append_message(b"tiny-ss transcript", label);
+        transcript
+    }
+}
+
+impl Transcript for Sha256Transcript {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        self.hasher.update((label.len() as u64).to_be_bytes());
+        self.hasher.update(label);
+        self.hasher.update((message.len() as u64).to_be_bytes());
+        self.hasher.update(message);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        self.append_message(label, &(dest.len() as u64).to_be_bytes());
+        let state = self.hasher.clone().finalize();
+        for (counter, chunk) in dest.chunks_mut(32).enumerate() {
+            let block = Sha256::new()
+                .chain_update(state)
+                .chain_update((counter as u64).to_be_bytes())
+                .finalize();
+            chunk.copy_from_slice(&block[..chunk.len()]);
+        }
+        // later challenges depend on this one
+        self.hasher.update(state);
+    }
+}
+
+#[cfg(feature = "merlin")]
+impl Transcript for merlin::Transcript {
+    fn append_message(&mut self, label: &'static [u8], message: &[u8]) {
+        merlin::Transcript::append_message(self, label, message);
+    }
+
+    fn challenge_bytes(&mut self, label: &'static [u8], dest: &mut [u8]) {
+        merlin::Transcript::challenge_bytes(self, label, dest);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn transcript_test() {
+        let challenge = |messages: &[&[u8]]| {
+            let mut transcript = Sha256Transcript::new(b"test");
+            for message in messages {
+                transcript.append_message(b"m", message);
+            }
+            let mut out = [0u8; 40];
+            transcript.challenge_bytes(b"c", &mut out);
+            out
+        };
+        assert_eq!(challenge(&[b"ab", b"c"]), challenge(&[b"ab", b"c"]));
+        // message boundaries are part of the transcript
+        assert_ne!(challenge(&[b"ab", b"c"]), challenge(&[b"a", b"bc"]));
+
+        let mut transcript = Sha256Transcript::new(b"test");
+        let (mut first, mut second) = ([0u8; 32], [0u8; 32]);
+        transcript.challenge_bytes(b"c", &mut first);
+        transcript.challenge_bytes(b"c", &mut second);
+        assert_ne!(first, second);
+    }
+
+    #[cfg(feature = "merlin")]
+    #[test]
+    fn merlin_test() {
+        use crate::{group::SchnorrGroup, pok::SchnorrProof};
+        use num_bigint::BigInt;
+
+        let group = SchnorrGroup::from_safe_prime(BigInt::from(2039)).unwrap();
+        let y = group.exp(group.g(), &BigInt::from(777));
+        let transcript = || merlin::Transcript::new(b"ceremony");
+        let proof = SchnorrProof::prove(
+            &group,
+            &mut transcript(),
+            (group.g(), &y),
+            &BigInt::from(777),
+            &BigInt::from(95),
+        );
+        assert!(proof.verify(&group, &mut transcript(), (group.g(), &y)));
+        assert!(!proof.verify(
+            &group,
+            &mut Sha256Transcript::new(b"ceremony"),
+            (group.g(), &y)
+        ));
+
+        // the challenges are merlin's own
+        let mut ours = transcript();
+        let mut theirs = transcript();
+        let (mut a, mut b) = ([0u8; 32], [0u8; 32]);
+        Transcript::append_message(&mut ours, b"m", b"message");
+        theirs.append_message(b"m", b"message");
+        Transcript::challenge_bytes(&mut ours, b"c", &mut a);
+        theirs.challenge_bytes(b"c", &mut b);
+        assert_eq!(a, b);
+    }
+}
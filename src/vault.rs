@@ -0,0 +1,118 @@
+//! Unseal keys of [HashiCorp Vault](https://developer.hashicorp.com/vault),
+//! for operators moving between Vault and this crate.
+//!
+//! Vault splits its root key byte by byte over GF(256) modulo the AES
+//! polynomial, as `Gf256Sharing` does, and hands out every share as its y
+//! bytes followed by a single byte holding x. `vault operator init` prints
+//! the shares in padded standard base64, and hex next to it in its JSON
+//! output. Vault picks the x-coordinates at random and interpolates through
+//! every share it is given, without a threshold of its own; `decode` and
+//! `combine` accept shares from it, and `encode` turns those of any
+//! `Gf256Sharing` into keys it accepts.
+
+use crate::{codec, gf256, gf256::ByteShare, poly, DecodeError, Share, TinySsError};
+use alloc::{string::String, vec::Vec};
+
+/// Vault's layout of `share`: its value, then its index in one byte. Fails
+/// with `InvalidIndex` for an index outside `1..=255` and with
+/// `LengthMismatch` for an empty value, neither of which Vault accepts.
+pub fn to_bytes(share: &ByteShare) -> Result<Vec<u8>, TinySsError> {
+    let x = u8::try_from(share.index()).map_err(|_| TinySsError::InvalidIndex)?;
+    if x == 0 {
+        return Err(TinySsError::InvalidIndex);
+    }
+    if share.value().is_empty() {
+        return Err(TinySsError::LengthMismatch);
+    }
+    let mut bytes = share.value().clone();
+    bytes.push(x);
+    Ok(bytes)
+}
+
+/// Parses Vault's layout, rejecting the zero x-coordinate and shares
+/// without a value byte.
+pub fn from_bytes(bytes: &[u8]) -> Result<ByteShare, DecodeError> {
+    match bytes {
+        [] | [_] => Err(DecodeError::Truncated),
+        [.., 0] => Err(DecodeError::InvalidValue),
+        [value @ .., x] => Ok(Share::new((*x).into(), value.to_vec())),
+    }
+}
+
+/// The unseal key of `share` as `vault operator init` prints it.
+pub fn encode(share: &ByteShare) -> Result<String, TinySsError> {
+    to_bytes(share).map(|bytes| codec::to_base64(&bytes))
+}
+
+/// Parses an unseal key in hex or else in base64, as Vault's unseal
+/// endpoint does.
+pub fn decode(key: &str) -> Result<ByteShare, DecodeError> {
+    let key = key.trim();
+    let bytes = codec::from_hex(key).or_else(|_| codec::from_base64(key))?;
+    from_bytes(&bytes)
+}
+
+/// Recovers the secret from `shares` as Vault's `Combine` does, by
+/// interpolating through all of them; as in Vault, fewer than two shares
+/// are an error, and so are too few for the threshold the secret was split
+/// with, which comes back as an unrelated byte string.
+pub fn combine(shares: &[ByteShare]) -> Result<Vec<u8>, TinySsError> {
+    for share in shares {
+        if share.index() == 0 || share.index() > 255 {
+            return Err(TinySsError::InvalidIndex);
+        }
+        if share.value().len() != shares[0].value().len() {
+            return Err(TinySsError::LengthMismatch);
+        }
+    }
+    let unique = poly::distinct_shares(shares, 2)?;
+    let points: Vec<(u8, &[u8])> = unique
+        .iter()
+        .map(|share| (share.index() as u8, share.value().as_slice()))
+        .collect();
+    Ok(gf256::interpolate(&points, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gf256::Gf256Sharing;
+
+    #[test]
+    fn roundtrip_test() {
+        let gf = Gf256Sharing::new(3, 5).unwrap();
+        let secret = b"vault root key 0123456789abcdef";
+        let shares = gf.split_with(secret, |buf| buf.fill(0x42));
+        let keys: Vec<String> = shares.iter().map(|s| encode(s).unwrap()).collect();
+        let decoded: Vec<ByteShare> = keys.iter().map(|k| decode(k).unwrap()).collect();
+        assert_eq!(decoded, shares);
+        assert_eq!(combine(&decoded[2..]).unwrap(), secret);
+        assert_eq!(combine(&decoded).unwrap(), secret);
+
+        let hex = codec::to_hex(&to_bytes(&shares[0]).unwrap());
+        assert_eq!(decode(&hex).unwrap(), shares[0]);
+    }
+
+    #[test]
+    fn layout_test() {
+        // y = 7 + 5x in the one value byte, x = 3 last
+        let share = Share::new(3, [7 ^ gf256::mul(5, 3)].to_vec());
+        let bytes = to_bytes(&share).unwrap();
+        assert_eq!(bytes, [7 ^ gf256::mul(5, 3), 3]);
+        assert_eq!(from_bytes(&bytes).unwrap(), share);
+        let other = Share::new(200, [7 ^ gf256::mul(5, 200)].to_vec());
+        assert_eq!(combine(&[share, other]).unwrap(), [7]);
+
+        assert_eq!(from_bytes(&[1]), Err(DecodeError::Truncated));
+        assert_eq!(from_bytes(&[1, 0]), Err(DecodeError::InvalidValue));
+        let share = Share::new(256, [1].to_vec());
+        assert_eq!(encode(&share), Err(TinySsError::InvalidIndex));
+        assert_eq!(
+            combine(&[Share::new(1, [1].to_vec())]),
+            Err(TinySsError::NotEnoughShares {
+                required: 2,
+                got: 1
+            })
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! Conversions between shares of this crate and of
+//! [`vsss-rs`](https://crates.io/crates/vsss-rs), so that the two can be
+//! mixed in one codebase during a migration.
+//!
+//! `vsss-rs` shares the scalars of `ff` prime fields as `DefaultShare`s of
+//! two `IdentifierPrimeField`s, and numbers the participants of a dealing 1
+//! to `limit` unless told otherwise. Its parameters map directly: a
+//! `threshold`-of-`limit` dealing there is a `SecretShare::ff(threshold,
+//! limit)` dealing here, whose shares convert one to one, except that this
+//! crate wants `threshold < limit`. Identifiers other than small integers,
+//! such as those of its random participant generators, have no index here
+//! and fail to convert.
+
+use crate::{Share, TinySsError};
+use ff::PrimeField;
+use vsss_rs::{DefaultShare, IdentifierPrimeField, ValuePrimeField};
+
+/// A `vsss-rs` share of a scalar of `F`.
+pub type VsssShare<F> = DefaultShare<IdentifierPrimeField<F>, ValuePrimeField<F>>;
+
+impl<F: PrimeField> From<&Share<F>> for VsssShare<F> {
+    fn from(share: &Share<F>) -> Self {
+        DefaultShare {
+            identifier: IdentifierPrimeField(F::from(share.index() as u64)),
+            value: IdentifierPrimeField(*share.value()),
+        }
+    }
+}
+
+impl<F: PrimeField> From<Share<F>> for VsssShare<F> {
+    fn from(share: Share<F>) -> Self {
+        VsssShare::from(&share)
+    }
+}
+
+/// Fails with `InvalidIndex` unless the identifier is a nonzero integer
+/// below 2^64 that fits into `usize`.
+impl<F: PrimeField> TryFrom<&VsssShare<F>> for Share<F> {
+    type Error = TinySsError;
+
+    fn try_from(share: &VsssShare<F>) -> Result<Self, Self::Error> {
+        let index = small_integer(&share.identifier.0).ok_or(TinySsError::InvalidIndex)?;
+        let index = usize::try_from(index).map_err(|_| TinySsError::InvalidIndex)?;
+        if index == 0 {
+            return Err(TinySsError::InvalidIndex);
+        }
+        Ok(Share::new(index, share.value.0))
+    }
+}
+
+impl<F: PrimeField> TryFrom<VsssShare<F>> for Share<F> {
+    type Error = TinySsError;
+
+    fn try_from(share: VsssShare<F>) -> Result<Self, Self::Error> {
+        Share::try_from(&share)
+    }
+}
+
+// The integer below 2^64 that `a` is, if any. The byte order of `F::Repr`
+// is up to the field, so both ends are tried.
+fn small_integer<F: PrimeField>(a: &F) -> Option<u64> {
+    let repr = a.to_repr();
+    let bytes = repr.as_ref();
+    let width = bytes.len().min(8);
+    let mut low = [0u8; 8];
+    low[..width].copy_from_slice(&bytes[..width]);
+    let mut high = [0u8; 8];
+    high[8 - width..].copy_from_slice(&bytes[bytes.len() - width..]);
+    [u64::from_le_bytes(low), u64::from_be_bytes(high)]
+        .into_iter()
+        .find(|&candidate| F::from(candidate) == *a)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{ff_field::FfField, SecretShare};
+    use alloc::vec::Vec;
+    use bls12_381::Scalar;
+
+    #[test]
+    fn conversion_test() {
+        let ss = SecretShare::<FfField<Scalar>>::ff(2, 3).unwrap();
+        let secret = Scalar::from(1234u64);
+        let shares = ss
+            .split_with_coefficients(secret, &[Scalar::from(166u64)])
+            .unwrap();
+        let converted: Vec<VsssShare<Scalar>> = shares.iter().map(VsssShare::from).collect();
+        assert_eq!(converted[2].identifier.0, Scalar::from(3u64));
+        assert_eq!(converted[2].value.0, Scalar::from(1234u64 + 3 * 166));
+        let back: Vec<Share<Scalar>> = converted
+            .into_iter()
+            .map(|share| share.try_into().unwrap())
+            .collect();
+        assert_eq!(back, shares);
+
+        let odd = VsssShare::from((-Scalar::one(), Scalar::one()));
+        assert_eq!(Share::try_from(&odd), Err(TinySsError::InvalidIndex));
+        let zero = VsssShare::from((Scalar::zero(), Scalar::one()));
+        assert_eq!(Share::try_from(zero), Err(TinySsError::InvalidIndex));
+    }
+
+    #[cfg(feature = "rand")]
+    #[test]
+    fn interop_test() {
+        use vsss_rs::{shamir, ReadableShareSet};
+
+        let secret = Scalar::from(0xdead_beefu64);
+        let mut rng = rand::thread_rng();
+        let theirs: Vec<VsssShare<Scalar>> =
+            shamir::split_secret(3, 5, &IdentifierPrimeField(secret), &mut rng).unwrap();
+        let ours: Vec<Share<Scalar>> = theirs.iter().map(|s| s.try_into().unwrap()).collect();
+        let ss = SecretShare::<FfField<Scalar>>::ff(3, 5).unwrap();
+        assert_eq!(ss.recover(&ours[2..]).unwrap(), secret);
+
+        let ours = ss.split(secret).unwrap();
+        let theirs: Vec<VsssShare<Scalar>> = ours[..3].iter().map(VsssShare::from).collect();
+        assert_eq!(theirs.combine().unwrap().0, secret);
+    }
+}
@@ -0,0 +1,239 @@
+//! Weighted threshold sharing.
+//!
+//! Every participant holds as many shares of one Shamir dealing as its
+//! weight, and any set of participants whose weights add up to the
+//! threshold can recover. The virtual shares get consecutive indices in the
+//! order the participants were added.
+
+use crate::{
+    field::{Field, PrimeField},
+    SecretShare, Share, TinySsError,
+};
+use alloc::{string::String, vec::Vec};
+
+/// The shares dealt to one weighted participant.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WeightedShare<V = num_bigint::BigInt> {
+    name: String,
+    /// one virtual share per unit of weight
+    shares: Vec<Share<V>>,
+}
+
+impl<V> WeightedShare<V> {
+    pub fn new(name: &str, shares: Vec<Share<V>>) -> Self {
+        WeightedShare {
+            name: String::from(name),
+            shares,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn shares(&self) -> &[Share<V>] {
+        &self.shares
+    }
+}
+
+/// A threshold scheme over weighted, named participants.
+#[derive(Clone, Debug)]
+pub struct WeightedSharing<F: Field = PrimeField> {
+    /// total weight needed for recovery
+    t: usize,
+    /// names and weights, in the order they were added
+    participants: Vec<(String, usize)>,
+    field: F,
+}
+
+impl<F: Field + Clone> WeightedSharing<F> {
+    /// Creates a scheme needing a total weight of `t`, without
+    /// participants yet.
+    pub fn new(t: usize, field: F) -> Result<Self, TinySsError> {
+        if t == 0 {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        Ok(WeightedSharing {
+            t,
+            participants: Vec::new(),
+            field,
+        })
+    }
+
+    /// Adds a participant holding `weight` virtual shares.
+    pub fn add_participant(&mut self, name: &str, weight: usize) -> Result<&mut Self, TinySsError> {
+        if weight == 0 {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        if self.weight(name).is_some() {
+            return Err(TinySsError::InvalidParticipant);
+        }
+        self.participants.push((String::from(name), weight));
+        Ok(self)
+    }
+
+    /// threshold
+    pub fn t(&self) -> usize {
+        self.t
+    }
+
+    /// sum of all weights
+    pub fn total_weight(&self) -> usize {
+        self.participants.iter().map(|(_, w)| w).sum()
+    }
+
+    /// Weight of the participant called `name`, if any.
+    pub fn weight(&self, name: &str) -> Option<usize> {
+        self.participants
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, w)| *w)
+    }
+
+    /// Whether the named participants may recover the secret together.
+    pub fn is_authorized(&self, names: &[&str]) -> bool {
+        let mut weight = 0;
+        for (i, name) in names.iter().enumerate() {
+            match self.weight(name) {
+                Some(w) if !names[..i].contains(name) => weight += w,
+                Some(_) => {}
+                None => return false,
+            }
+        }
+        weight >= self.t
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: F::Elem) -> Result<Vec<WeightedShare<F::Elem>>, TinySsError> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, || self.field.random(&mut |buf| rng.fill_bytes(buf)))
+    }
+
+    /// Splits `secret` drawing the random coefficients from `sample`, which
+    /// must return uniformly random field elements.
+    ///
+    /// The total weight must exceed the threshold.
+    pub fn split_with<S>(
+        &self,
+        secret: F::Elem,
+        sample: S,
+    ) -> Result<Vec<WeightedShare<F::Elem>>, TinySsError>
+    where
+        S: FnMut() -> F::Elem,
+    {
+        let mut shares = self.scheme()?.split_with(secret, sample)?.into_iter();
+        Ok(self
+            .participants
+            .iter()
+            .map(|(name, weight)| WeightedShare::new(name, shares.by_ref().take(*weight).collect()))
+            .collect())
+    }
+
+    /// Recovers the secret from the shares of participants whose weights
+    /// add up to at least `t`.
+    pub fn recover(&self, shares: &[WeightedShare<F::Elem>]) -> Result<F::Elem, TinySsError> {
+        let mut virtual_shares = Vec::new();
+        for share in shares {
+            let (first, weight) = self
+                .range(&share.name)
+                .ok_or(TinySsError::InvalidParticipant)?;
+            if share
+                .shares
+                .iter()
+                .any(|s| s.index() < first || s.index() >= first + weight)
+            {
+                return Err(TinySsError::InvalidIndex);
+            }
+            virtual_shares.extend_from_slice(&share.shares);
+        }
+        self.scheme()?.recover(&virtual_shares)
+    }
+
+    // Flat scheme over all virtual shares
+    fn scheme(&self) -> Result<SecretShare<F>, TinySsError> {
+        SecretShare::with_field(self.t, self.total_weight(), self.field.clone())
+    }
+
+    // First virtual index and weight of the participant called `name`
+    fn range(&self, name: &str) -> Option<(usize, usize)> {
+        let mut first = 1;
+        for (n, weight) in &self.participants {
+            if n == name {
+                return Some((first, *weight));
+            }
+            first += weight;
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use num_bigint::BigInt;
+
+    fn scheme() -> WeightedSharing {
+        let field = PrimeField::new(BigInt::from(1613)).unwrap();
+        let mut weighted = WeightedSharing::new(4, field).unwrap();
+        weighted
+            .add_participant("alice", 3)
+            .unwrap()
+            .add_participant("bob", 1)
+            .unwrap()
+            .add_participant("carol", 2)
+            .unwrap();
+        weighted
+    }
+
+    #[test]
+    fn weighted_test() {
+        let weighted = scheme();
+        assert_eq!(weighted.total_weight(), 6);
+        assert!(weighted.is_authorized(&["alice", "bob"]));
+        assert!(!weighted.is_authorized(&["bob", "carol"]));
+
+        let mut next = 0;
+        let shares = weighted
+            .split_with(BigInt::from(1234), || {
+                next += 97;
+                BigInt::from(next)
+            })
+            .unwrap();
+        assert_eq!(shares[0].name(), "alice");
+        assert_eq!(shares[2].shares().len(), 2);
+        assert_eq!(weighted.recover(&shares[..2]).unwrap(), BigInt::from(1234));
+        assert_eq!(
+            weighted.recover(&shares[1..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 4,
+                got: 3
+            }
+        );
+
+        // a participant cannot pass off someone else's shares
+        let forged = WeightedShare::new("bob", shares[0].shares().to_vec());
+        assert_eq!(
+            weighted.recover(&[forged]).unwrap_err(),
+            TinySsError::InvalidIndex
+        );
+    }
+
+    #[test]
+    fn participant_test() {
+        let mut weighted = scheme();
+        assert_eq!(
+            weighted.add_participant("bob", 2).unwrap_err(),
+            TinySsError::InvalidParticipant
+        );
+        assert_eq!(
+            weighted.add_participant("dave", 0).unwrap_err(),
+            TinySsError::InvalidThreshold
+        );
+        let unknown = WeightedShare::new("eve", Vec::<Share>::new());
+        assert_eq!(
+            weighted.recover(&[unknown]).unwrap_err(),
+            TinySsError::InvalidParticipant
+        );
+    }
+}
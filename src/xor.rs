@@ -0,0 +1,121 @@
+//! Unanimous `n`-of-`n` splitting of byte strings by XOR.
+//!
+//! All shares but the last are random, and the last is the secret XORed
+//! with all of them. Every share has the length of the secret, and any
+//! `n - 1` shares are independent of it.
+
+use crate::{gf256::ByteShare, poly, Share, TinySsError};
+use alloc::{vec, vec::Vec};
+
+/// An `n`-of-`n` scheme splitting byte strings by XOR.
+#[derive(Clone, Debug)]
+pub struct XorSharing {
+    /// total number of shares, all needed for recovery
+    n: usize,
+}
+
+impl XorSharing {
+    /// Creates a scheme of `n >= 2` shares.
+    pub fn new(n: usize) -> Result<Self, TinySsError> {
+        if n < 2 {
+            return Err(TinySsError::InvalidThreshold);
+        }
+        Ok(XorSharing { n })
+    }
+
+    /// total number of shares
+    pub fn n(&self) -> usize {
+        self.n
+    }
+
+    #[cfg(feature = "rand")]
+    pub fn split(&self, secret: &[u8]) -> Vec<ByteShare> {
+        use rand::RngCore;
+        let mut rng = rand::thread_rng();
+        self.split_with(secret, |buf| rng.fill_bytes(buf))
+    }
+
+    /// Splits `secret` into shares indexed `1..=n`, drawing all but the last
+    /// from `fill_random`, which must fill the buffer with uniformly random
+    /// bytes.
+    pub fn split_with<F>(&self, secret: &[u8], mut fill_random: F) -> Vec<ByteShare>
+    where
+        F: FnMut(&mut [u8]),
+    {
+        let mut last = secret.to_vec();
+        let mut shares: Vec<ByteShare> = (1..self.n)
+            .map(|index| {
+                let mut value = vec![0u8; secret.len()];
+                fill_random(&mut value);
+                xor_into(&mut last, &value);
+                Share::new(index, value)
+            })
+            .collect();
+        shares.push(Share::new(self.n, last));
+        shares
+    }
+
+    /// Recovers the secret from all `n` shares of equal length.
+    pub fn recover(&self, shares: &[ByteShare]) -> Result<Vec<u8>, TinySsError> {
+        for share in shares {
+            if share.index() == 0 || share.index() > self.n {
+                return Err(TinySsError::InvalidIndex);
+            }
+            if share.value().len() != shares[0].value().len() {
+                return Err(TinySsError::LengthMismatch);
+            }
+        }
+        let unique = poly::distinct_shares(shares, self.n)?;
+        let mut secret = vec![0u8; unique[0].value().len()];
+        for share in unique {
+            xor_into(&mut secret, share.value());
+        }
+        Ok(secret)
+    }
+}
+
+fn xor_into(acc: &mut [u8], bytes: &[u8]) {
+    for (a, b) in acc.iter_mut().zip(bytes) {
+        *a ^= b;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn xor_test() {
+        let xor = XorSharing::new(3).unwrap();
+        let mut next = 0u8;
+        let shares = xor.split_with(b"ss", |buf| {
+            for b in buf {
+                next += 3;
+                *b = next;
+            }
+        });
+        assert_eq!(shares[0], Share::new(1, vec![3, 6]));
+        assert_eq!(shares[2], Share::new(3, vec![b's' ^ 3 ^ 9, b's' ^ 6 ^ 12]));
+        assert_eq!(xor.recover(&shares).unwrap(), b"ss");
+        assert_eq!(
+            xor.recover(&shares[1..]).unwrap_err(),
+            TinySsError::NotEnoughShares {
+                required: 3,
+                got: 2
+            }
+        );
+    }
+
+    #[test]
+    fn error_test() {
+        assert_eq!(
+            XorSharing::new(1).unwrap_err(),
+            TinySsError::InvalidThreshold
+        );
+        let xor = XorSharing::new(2).unwrap();
+        let shares = [Share::new(1, vec![1, 2]), Share::new(2, vec![3])];
+        assert_eq!(xor.recover(&shares), Err(TinySsError::LengthMismatch));
+        let shares = [Share::new(1, vec![1]), Share::new(3, vec![3])];
+        assert_eq!(xor.recover(&shares), Err(TinySsError::InvalidIndex));
+    }
+}
@@ -0,0 +1,83 @@
+//! Wiping of secret values before their memory is freed.
+//!
+//! With the `zeroize` feature enabled, `SecretShare` overwrites the
+//! polynomial coefficients, the copy of the secret and the interpolation
+//! scratch values once it is done with them, through `Field::zeroize`.
+//! Shares and recovered secrets belong to the caller, who wipes them with
+//! the [`zeroize`](https://crates.io/crates/zeroize) crate's `Zeroize` or
+//! keeps them in its `Zeroizing`, re-exported here. `Share` and
+//! `SecretScalar` implement `Zeroize` for the element types of the fields
+//! of this crate, and `SecretBytes` is `ZeroizeOnDrop`.
+//!
+//! `BigInt` implements no `Zeroize` of its own, and gives no access to its
+//! digits, so `zeroize_bigint` overwrites them through `assign_from_slice`,
+//! which reuses the buffer; spare capacity left over from larger
+//! intermediate values is not reached.
+
+pub use ::zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
+use alloc::vec;
+use num_bigint::{BigInt, Sign};
+use num_traits::Zero;
+
+/// Overwrites `x` in place and leaves it zero.
+pub fn zeroize_bigint(x: &mut BigInt) {
+    // assigning as many all-ones digits overwrites the buffer in place,
+    // where assigning zero would free it untouched
+    let ones = vec![u32::MAX; x.iter_u32_digits().len()];
+    x.assign_from_slice(Sign::Plus, &ones);
+    core::hint::black_box(&*x);
+    x.set_zero();
+}
+
+// `Zeroize` for a wrapper `$outer<V>` around its field `.$field`, once per
+// value type: a blanket impl over `V: Zeroize` would leave out `BigInt`,
+// and the orphan rule keeps it from gaining an impl here
+macro_rules! impl_zeroize {
+    ($outer:ident, $field:tt) => {
+        impl $crate::zeroize::Zeroize for $outer<num_bigint::BigInt> {
+            fn zeroize(&mut self) {
+                $crate::zeroize::zeroize_bigint(&mut self.$field);
+            }
+        }
+
+        $crate::zeroize::impl_zeroize!($outer, $field, u8, u64, alloc::vec::Vec<u8>);
+    };
+    ($outer:ident, $field:tt, $($value:ty),+) => {
+        $(
+            impl $crate::zeroize::Zeroize for $outer<$value> {
+                fn zeroize(&mut self) {
+                    $crate::zeroize::Zeroize::zeroize(&mut self.$field);
+                }
+            }
+        )+
+    };
+}
+
+pub(crate) use impl_zeroize;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{SecretShare, Share};
+    use alloc::vec::Vec;
+
+    #[test]
+    fn zeroize_test() {
+        let mut x = BigInt::from(-0x1234_5678_9abc_def0_1234_i128);
+        zeroize_bigint(&mut x);
+        assert!(x.is_zero());
+
+        let ss = SecretShare::new(2, 3, BigInt::from(1613)).unwrap();
+        let shares: Zeroizing<Vec<Share>> = Zeroizing::new(
+            ss.split_with_coefficients(BigInt::from(1000), &[BigInt::from(5)])
+                .unwrap(),
+        );
+        assert_eq!(ss.recover(&shares[1..]).unwrap(), BigInt::from(1000));
+        let mut share = shares[0].clone();
+        share.zeroize();
+        assert_eq!(share, Share::new(1, BigInt::from(0)));
+        let mut share = Share::new(2, 0x42u8);
+        share.zeroize();
+        assert_eq!(share, Share::new(2, 0));
+    }
+}
@@ -0,0 +1,367 @@
+//! Differential tests of splitting and recovery against a straightforward
+//! reference: Horner's rule, Lagrange interpolation term by term, and
+//! inverses by Fermat's little theorem, none of it shared with the crate.
+//! Every backend deals random secrets under random thresholds and must give
+//! the shares the reference gives, then recover from random subsets of them
+//! as the reference does, so that the faster paths (block evaluation,
+//! subproduct trees, binary inversion, Montgomery and word arithmetic,
+//! vectorized GF(256) slices) cannot drift from the algebra unnoticed.
+//!
+//! `cargo test --test differential` runs a few rounds from a fixed seed;
+//! `DIFFERENTIAL_ROUNDS` and `DIFFERENTIAL_SEED` set others, and failures
+//! report the seed and round to replay. The thresholds in the thousands
+//! take a while under the reference and only run with `--ignored`.
+
+use num_bigint::{BigInt, Sign};
+use num_traits::{One, Zero};
+use tiny_ss::{gf256::Gf256Sharing, presets, SecretShare, Share};
+
+/// the Mersenne prime 2^61 - 1
+const P61: u64 = (1 << 61) - 1;
+
+/// splitmix64, so that a seed replays a failing run
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    // uniform enough in lo..=hi for test parameters
+    fn range(&mut self, lo: usize, hi: usize) -> usize {
+        lo + (self.next() % (hi - lo + 1) as u64) as usize
+    }
+
+    // below 2p, so that non-canonical inputs come up too
+    fn below_twice(&mut self, p: &BigInt) -> BigInt {
+        let bytes: Vec<u8> = (0..p.bits() / 8 + 9).map(|_| self.next() as u8).collect();
+        BigInt::from_bytes_be(Sign::Plus, &bytes) % (p << 1)
+    }
+
+    // `count` distinct indices out of 1..=n, in random order
+    fn subset(&mut self, n: usize, count: usize) -> Vec<usize> {
+        let mut indices: Vec<usize> = (1..=n).collect();
+        for i in 0..count {
+            let j = self.range(i, n - 1);
+            indices.swap(i, j);
+        }
+        indices.truncate(count);
+        indices
+    }
+}
+
+/// Arithmetic modulo a prime, written for clarity alone.
+mod reference {
+    use num_bigint::BigInt;
+    use num_traits::{One, Zero};
+
+    pub fn reduce(a: &BigInt, p: &BigInt) -> BigInt {
+        ((a % p) + p) % p
+    }
+
+    pub fn inverse(a: &BigInt, p: &BigInt) -> BigInt {
+        reduce(a, p).modpow(&(p - 2u32), p)
+    }
+
+    // sum of c_k x^k
+    pub fn evaluate(coefficients: &[BigInt], x: &BigInt, p: &BigInt) -> BigInt {
+        let mut value = BigInt::zero();
+        for c in coefficients.iter().rev() {
+            value = reduce(&(value * x + c), p);
+        }
+        value
+    }
+
+    // sum of y_j prod_{k != j} (x - x_k) / (x_j - x_k)
+    pub fn interpolate(points: &[(BigInt, BigInt)], x: &BigInt, p: &BigInt) -> BigInt {
+        let mut value = BigInt::zero();
+        for (j, (xj, yj)) in points.iter().enumerate() {
+            let (mut numerator, mut denominator) = (BigInt::one(), BigInt::one());
+            for (k, (xk, _)) in points.iter().enumerate() {
+                if k != j {
+                    numerator = reduce(&(numerator * (x - xk)), p);
+                    denominator = reduce(&(denominator * (xj - xk)), p);
+                }
+            }
+            value = reduce(&(value + yj * numerator * inverse(&denominator, p)), p);
+        }
+        value
+    }
+
+    // GF(2^8) modulo x^8 + x^4 + x^3 + x + 1, bit by bit
+    pub fn gf256_mul(mut a: u8, mut b: u8) -> u8 {
+        let mut product = 0;
+        while b != 0 {
+            if b & 1 == 1 {
+                product ^= a;
+            }
+            a = (a << 1) ^ if a & 0x80 != 0 { 0x1b } else { 0 };
+            b >>= 1;
+        }
+        product
+    }
+
+    pub fn gf256_inverse(a: u8) -> u8 {
+        // a^254
+        let mut inverse = 1;
+        for _ in 0..254 {
+            inverse = gf256_mul(inverse, a);
+        }
+        inverse
+    }
+
+    pub fn gf256_interpolate_at_zero(points: &[(u8, u8)]) -> u8 {
+        let mut value = 0;
+        for (j, &(xj, yj)) in points.iter().enumerate() {
+            let (mut numerator, mut denominator) = (1, 1);
+            for (k, &(xk, _)) in points.iter().enumerate() {
+                if k != j {
+                    numerator = gf256_mul(numerator, xk);
+                    denominator = gf256_mul(denominator, xj ^ xk);
+                }
+            }
+            value ^= gf256_mul(yj, gf256_mul(numerator, gf256_inverse(denominator)));
+        }
+        value
+    }
+}
+
+fn env(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+// Runs `round` for every round, from the configured seed
+fn rounds(default_rounds: u64, mut round: impl FnMut(&mut Rng, &str)) {
+    let seed = env("DIFFERENTIAL_SEED", 0x0074_696e_792d_7373);
+    for i in 0..env("DIFFERENTIAL_ROUNDS", default_rounds) {
+        let mut rng = Rng(seed ^ i.wrapping_mul(0xd6e8_feb8_6659_fd93));
+        round(&mut rng, &format!("seed {:#x}, round {}", seed, i));
+    }
+}
+
+// Checks shares dealt over p from `secret` and `coefficients` against the
+// reference, then recovery from a random subset of at least as many as
+// the polynomial has coefficients
+fn check_dealing(
+    rng: &mut Rng,
+    context: &str,
+    p: &BigInt,
+    secret: &BigInt,
+    coefficients: &[BigInt],
+    shares: &[Share],
+    recover: impl Fn(&[Share]) -> BigInt,
+) {
+    let t = coefficients.len() + 1;
+    let polynomial: Vec<BigInt> = std::iter::once(secret)
+        .chain(coefficients)
+        .map(|c| reference::reduce(c, p))
+        .collect();
+    for share in shares {
+        let expected = reference::evaluate(&polynomial, &BigInt::from(share.index()), p);
+        assert_eq!(
+            share.value(),
+            &expected,
+            "share {}, {}",
+            share.index(),
+            context
+        );
+    }
+    let count = rng.range(t, shares.len());
+    let subset: Vec<Share> = rng
+        .subset(shares.len(), count)
+        .into_iter()
+        .map(|i| shares[i - 1].clone())
+        .collect();
+    let points: Vec<(BigInt, BigInt)> = subset[..t]
+        .iter()
+        .map(|share| (BigInt::from(share.index()), share.value().clone()))
+        .collect();
+    let expected = reference::interpolate(&points, &BigInt::zero(), p);
+    assert_eq!(expected, polynomial[0], "reference recovery, {}", context);
+    assert_eq!(recover(&subset), expected, "recovery, {}", context);
+}
+
+#[test]
+fn prime_fields() {
+    let primes = [
+        BigInt::from(1613),
+        BigInt::from(P61),
+        presets::modulus(presets::SECP256K1_ORDER),
+        presets::modulus(presets::ED25519_ORDER),
+        (BigInt::one() << 521) - 1,
+    ];
+    rounds(40, |rng, context| {
+        let p = &primes[rng.range(0, primes.len() - 1)];
+        let t = rng.range(1, 40);
+        let n = rng.range(t + 1, t + 20);
+        let secret = rng.below_twice(p);
+        let coefficients: Vec<BigInt> = (1..t).map(|_| rng.below_twice(p)).collect();
+
+        let ss = SecretShare::new(t, n, p.clone()).unwrap();
+        let shares = ss
+            .split_with_coefficients(secret.clone(), &coefficients)
+            .unwrap();
+        check_dealing(rng, context, p, &secret, &coefficients, &shares, |subset| {
+            ss.recover(subset).unwrap()
+        });
+        let indices: Vec<usize> = shares[n - t..].iter().map(Share::index).collect();
+        let recovered = ss
+            .recovery_context(&indices)
+            .unwrap()
+            .recover(&shares[n - t..])
+            .unwrap();
+        assert_eq!(
+            recovered,
+            reference::reduce(&secret, p),
+            "context, {}",
+            context
+        );
+
+        // the same dealing in Montgomery form
+        let ss = SecretShare::montgomery(t, n, p.clone()).unwrap();
+        let field = ss.field();
+        let to = |v: &BigInt| field.to_montgomery(&reference::reduce(v, p));
+        let shares: Vec<Share> = ss
+            .split_with_coefficients(
+                to(&secret),
+                &coefficients.iter().map(to).collect::<Vec<_>>(),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|share| Share::new(share.index(), field.from_montgomery(share.value())))
+            .collect();
+        check_dealing(rng, context, p, &secret, &coefficients, &shares, |subset| {
+            let subset: Vec<Share> = subset
+                .iter()
+                .map(|share| Share::new(share.index(), field.to_montgomery(share.value())))
+                .collect();
+            field.from_montgomery(&ss.recover(&subset).unwrap())
+        });
+    });
+}
+
+#[test]
+fn word_field() {
+    let p = BigInt::from(P61);
+    rounds(40, |rng, context| {
+        let t = rng.range(1, 40);
+        let n = rng.range(t + 1, t + 20);
+        let secret = rng.next() % P61;
+        let coefficients: Vec<u64> = (1..t).map(|_| rng.next() % P61).collect();
+
+        let ss = SecretShare::mersenne61(t, n).unwrap();
+        let shares: Vec<Share> = ss
+            .split_with_coefficients(secret, &coefficients)
+            .unwrap()
+            .into_iter()
+            .map(|share| Share::new(share.index(), BigInt::from(*share.value())))
+            .collect();
+        let big: Vec<BigInt> = coefficients.iter().copied().map(BigInt::from).collect();
+        check_dealing(
+            rng,
+            context,
+            &p,
+            &BigInt::from(secret),
+            &big,
+            &shares,
+            |subset| {
+                let subset: Vec<Share<u64>> = subset
+                    .iter()
+                    .map(|share| Share::new(share.index(), u64::try_from(share.value()).unwrap()))
+                    .collect();
+                BigInt::from(ss.recover(&subset).unwrap())
+            },
+        );
+    });
+}
+
+// Thresholds past those from which dealings are evaluated block by block
+// and interpolation goes through a subproduct tree
+#[test]
+#[ignore]
+fn large_thresholds() {
+    let p = BigInt::from(P61);
+    rounds(1, |rng, context| {
+        for (t, n) in [(130, 300), (2100, 2200)] {
+            let secret = rng.below_twice(&p);
+            let coefficients: Vec<BigInt> = (1..t).map(|_| rng.below_twice(&p)).collect();
+            let ss = SecretShare::new(t, n, p.clone()).unwrap();
+            let shares = ss
+                .split_with_coefficients(secret.clone(), &coefficients)
+                .unwrap();
+            check_dealing(
+                rng,
+                context,
+                &p,
+                &secret,
+                &coefficients,
+                &shares,
+                |subset| ss.recover(subset).unwrap(),
+            );
+        }
+    });
+}
+
+#[test]
+fn gf256_bytes() {
+    rounds(40, |rng, context| {
+        let t = rng.range(1, 20);
+        let n = rng.range(t + 1, 255);
+        let len = rng.range(0, 70);
+        let secret: Vec<u8> = (0..len).map(|_| rng.next() as u8).collect();
+        let random: Vec<u8> = (0..len * (t - 1)).map(|_| rng.next() as u8).collect();
+
+        let gf = Gf256Sharing::new(t, n).unwrap();
+        let shares = gf.split_with(&secret, |buf| buf.copy_from_slice(&random));
+        // byte i has coefficient j at random[(j - 1) * len + i]
+        for share in &shares {
+            let x = share.index() as u8;
+            let expected: Vec<u8> = (0..len)
+                .map(|i| {
+                    (0..t - 1)
+                        .rev()
+                        .map(|j| random[j * len + i])
+                        .chain([secret[i]])
+                        .fold(0, |value, c| reference::gf256_mul(value, x) ^ c)
+                })
+                .collect();
+            assert_eq!(
+                share.value(),
+                &expected,
+                "share {}, {}",
+                share.index(),
+                context
+            );
+        }
+
+        let count = rng.range(t, n);
+        let subset: Vec<_> = rng
+            .subset(n, count)
+            .into_iter()
+            .map(|i| shares[i - 1].clone())
+            .collect();
+        let expected: Vec<u8> = (0..len)
+            .map(|i| {
+                let points: Vec<(u8, u8)> = subset[..t]
+                    .iter()
+                    .map(|share| (share.index() as u8, share.value()[i]))
+                    .collect();
+                reference::gf256_interpolate_at_zero(&points)
+            })
+            .collect();
+        assert_eq!(expected, secret, "reference recovery, {}", context);
+        assert_eq!(
+            gf.recover(&subset).unwrap(),
+            secret,
+            "recovery, {}",
+            context
+        );
+    });
+}
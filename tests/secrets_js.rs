@@ -0,0 +1,119 @@
+//! Interoperability with secrets.js itself, run under node: shares it deals
+//! must combine here, and shares dealt here must combine there, at the
+//! default 8 bits and at other field sizes and paddings.
+//!
+//! The test needs node and a copy of the library, so it is ignored unless
+//! asked for: `SECRETS_JS=/path/to/secrets.js cargo test --test secrets_js
+//! -- --ignored` runs it against that file.
+
+use serde_json::{json, Value};
+use std::process::Command;
+use tiny_ss::secrets_js::{combine, SecretsJsShare, SecretsJsSharing};
+
+// Runs every job through `secrets.share` or `secrets.combine`, after
+// `secrets.init` with the job's bits, and returns their results in order
+const SCRIPT: &str = r#"
+const secrets = require(process.argv[1]);
+const jobs = JSON.parse(process.argv[2]);
+console.log(JSON.stringify(jobs.map((job) => {
+  secrets.init(job.bits);
+  return job.shares
+    ? secrets.combine(job.shares)
+    : secrets.share(job.secret, job.n, job.t, job.padding);
+})));
+"#;
+
+fn run(jobs: &Value) -> Vec<Value> {
+    let library = std::env::var("SECRETS_JS").expect("SECRETS_JS names the secrets.js to run");
+    let output = Command::new("node")
+        .args(["-e", SCRIPT, &library, &jobs.to_string()])
+        .output()
+        .expect("node runs");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// bits, t, n and padding of every dealing, the secret differing by dealing
+const CASES: [(u32, usize, usize, usize); 5] = [
+    (8, 3, 5, 128),
+    (8, 2, 2, 0),
+    (3, 3, 7, 128),
+    (12, 4, 9, 256),
+    (20, 2, 3, 64),
+];
+
+fn secret(case: usize) -> Vec<u8> {
+    format!("browser-generated secret {}", case).into_bytes()
+}
+
+#[test]
+#[ignore = "needs node and secrets.js; set SECRETS_JS to its path"]
+fn secrets_js_test() {
+    // dealt by secrets.js, combined here from the last t shares
+    let jobs: Vec<Value> = CASES
+        .iter()
+        .enumerate()
+        .map(|(case, &(bits, t, n, padding))| {
+            json!({ "bits": bits, "secret": hex(&secret(case)), "t": t, "n": n, "padding": padding })
+        })
+        .collect();
+    for (case, (dealt, &(_, t, n, _))) in run(&Value::from(jobs)).iter().zip(&CASES).enumerate() {
+        let lines: Vec<&str> = dealt
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|line| line.as_str().unwrap())
+            .collect();
+        let shares: Vec<SecretsJsShare> = lines.iter().map(|line| line.parse().unwrap()).collect();
+        assert_eq!(shares.len(), n);
+        assert_eq!(
+            combine(&shares[n - t..]).unwrap(),
+            secret(case),
+            "case {}",
+            case
+        );
+        for (share, line) in shares.iter().zip(lines) {
+            assert_eq!(share.to_string(), line);
+        }
+    }
+
+    // dealt here, combined by secrets.js from the first t shares
+    let jobs: Vec<Value> = CASES
+        .iter()
+        .enumerate()
+        .map(|(case, &(bits, t, n, padding))| {
+            let ss = SecretsJsSharing::with_bits(t, n, bits)
+                .unwrap()
+                .with_padding(padding)
+                .unwrap();
+            let mut next = case as u8;
+            let fill = |buf: &mut [u8]| {
+                for byte in buf {
+                    next = next.wrapping_mul(197).wrapping_add(31);
+                    *byte = next;
+                }
+            };
+            let shares: Vec<String> = ss.split_with(&secret(case), fill)[..t]
+                .iter()
+                .map(ToString::to_string)
+                .collect();
+            json!({ "bits": bits, "shares": shares })
+        })
+        .collect();
+    for (case, combined) in run(&Value::from(jobs)).iter().enumerate() {
+        assert_eq!(
+            combined.as_str().unwrap(),
+            hex(&secret(case)),
+            "case {}",
+            case
+        );
+    }
+}